@@ -1,19 +1,23 @@
-use crate::models::{VideoDatabase, VideoFile};
+use crate::models::{self, MetadataImportRecord, SceneInfo, ThemeMode, VideoDatabase, VideoFile};
 use crate::video_scanner;
 use crate::video_player;
 use crate::database;
-use crate::scene_detection::{self, get_video_duration, get_video_resolution};
+use crate::scene_detection::{self, get_video_duration, get_video_resolution, get_video_frame_rate, get_video_codec, is_video_hdr, get_video_chapters};
 use crate::thumbnail;
-use crate::i18n::{I18n, Language};
+use crate::i18n::I18n;
 use crate::license;
 use eframe::egui;
 use rfd::FileDialog;
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-use notify::{Watcher, RecursiveMode, Event};
-use std::sync::mpsc::{channel, Receiver, Sender};
-use std::time::SystemTime;
+use std::sync::atomic::{AtomicBool, Ordering};
+use notify::{Watcher, RecursiveMode, Event, EventKind};
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::time::{Duration, Instant};
+
+/// Image decoded by a background texture loader: (path, RGBA pixels, size, blurred)
+type LoadedImage = (PathBuf, Vec<u8>, [usize; 2], bool);
 
 pub struct VideoPlayerApp {
     pub database: VideoDatabase,
@@ -23,9 +27,51 @@ pub struct VideoPlayerApp {
     pub selected_folder_filter: HashSet<String>, // Multiple folder selection (premium feature)
     pub selected_tag_filter: HashSet<String>, // Multiple tag selection (premium feature)
     pub last_selected_folder: Option<usize>, // Last selected folder index for shift-click range selection
-    pub last_selected_tag: Option<usize>, // Last selected tag index for shift-click range selection
+    pub last_selected_tag: Option<String>, // Last selected tag path, anchor for shift-click range selection
+    pub expanded_tag_nodes: HashSet<String>, // Parent tags ("a" of "a/b") expanded in the sidebar tag tree
     pub tag_filter_mode: FilterMode, // AND or OR mode for tag filter (premium feature)
     pub min_rating_filter: u8, // 0 = show all, 1-5 = show videos with rating >= this value
+    pub watched_filter: WatchedFilter, // Show all, only watched or only unwatched videos
+    pub show_recent_only: bool, // Only show recently played videos, newest first
+    pub added_month_filter: Option<(i32, u32)>, // Only show videos added in this (year, month)
+    pub filter_presets: Vec<models::FilterPreset>, // Saved filter combinations
+    pub tag_rules: Vec<models::TagRule>, // Regex auto-tagging rules
+    pub compiled_tag_rules: Vec<(regex::Regex, String)>, // Valid rules, compiled (pattern, tag template)
+    pub tag_rule_errors: Vec<Option<String>>, // Compile error per rule, shown in the rules editor
+    pub tag_rules_preview: Option<(usize, usize)>, // (videos, new tags) "Apply rules to all" would change, awaiting confirmation
+    pub new_preset_name: String, // Input buffer for saving the current filter as a preset
+    pub renaming_preset: Option<(usize, String)>, // Preset being renamed (index, new name)
+    pub show_statistics_window: bool, // Show library statistics window
+    pub library_stats: Option<LibraryStats>, // Statistics computed when the window opened (dropped on close)
+    pub show_duplicates_window: bool, // Show the duplicate finder window
+    pub duplicate_groups: Vec<Vec<String>>, // Groups of video IDs that look like the same video
+    pub duplicate_max_distance: u32, // Max Hamming distance between thumbnail hashes to count as duplicates
+    pub duplicates_scanned: bool, // Whether a duplicate scan has been run this session
+    pub duplicate_scan_receiver: Option<Receiver<Vec<Vec<String>>>>, // Receiver for the background duplicate scan
+    pub recent_days: u32, // Days covered by the "Recent" filter
+    pub show_recent_row: bool, // Show "Continue watching" row above the grid
+    pub prune_hidden_selection: bool, // Deselect videos hidden by the current filters
+    pub video_extensions: Vec<String>, // File extensions picked up when scanning/adding
+    pub new_extension_input: String, // Input buffer for adding a video extension
+    pub exclude_patterns: Vec<String>, // Glob/substring patterns skipped when scanning folders
+    pub use_embedded_metadata: bool, // Prefer the title/creation date embedded in files on import
+    pub auto_tag_from_folder: bool, // Tag videos found by folder imports/rescans with their parent directory name
+    pub new_exclude_pattern_input: String, // Input buffer for adding an exclude pattern
+    pub show_quality_badge: bool, // Show a resolution badge on grid thumbnails
+    pub show_duration_badge: bool, // Show the video length on grid thumbnails
+    pub group_by_folder: bool, // Split the grid into collapsible sections per folder
+    pub mark_watched_on_play: bool, // Mark videos watched when playback starts instead of after 90%
+    pub hdr_colorspace_hint: bool, // Pass --target-colorspace-hint to mpv for HDR videos
+    pub pin_favorites_to_top: bool, // List top-rated videos first, whatever the sort
+    pub pin_min_rating: u8, // Minimum rating of pinned videos (premium; free tier pins favorites)
+    pub default_subtitle: String, // Preferred subtitle languages for mpv, "off" to disable (empty = mpv default)
+    pub default_audio_lang: String, // Preferred audio languages for mpv (empty = mpv default)
+    pub ffmpeg_path: Option<PathBuf>, // User-selected ffmpeg binary (None = bundled/system)
+    pub ffprobe_path: Option<PathBuf>, // User-selected ffprobe binary (None = bundled/system)
+    pub external_tools_error: Option<String>, // Validation error of the last selected binary
+    pub screenshot_dir: Option<PathBuf>, // Where mpv saves screenshots (None = Pictures/CicadaGallery)
+    pub auto_collapse_filters: bool, // Move filters into a window on narrow windows
+    pub show_filters_window: bool, // Filters window is open (compact layout only)
     pub show_options_window: bool,
     pub selected_options_tab: OptionsTab, // Currently selected options tab
     pub show_folder_management_window: bool, // Show folder management window
@@ -36,21 +82,46 @@ pub struct VideoPlayerApp {
     pub mpv_shortcuts_pos: Option<egui::Pos2>, // MPV shortcuts panel position
     pub thumbnail_scale: f32, // 0.5 to 3.0 (50% to 300%)
     pub scene_panel_visible: bool, // Show/hide scene panel
-    pub texture_cache: HashMap<PathBuf, egui::TextureHandle>,
+    pub show_scene_lightbox: bool, // Show full-window scene overview for the selected video
+    pub lightbox_focused_scene: usize, // Keyboard-focused scene index in the lightbox
+    pub show_review_mode: bool, // Full-window one-video-at-a-time rating mode
+    pub review_video_id: Option<String>, // Video currently shown in review mode
+    pub scene_sort_by_score: bool, // Sort scenes by scene-change score instead of time
+    pub scene_timestamp_input: String, // Timestamp input for manual scene capture
+    pub scene_capture_error: Option<String>, // Error message for manual scene capture (timestamp entry / mpv frame)
+    pub thumbnail_timestamp_video: Option<String>, // Video whose main thumbnail is being picked by timestamp (dialog open)
+    pub thumbnail_timestamp_input: String, // Timestamp input for the main thumbnail
+    pub thumbnail_timestamp_error: Option<String>, // Error message of the thumbnail timestamp dialog
+    pub player_error_message: Option<String>, // Shown in a dialog when the video player could not be launched
+    pub show_ffmpeg_missing_banner: bool, // FFprobe could not be run at startup (until dismissed)
+    pub texture_cache: TextureCache, // Thumbnail textures (LRU, bounded by max_texture_cache)
+    pub max_texture_cache: usize, // Maximum number of textures kept in memory
     pub favorite_icon_texture: Option<egui::TextureHandle>,
     pub delete_confirm_video: Option<String>, // Video ID pending deletion confirmation
+    pub delete_to_trash_confirm: bool, // Second confirmation step before moving files to the recycle bin
     pub selected_videos: HashSet<String>, // IDs of selected videos for multi-selection
     pub last_selected_video: Option<String>, // Last selected video ID for shift-click range selection
     pub mpv_always_on_top: bool, // Keep mpv window always on top
+    pub loop_playback: bool, // Loop the played video endlessly
+    pub fullscreen_on_launch: bool, // Open mpv in fullscreen
     pub mpv_volume: u8, // MPV volume (0-100)
     pub selected_scenes: HashSet<usize>, // Indices of selected scenes
     pub last_selected_scene: Option<usize>, // Last selected scene index for shift-click range selection
     pub show_tag_add_popup: bool, // Show tag addition popup
+    pub show_bulk_rating_popup: bool, // Show rating popup for the multi-selection
+    pub show_bulk_folder_popup: bool, // Show folder add/remove popup for the multi-selection
+    pub bulk_folder_input: String, // New folder name typed in the bulk folder popup
     pub new_tag_input: String, // Input for new tag creation
     pub new_folder_input: String, // Input for new folder creation
+    pub move_folder_input: String, // New folder name typed in the "Move to folder" menu
     pub show_full_filename: bool, // Show full filename in grid view
     pub show_tags_in_grid: bool, // Show tags in grid view
-    pub dark_mode: bool, // Dark mode theme
+    pub theme_mode: ThemeMode, // UI theme (System follows the OS setting)
+    pub accent_color: [u8; 3], // Selection highlight color (RGB)
+    pub scene_panel_width: f32, // Width of the right scene panel (saved on exit)
+    pub fixed_grid_columns: bool, // Use grid_columns instead of fitting columns to the width
+    pub grid_columns: usize, // Column count of the fixed-column grid (2-10)
+    pub grid_scale: f32, // Thumbnail scale the grid is drawn with this frame (fitted in fixed-column mode)
     pub use_gpu_hq: bool, // Use GPU high-quality rendering (mpv profile=gpu-hq)
     pub use_custom_shaders: bool, // Use custom GLSL shaders from mpv/glsl_shaders directory
     pub selected_shader: Option<String>, // Selected shader filename
@@ -59,33 +130,52 @@ pub struct VideoPlayerApp {
     pub sort_field: SortField, // Current sort field
     pub sort_order: SortOrder, // Current sort order
     pub watched_folders: HashSet<PathBuf>, // Folders being watched for changes
+    pub profile_watched_folders: HashMap<String, Vec<PathBuf>>, // Watched folders of each profile (current one synced on save)
     pub fs_watcher: Option<notify::RecommendedWatcher>, // File system watcher
     pub fs_events: Option<Arc<Mutex<Receiver<Result<Event, notify::Error>>>>>, // Channel for file system events
-    pub pending_rescan: bool, // Flag to trigger rescan on next update
-    pub last_rescan_time: SystemTime, // Last time a rescan was performed
+    pub pending_rescan_dirs: HashSet<PathBuf>, // Directories with file changes waiting for a rescan
+    pub last_fs_event: Option<Instant>, // When the last relevant file event arrived (for debouncing)
+    pub rescan_debounce_secs: u32, // Quiet time after file events before rescanning
+    pub watcher_failed: bool, // The watcher reported an error or could not be created (retried with backoff)
+    pub watcher_unavailable: Vec<PathBuf>, // Watched folders that are not being watched (missing drive, watch error)
+    pub watcher_next_check: Option<Instant>, // When to retry the watcher / re-check folder availability
+    pub watcher_backoff_secs: u64, // Current retry delay after watcher errors
     pub is_premium: bool, // Premium/free tier (false = free, true = premium)
     pub show_premium_promotion_window: bool, // Show premium promotion window when limit reached
     pub show_license_window: bool, // Show license activation window
     pub license_input: String, // License key input field
     pub license_status_message: Option<String>, // License activation status message
     pub current_license: Option<license::License>, // Currently activated license
+    pub license_expiry_warning_days: u32, // Show the expiry banner this many days before expires_at (0 = off)
+    pub license_expiry_banner_dismissed: bool, // Expiry banner closed for this session
+    pub show_license_expired_notice: bool, // One-time notice that an expired license fell back to free
+    pub show_deactivate_license_confirm: bool, // Confirmation dialog for removing the license from this machine
+    pub license_expired_notice_shown: Option<i64>, // expires_at of the license the expiry notice was shown for
     pub folder_delete_confirm: Option<(String, usize)>, // (folder_name, video_count) pending deletion
     pub tag_delete_confirm: Option<(String, usize)>, // (tag_name, video_count) pending deletion
+    pub tag_rename: Option<(String, String)>, // (original tag, new name) while renaming in tag management
+    pub folder_rename: Option<(String, String)>, // (original folder, new name) while renaming in folder management
+    pub folder_rename_error: Option<String>, // Validation error of the folder rename
     pub last_window_size: Option<(f32, f32)>, // Track last window size for saving on exit
     pub last_window_pos: Option<(f32, f32)>, // Track last window position
     // Async thumbnail loading
     pub pending_textures: HashSet<PathBuf>, // Textures currently being loaded
+    pub pending_blurred_textures: HashSet<PathBuf>, // Blurred textures currently being loaded
     pub failed_textures: HashSet<PathBuf>, // Textures that failed to load (to avoid retrying)
-    pub texture_load_receiver: Option<Receiver<(PathBuf, Vec<u8>, [usize; 2])>>, // Receiver for loaded image data
-    pub texture_load_sender: Option<Sender<(PathBuf, Vec<u8>, [usize; 2])>>, // Sender for loaded image data
-    pub texture_fail_receiver: Option<Receiver<PathBuf>>, // Receiver for failed image paths
-    pub texture_fail_sender: Option<Sender<PathBuf>>, // Sender for failed image paths
+    pub texture_load_receiver: Option<Receiver<LoadedImage>>, // Receiver for loaded image data
+    pub texture_load_sender: Option<Sender<LoadedImage>>, // Sender for loaded image data
+    pub texture_fail_receiver: Option<Receiver<(PathBuf, bool)>>, // Receiver for failed image paths (true = blurred)
+    pub texture_fail_sender: Option<Sender<(PathBuf, bool)>>, // Sender for failed image paths (true = blurred)
     pub thumbnail_clicked_this_frame: bool, // Flag to track if a thumbnail was clicked this frame
     pub profile_details_expanded: bool, // Whether profile details section is expanded
     // Backup management
     pub show_backup_restore_window: bool, // Show backup restore selection window
     pub available_backups: Vec<(PathBuf, String)>, // List of available backups (path, timestamp)
     pub backup_status_message: Option<String>, // Backup operation status message
+    pub cache_status_message: Option<String>, // Cache cleanup result message
+    pub clear_cache_confirm: bool, // Confirmation for deleting the whole thumbnail cache
+    pub backups_to_keep: usize, // Number of backups to keep (0 = unlimited)
+    pub backup_interval_days: i64, // Days between automatic backups
     pub restore_in_progress: bool, // Flag to indicate restore operation is in progress
     pub restore_result_receiver: Option<Receiver<Result<(), String>>>, // Receiver for restore result
     pub thumbnail_regen_in_progress: bool, // Flag to indicate thumbnail regeneration is in progress
@@ -99,6 +189,182 @@ pub struct VideoPlayerApp {
     pub profile_switch_pending: Option<String>, // Profile name to switch to (requires restart)
     pub profile_rename_target: Option<String>, // Profile name being renamed
     pub profile_rename_new_name: String, // New name for the profile being renamed
+    // Playback position tracking
+    pub playback_monitor_receiver: Option<Receiver<video_player::PlaybackStatus>>, // Receiver for mpv playback status
+    pub playback_dirty_videos: HashSet<String>, // Videos whose playback position/state changed since last save
+    pub player_levels: HashMap<PathBuf, (Option<f64>, Option<f64>)>, // Last volume/speed mpv reported per video, stored when it closes
+    pub player_launch_levels: (u8, f64), // Volume/speed the last playback was started with
+    // Protected videos
+    pub protection_pin_hash: Option<String>, // Salted PBKDF2 hash of the protection PIN
+    pub protection_pin_salt: Option<String>, // Per-install salt for the PIN hash
+    pub protected_unlocked: bool, // Protected videos are visible (session only)
+    pub show_unlock_window: bool, // Show PIN entry window
+    pub pin_input: String, // PIN entry field
+    pub pin_status_message: Option<String>, // PIN entry / change status message
+    pub blurred_texture_cache: HashMap<PathBuf, egui::TextureHandle>, // Blurred thumbnails for protected videos
+    // Thumbnail disk cache
+    pub thumbnail_access_log: thumbnail::ThumbnailAccessLog, // Last access time per thumbnail file
+    pub thumbnail_eviction_enabled: bool, // Delete thumbnails not viewed recently when over budget
+    pub thumbnail_eviction_days: u32, // Thumbnails not viewed within this many days can be deleted
+    pub thumbnail_cache_budget_mb: u64, // Disk budget for the thumbnail cache (MB)
+    pub thumbnail_max_width: u32, // Maximum width of generated thumbnails (px)
+    pub thumbnail_quality: u8, // FFmpeg JPEG quality of generated thumbnails (2 = best, 31 = smallest)
+    // Scene detection
+    pub scene_threshold: f32, // FFmpeg scene-change threshold
+    pub max_scenes: usize, // Maximum number of detected scenes per video
+    pub scene_thread_count: usize, // FFmpeg processes extracting scene thumbnails at once
+    pub max_concurrent_texture_loads: usize, // Thumbnail images decoded in parallel
+    pub similar_scene_threshold: f32, // Similarity above which adjacent scenes are removed
+    // Batch scene generation
+    pub scene_batch_receiver: Option<Receiver<SceneBatchUpdate>>, // Receiver for batch scene generation results
+    pub scene_batch_cancel: Option<Arc<AtomicBool>>, // Cancellation flag for the running batch
+    pub scene_batch_progress: Option<(usize, usize)>, // (done, total) while a batch is running
+    pub scene_batch_label: &'static str, // i18n key of the running batch's progress text
+    pub status_message: Option<String>, // Message shown in the status bar until dismissed
+    pub title_edit: Option<(String, String)>, // (video id, new title) while editing a title inline
+    pub notes_video_id: Option<String>, // Video whose notes are loaded into notes_buffer
+    pub notes_buffer: String, // Notes being edited in the details panel
+    // Accurate duration re-probing
+    pub duration_probe_receiver: Option<Receiver<DurationProbeUpdate>>, // Receiver for re-probed durations
+    pub duration_probe_cancel: Option<Arc<AtomicBool>>, // Cancellation flag for the running re-probe
+    pub duration_probe_progress: Option<(usize, usize)>, // (done, total) while re-probing
+    pub file_copy_receiver: Option<Receiver<FileCopyUpdate>>, // Receiver for the "Copy file to…" worker
+    pub file_copy_progress: Option<(usize, usize)>, // (done, total) while copying files
+    pub file_copy_errors: Vec<String>, // Files that failed to copy in the running job
+    pub import_receiver: Option<Receiver<ImportUpdate>>, // Receiver for the background folder import
+    pub import_cancel: Option<Arc<AtomicBool>>, // Cancellation flag for the running import
+    pub import_progress: Option<(usize, usize, usize)>, // (files found, processed, to process) while importing
+    pub import_folders: Vec<PathBuf>, // Folders being imported (watched once the import completes)
+    pub import_added: usize, // Videos added by the running import
+    pub pending_import_issues: Vec<(PathBuf, String)>, // Problems collected by the running import
+    pub import_issues: Vec<(PathBuf, String)>, // Files skipped or incomplete in the last import/rescan (path, reason)
+    pub show_import_issues_window: bool, // Show the import issues window
+    pub grid_row_height: f32, // Tallest grid row seen with the current layout
+    pub grid_scroll_offset: f32, // Vertical scroll offset of the grid view
+    pub list_scroll_offset: f32, // Vertical scroll offset of the list view
+    pub scroll_filter_key: Option<u64>, // Filter/search fingerprint the scroll offsets belong to
+    pub export_all_videos: bool, // Export the whole library instead of the filtered/selected videos
+    pub grid_row_layout: Option<(f32, bool, bool, usize)>, // Layout the grid row height was measured for
+    pub collapsed_grid_folders: HashSet<Option<String>>, // Folder sections collapsed in the grouped grid (None = Ungrouped)
+    pub undo_stack: Vec<UndoEntry>, // Recent deletions that Ctrl+Z can restore (database records only)
+}
+
+/// Maximum number of deletions kept for undo
+const MAX_UNDO_ENTRIES: usize = 20;
+
+/// A deletion that can be undone. Items are stored in removal order with their original index.
+pub enum UndoEntry {
+    Videos(Vec<(usize, VideoFile)>),
+    Scenes { video_id: String, scenes: Vec<(usize, SceneInfo)> },
+}
+
+/// Result of one video processed by the duration re-probe worker
+pub struct DurationProbeUpdate {
+    pub video_id: String,
+    pub duration: Option<f64>,
+    pub done: usize,
+    pub total: usize,
+}
+
+/// Result of one file copied by the "Copy file to…" worker
+pub struct FileCopyUpdate {
+    pub error: Option<String>, // "<file>: <error>" if the copy failed
+    pub done: usize,
+    pub total: usize,
+}
+
+/// Progress of the background folder import
+pub enum ImportUpdate {
+    Scanned(usize), // Video files found so far
+    LimitReached, // Free tier limit cut the import short
+    Processing(usize), // Scan finished; number of new videos to process
+    Processed { video: Box<VideoFile>, done: usize, total: usize },
+    Skipped(PathBuf, String), // File that could not be read (error message)
+}
+
+/// Library overview shown in the statistics window
+#[derive(Clone)]
+pub struct LibraryStats {
+    pub video_count: usize,
+    pub total_duration: f64, // Seconds, videos with a known duration only
+    pub total_size: u64, // Bytes
+    pub rating_counts: [usize; 6], // Index = rating (0 = unrated)
+    pub top_tags: Vec<(String, usize)>, // Up to 10 tags by video count
+    pub resolution_counts: Vec<(String, usize)>, // Resolution class ("1080p", ...), highest first
+}
+
+/// Window width below which filters collapse into a window (when enabled)
+const COMPACT_LAYOUT_WIDTH: f32 = 900.0;
+
+/// Texture cache that frees the least recently used textures beyond its capacity.
+/// Evicted textures are simply reloaded through the async loader when shown again.
+pub struct TextureCache {
+    entries: HashMap<PathBuf, (egui::TextureHandle, u64)>, // path -> (texture, last access tick)
+    tick: u64,
+    capacity: usize,
+}
+
+impl TextureCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            tick: 0,
+            capacity,
+        }
+    }
+    
+    /// Get a texture and mark it as most recently used
+    pub fn get(&mut self, path: &std::path::Path) -> Option<&egui::TextureHandle> {
+        self.tick += 1;
+        let tick = self.tick;
+        self.entries.get_mut(path).map(|(texture, last_access)| {
+            *last_access = tick;
+            &*texture
+        })
+    }
+    
+    pub fn insert(&mut self, path: PathBuf, texture: egui::TextureHandle) {
+        self.tick += 1;
+        self.entries.insert(path, (texture, self.tick));
+        self.evict();
+    }
+    
+    pub fn remove(&mut self, path: &std::path::Path) -> Option<egui::TextureHandle> {
+        self.entries.remove(path).map(|(texture, _)| texture)
+    }
+    
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+    }
+    
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+    
+    /// Drop least recently used textures until within capacity
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            let oldest = self.entries.iter()
+                .min_by_key(|(_, (_, last_access))| *last_access)
+                .map(|(path, _)| path.clone());
+            match oldest {
+                Some(path) => {
+                    self.entries.remove(&path);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+/// Result of one video processed by the batch scene generator
+pub struct SceneBatchUpdate {
+    pub video_id: String,
+    pub scenes: Vec<SceneInfo>,
+    pub thumbnail_path: Option<PathBuf>, // Regenerated main thumbnail (thumbnail batches)
+    pub done: usize,
+    pub total: usize,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -112,8 +378,16 @@ pub enum SortField {
     AddedDate,
     FileName,
     Duration,
+    Resolution, // Pixel count
+    FileSize,
+    Rating,
+    Folder,
+    Manual, // Order arranged by dragging thumbnails in the grid
 }
 
+/// Drag-and-drop payload of a grid thumbnail being moved in the manual order
+struct ManualOrderDrag(String);
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum SortOrder {
     Ascending,
@@ -126,6 +400,13 @@ pub enum FilterMode {
     And, // All of the selected (AND)
 }
 
+#[derive(Debug, Clone, PartialEq, Copy, Hash)]
+pub enum WatchedFilter {
+    All,
+    Watched,
+    Unwatched,
+}
+
 #[derive(Debug, Clone, PartialEq, Copy)]
 pub enum OptionsTab {
     Display,   // 表示
@@ -134,6 +415,27 @@ pub enum OptionsTab {
     License,   // ライセンス
 }
 
+/// Main thumbnails of the videos of every profile (kept when pruning the cache).
+/// The cache directory is shared by all profiles; `None` if another library cannot be read.
+fn main_thumbnail_paths(database: &VideoDatabase, current_profile: &str) -> Option<HashSet<PathBuf>> {
+    let mut paths: HashSet<PathBuf> = database.videos.iter()
+        .filter_map(|v| v.thumbnail_path.clone())
+        .collect();
+    for (profile_name, _) in database::list_profiles().unwrap_or_default() {
+        if profile_name == current_profile {
+            continue;
+        }
+        match database::load_profile_database(&profile_name) {
+            Ok(library) => paths.extend(library.videos.iter().filter_map(|v| v.thumbnail_path.clone())),
+            Err(e) => {
+                eprintln!("[Cache] Skipping thumbnail pruning, cannot read profile {}: {}", profile_name, e);
+                return None;
+            }
+        }
+    }
+    Some(paths)
+}
+
 impl Default for VideoPlayerApp {
     fn default() -> Self {
         // Load settings first to get current profile
@@ -167,15 +469,35 @@ impl Default for VideoPlayerApp {
             let _ = database::save_database(&database);
         }
         
-        // Perform backup if needed (every 3 days)
+        // Perform backup if needed (every backup_interval_days days)
         match database::perform_backup_if_needed(&mut settings) {
             Ok(true) => eprintln!("[init] Database backup created"),
             Ok(false) => eprintln!("[init] Database backup not needed yet"),
             Err(e) => eprintln!("[init] Failed to create backup: {}", e),
         }
         
+        thumbnail::set_thumbnail_options(settings.thumbnail_max_width, settings.thumbnail_quality);
+        thumbnail::set_tool_paths(settings.ffmpeg_path.clone(), settings.ffprobe_path.clone());
+        video_scanner::set_use_embedded_metadata(settings.use_embedded_metadata);
+        
+        // Evict thumbnails not viewed recently if the disk cache is over budget
+        let mut thumbnail_access_log = thumbnail::ThumbnailAccessLog::load();
+        let mut startup_freed_bytes = 0;
+        if settings.thumbnail_eviction_enabled {
+            if let Some(protected) = main_thumbnail_paths(&database, &current_profile) {
+                let budget_bytes = settings.thumbnail_cache_budget_mb * 1024 * 1024;
+                let (evicted, freed) = thumbnail::evict_stale_thumbnails(&mut thumbnail_access_log, budget_bytes, settings.thumbnail_eviction_days, &protected);
+                if evicted > 0 {
+                    thumbnail_access_log.save();
+                }
+                startup_freed_bytes = freed;
+            }
+        }
+        
         // Initialize i18n with loaded language
         let i18n = I18n::new(settings.language);
+        let startup_status = (startup_freed_bytes > 0)
+            .then(|| i18n.t("thumbnail_cache_pruned").replace("{}", &format!("{:.1}", startup_freed_bytes as f64 / 1024.0 / 1024.0)));
         
         // Verify license if exists (only source of premium status)
         let (is_premium, current_license) = if let Some(ref license_key) = settings.license_key {
@@ -204,6 +526,12 @@ impl Default for VideoPlayerApp {
             (false, None)
         };
         
+        let (compiled_tag_rules, tag_rule_errors) = video_scanner::compile_tag_rules(&settings.tag_rules);
+        
+        // 期限切れは一度だけ通知する (同じ expires_at では再表示しない)
+        let show_license_expired_notice = current_license.as_ref()
+            .is_some_and(|license| license.is_expired && license.info.expires_at != settings.license_expired_notice_shown);
+        
         Self {
             database,
             selected_video: None,
@@ -213,8 +541,54 @@ impl Default for VideoPlayerApp {
             selected_tag_filter: HashSet::new(),
             last_selected_folder: None,
             last_selected_tag: None,
+            expanded_tag_nodes: HashSet::new(),
             tag_filter_mode: FilterMode::Or,
             min_rating_filter: 0,
+            watched_filter: WatchedFilter::All,
+            show_recent_only: false,
+            added_month_filter: None,
+            filter_presets: settings.filter_presets.clone(),
+            compiled_tag_rules,
+            tag_rule_errors,
+            tag_rules: settings.tag_rules.clone(),
+            tag_rules_preview: None,
+            new_preset_name: String::new(),
+            renaming_preset: None,
+            show_statistics_window: false,
+            library_stats: None,
+            show_duplicates_window: false,
+            duplicate_groups: Vec::new(),
+            duplicate_max_distance: 6,
+            duplicates_scanned: false,
+            duplicate_scan_receiver: None,
+            recent_days: settings.recent_days.max(1),
+            show_recent_row: settings.show_recent_row,
+            prune_hidden_selection: settings.prune_hidden_selection,
+            video_extensions: if settings.video_extensions.is_empty() {
+                crate::models::default_video_extensions()
+            } else {
+                settings.video_extensions.clone()
+            },
+            new_extension_input: String::new(),
+            exclude_patterns: settings.exclude_patterns.clone(),
+            use_embedded_metadata: settings.use_embedded_metadata,
+            auto_tag_from_folder: settings.auto_tag_from_folder,
+            new_exclude_pattern_input: String::new(),
+            show_quality_badge: settings.show_quality_badge,
+            show_duration_badge: settings.show_duration_badge,
+            group_by_folder: settings.group_by_folder,
+            mark_watched_on_play: settings.mark_watched_on_play,
+            hdr_colorspace_hint: settings.hdr_colorspace_hint,
+            pin_favorites_to_top: settings.pin_favorites_to_top,
+            pin_min_rating: settings.pin_min_rating,
+            default_subtitle: settings.default_subtitle.clone().unwrap_or_default(),
+            default_audio_lang: settings.default_audio_lang.clone().unwrap_or_default(),
+            ffmpeg_path: settings.ffmpeg_path.clone(),
+            ffprobe_path: settings.ffprobe_path.clone(),
+            external_tools_error: None,
+            screenshot_dir: settings.screenshot_dir.clone(),
+            auto_collapse_filters: settings.auto_collapse_filters,
+            show_filters_window: false,
             show_options_window: false,
             selected_options_tab: OptionsTab::Display,
             show_folder_management_window: false,
@@ -225,21 +599,46 @@ impl Default for VideoPlayerApp {
             mpv_shortcuts_pos: settings.mpv_shortcuts_position.map(|(x, y)| egui::Pos2::new(x, y)),
             thumbnail_scale: settings.thumbnail_scale,
             scene_panel_visible: true,
-            texture_cache: HashMap::new(),
+            show_scene_lightbox: false,
+            lightbox_focused_scene: 0,
+            show_review_mode: false,
+            review_video_id: None,
+            scene_sort_by_score: false,
+            scene_timestamp_input: String::new(),
+            scene_capture_error: None,
+            thumbnail_timestamp_video: None,
+            thumbnail_timestamp_input: String::new(),
+            thumbnail_timestamp_error: None,
+            player_error_message: None,
+            show_ffmpeg_missing_banner: !scene_detection::is_ffprobe_available(),
+            texture_cache: TextureCache::new(settings.max_texture_cache.clamp(100, 5000)),
+            max_texture_cache: settings.max_texture_cache.clamp(100, 5000),
             favorite_icon_texture: None,
             delete_confirm_video: None,
+            delete_to_trash_confirm: false,
             selected_videos: HashSet::new(),
             last_selected_video: None,
             mpv_always_on_top: settings.mpv_always_on_top,
+            loop_playback: settings.loop_playback,
+            fullscreen_on_launch: settings.fullscreen_on_launch,
             mpv_volume: settings.mpv_volume,
             selected_scenes: HashSet::new(),
             last_selected_scene: None,
             show_tag_add_popup: false,
+            show_bulk_rating_popup: false,
+            show_bulk_folder_popup: false,
+            bulk_folder_input: String::new(),
             new_tag_input: String::new(),
             new_folder_input: String::new(),
+            move_folder_input: String::new(),
             show_full_filename: settings.show_full_filename,
             show_tags_in_grid: settings.show_tags_in_grid,
-            dark_mode: settings.dark_mode,
+            theme_mode: settings.theme_mode.unwrap_or(if settings.dark_mode { ThemeMode::Dark } else { ThemeMode::Light }),
+            accent_color: settings.accent_color,
+            scene_panel_width: settings.scene_panel_width,
+            fixed_grid_columns: settings.fixed_grid_columns,
+            grid_columns: settings.grid_columns.clamp(2, 10),
+            grid_scale: settings.thumbnail_scale,
             use_gpu_hq: settings.use_gpu_hq,
             use_custom_shaders: settings.use_custom_shaders,
             selected_shader: settings.selected_shader,
@@ -247,23 +646,41 @@ impl Default for VideoPlayerApp {
             metadata_loaded: HashSet::new(),
             sort_field: SortField::AddedDate,
             sort_order: SortOrder::Descending,
-            watched_folders: settings.watched_folders.into_iter().collect(),
+            // Older settings only have the global list, which belongs to the current profile
+            watched_folders: settings.profile_watched_folders.get(&current_profile).cloned()
+                .unwrap_or_else(|| settings.watched_folders.clone())
+                .into_iter().collect(),
+            profile_watched_folders: settings.profile_watched_folders.clone(),
             fs_watcher: None,
             fs_events: None,
-            pending_rescan: false,
-            last_rescan_time: SystemTime::now(),
+            pending_rescan_dirs: HashSet::new(),
+            last_fs_event: None,
+            rescan_debounce_secs: settings.rescan_debounce_secs.max(1),
+            watcher_failed: false,
+            watcher_unavailable: Vec::new(),
+            watcher_next_check: None,
+            watcher_backoff_secs: WATCHER_MIN_BACKOFF_SECS,
             is_premium,
             show_premium_promotion_window: false,
             show_license_window: false,
             license_input: String::new(),
             license_status_message: None,
             current_license,
+            license_expiry_warning_days: settings.license_expiry_warning_days,
+            license_expiry_banner_dismissed: false,
+            show_license_expired_notice,
+            show_deactivate_license_confirm: false,
+            license_expired_notice_shown: settings.license_expired_notice_shown,
             folder_delete_confirm: None,
             tag_delete_confirm: None,
+            tag_rename: None,
+            folder_rename: None,
+            folder_rename_error: None,
             last_window_size: None,
             last_window_pos: None,
             // Async thumbnail loading
             pending_textures: HashSet::new(),
+            pending_blurred_textures: HashSet::new(),
             failed_textures: HashSet::new(),
             texture_load_receiver: None,
             texture_load_sender: None,
@@ -275,6 +692,10 @@ impl Default for VideoPlayerApp {
             show_backup_restore_window: false,
             available_backups: Vec::new(),
             backup_status_message: None,
+            cache_status_message: None,
+            clear_cache_confirm: false,
+            backups_to_keep: settings.backups_to_keep.min(100),
+            backup_interval_days: settings.backup_interval_days.clamp(1, 365),
             restore_in_progress: false,
             restore_result_receiver: None,
             thumbnail_regen_in_progress: false,
@@ -288,13 +709,71 @@ impl Default for VideoPlayerApp {
             profile_switch_pending: None,
             profile_rename_target: None,
             profile_rename_new_name: String::new(),
+            // Playback position tracking
+            playback_monitor_receiver: None,
+            playback_dirty_videos: HashSet::new(),
+            player_levels: HashMap::new(),
+            player_launch_levels: (settings.mpv_volume, 1.0),
+            // Protected videos
+            protection_pin_hash: settings.protection_pin_hash.clone(),
+            protection_pin_salt: settings.protection_pin_salt.clone(),
+            protected_unlocked: false,
+            show_unlock_window: false,
+            pin_input: String::new(),
+            pin_status_message: None,
+            blurred_texture_cache: HashMap::new(),
+            // Thumbnail disk cache
+            thumbnail_access_log,
+            thumbnail_eviction_enabled: settings.thumbnail_eviction_enabled,
+            thumbnail_eviction_days: settings.thumbnail_eviction_days,
+            thumbnail_cache_budget_mb: settings.thumbnail_cache_budget_mb,
+            thumbnail_max_width: settings.thumbnail_max_width,
+            thumbnail_quality: settings.thumbnail_quality,
+            // Scene detection
+            scene_threshold: settings.scene_threshold.clamp(0.05, 0.9),
+            max_scenes: settings.max_scenes.clamp(5, 500),
+            scene_thread_count: settings.scene_thread_count.max(1),
+            max_concurrent_texture_loads: settings.max_concurrent_texture_loads.max(1),
+            similar_scene_threshold: settings.similar_scene_threshold.clamp(0.5, 1.0),
+            // Batch scene generation
+            scene_batch_receiver: None,
+            scene_batch_cancel: None,
+            scene_batch_progress: None,
+            scene_batch_label: "scene_batch_progress",
+            status_message: startup_status,
+            title_edit: None,
+            notes_video_id: None,
+            notes_buffer: String::new(),
+            // Accurate duration re-probing
+            duration_probe_receiver: None,
+            duration_probe_cancel: None,
+            duration_probe_progress: None,
+            file_copy_receiver: None,
+            file_copy_progress: None,
+            file_copy_errors: Vec::new(),
+            import_receiver: None,
+            import_cancel: None,
+            import_progress: None,
+            import_folders: Vec::new(),
+            pending_import_issues: Vec::new(),
+            import_issues: Vec::new(),
+            show_import_issues_window: false,
+            import_added: 0,
+            grid_row_height: 0.0,
+            grid_scroll_offset: 0.0,
+            list_scroll_offset: 0.0,
+            scroll_filter_key: None,
+            export_all_videos: false,
+            grid_row_layout: None,
+            collapsed_grid_folders: HashSet::new(),
+            undo_stack: Vec::new(),
         }
     }
 }
 
 impl VideoPlayerApp {
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        // Set up custom fonts for multilingual support (especially Japanese)
+        // Set up custom fonts for multilingual support (Japanese and Chinese)
         let mut fonts = egui::FontDefinitions::default();
         
         // Try to load Windows system fonts that support Japanese and other languages
@@ -303,6 +782,7 @@ impl VideoPlayerApp {
             ("meiryo", "C:\\Windows\\Fonts\\meiryo.ttc"),      // Meiryo (Japanese)
             ("yugo", "C:\\Windows\\Fonts\\YuGothM.ttc"),        // Yu Gothic (Japanese)
             ("msgothic", "C:\\Windows\\Fonts\\msgothic.ttc"),  // MS Gothic (Japanese)
+            ("msyh", "C:\\Windows\\Fonts\\msyh.ttc"),          // Microsoft YaHei (Simplified Chinese)
             ("segoeui", "C:\\Windows\\Fonts\\segoeui.ttf"),    // Segoe UI (supports many languages)
         ];
         
@@ -352,15 +832,23 @@ impl VideoPlayerApp {
         let settings = crate::models::AppSettings {
             thumbnail_scale: self.thumbnail_scale,
             mpv_always_on_top: self.mpv_always_on_top,
+            loop_playback: self.loop_playback,
+            fullscreen_on_launch: self.fullscreen_on_launch,
             show_full_filename: self.show_full_filename,
             show_tags_in_grid: self.show_tags_in_grid,
-            dark_mode: self.dark_mode,
+            dark_mode: self.theme_mode == ThemeMode::Dark,
+            theme_mode: Some(self.theme_mode),
+            accent_color: self.accent_color,
+            scene_panel_width: self.scene_panel_width,
+            fixed_grid_columns: self.fixed_grid_columns,
+            grid_columns: self.grid_columns,
             use_gpu_hq: self.use_gpu_hq,
             use_custom_shaders: self.use_custom_shaders,
             selected_shader: self.selected_shader.clone(),
             language: self.i18n.get_language(),
             added_dates_updated: true, // Keep as true to avoid re-updating
             watched_folders: self.watched_folders.iter().cloned().collect(),
+            profile_watched_folders: self.profile_watched_folders_snapshot(),
             mpv_shortcuts_open: self.mpv_shortcuts_open,
             mpv_shortcuts_position: self.mpv_shortcuts_pos.map(|p| (p.x, p.y)),
             mpv_volume: self.mpv_volume,
@@ -370,6 +858,46 @@ impl VideoPlayerApp {
             window_maximized: false, // Don't save maximized state to avoid flicker on startup
             last_backup_date: database::load_settings().ok().and_then(|s| s.last_backup_date),
             current_profile: self.current_profile.clone(),
+            backups_to_keep: self.backups_to_keep,
+            backup_interval_days: self.backup_interval_days,
+            protection_pin_hash: self.protection_pin_hash.clone(),
+            protection_pin_salt: self.protection_pin_salt.clone(),
+            recent_days: self.recent_days,
+            show_recent_row: self.show_recent_row,
+            prune_hidden_selection: self.prune_hidden_selection,
+            video_extensions: self.video_extensions.clone(),
+            exclude_patterns: self.exclude_patterns.clone(),
+            use_embedded_metadata: self.use_embedded_metadata,
+            auto_tag_from_folder: self.auto_tag_from_folder,
+            rescan_debounce_secs: self.rescan_debounce_secs,
+            license_expiry_warning_days: self.license_expiry_warning_days,
+            license_expired_notice_shown: self.license_expired_notice_shown,
+            show_quality_badge: self.show_quality_badge,
+            show_duration_badge: self.show_duration_badge,
+            group_by_folder: self.group_by_folder,
+            mark_watched_on_play: self.mark_watched_on_play,
+            hdr_colorspace_hint: self.hdr_colorspace_hint,
+            pin_favorites_to_top: self.pin_favorites_to_top,
+            pin_min_rating: self.pin_min_rating,
+            default_subtitle: Some(self.default_subtitle.trim().to_string()).filter(|s| !s.is_empty()),
+            default_audio_lang: Some(self.default_audio_lang.trim().to_string()).filter(|s| !s.is_empty()),
+            ffmpeg_path: self.ffmpeg_path.clone(),
+            ffprobe_path: self.ffprobe_path.clone(),
+            screenshot_dir: self.screenshot_dir.clone(),
+            auto_collapse_filters: self.auto_collapse_filters,
+            max_texture_cache: self.max_texture_cache,
+            thumbnail_eviction_enabled: self.thumbnail_eviction_enabled,
+            thumbnail_eviction_days: self.thumbnail_eviction_days,
+            thumbnail_cache_budget_mb: self.thumbnail_cache_budget_mb,
+            thumbnail_max_width: self.thumbnail_max_width,
+            thumbnail_quality: self.thumbnail_quality,
+            scene_threshold: self.scene_threshold,
+            max_scenes: self.max_scenes,
+            scene_thread_count: self.scene_thread_count,
+            max_concurrent_texture_loads: self.max_concurrent_texture_loads,
+            similar_scene_threshold: self.similar_scene_threshold,
+            filter_presets: self.filter_presets.clone(),
+            tag_rules: self.tag_rules.clone(),
         };
         
         if let Err(e) = database::save_settings(&settings) {
@@ -392,7 +920,6 @@ impl VideoPlayerApp {
             return;
         }
         
-        let video_extensions = ["mp4", "avi", "mkv", "mov", "wmv", "flv", "webm", "m4v", "mpg", "mpeg"];
         let cache_dir = thumbnail::get_cache_dir();
         
         let mut folders_to_add: Vec<PathBuf> = Vec::new();
@@ -402,70 +929,15 @@ impl VideoPlayerApp {
             if let Some(path) = dropped_file.path {
                 if path.is_dir() {
                     folders_to_add.push(path);
-                } else if path.is_file() {
-                    // Check if it's a video file
-                    if let Some(ext) = path.extension() {
-                        let ext_lower = ext.to_string_lossy().to_lowercase();
-                        if video_extensions.contains(&ext_lower.as_str()) {
-                            files_to_add.push(path);
-                        }
-                    }
-                }
-            }
-        }
-        
-        // Process folders first
-        for folder in folders_to_add {
-            if !self.is_premium && self.database.videos.len() >= 100 {
-                self.show_premium_promotion_window = true;
-                break;
-            }
-            
-            eprintln!("[drag&drop] Processing folder: {:?}", folder);
-            
-            let videos = video_scanner::scan_directory(folder.clone());
-            
-            // Get existing video paths for quick lookup
-            let existing_paths: HashSet<PathBuf> = self.database.videos.iter()
-                .filter_map(|v| v.path.canonicalize().ok())
-                .collect();
-            
-            // Filter out existing videos
-            let new_videos: Vec<_> = videos.into_iter()
-                .filter(|video| {
-                    match video.path.canonicalize() {
-                        Ok(canonical_path) => !existing_paths.contains(&canonical_path),
-                        Err(_) => false,
-                    }
-                })
-                .collect();
-            
-            // Process videos with free tier limit
-            let current_count = self.database.videos.len();
-            let processed_videos = if self.is_premium {
-                video_scanner::process_videos_parallel(new_videos, &cache_dir)
-            } else {
-                let remaining_slots = 100_usize.saturating_sub(current_count);
-                if remaining_slots == 0 {
-                    self.show_premium_promotion_window = true;
-                    Vec::new()
-                } else {
-                    if new_videos.len() > remaining_slots {
-                        self.show_premium_promotion_window = true;
-                    }
-                    video_scanner::process_videos_parallel_with_limit(new_videos, &cache_dir, remaining_slots)
+                } else if path.is_file() && VideoFile::is_video_file(&path, &self.video_extensions) {
+                    files_to_add.push(path);
                 }
-            };
-            
-            for video in processed_videos {
-                self.database.add_video(video);
             }
-            
-            // Add folder to watched folders
-            self.watched_folders.insert(folder);
         }
         
         // Process individual files
+        let mut issues = Vec::new();
+        let mut added = Vec::new();
         for file in files_to_add {
             if !self.is_premium && self.database.videos.len() >= 100 {
                 self.show_premium_promotion_window = true;
@@ -475,7 +947,10 @@ impl VideoPlayerApp {
             // Check if file already exists in database
             let canonical_path = match file.canonicalize() {
                 Ok(p) => p,
-                Err(_) => continue,
+                Err(e) => {
+                    issues.push((file, format!("{}: {}", self.i18n.t("issue_path_unreadable"), e)));
+                    continue;
+                }
             };
             
             let already_exists = self.database.videos.iter()
@@ -492,15 +967,29 @@ impl VideoPlayerApp {
             video.thumbnail_path = thumbnail::create_video_thumbnail(&file, &cache_dir);
             video.duration = get_video_duration(&file);
             video.resolution = get_video_resolution(&file);
+            video.frame_rate = get_video_frame_rate(&file);
+            (video.codec, video.container) = get_video_codec(&file);
+            video.is_hdr = is_video_hdr(&file);
+            video.chapters = get_video_chapters(&file);
+            video.content_hash = video_scanner::compute_content_hash(&file);
+            video_scanner::apply_embedded_metadata(&mut video);
+            Self::apply_tag_rules(&self.compiled_tag_rules, &mut self.database, &mut video);
             
+            if let Some(key) = video_scanner::processing_issue(&video) {
+                issues.push((file.clone(), self.i18n.t(key)));
+            }
+            added.push(video.id.clone());
             self.database.add_video(video);
         }
         
-        // Setup folder watcher for new folders
-        self.setup_folder_watcher();
+        // Save the new videos
+        self.persist_videos(&added);
+        self.report_import_issues(issues);
         
-        // Save database
-        let _ = database::save_database(&self.database);
+        // Folders are scanned in the background (after the files, so the free tier limit accounts for them)
+        if !folders_to_add.is_empty() {
+            self.start_folder_import(folders_to_add);
+        }
     }
     
     pub fn add_files(&mut self) {
@@ -512,10 +1001,12 @@ impl VideoPlayerApp {
         }
         
         if let Some(files) = FileDialog::new()
-            .add_filter("Video Files", &["mp4", "avi", "mkv", "mov", "wmv", "flv", "webm", "m4v", "mpg", "mpeg"])
+            .add_filter("Video Files", &self.video_extensions)
             .pick_files()
         {
             let cache_dir = thumbnail::get_cache_dir();
+            let mut issues = Vec::new();
+            let mut added = Vec::new();
             for file in files {
                 // Check limit for each file
                 if !self.is_premium && self.database.videos.len() >= 100 {
@@ -532,12 +1023,88 @@ impl VideoPlayerApp {
                 // Get video metadata using FFmpeg
                 video.duration = get_video_duration(&file);
                 video.resolution = get_video_resolution(&file);
+                video.frame_rate = get_video_frame_rate(&file);
+                (video.codec, video.container) = get_video_codec(&file);
+                video.is_hdr = is_video_hdr(&file);
+                video.chapters = get_video_chapters(&file);
+                video.content_hash = video_scanner::compute_content_hash(&file);
+                video_scanner::apply_embedded_metadata(&mut video);
+                Self::apply_tag_rules(&self.compiled_tag_rules, &mut self.database, &mut video);
                 
+                if let Some(key) = video_scanner::processing_issue(&video) {
+                    issues.push((file.clone(), self.i18n.t(key)));
+                }
+                added.push(video.id.clone());
                 self.database.add_video(video);
             }
-            // Save database
-            let _ = database::save_database(&self.database);
+            // Save the new videos
+            self.persist_videos(&added);
+            self.report_import_issues(issues);
+        }
+    }
+    
+    /// Tag a video found in a watched folder with its parent directory name
+    fn apply_folder_tag(database: &mut VideoDatabase, video: &mut VideoFile) {
+        if let Some(tag) = video_scanner::folder_tag(&video.path) {
+            if !video.tags.contains(&tag) {
+                video.tags.push(tag.clone());
+            }
+            database.add_tag(tag);
+        }
+    }
+    
+    /// Add the tags produced by the auto-tagging rules; returns how many were new to the video
+    fn apply_tag_rules(rules: &[(regex::Regex, String)], database: &mut VideoDatabase, video: &mut VideoFile) -> usize {
+        let mut added = 0;
+        for tag in video_scanner::rule_tags(rules, video) {
+            if !video.tags.contains(&tag) {
+                video.tags.push(tag.clone());
+                added += 1;
+            }
+            database.add_tag(tag);
+        }
+        added
+    }
+    
+    /// Recompile the rules after they were edited
+    fn rebuild_tag_rules(&mut self) {
+        (self.compiled_tag_rules, self.tag_rule_errors) = video_scanner::compile_tag_rules(&self.tag_rules);
+        self.tag_rules_preview = None;
+    }
+    
+    /// (videos, new tags) that applying the rules to the whole library would add
+    fn preview_tag_rules(&self) -> (usize, usize) {
+        self.database.videos.iter()
+            .map(|video| video_scanner::rule_tags(&self.compiled_tag_rules, video).iter()
+                .filter(|tag| !video.tags.contains(tag))
+                .count())
+            .filter(|&count| count > 0)
+            .fold((0, 0), |(videos, tags), count| (videos + 1, tags + count))
+    }
+    
+    /// Run the auto-tagging rules over every video in the library
+    fn apply_tag_rules_to_all(&mut self) {
+        let mut changed = Vec::new();
+        let mut videos = std::mem::take(&mut self.database.videos);
+        for video in &mut videos {
+            if Self::apply_tag_rules(&self.compiled_tag_rules, &mut self.database, video) > 0 {
+                changed.push(video.id.clone());
+            }
+        }
+        self.database.videos = videos;
+        self.persist_videos(&changed);
+        eprintln!("[tag_rules] Tagged {} videos", changed.len());
+        self.status_message = Some(self.i18n.t("tag_rules_applied").replace("{}", &changed.len().to_string()));
+    }
+    
+    /// Keep the problems of the last import/rescan and open the issues window if there are any
+    fn report_import_issues(&mut self, issues: Vec<(PathBuf, String)>) {
+        if issues.is_empty() {
+            return;
         }
+        eprintln!("[import] {} files had problems", issues.len());
+        self.import_issues = issues;
+        self.show_import_issues_window = true;
     }
     
     pub fn add_folder(&mut self) {
@@ -549,113 +1116,226 @@ impl VideoPlayerApp {
         }
         
         if let Some(folder) = FileDialog::new().pick_folder() {
+            self.start_folder_import(vec![folder]);
+        }
+    }
+    
+    /// Scan folders and import their new videos on a background thread.
+    /// Folders are added to the watched folders once the import completes without cancellation.
+    pub fn start_folder_import(&mut self, folders: Vec<PathBuf>) {
+        if self.import_receiver.is_some() {
+            self.status_message = Some(self.i18n.t("import_already_running"));
+            return;
+        }
+        
+        let existing: Vec<PathBuf> = self.database.videos.iter().map(|v| v.path.clone()).collect();
+        // Free tier: only as many new videos as there are slots left
+        let limit = if self.is_premium {
+            None
+        } else {
+            Some(100_usize.saturating_sub(self.database.videos.len()))
+        };
+        let extensions = self.video_extensions.clone();
+        let exclude_patterns = self.exclude_patterns.clone();
+        let scan_folders = folders.clone();
+        
+        let (tx, rx) = channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let cancel_flag = Arc::clone(&cancel);
+        
+        std::thread::spawn(move || {
             let cache_dir = thumbnail::get_cache_dir();
-            let videos = video_scanner::scan_directory(folder.clone());
             
-            eprintln!("[add_folder] Scanned {} videos from folder: {:?}", videos.len(), folder);
-            eprintln!("[add_folder] Current database has {} videos", self.database.videos.len());
-            
-            // Get existing video paths for quick lookup (normalized for case-insensitive comparison)
-            let existing_paths: HashSet<PathBuf> = self.database.videos.iter()
-                .filter_map(|v| {
-                    // Canonicalize path to handle case-insensitivity and path normalization
-                    match v.path.canonicalize() {
-                        Ok(p) => Some(p),
-                        Err(e) => {
-                            eprintln!("[add_folder] Warning: Failed to canonicalize existing path {:?}: {}", v.path, e);
-                            None
-                        }
-                    }
-                })
+            // Canonicalize paths to handle case-insensitivity and path normalization
+            let mut known_paths: HashSet<PathBuf> = existing.iter()
+                .filter_map(|path| path.canonicalize().ok())
                 .collect();
             
-            eprintln!("[add_folder] Built index of {} existing canonical paths", existing_paths.len());
-            
-            // Filter out existing videos
-            let new_videos: Vec<_> = videos.into_iter()
-                .filter(|video| {
+            let mut new_videos = Vec::new();
+            let mut scanned = 0;
+            for folder in scan_folders {
+                eprintln!("[import] Scanning folder: {:?}", folder);
+                let videos = video_scanner::scan_directory_with_progress(folder, &extensions, &exclude_patterns, &cancel_flag, |found| {
+                    let _ = tx.send(ImportUpdate::Scanned(scanned + found));
+                });
+                scanned += videos.len();
+                
+                // Skip videos already in the library (or found twice via overlapping folders)
+                new_videos.extend(videos.into_iter().filter(|video| {
                     match video.path.canonicalize() {
-                        Ok(canonical_path) => {
-                            if existing_paths.contains(&canonical_path) {
-                                eprintln!("[add_folder] Skipping existing video: {:?}", video.path);
-                                false
-                            } else {
-                                true
-                            }
-                        }
+                        Ok(canonical_path) => known_paths.insert(canonical_path),
                         Err(e) => {
-                            eprintln!("[add_folder] Failed to canonicalize path {:?}: {}", video.path, e);
+                            let _ = tx.send(ImportUpdate::Skipped(video.path.clone(), e.to_string()));
                             false
                         }
                     }
-                })
-                .collect();
+                }));
+            }
+            
+            if let Some(limit) = limit {
+                if new_videos.len() > limit {
+                    eprintln!("[Free tier] Limiting to {} videos (slots remaining)", limit);
+                    new_videos.truncate(limit);
+                    let _ = tx.send(ImportUpdate::LimitReached);
+                }
+            }
             
-            eprintln!("[add_folder] Found {} new videos to process", new_videos.len());
+            let total = new_videos.len();
+            eprintln!("[import] Found {} new videos to process", total);
+            let _ = tx.send(ImportUpdate::Processing(total));
             
-            // Apply free tier limit and process videos in parallel
-            let current_count = self.database.videos.len();
-            let processed_videos = if self.is_premium {
-                // Premium: process all videos in parallel
-                video_scanner::process_videos_parallel(new_videos, &cache_dir)
-            } else {
-                // Free tier: limit to 100 total videos
-                let remaining_slots = 100_usize.saturating_sub(current_count);
-                if remaining_slots == 0 {
-                    eprintln!("[Free tier] Video limit reached. Cannot add more videos.");
+            let done = std::sync::atomic::AtomicUsize::new(0);
+            video_scanner::process_videos_streaming(new_videos, &cache_dir, &cancel_flag, |video| {
+                let done = done.fetch_add(1, Ordering::Relaxed) + 1;
+                let _ = tx.send(ImportUpdate::Processed { video: Box::new(video), done, total });
+            });
+        });
+        
+        self.import_receiver = Some(rx);
+        self.import_cancel = Some(cancel);
+        self.import_progress = Some((0, 0, 0));
+        self.import_folders = folders;
+        self.pending_import_issues.clear();
+    }
+    
+    /// Add videos reported by the import worker and finish the import when it is done
+    fn process_folder_import(&mut self, ctx: &egui::Context) {
+        let Some(ref receiver) = self.import_receiver else {
+            return;
+        };
+        
+        let mut finished = false;
+        let mut added_ids = Vec::new();
+        loop {
+            match receiver.try_recv() {
+                Ok(ImportUpdate::Scanned(found)) => {
+                    if let Some(progress) = self.import_progress.as_mut() {
+                        progress.0 = found;
+                    }
+                }
+                Ok(ImportUpdate::LimitReached) => {
                     self.show_premium_promotion_window = true;
-                    Vec::new()
-                } else {
-                    if new_videos.len() > remaining_slots {
-                        eprintln!("[Free tier] Limiting to {} videos (slots remaining)", remaining_slots);
+                }
+                Ok(ImportUpdate::Processing(total)) => {
+                    if let Some(progress) = self.import_progress.as_mut() {
+                        progress.2 = total;
+                    }
+                }
+                Ok(ImportUpdate::Processed { video, done, total }) => {
+                    // Re-check the limit in case videos were added another way meanwhile
+                    if !self.is_premium && self.database.videos.len() >= 100 {
                         self.show_premium_promotion_window = true;
+                    } else if self.database.get_video_by_path(&video.path).is_none() {
+                        eprintln!("[import] Adding new video: {:?}", video.path);
+                        if let Some(key) = video_scanner::processing_issue(&video) {
+                            self.pending_import_issues.push((video.path.clone(), self.i18n.t(key)));
+                        }
+                        added_ids.push(video.id.clone());
+                        let mut video = *video;
+                        if self.auto_tag_from_folder {
+                            Self::apply_folder_tag(&mut self.database, &mut video);
+                        }
+                        Self::apply_tag_rules(&self.compiled_tag_rules, &mut self.database, &mut video);
+                        self.database.add_video(video);
+                    }
+                    if let Some(progress) = self.import_progress.as_mut() {
+                        progress.1 = done;
+                        progress.2 = total;
                     }
-                    video_scanner::process_videos_parallel_with_limit(new_videos, &cache_dir, remaining_slots)
                 }
-            };
-            
-            // Add processed videos to database
-            for video in processed_videos {
-                eprintln!("[add_folder] Adding new video: {:?}", video.path);
-                self.database.add_video(video);
+                Ok(ImportUpdate::Skipped(path, error)) => {
+                    eprintln!("[import] Skipping unreadable file {:?}: {}", path, error);
+                    self.pending_import_issues.push((path, format!("{}: {}", self.i18n.t("issue_path_unreadable"), error)));
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
+                }
+            }
+        }
+        
+        // Persist as videos arrive so a crash mid-import keeps what was processed
+        if !added_ids.is_empty() {
+            self.import_added += added_ids.len();
+            self.persist_videos(&added_ids);
+        }
+        
+        if finished {
+            let cancelled = self.import_cancel.as_ref().is_some_and(|c| c.load(Ordering::Relaxed));
+            let folders = std::mem::take(&mut self.import_folders);
+            // A cancelled folder is not watched, otherwise the next rescan would import the rest anyway
+            if !cancelled {
+                self.watched_folders.extend(folders);
+                self.setup_folder_watcher();
+                self.save_settings();
             }
             
-            // Add folder to watched folders
-            self.watched_folders.insert(folder.clone());
-            self.setup_folder_watcher();
+            let message_key = if cancelled { "import_cancelled" } else { "import_finished" };
+            let mut message = self.i18n.t(message_key).replace("{}", &self.import_added.to_string());
+            let freed = self.prune_thumbnail_cache();
+            if freed > 0 {
+                message.push_str(" — ");
+                message.push_str(&self.i18n.t("thumbnail_cache_pruned").replace("{}", &format!("{:.1}", freed as f64 / 1024.0 / 1024.0)));
+            }
+            self.status_message = Some(message);
+            eprintln!("[import] Finished: {} videos added (cancelled: {})", self.import_added, cancelled);
             
-            // Save database
-            let _ = database::save_database(&self.database);
+            self.import_receiver = None;
+            self.import_cancel = None;
+            self.import_progress = None;
+            self.import_added = 0;
+            let issues = std::mem::take(&mut self.pending_import_issues);
+            self.report_import_issues(issues);
+        } else {
+            ctx.request_repaint_after(std::time::Duration::from_millis(250));
         }
     }
     
     pub fn rescan_folders(&mut self) {
         // Only rescan folders that are in watched_folders (explicitly added by user)
         // Do not automatically scan all video parent folders
-        let folders = self.watched_folders.clone();
+        let folders: Vec<PathBuf> = self.watched_folders.iter().cloned().collect();
         
         if folders.is_empty() {
             eprintln!("[rescan] No watched folders to scan");
             return;
         }
         
+        // A full rescan covers any pending watcher changes
+        self.pending_rescan_dirs.clear();
         eprintln!("[rescan] Rescanning {} watched folders", folders.len());
-        
+        let issues = self.rescan_paths(folders);
+        self.report_import_issues(issues);
+    }
+    
+    /// Rescan only the directories reported by the file watcher (nested ones are covered by their parent)
+    fn rescan_changed_dirs(&mut self, dirs: HashSet<PathBuf>) {
+        let folders: Vec<PathBuf> = dirs.iter()
+            .filter(|dir| !dirs.iter().any(|other| other != *dir && dir.starts_with(other)))
+            .cloned()
+            .collect();
+        eprintln!("[rescan] Rescanning {} changed folders", folders.len());
+        let issues = self.rescan_paths(folders);
+        self.report_import_issues(issues);
+    }
+    
+    /// Scan the given folders (recursively) for new/changed files, then drop videos whose files are gone.
+    /// Returns the files that were skipped or could not be fully read.
+    fn rescan_paths(&mut self, folders: Vec<PathBuf>) -> Vec<(PathBuf, String)> {
+        let mut issues = Vec::new();
         let cache_dir = thumbnail::get_cache_dir();
-        let mut videos_to_remove = Vec::new();
-        
-        // Check for deleted files
-        for video in &self.database.videos {
-            if !video.path.exists() {
-                videos_to_remove.push(video.id.clone());
-            }
-        }
         
-        // Remove deleted videos
-        for video_id in videos_to_remove {
-            eprintln!("[rescan] Removing deleted video: {}", video_id);
-            self.delete_video(&video_id, true);
-        }
+        // Missing files are only removed after scanning, so that moved/renamed
+        // files can be matched back to their record by content hash.
+        // Only videos under the scanned folders count, and never those on a folder that is
+        // currently unavailable (e.g. a disconnected drive) - their files are not gone for good
+        let mut missing_videos: Vec<(String, u64, Option<String>)> = self.database.videos.iter()
+            .filter(|v| folders.iter().any(|folder| v.path.starts_with(folder)))
+            .filter(|v| !self.watcher_unavailable.iter().any(|folder| v.path.starts_with(folder)))
+            .filter(|v| !v.path.exists())
+            .map(|v| (v.id.clone(), v.file_size, v.content_hash.clone()))
+            .collect();
         
         // Scan each folder for new or updated files
         for folder_path in folders {
@@ -677,7 +1357,7 @@ impl VideoPlayerApp {
             
             eprintln!("[rescan] Found {} existing videos in this folder tree", existing_paths.len());
             
-            let scanned_videos = video_scanner::scan_directory(folder_path.clone());
+            let scanned_videos = video_scanner::scan_directory(folder_path.clone(), &self.video_extensions, &self.exclude_patterns);
             
             // Separate new videos and existing videos that need updates
             let mut new_videos: Vec<_> = Vec::new();
@@ -688,11 +1368,16 @@ impl VideoPlayerApp {
                     Ok(p) => p,
                     Err(e) => {
                         eprintln!("[rescan] Failed to canonicalize path {:?}: {}", video.path, e);
+                        issues.push((video.path.clone(), format!("{}: {}", self.i18n.t("issue_path_unreadable"), e)));
                         continue;
                     }
                 };
                 
                 if !existing_paths.contains(&canonical_path) {
+                    if let Some(video_id) = Self::match_moved_video(&mut missing_videos, &video) {
+                        self.relocate_video(&video_id, &video.path);
+                        continue;
+                    }
                     eprintln!("[rescan] Found new video: {:?}", video.path);
                     new_videos.push(video);
                 } else {
@@ -700,7 +1385,11 @@ impl VideoPlayerApp {
                     if let Some(existing_video) = self.database.get_video_by_path(&video.path) {
                         let needs_update = existing_video.duration.is_none() 
                             || existing_video.resolution.is_none()
-                            || existing_video.thumbnail_path.is_none();
+                            || existing_video.frame_rate.is_none()
+                            || existing_video.codec.is_none()
+                            || existing_video.thumbnail_path.is_none()
+                            || existing_video.content_hash.is_none()
+                            || existing_video.modified_time.is_none();
                         
                         // Check for file size change
                         let size_changed = if let Ok(metadata) = std::fs::metadata(&video.path) {
@@ -709,7 +1398,13 @@ impl VideoPlayerApp {
                             false
                         };
                         
-                        if needs_update || size_changed {
+                        // Re-encodes can keep the same size, so also check the modification time
+                        let mtime_advanced = matches!(
+                            (existing_video.modified_time, video.modified_time),
+                            (Some(stored), Some(current)) if current > stored
+                        );
+                        
+                        if needs_update || size_changed || mtime_advanced {
                             videos_needing_update.push(video.path.clone());
                         }
                     }
@@ -722,18 +1417,32 @@ impl VideoPlayerApp {
             let processed_new_videos = video_scanner::process_videos_parallel(new_videos, &cache_dir);
             
             // Add new videos to database
-            for video in processed_new_videos {
+            for mut video in processed_new_videos {
+                if let Some(key) = video_scanner::processing_issue(&video) {
+                    issues.push((video.path.clone(), self.i18n.t(key)));
+                }
+                if self.auto_tag_from_folder {
+                    Self::apply_folder_tag(&mut self.database, &mut video);
+                }
+                Self::apply_tag_rules(&self.compiled_tag_rules, &mut self.database, &mut video);
                 self.database.add_video(video);
             }
             
             // Update existing videos that need metadata (this is typically fewer, so less critical)
             for video_path in videos_needing_update {
                 if let Some(existing_video) = self.database.get_video_by_path_mut(&video_path) {
-                    // Check if file was modified (by comparing file size)
+                    // Check if file was modified (by comparing file size and modification time)
                     if let Ok(metadata) = std::fs::metadata(&video_path) {
                         let file_size = metadata.len();
-                        if existing_video.file_size != file_size {
-                            eprintln!("[rescan] File size changed for: {:?}", video_path);
+                        let modified_time = VideoFile::read_modified_time(&video_path);
+                        let mtime_advanced = matches!(
+                            (existing_video.modified_time, modified_time),
+                            (Some(stored), Some(current)) if current > stored
+                        );
+                        // Videos stored before modified_time existed only record it
+                        existing_video.modified_time = modified_time;
+                        if existing_video.file_size != file_size || mtime_advanced {
+                            eprintln!("[rescan] File changed: {:?}", video_path);
                             // Update file size
                             existing_video.file_size = file_size;
                             
@@ -743,6 +1452,11 @@ impl VideoPlayerApp {
                             // Update metadata
                             existing_video.duration = get_video_duration(&video_path);
                             existing_video.resolution = get_video_resolution(&video_path);
+                            existing_video.frame_rate = get_video_frame_rate(&video_path);
+                            (existing_video.codec, existing_video.container) = get_video_codec(&video_path);
+                            existing_video.is_hdr = is_video_hdr(&video_path);
+                            existing_video.chapters = get_video_chapters(&video_path);
+                            existing_video.content_hash = video_scanner::compute_content_hash(&video_path);
                         }
                     }
                     
@@ -753,3497 +1467,8918 @@ impl VideoPlayerApp {
                     if existing_video.resolution.is_none() {
                         existing_video.resolution = get_video_resolution(&video_path);
                     }
+                    if existing_video.frame_rate.is_none() {
+                        existing_video.frame_rate = get_video_frame_rate(&video_path);
+                    }
+                    if existing_video.codec.is_none() {
+                        // Probed together: videos imported before either existed have no codec yet
+                        (existing_video.codec, existing_video.container) = get_video_codec(&video_path);
+                        existing_video.is_hdr = is_video_hdr(&video_path);
+                    }
                     // Update missing thumbnail
                     if existing_video.thumbnail_path.is_none() {
                         existing_video.thumbnail_path = thumbnail::create_video_thumbnail(&video_path, &cache_dir);
                     }
+                    // Backfill content hash for videos imported before it existed
+                    if existing_video.content_hash.is_none() {
+                        existing_video.content_hash = video_scanner::compute_content_hash(&video_path);
+                    }
                 }
             }
         }
         
-        // Update last rescan time
-        self.last_rescan_time = SystemTime::now();
-        self.pending_rescan = false;
+        // Remove videos whose files were deleted (not found anywhere by hash)
+        for (video_id, _, _) in missing_videos {
+            eprintln!("[rescan] Removing deleted video: {}", video_id);
+            self.delete_video(&video_id, true);
+        }
         
         let _ = database::save_database(&self.database);
+        issues
     }
     
-    pub fn set_rating(&mut self, video_id: &str, rating: u8) {
+    /// Find a missing video whose content hash matches a newly scanned file, taking it out of `missing`
+    fn match_moved_video(missing: &mut Vec<(String, u64, Option<String>)>, scanned: &VideoFile) -> Option<String> {
+        // Compare sizes first so unrelated files are never read
+        if !missing.iter().any(|(_, size, hash)| hash.is_some() && *size == scanned.file_size) {
+            return None;
+        }
+        let scanned_hash = video_scanner::compute_content_hash(&scanned.path)?;
+        let index = missing.iter().position(|(_, size, hash)| {
+            *size == scanned.file_size && hash.as_deref() == Some(scanned_hash.as_str())
+        })?;
+        Some(missing.remove(index).0)
+    }
+    
+    /// Point an existing record at the new location of its file, keeping tags, rating, scenes and notes
+    fn relocate_video(&mut self, video_id: &str, new_path: &std::path::Path) {
         if let Some(video) = self.database.get_video_mut(video_id) {
-            // Free tier: only favorite (1) or no favorite (0)
-            // Premium: 1-5 star ratings
-            if !self.is_premium && rating > 1 {
-                video.rating = 1; // Cap at favorite for free tier
-            } else {
-                video.rating = rating.min(5); // Cap at 5 stars
+            eprintln!("[rescan] Detected moved video: {:?} -> {:?}", video.path, new_path);
+            // Keep custom titles; follow the file name only if the title was never edited
+            let old_file_name = video.path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            if video.title == old_file_name {
+                if let Some(new_file_name) = new_path.file_name().and_then(|n| n.to_str()) {
+                    video.title = new_file_name.to_string();
+                }
             }
-            let _ = database::save_database(&self.database);
+            video.path = new_path.to_path_buf();
         }
     }
     
-    /// Set rating for multiple videos at once
-    pub fn set_rating_for_selected(&mut self, rating: u8) {
-        let video_ids: Vec<String> = self.selected_videos.iter().cloned().collect();
-        for video_id in video_ids {
-            if let Some(video) = self.database.get_video_mut(&video_id) {
-                if !self.is_premium && rating > 1 {
-                    video.rating = 1;
-                } else {
-                    video.rating = rating.min(5);
-                }
+    /// Launch mpv for a video at the given timestamp using the current player settings
+    pub fn play_video_at(&mut self, video_path: &std::path::Path, timestamp: f64) {
+        self.play_videos(&[video_path.to_path_buf()], timestamp);
+    }
+    
+    /// Whether a context menu action on `clicked_id` applies to the whole selection
+    fn acts_on_selection(&self, clicked_id: &str) -> bool {
+        self.selected_videos.len() > 1 && self.selected_videos.contains(clicked_id)
+    }
+    
+    /// IDs a context menu action on `clicked_id` applies to: the selection it belongs to, or just that video
+    fn target_ids_for(&self, clicked_id: &str) -> Vec<String> {
+        if self.acts_on_selection(clicked_id) {
+            self.selected_videos.iter().cloned().collect()
+        } else {
+            vec![clicked_id.to_string()]
+        }
+    }
+    
+    /// Paths of the selection (in display order), or of every video in the current filtered list
+    fn playlist_paths(&self, clicked_id: &str) -> Vec<PathBuf> {
+        let use_selection = self.acts_on_selection(clicked_id);
+        self.get_filtered_videos().into_iter()
+            .filter(|v| !use_selection || self.selected_videos.contains(&v.id))
+            .map(|v| v.path.clone())
+            .collect()
+    }
+    
+    /// Play the selection or the current list as a playlist in display order
+    pub fn play_all(&mut self, clicked_id: &str) {
+        let paths = self.playlist_paths(clicked_id);
+        if !paths.is_empty() {
+            eprintln!("[Play all] Queuing {} videos", paths.len());
+            self.play_videos(&paths, 0.0);
+        }
+    }
+    
+    /// Play the selection or the current list as a playlist in random order
+    pub fn shuffle_play(&mut self, clicked_id: &str) {
+        use rand::seq::SliceRandom;
+        let mut paths = self.playlist_paths(clicked_id);
+        paths.shuffle(&mut rand::thread_rng());
+        if !paths.is_empty() {
+            eprintln!("[Shuffle] Queuing {} videos", paths.len());
+            self.play_videos(&paths, 0.0);
+        }
+    }
+    
+    /// Launch mpv with one video or a playlist (playlists start from the beginning: pass 0)
+    pub fn play_videos(&mut self, video_paths: &[PathBuf], timestamp: f64) {
+        let Some(video_path) = video_paths.first() else {
+            return;
+        };
+        let first_video = self.database.videos.iter().find(|v| v.path == *video_path);
+        let options = video_player::MpvOptions {
+            always_on_top: self.mpv_always_on_top,
+            loop_playback: self.loop_playback,
+            fullscreen: self.fullscreen_on_launch,
+            use_gpu_hq: self.is_premium && self.use_gpu_hq,
+            use_custom_shaders: self.is_premium && self.use_custom_shaders,
+            selected_shader: self.selected_shader.as_deref(),
+            volume: first_video.and_then(|v| v.preferred_volume).unwrap_or(self.mpv_volume),
+            speed: first_video.and_then(|v| v.preferred_speed),
+            subtitle: Some(self.default_subtitle.as_str()),
+            audio_lang: Some(self.default_audio_lang.as_str()),
+            screenshot_dir: &self.effective_screenshot_dir(),
+            colorspace_hint: self.hdr_colorspace_hint && first_video.is_some_and(|v| v.is_hdr),
+        };
+        self.player_launch_levels = (options.volume, options.speed.unwrap_or(1.0));
+        if let Err(e) = video_player::play_video_at_timestamp(video_paths, timestamp, &options) {
+            eprintln!("Video playback error: {}", e);
+            self.player_error_message = Some(if e.is::<video_player::PlayerNotFound>() {
+                self.i18n.t("player_not_found")
+            } else {
+                format!("{}: {}", self.i18n.t("player_launch_failed"), e)
+            });
+            return;
+        }
+        
+        // Update play statistics
+        if let Some(video) = self.database.videos.iter_mut().find(|v| v.path == *video_path) {
+            video.last_played = Some(chrono::Utc::now());
+            video.play_count = video.play_count.saturating_add(1);
+            if self.mark_watched_on_play {
+                video.watched = true;
             }
+            let _ = database::persist_video(video);
+        }
+        
+        // Track the playback position so it can be resumed later
+        if self.playback_monitor_receiver.is_none() {
+            let (sender, receiver) = channel();
+            video_player::spawn_playback_monitor(sender);
+            self.playback_monitor_receiver = Some(receiver);
         }
-        let _ = database::save_database(&self.database);
     }
     
-    /// Activate a license key
-    pub fn activate_license(&mut self, license_key: &str) {
-        match license::verify_license(license_key) {
-            Ok(license) if license.is_premium_active() => {
-                self.current_license = Some(license.clone());
-                self.is_premium = true;
-                self.license_status_message = Some(format!(
-                    "✅ License activated successfully!\nIssued to: {}\nExpires: {}",
-                    license.info.issued_to,
-                    license.info.expires_at.map(|ts| license::format_timestamp(ts))
-                        .unwrap_or_else(|| "Never".to_string())
-                ));
-                
-                // Save license key to settings (premium status derived from license)
-                if let Ok(mut settings) = database::load_settings() {
-                    settings.license_key = Some(license_key.to_string());
-                    let _ = database::save_settings(&settings);
+    /// Generate scenes for every video without scenes on a background thread
+    pub fn start_scene_batch(&mut self) {
+        if self.scene_batch_receiver.is_some() {
+            return;
+        }
+        
+        let pending: Vec<VideoFile> = self.database.videos.iter()
+            .filter(|v| v.scenes.is_empty() && v.path.exists())
+            .cloned()
+            .collect();
+        if pending.is_empty() {
+            return;
+        }
+        
+        let (tx, rx) = channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let cancel_flag = Arc::clone(&cancel);
+        let (scene_threshold, max_scenes, scene_thread_count) = (self.scene_threshold, self.max_scenes, self.scene_thread_count);
+        let total = pending.len();
+        
+        std::thread::spawn(move || {
+            let cache_dir = thumbnail::get_cache_dir();
+            for (i, mut video) in pending.into_iter().enumerate() {
+                if cancel_flag.load(Ordering::Relaxed) {
+                    eprintln!("[SceneBatch] Cancelled after {} / {}", i, total);
+                    break;
+                }
+                if let Err(e) = scene_detection::detect_scenes(&mut video, &cache_dir, scene_threshold, max_scenes, scene_thread_count) {
+                    eprintln!("[SceneBatch] Failed to generate scenes for {:?}: {}", video.path, e);
+                }
+                let update = SceneBatchUpdate {
+                    video_id: video.id,
+                    scenes: video.scenes,
+                    thumbnail_path: None,
+                    done: i + 1,
+                    total,
+                };
+                if tx.send(update).is_err() {
+                    break;
                 }
-                
-                eprintln!("[License] Premium license activated for {}", license.info.issued_to);
-            },
-            Ok(license) if license.is_expired => {
-                self.license_status_message = Some(format!(
-                    "❌ License expired\nExpired on: {}",
-                    license.info.expires_at.map(|ts| license::format_timestamp(ts))
-                        .unwrap_or_else(|| "Unknown".to_string())
-                ));
-            },
-            Ok(license) => {
-                self.license_status_message = Some(format!(
-                    "❌ Invalid license type: {}",
-                    license.info.license_type
-                ));
-            },
-            Err(e) => {
-                self.license_status_message = Some(format!("❌ License verification failed:\n{}", e));
             }
-        }
+        });
+        
+        self.scene_batch_receiver = Some(rx);
+        self.scene_batch_cancel = Some(cancel);
+        self.scene_batch_progress = Some((0, total));
+        self.scene_batch_label = "scene_batch_progress";
     }
     
-    pub fn generate_scenes(&mut self, video_id: &str) {
-        if let Some(video) = self.database.get_video_mut(video_id) {
+    /// Extract the main thumbnails of several videos again on the batch worker thread
+    pub fn start_thumbnail_batch(&mut self, video_ids: &[String]) {
+        if self.scene_batch_receiver.is_some() {
+            self.status_message = Some(self.i18n.t("batch_already_running"));
+            return;
+        }
+        
+        let pending: Vec<(String, PathBuf)> = video_ids.iter()
+            .filter_map(|id| self.database.get_video(id))
+            .map(|v| (v.id.clone(), v.path.clone()))
+            .collect();
+        if pending.is_empty() {
+            return;
+        }
+        
+        let (tx, rx) = channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let cancel_flag = Arc::clone(&cancel);
+        let total = pending.len();
+        
+        std::thread::spawn(move || {
             let cache_dir = thumbnail::get_cache_dir();
-            let _ = scene_detection::detect_scenes(video, &cache_dir);
-            
-            // Free tier: limit to 5 scenes
-            if !self.is_premium && video.scenes.len() > 5 {
-                // Remove excess scenes and their thumbnails
-                while video.scenes.len() > 5 {
-                    if let Some(scene) = video.scenes.pop() {
-                        let _ = std::fs::remove_file(&scene.thumbnail_path);
-                        self.texture_cache.remove(&scene.thumbnail_path);
-                    }
+            for (i, (video_id, video_path)) in pending.into_iter().enumerate() {
+                if cancel_flag.load(Ordering::Relaxed) {
+                    eprintln!("[ThumbnailBatch] Cancelled after {} / {}", i, total);
+                    break;
+                }
+                // create_video_thumbnail reuses an existing file, so remove the stale one first
+                let _ = std::fs::remove_file(thumbnail::video_thumbnail_path(&video_path, &cache_dir));
+                let thumbnail_path = thumbnail::create_video_thumbnail(&video_path, &cache_dir);
+                if thumbnail_path.is_none() {
+                    eprintln!("[Thumbnail] Failed to regenerate thumbnail for {:?}", video_path);
+                }
+                let update = SceneBatchUpdate {
+                    video_id,
+                    scenes: Vec::new(),
+                    thumbnail_path,
+                    done: i + 1,
+                    total,
+                };
+                if tx.send(update).is_err() {
+                    break;
                 }
             }
-            
-            let _ = database::save_database(&self.database);
+        });
+        
+        self.scene_batch_receiver = Some(rx);
+        self.scene_batch_cancel = Some(cancel);
+        self.scene_batch_progress = Some((0, total));
+        self.scene_batch_label = "thumbnail_batch_progress";
+    }
+    
+    /// Apply the thumbnail cache budget (if enabled); returns the bytes freed
+    fn prune_thumbnail_cache(&mut self) -> u64 {
+        if !self.thumbnail_eviction_enabled {
+            return 0;
+        }
+        let budget_bytes = self.thumbnail_cache_budget_mb * 1024 * 1024;
+        let Some(protected) = main_thumbnail_paths(&self.database, &self.current_profile) else {
+            return 0;
+        };
+        let (evicted, freed) = thumbnail::evict_stale_thumbnails(&mut self.thumbnail_access_log, budget_bytes, self.thumbnail_eviction_days, &protected);
+        if evicted > 0 {
+            self.thumbnail_access_log.save();
         }
+        freed
     }
     
-    pub fn delete_scene(&mut self, video_id: &str, scene_timestamp: f64) {
-        if let Some(video) = self.database.get_video_mut(video_id) {
-            // Find and remove the scene
-            if let Some(index) = video.scenes.iter().position(|s| (s.timestamp - scene_timestamp).abs() < 0.01) {
-                let scene = video.scenes.remove(index);
-                
-                // Delete thumbnail file
-                let _ = std::fs::remove_file(&scene.thumbnail_path);
-                
-                // Remove from texture cache
-                self.texture_cache.remove(&scene.thumbnail_path);
-                
-                // Save database
-                let _ = database::save_database(&self.database);
-            }
+    /// Selection highlight color chosen in the options
+    fn accent_color32(&self) -> egui::Color32 {
+        let [r, g, b] = self.accent_color;
+        egui::Color32::from_rgb(r, g, b)
+    }
+    
+    /// Check if any popup, window, or dialog is open (keyboard shortcuts are ignored then)
+    fn any_popup_open(&self) -> bool {
+        self.show_options_window || 
+        self.show_folder_management_window || 
+        self.show_tag_management_window ||
+        self.show_shader_management_window ||
+        self.show_license_window ||
+        self.show_deactivate_license_confirm ||
+        self.show_premium_promotion_window ||
+        self.show_tag_add_popup ||
+        self.show_bulk_rating_popup ||
+        self.show_bulk_folder_popup ||
+        self.delete_confirm_video.is_some() ||
+        self.folder_delete_confirm.is_some() ||
+        self.tag_delete_confirm.is_some() ||
+        self.show_unlock_window ||
+        self.thumbnail_timestamp_video.is_some() ||
+        self.player_error_message.is_some() ||
+        self.show_scene_lightbox ||
+        self.show_review_mode ||
+        self.show_duplicates_window ||
+        self.show_filters_window
+    }
+    
+    /// Whether the dark theme should be used; `System` follows the OS setting
+    /// (reported by the window system, including changes during the session)
+    fn resolve_dark_mode(&self, ctx: &egui::Context) -> bool {
+        match self.theme_mode {
+            ThemeMode::Light => false,
+            ThemeMode::Dark => true,
+            ThemeMode::System => ctx.system_theme() == Some(egui::Theme::Dark),
         }
-        
-        // Clear scene selection
-        self.selected_scenes.clear();
-        self.last_selected_scene = None;
     }
     
-    pub fn delete_selected_scenes(&mut self, video_id: &str) {
-        if let Some(video) = self.database.get_video_mut(video_id) {
-            // Sort indices in descending order to remove from back to front
-            let mut indices: Vec<usize> = self.selected_scenes.iter().copied().collect();
-            indices.sort_by(|a, b| b.cmp(a));
-            
-            for index in indices {
-                if index < video.scenes.len() {
-                    let scene = video.scenes.remove(index);
-                    
-                    // Delete thumbnail file
-                    let _ = std::fs::remove_file(&scene.thumbnail_path);
-                    
-                    // Remove from texture cache
-                    self.texture_cache.remove(&scene.thumbnail_path);
-                }
+    /// Folder where mpv screenshots are saved
+    fn effective_screenshot_dir(&self) -> PathBuf {
+        self.screenshot_dir.clone().unwrap_or_else(video_player::default_screenshot_dir)
+    }
+    
+    /// Save a screenshot of the frame currently shown by mpv
+    pub fn take_player_screenshot(&mut self) {
+        let dir = self.effective_screenshot_dir();
+        self.status_message = Some(if video_player::take_screenshot(&dir) {
+            self.i18n.t("screenshot_saved").replace("{}", &dir.display().to_string())
+        } else {
+            self.i18n.t("screenshot_no_player")
+        });
+    }
+    
+    /// Switch to another profile without restarting: reload its library and reset per-library state
+    pub fn switch_profile(&mut self, profile_name: &str) {
+        if profile_name == self.current_profile {
+            return;
+        }
+        
+        let database = match database::load_profile_database(profile_name) {
+            Ok(database) => database,
+            Err(e) => {
+                self.profile_status_message = Some(format!("{}: {}", self.i18n.t("profile_switch_failed"), e));
+                return;
             }
-            
-            // Save database
-            let _ = database::save_database(&self.database);
+        };
+        
+        // Keep the old profile's watched folders for when it is switched back to
+        let old_folders: Vec<PathBuf> = self.watched_folders.iter().cloned().collect();
+        self.profile_watched_folders.insert(self.current_profile.clone(), old_folders);
+        
+        // Flush pending writes of the old profile
+        self.flush_playback_changes();
+        self.flush_notes();
+        
+        // Stop background workers that would apply results to the old library
+        self.cancel_scene_batch();
+        self.scene_batch_receiver = None;
+        self.scene_batch_cancel = None;
+        self.scene_batch_progress = None;
+        if let Some(ref cancel) = self.duration_probe_cancel {
+            cancel.store(true, Ordering::Relaxed);
         }
+        self.duration_probe_receiver = None;
+        self.duration_probe_cancel = None;
+        self.duration_probe_progress = None;
+        self.duplicate_scan_receiver = None;
+        self.duplicate_groups.clear();
+        self.duplicates_scanned = false;
+        if let Some(ref cancel) = self.import_cancel {
+            cancel.store(true, Ordering::Relaxed);
+        }
+        self.import_receiver = None;
+        self.import_cancel = None;
+        self.import_progress = None;
+        self.import_folders.clear();
+        self.import_added = 0;
         
-        // Clear scene selection
+        database::set_current_profile(profile_name);
+        self.database = database;
+        self.current_profile = profile_name.to_string();
+        self.profile_switch_pending = None;
+        
+        // Textures and selection belong to the previous library
+        self.texture_cache.clear();
+        self.pending_textures.clear();
+        self.pending_blurred_textures.clear();
+        self.failed_textures.clear();
+        self.blurred_texture_cache.clear();
+        self.metadata_loaded.clear();
+        self.clear_selection();
+        self.scene_panel_visible = false;
         self.selected_scenes.clear();
         self.last_selected_scene = None;
-    }
-    
-    pub fn toggle_scene_selection(&mut self, index: usize) {
-        if self.selected_scenes.contains(&index) {
-            self.selected_scenes.remove(&index);
-        } else {
-            self.selected_scenes.insert(index);
+        self.selected_folder_filter.clear();
+        self.selected_tag_filter.clear();
+        self.undo_stack.clear();
+        self.title_edit = None;
+        
+        // Each profile has its own watched folders
+        self.watched_folders = self.profile_watched_folders.get(profile_name)
+            .map(|folders| folders.iter().cloned().collect())
+            .unwrap_or_default();
+        self.setup_folder_watcher();
+        
+        // Remember the profile for the next launch
+        if let Ok(mut settings) = database::load_settings() {
+            settings.current_profile = profile_name.to_string();
+            settings.watched_folders = self.watched_folders.iter().cloned().collect();
+            settings.profile_watched_folders = self.profile_watched_folders_snapshot();
+            let _ = database::save_settings(&settings);
         }
+        self.available_profiles = database::list_profiles().unwrap_or_default();
+        
+        eprintln!("[Profile] Switched to profile: {} ({} videos)", profile_name, self.database.videos.len());
+        self.status_message = Some(self.i18n.t("profile_switched").replace("{}", profile_name));
     }
     
-    pub fn select_scene_range(&mut self, start: usize, end: usize) {
-        let (min, max) = if start <= end { (start, end) } else { (end, start) };
-        for i in min..=max {
-            self.selected_scenes.insert(i);
-        }
+    /// Watched folders of all profiles, with the current profile's list up to date
+    fn profile_watched_folders_snapshot(&self) -> HashMap<String, Vec<PathBuf>> {
+        let mut map = self.profile_watched_folders.clone();
+        map.insert(self.current_profile.clone(), self.watched_folders.iter().cloned().collect());
+        map
     }
     
-    /// Refresh the video profile: regenerate thumbnail, metadata, and scenes
-    pub fn refresh_video_profile(&mut self, video_id: &str) {
-        let cache_dir = thumbnail::get_cache_dir();
+    /// Move videos to another profile's library. Videos already in the target are skipped.
+    /// Thumbnails stay where they are since the thumbnail cache is shared by all profiles.
+    pub fn move_videos_to_profile(&mut self, video_ids: &[String], profile_name: &str) {
+        let videos: Vec<&VideoFile> = video_ids.iter()
+            .filter_map(|id| self.database.get_video(id))
+            .collect();
         
-        // Get video info
-        let video_path = if let Some(video) = self.database.get_video(video_id) {
-            video.path.clone()
+        // Free tier: the target library is limited to 100 videos as well
+        let limit = if self.is_premium {
+            usize::MAX
         } else {
-            return;
+            let target_count = self.available_profiles.iter()
+                .find(|(name, _)| name == profile_name)
+                .map(|(_, count)| *count as usize)
+                .unwrap_or(0);
+            100usize.saturating_sub(target_count)
         };
         
-        // Delete existing thumbnail
-        if let Some(video) = self.database.get_video(video_id) {
-            if let Some(ref thumb_path) = video.thumbnail_path {
-                let _ = std::fs::remove_file(thumb_path);
-                self.texture_cache.remove(thumb_path);
-                self.pending_textures.remove(thumb_path);
-                self.failed_textures.remove(thumb_path);
-            }
-            
-            // Delete scene thumbnails
-            let scene_dir = cache_dir.join("scenes").join(&video.id);
-            if scene_dir.exists() {
-                // Remove scene textures from cache
-                for scene in &video.scenes {
-                    self.texture_cache.remove(&scene.thumbnail_path);
-                    self.pending_textures.remove(&scene.thumbnail_path);
-                    self.failed_textures.remove(&scene.thumbnail_path);
-                }
-                let _ = std::fs::remove_dir_all(&scene_dir);
+        let moved_ids = match database::copy_videos_to_profile(profile_name, &videos, limit) {
+            Ok(ids) => ids,
+            Err(e) => {
+                eprintln!("[Profile] Failed to move videos to '{}': {}", profile_name, e);
+                self.status_message = Some(format!("{}: {}", self.i18n.t("move_to_profile_failed"), e));
+                return;
             }
-        }
+        };
+        let skipped = videos.len() - moved_ids.len();
         
-        // Update video profile with fresh data
-        if let Some(video) = self.database.get_video_mut(video_id) {
-            // Regenerate thumbnail
-            video.thumbnail_path = thumbnail::create_video_thumbnail(&video_path, &cache_dir);
-            
-            // Refresh metadata
-            video.duration = scene_detection::get_video_duration(&video_path);
-            video.resolution = scene_detection::get_video_resolution(&video_path);
-            video.frame_rate = scene_detection::get_video_frame_rate(&video_path);
-            
-            // Update file size
-            if let Ok(metadata) = std::fs::metadata(&video_path) {
-                video.file_size = metadata.len();
+        // Remove the moved videos from this profile
+        self.database.videos.retain(|v| !moved_ids.contains(&v.id));
+        let moved_refs: Vec<&str> = moved_ids.iter().map(String::as_str).collect();
+        let _ = database::persist_deletion(&moved_refs);
+        for id in &moved_ids {
+            self.selected_videos.remove(id);
+            if self.selected_video.as_ref() == Some(id) {
+                self.selected_video = None;
+                self.scene_panel_visible = false;
             }
-            
-            // Clear scenes so they will be regenerated
-            video.scenes.clear();
         }
+        self.available_profiles = database::list_profiles().unwrap_or_default();
         
-        // Save database
-        let _ = database::save_database(&self.database);
+        eprintln!("[Profile] Moved {} video(s) to '{}' ({} skipped)", moved_ids.len(), profile_name, skipped);
+        let mut message = self.i18n.t("moved_to_profile")
+            .replacen("{}", &moved_ids.len().to_string(), 1)
+            .replacen("{}", profile_name, 1);
+        if skipped > 0 {
+            message.push_str(&self.i18n.t("move_to_profile_skipped").replace("{}", &skipped.to_string()));
+        }
+        self.status_message = Some(message);
     }
     
-    /// Regenerate missing thumbnails for all videos after backup restore
-    /// This checks if thumbnail files exist on disk and regenerates if missing
-    pub fn regenerate_missing_thumbnails(&mut self) {
-        let cache_dir = thumbnail::get_cache_dir();
-        let mut needs_save = false;
+    /// "Move to profile" submenu for a video's context menu (applies to the selection if it contains the video)
+    fn show_move_to_profile_menu(&mut self, ui: &mut egui::Ui, video_id: &str) {
+        if self.available_profiles.len() < 2 {
+            return;
+        }
         
-        for video in &mut self.database.videos {
-            // Check if video file exists
-            if !video.path.exists() {
-                eprintln!("[Restore] Skipping missing video file: {:?}", video.path);
-                continue;
+        ui.menu_button(self.i18n.t("move_to_profile"), |ui| {
+            let profiles: Vec<String> = self.available_profiles.iter()
+                .map(|(name, _)| name.clone())
+                .filter(|name| *name != self.current_profile)
+                .collect();
+            for profile_name in profiles {
+                if ui.button(&profile_name).clicked() {
+                    let target_ids = self.target_ids_for(video_id);
+                    self.move_videos_to_profile(&target_ids, &profile_name);
+                    ui.close_menu();
+                }
             }
+        });
+    }
+    
+    /// "Copy path" / "Copy file to..." items for the clicked video (or the selection it belongs to)
+    fn show_copy_menu_items(&mut self, ui: &mut egui::Ui, video_id: &str) {
+        if ui.button(self.i18n.t("copy_path")).clicked() {
+            self.copy_video_paths(ui.ctx(), &self.target_ids_for(video_id));
+            ui.close_menu();
+        }
+        if ui.add_enabled(self.file_copy_receiver.is_none(), egui::Button::new(self.i18n.t("copy_file_to"))).clicked() {
+            ui.close_menu();
+            self.copy_video_files_to_folder(&self.target_ids_for(video_id));
+        }
+    }
+    
+    /// "Move to folder" submenu: assigns the clicked video (or the selection it belongs to) to a folder
+    fn show_move_to_folder_menu(&mut self, ui: &mut egui::Ui, video_id: &str) {
+        ui.menu_button(self.i18n.t("move_to_folder"), |ui| {
+            let target_ids = self.target_ids_for(video_id);
+            let current_folders = self.database.get_video(video_id).map(|v| v.folders.clone()).unwrap_or_default();
             
-            // Check main thumbnail
-            let needs_main_thumb = match &video.thumbnail_path {
-                Some(path) => !path.exists(),
-                None => true,
-            };
-            
-            if needs_main_thumb {
-                eprintln!("[Restore] Regenerating thumbnail for: {:?}", video.path);
-                video.thumbnail_path = thumbnail::create_video_thumbnail(&video.path, &cache_dir);
-                needs_save = true;
+            let folders = self.database.folders.clone();
+            for folder in folders {
+                let label = if current_folders.contains(&folder) {
+                    format!("✔ {}", folder)
+                } else {
+                    format!("📁 {}", folder)
+                };
+                if ui.button(label).clicked() {
+                    self.move_videos_to_folder(&target_ids, folder);
+                    ui.close_menu();
+                }
             }
             
-            // Check scene thumbnails
-            let has_missing_scenes = video.scenes.iter().any(|scene| !scene.thumbnail_path.exists());
-            
-            if has_missing_scenes || (!video.scenes.is_empty() && video.scenes.iter().all(|s| !s.thumbnail_path.exists())) {
-                eprintln!("[Restore] Regenerating scene thumbnails for: {:?}", video.path);
-                // Clear existing scenes
-                video.scenes.clear();
-                // Regenerate scenes
-                if let Err(e) = scene_detection::detect_scenes(video, &cache_dir) {
-                    eprintln!("[Restore] Failed to regenerate scenes for {:?}: {}", video.path, e);
-                }
-                needs_save = true;
-            }
-        }
-        
-        if needs_save {
-            let _ = database::save_database(&self.database);
-            eprintln!("[Restore] Thumbnail regeneration complete");
-        } else {
-            eprintln!("[Restore] All thumbnails are intact, no regeneration needed");
-        }
-    }
-        /// Refresh scene thumbnails for a video: delete existing scenes and regenerate
-    pub fn refresh_scenes(&mut self, video_id: &str) {
-        // Just clear texture caches to force reload from disk
-        // Does NOT regenerate scenes
-        if let Some(video) = self.database.get_video(video_id) {
-            for scene in &video.scenes {
-                self.texture_cache.remove(&scene.thumbnail_path);
-                self.pending_textures.remove(&scene.thumbnail_path);
-                self.failed_textures.remove(&scene.thumbnail_path);
-            }
-        }
-    }
-
-    pub fn delete_video(&mut self, video_id: &str, delete_cache: bool) {
-        // Get video info before deletion
-        if let Some(video) = self.database.get_video(video_id) {
-            // Delete cache files if requested
-            if delete_cache {
-                let cache_dir = thumbnail::get_cache_dir();
-                
-                // Delete main thumbnail
-                if let Some(ref thumb_path) = video.thumbnail_path {
-                    let _ = std::fs::remove_file(thumb_path);
-                    // Remove from texture cache
-                    self.texture_cache.remove(thumb_path);
-                }
-                
-                // Delete scene thumbnails
-                let scene_dir = cache_dir.join("scenes").join(&video.id);
-                if scene_dir.exists() {
-                    let _ = std::fs::remove_dir_all(&scene_dir);
-                }
-                
-                // Remove scene textures from cache
-                for scene in &video.scenes {
-                    self.texture_cache.remove(&scene.thumbnail_path);
+            ui.separator();
+            ui.label(self.i18n.t("new_folder_name"));
+            ui.horizontal(|ui| {
+                let response = ui.text_edit_singleline(&mut self.move_folder_input);
+                let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                let name = self.move_folder_input.trim().to_string();
+                if (ui.add_enabled(!name.is_empty(), egui::Button::new("OK")).clicked() || submitted) && !name.is_empty() {
+                    self.move_videos_to_folder(&target_ids, name);
+                    self.move_folder_input.clear();
+                    ui.close_menu();
                 }
+            });
+        });
+    }
+    
+    /// Make a folder (created if new) the only folder of the videos, and save
+    fn move_videos_to_folder(&mut self, video_ids: &[String], folder: String) {
+        for video_id in video_ids {
+            if let Some(video) = self.database.get_video_mut(video_id) {
+                video.folders.clear();
             }
-            
-            // Remove from database
-            self.database.remove_video(video_id);
-            
-            // Clear selection if deleted video was selected
-            if self.selected_video.as_deref() == Some(video_id) {
-                self.selected_video = None;
-                self.scene_panel_visible = false;
-            }
-            
-            // Remove from multi-selection if selected
-            self.selected_videos.remove(video_id);
-            
-            // Save database
-            let _ = database::save_database(&self.database);
+            self.add_video_folder(video_id, folder.clone());
         }
+        self.persist_videos(video_ids);
+        eprintln!("[folder] Moved {} videos to '{}'", video_ids.len(), folder);
+        self.status_message = Some(self.i18n.t("moved_to_folder")
+            .replacen("{}", &video_ids.len().to_string(), 1)
+            .replacen("{}", &folder, 1));
     }
     
-    /// Get the path to the scene capture request file
-    fn get_scene_capture_request_path() -> Option<PathBuf> {
-        dirs::data_local_dir().map(|p| p.join("CicadaGallery").join("scene_capture_request.json"))
+    /// Save the current filter/search state as a preset (a preset with the same name is replaced)
+    fn save_filter_preset(&mut self, name: &str) {
+        let mut folders: Vec<String> = self.selected_folder_filter.iter().cloned().collect();
+        folders.sort();
+        let mut tags: Vec<String> = self.selected_tag_filter.iter().cloned().collect();
+        tags.sort();
+        let preset = models::FilterPreset {
+            name: name.to_string(),
+            folders,
+            tags,
+            tag_filter_and: self.tag_filter_mode == FilterMode::And,
+            min_rating: self.min_rating_filter,
+            search_query: self.search_query.clone(),
+        };
+        if let Some(existing) = self.filter_presets.iter_mut().find(|p| p.name == name) {
+            *existing = preset;
+        } else {
+            self.filter_presets.push(preset);
+        }
+        self.save_settings();
     }
     
-    /// Check for and process scene capture requests from mpv
-    pub fn check_scene_capture_requests(&mut self) {
-        let request_path = match Self::get_scene_capture_request_path() {
-            Some(p) => p,
-            None => return,
-        };
-        
-        if !request_path.exists() {
+    /// Replace the current filter/search state with a saved preset
+    fn apply_filter_preset(&mut self, index: usize) {
+        let Some(preset) = self.filter_presets.get(index).cloned() else {
             return;
-        }
-        
-        // Read the request file
-        let content = match std::fs::read_to_string(&request_path) {
-            Ok(c) => c,
-            Err(_) => return,
-        };
-        
-        // Delete the file immediately to prevent re-processing
-        let _ = std::fs::remove_file(&request_path);
-        
-        // Parse the JSON request
-        #[derive(serde::Deserialize)]
-        struct CaptureRequest {
-            path: String,
-            timestamp: f64,
-        }
-        
-        let request: CaptureRequest = match serde_json::from_str(&content) {
-            Ok(r) => r,
-            Err(_) => return,
-        };
-        
-        // Find the video in the database by path
-        let video_id = self.database.videos.iter()
-            .find(|v| v.path.to_string_lossy() == request.path)
-            .map(|v| v.id.clone());
-        
-        let video_id = match video_id {
-            Some(id) => id,
-            None => return,
         };
-        
-        // Add scene at the specified timestamp
-        let cache_dir = thumbnail::get_cache_dir();
-        let (success, timestamp_str, video_title) = {
-            if let Some(video) = self.database.get_video_mut(&video_id) {
-                let success = scene_detection::add_scene_at_timestamp(video, request.timestamp, &cache_dir).is_some();
-                let timestamp_str = scene_detection::format_timestamp(request.timestamp);
-                let video_title = video.title.clone();
-                (success, timestamp_str, video_title)
+        // 削除済みのフォルダ/タグは無視
+        self.selected_folder_filter = preset.folders.into_iter()
+            .filter(|f| self.database.folders.contains(f))
+            .collect();
+        self.selected_tag_filter = preset.tags.into_iter()
+            .filter(|t| self.database.tags.contains(t))
+            .collect();
+        self.last_selected_folder = None;
+        self.last_selected_tag = None;
+        self.tag_filter_mode = if preset.tag_filter_and { FilterMode::And } else { FilterMode::Or };
+        self.min_rating_filter = preset.min_rating;
+        self.search_query = preset.search_query;
+    }
+    
+    /// Reset search and every sidebar/top-bar filter
+    fn clear_all_filters(&mut self) {
+        self.search_query.clear();
+        self.selected_folder_filter.clear();
+        self.selected_tag_filter.clear();
+        self.last_selected_folder = None;
+        self.last_selected_tag = None;
+        self.min_rating_filter = 0;
+        self.watched_filter = WatchedFilter::All;
+        self.show_recent_only = false;
+        self.added_month_filter = None;
+    }
+    
+    /// Centered message shown instead of an empty grid/list
+    fn show_empty_message(&mut self, ui: &mut egui::Ui) {
+        // 保護された動画しかない場合も「空のライブラリ」として扱う
+        let library_empty = !self.database.videos.iter().any(|v| self.protected_unlocked || !v.protected);
+        ui.vertical_centered(|ui| {
+            ui.add_space((ui.available_height() / 3.0).max(20.0));
+            if library_empty {
+                ui.heading(self.i18n.t("no_videos_found"));
+                ui.label(self.i18n.t("add_videos_to_start"));
             } else {
-                return;
+                ui.heading(self.i18n.t("no_matching_videos"));
+                ui.label(self.i18n.t("no_matching_videos_hint"));
+                ui.add_space(8.0);
+                if ui.button(self.i18n.t("clear_filters")).clicked() {
+                    self.clear_all_filters();
+                }
             }
-        };
-        
-        if success {
-            // Save database
-            let _ = database::save_database(&self.database);
-            eprintln!("[Scene Capture] Added scene at {} for video {}", timestamp_str, video_title);
-        }
+        });
     }
     
-    pub fn toggle_video_selection(&mut self, video_id: &str) {
-        if self.selected_videos.contains(video_id) {
-            self.selected_videos.remove(video_id);
-        } else {
-            self.selected_videos.insert(video_id.to_string());
-        }
+    /// Fingerprint of the filter/search state (sorting is not included: it keeps the same videos)
+    fn filter_state_key(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.search_query.hash(&mut hasher);
+        let mut folders: Vec<&String> = self.selected_folder_filter.iter().collect();
+        folders.sort();
+        folders.hash(&mut hasher);
+        let mut tags: Vec<&String> = self.selected_tag_filter.iter().collect();
+        tags.sort();
+        tags.hash(&mut hasher);
+        (self.tag_filter_mode == FilterMode::And).hash(&mut hasher);
+        self.min_rating_filter.hash(&mut hasher);
+        self.watched_filter.hash(&mut hasher);
+        self.show_recent_only.hash(&mut hasher);
+        self.recent_days.hash(&mut hasher);
+        self.added_month_filter.hash(&mut hasher);
+        self.protected_unlocked.hash(&mut hasher);
+        hasher.finish()
     }
     
-    pub fn select_all_videos(&mut self, video_ids: Vec<String>) {
-        self.selected_videos.clear();
-        self.selected_videos.extend(video_ids);
+    /// Delete cached thumbnails that no video in any profile refers to
+    pub fn clean_thumbnail_cache(&mut self) {
+        // The cache directory is shared by all profiles, so collect references from every library
+        let mut referenced: HashSet<PathBuf> = HashSet::new();
+        let mut libraries = vec![self.database.clone()];
+        for (profile_name, _) in database::list_profiles().unwrap_or_default() {
+            if profile_name == self.current_profile {
+                continue;
+            }
+            match database::load_profile_database(&profile_name) {
+                Ok(library) => libraries.push(library),
+                Err(e) => {
+                    // Never delete files another profile might still use
+                    self.cache_status_message = Some(format!("{}: {}", self.i18n.t("clean_cache_failed"), e));
+                    return;
+                }
+            }
+        }
+        for video in libraries.iter().flat_map(|library| library.videos.iter()) {
+            referenced.extend(video.thumbnail_path.iter().cloned());
+            referenced.extend(video.preview_thumbnail_path.iter().cloned());
+            referenced.extend(video.sprite_sheet_path.iter().cloned());
+            referenced.extend(video.scenes.iter().map(|scene| scene.thumbnail_path.clone()));
+        }
+        
+        let (removed, freed) = thumbnail::remove_orphaned_thumbnails(&referenced);
+        self.cache_status_message = Some(self.i18n.t("cache_cleaned")
+            .replacen("{}", &removed.to_string(), 1)
+            .replacen("{}", &format!("{:.1}", freed as f64 / 1024.0 / 1024.0), 1));
     }
     
-    pub fn clear_selection(&mut self) {
-        self.selected_videos.clear();
-        self.selected_video = None;
-        self.last_selected_video = None;
+    /// Delete the whole thumbnail cache (thumbnails are regenerated when shown)
+    pub fn clear_all_thumbnail_cache(&mut self) {
+        let (removed, freed) = thumbnail::clear_thumbnail_cache();
+        self.texture_cache.clear();
+        self.pending_textures.clear();
+        self.pending_blurred_textures.clear();
+        self.failed_textures.clear();
+        self.blurred_texture_cache.clear();
+        self.cache_status_message = Some(self.i18n.t("cache_cleaned")
+            .replacen("{}", &removed.to_string(), 1)
+            .replacen("{}", &format!("{:.1}", freed as f64 / 1024.0 / 1024.0), 1));
     }
     
-    pub fn select_range(&mut self, video_ids: &[String], from_id: &str, to_id: &str) {
-        // Find indices of from and to
-        let from_idx = video_ids.iter().position(|id| id == from_id);
-        let to_idx = video_ids.iter().position(|id| id == to_id);
+    /// Delete the library's video and scene thumbnails so they are regenerated
+    /// with the current size/quality settings as they are displayed again
+    pub fn regenerate_thumbnails(&mut self) {
+        let paths: Vec<PathBuf> = self.database.videos.iter()
+            .flat_map(|video| {
+                video.thumbnail_path.iter().cloned()
+                    .chain(video.scenes.iter().map(|scene| scene.thumbnail_path.clone()))
+            })
+            .collect();
         
-        if let (Some(from), Some(to)) = (from_idx, to_idx) {
-            let start = from.min(to);
-            let end = from.max(to);
-            
-            for id in &video_ids[start..=end] {
-                self.selected_videos.insert(id.clone());
+        let mut removed = 0;
+        for path in &paths {
+            if std::fs::remove_file(path).is_ok() {
+                removed += 1;
             }
+            self.texture_cache.remove(path);
+            self.pending_textures.remove(path);
+            self.failed_textures.remove(path);
+            self.blurred_texture_cache.remove(path);
         }
+        
+        eprintln!("[Thumbnail] Removed {} thumbnails for regeneration", removed);
+        self.cache_status_message = Some(self.i18n.t("thumbnails_regenerating").replace("{}", &removed.to_string()));
     }
     
-    pub fn delete_selected_videos(&mut self, delete_cache: bool) {
-        let video_ids: Vec<String> = self.selected_videos.iter().cloned().collect();
-        for video_id in video_ids {
-            self.delete_video(&video_id, delete_cache);
+    /// Group videos whose thumbnails look alike (same video under a different name or folder).
+    /// Runs on a background thread; the groups are picked up by `process_duplicate_scan`.
+    pub fn find_duplicate_videos(&mut self) {
+        use rayon::prelude::*;
+        
+        if self.duplicate_scan_receiver.is_some() {
+            return;
         }
-        self.selected_videos.clear();
-    }
-    
-    /// Setup file system watcher for monitored folders
-    pub fn setup_folder_watcher(&mut self) {
-        // Collect all folders to watch from database
-        let folders: HashSet<PathBuf> = self.database.videos.iter()
-            .filter_map(|v| v.path.parent().map(|p| p.to_path_buf()))
-            .collect();
         
-        self.watched_folders = folders.clone();
+        let candidates: Vec<(String, PathBuf, Option<f64>)> = self.database.videos.iter()
+            .filter(|v| self.protected_unlocked || !v.protected)
+            .filter_map(|v| v.thumbnail_path.clone().map(|thumb| (v.id.clone(), thumb, v.duration)))
+            .collect();
         
-        // Create a channel for receiving events
+        let max_distance = self.duplicate_max_distance;
         let (tx, rx) = channel();
-        let rx = Arc::new(Mutex::new(rx));
-        
-        // Create a new watcher
-        match notify::recommended_watcher(move |res| {
-            if let Err(e) = tx.send(res) {
-                eprintln!("Error sending watch event: {:?}", e);
-            }
-        }) {
-            Ok(mut watcher) => {
-                // Watch all folders
-                for folder in &self.watched_folders {
-                    if folder.exists() {
-                        if let Err(e) = watcher.watch(folder, RecursiveMode::Recursive) {
-                            eprintln!("Failed to watch folder {:?}: {:?}", folder, e);
-                        } else {
-                            eprintln!("[watcher] Watching folder: {:?}", folder);
+        std::thread::spawn(move || {
+            // Decoding thumbnails is the slow part, so hash them in parallel
+            let hashes: Vec<(String, u64, Option<f64>)> = candidates.into_par_iter()
+                .filter_map(|(id, thumb, duration)| thumbnail::perceptual_hash(&thumb).map(|hash| (id, hash, duration)))
+                .collect();
+            
+            let mut grouped = vec![false; hashes.len()];
+            let mut groups = Vec::new();
+            for i in 0..hashes.len() {
+                if grouped[i] {
+                    continue;
+                }
+                let mut group = vec![hashes[i].0.clone()];
+                for j in (i + 1)..hashes.len() {
+                    if grouped[j] || thumbnail::hash_distance(hashes[i].1, hashes[j].1) > max_distance {
+                        continue;
+                    }
+                    // Similar frames from videos of clearly different length are not the same video
+                    if let (Some(a), Some(b)) = (hashes[i].2, hashes[j].2) {
+                        if (a - b).abs() > 2.0 {
+                            continue;
                         }
                     }
+                    grouped[j] = true;
+                    group.push(hashes[j].0.clone());
+                }
+                if group.len() > 1 {
+                    groups.push(group);
                 }
-                
-                self.fs_watcher = Some(watcher);
-                self.fs_events = Some(rx);
             }
-            Err(e) => {
-                eprintln!("Failed to create file system watcher: {:?}", e);
+            
+            eprintln!("[Duplicates] Found {} groups among {} videos", groups.len(), hashes.len());
+            let _ = tx.send(groups);
+        });
+        self.duplicate_scan_receiver = Some(rx);
+    }
+    
+    /// Pick up the result of the background duplicate scan
+    fn process_duplicate_scan(&mut self, ctx: &egui::Context) {
+        let Some(ref receiver) = self.duplicate_scan_receiver else {
+            return;
+        };
+        match receiver.try_recv() {
+            Ok(groups) => {
+                // Videos deleted while the scan was running are left out
+                self.duplicate_groups = groups.into_iter()
+                    .map(|group| group.into_iter().filter(|id| self.database.get_video(id).is_some()).collect::<Vec<_>>())
+                    .filter(|group| group.len() > 1)
+                    .collect();
+                self.duplicates_scanned = true;
+                self.duplicate_scan_receiver = None;
+            }
+            Err(TryRecvError::Empty) => {
+                ctx.request_repaint_after(std::time::Duration::from_millis(250));
+            }
+            Err(TryRecvError::Disconnected) => {
+                self.duplicate_scan_receiver = None;
             }
         }
     }
     
-    /// Check for file system changes and trigger rescan if needed
-    pub fn check_folder_changes(&mut self) {
-        if let Some(rx) = &self.fs_events {
-            if let Ok(rx_guard) = rx.lock() {
-                // Check for pending events
-                let mut has_changes = false;
-                
-                // Process all pending events
-                while let Ok(result) = rx_guard.try_recv() {
-                    match result {
-                        Ok(event) => {
-                            // Check if the event is related to video files
-                            for path in &event.paths {
-                                if path.is_file() && VideoFile::is_video_file(path) {
-                                    eprintln!("[watcher] Detected change: {:?}", path);
-                                    has_changes = true;
-                                    break;
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            eprintln!("Watch error: {:?}", e);
-                        }
+    /// Request cancellation of the running batch (the current video still finishes)
+    pub fn cancel_scene_batch(&mut self) {
+        if let Some(ref cancel) = self.scene_batch_cancel {
+            cancel.store(true, Ordering::Relaxed);
+        }
+    }
+    
+    /// Apply finished videos from the batch scene generator
+    fn process_scene_batch(&mut self, ctx: &egui::Context) {
+        let Some(ref receiver) = self.scene_batch_receiver else {
+            return;
+        };
+        
+        let mut finished = false;
+        let mut changed = Vec::new();
+        let mut thumbnails = Vec::new();
+        loop {
+            match receiver.try_recv() {
+                Ok(update) => {
+                    if let Some(path) = update.thumbnail_path {
+                        thumbnails.push((update.video_id.clone(), path));
                     }
-                }
-                
-                // Set pending rescan flag if changes detected
-                if has_changes {
-                    // Only trigger rescan if enough time has passed since last rescan
-                    if let Ok(elapsed) = self.last_rescan_time.elapsed() {
-                        if elapsed.as_secs() >= 5 {
-                            self.pending_rescan = true;
+                    if let Some(video) = self.database.get_video_mut(&update.video_id) {
+                        // Keep scenes added while the batch was running
+                        if video.scenes.is_empty() {
+                            video.scenes = update.scenes;
+                            changed.push(update.video_id);
                         }
                     }
+                    self.scene_batch_progress = Some((update.done, update.total));
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
                 }
             }
         }
-    }
-    
-    /// Initialize the async texture loading channel if not already done
-    fn ensure_texture_loader(&mut self) {
-        if self.texture_load_sender.is_none() {
-            let (tx, rx) = channel();
-            self.texture_load_sender = Some(tx);
-            self.texture_load_receiver = Some(rx);
+        
+        for (video_id, path) in thumbnails {
+            self.replace_main_thumbnail(&video_id, path);
         }
-        if self.texture_fail_sender.is_none() {
-            let (tx, rx) = channel();
-            self.texture_fail_sender = Some(tx);
-            self.texture_fail_receiver = Some(rx);
+        
+        if finished {
+            eprintln!("[SceneBatch] Finished");
+            self.scene_batch_receiver = None;
+            self.scene_batch_cancel = None;
+            self.scene_batch_progress = None;
+        } else {
+            // Keep polling while the worker is running
+            ctx.request_repaint_after(std::time::Duration::from_millis(250));
         }
+        
+        // Saved as they arrive so a crash doesn't lose finished work
+        self.persist_videos(&changed);
     }
     
-    /// Process any completed texture loads from background threads
-    fn process_loaded_textures(&mut self, ctx: &egui::Context) {
-        // Process successful loads
-        if let Some(ref receiver) = self.texture_load_receiver {
-            // Process up to 10 textures per frame to avoid blocking
-            for _ in 0..10 {
-                match receiver.try_recv() {
-                    Ok((path, pixels, size)) => {
-                        // Remove from pending set
-                        self.pending_textures.remove(&path);
-                        
-                        // Create texture from received data
-                        let color_image = egui::ColorImage::from_rgba_unmultiplied(size, &pixels);
-                        let texture = ctx.load_texture(
-                            path.to_string_lossy().to_string(),
-                            color_image,
-                            Default::default(),
-                        );
-                        self.texture_cache.insert(path, texture);
-                    }
-                    Err(std::sync::mpsc::TryRecvError::Empty) => break,
-                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
-                        // Channel disconnected, recreate it
-                        self.texture_load_sender = None;
-                        self.texture_load_receiver = None;
-                        self.pending_textures.clear();
-                        break;
-                    }
-                }
-            }
+    /// Re-probe durations of the given videos with the accurate (slow) method in the background
+    pub fn start_duration_probe(&mut self, video_ids: &[String]) {
+        if self.duration_probe_receiver.is_some() {
+            return;
         }
         
-        // Process failed loads
-        if let Some(ref receiver) = self.texture_fail_receiver {
-            for _ in 0..10 {
-                match receiver.try_recv() {
-                    Ok(path) => {
-                        // Remove from pending and add to failed
-                        self.pending_textures.remove(&path);
-                        self.failed_textures.insert(path);
-                    }
-                    Err(std::sync::mpsc::TryRecvError::Empty) => break,
-                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
-                        self.texture_fail_sender = None;
-                        self.texture_fail_receiver = None;
-                        break;
-                    }
+        let targets: Vec<(String, PathBuf)> = video_ids.iter()
+            .filter_map(|id| self.database.get_video(id))
+            .filter(|v| v.path.exists())
+            .map(|v| (v.id.clone(), v.path.clone()))
+            .collect();
+        if targets.is_empty() {
+            return;
+        }
+        
+        let (tx, rx) = channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let cancel_flag = Arc::clone(&cancel);
+        let total = targets.len();
+        
+        std::thread::spawn(move || {
+            for (i, (video_id, path)) in targets.into_iter().enumerate() {
+                if cancel_flag.load(Ordering::Relaxed) {
+                    eprintln!("[Duration] Re-probe cancelled after {} / {}", i, total);
+                    break;
+                }
+                let duration = scene_detection::get_video_duration_accurate(&path);
+                let update = DurationProbeUpdate {
+                    video_id,
+                    duration,
+                    done: i + 1,
+                    total,
+                };
+                if tx.send(update).is_err() {
+                    break;
                 }
             }
-        }
+        });
+        
+        self.duration_probe_receiver = Some(rx);
+        self.duration_probe_cancel = Some(cancel);
+        self.duration_probe_progress = Some((0, total));
     }
     
-    fn load_image_texture(&mut self, ctx: &egui::Context, image_path: &PathBuf) -> Option<egui::TextureHandle> {
-        // Check if already cached - this is fast
-        if let Some(texture) = self.texture_cache.get(image_path) {
-            return Some(texture.clone());
-        }
-        
-        // Check if already failed (don't retry failed images)
-        if self.failed_textures.contains(image_path) {
-            return None;
+    /// Copy the paths of the given videos to the clipboard (one per line, in display order)
+    pub fn copy_video_paths(&mut self, ctx: &egui::Context, video_ids: &[String]) {
+        let display_order: HashMap<&str, usize> = self.get_filtered_videos().into_iter()
+            .enumerate()
+            .map(|(index, v)| (v.id.as_str(), index))
+            .collect();
+        let mut videos: Vec<&VideoFile> = video_ids.iter()
+            .filter_map(|id| self.database.get_video(id))
+            .collect();
+        videos.sort_by_key(|v| display_order.get(v.id.as_str()).copied().unwrap_or(usize::MAX));
+        let paths: Vec<String> = videos.iter()
+            .map(|v| v.path.display().to_string())
+            .collect();
+        if paths.is_empty() {
+            return;
         }
-        
-        // Check if already loading
-        if self.pending_textures.contains(image_path) {
-            return None; // Still loading, return None to show placeholder
+        ctx.copy_text(paths.join("\n"));
+        self.status_message = Some(self.i18n.t("paths_copied").replace("{}", &paths.len().to_string()));
+    }
+    
+    /// Ask for a destination folder and copy the video files there in the background
+    pub fn copy_video_files_to_folder(&mut self, video_ids: &[String]) {
+        if self.file_copy_receiver.is_some() {
+            return;
         }
-        
-        // Limit concurrent loading threads to prevent resource exhaustion
-        const MAX_CONCURRENT_LOADS: usize = 8;
-        if self.pending_textures.len() >= MAX_CONCURRENT_LOADS {
-            return None; // Too many pending loads, wait for some to complete
+        let sources: Vec<PathBuf> = video_ids.iter()
+            .filter_map(|id| self.database.get_video(id))
+            .map(|v| v.path.clone())
+            .collect();
+        if sources.is_empty() {
+            return;
         }
+        let Some(destination) = FileDialog::new().pick_folder() else {
+            return;
+        };
         
-        // Ensure loader is initialized
-        self.ensure_texture_loader();
-        
-        // Start async loading
-        if let (Some(ref success_sender), Some(ref fail_sender)) = (&self.texture_load_sender, &self.texture_fail_sender) {
-            let path = image_path.clone();
-            let success_tx = success_sender.clone();
-            let fail_tx = fail_sender.clone();
-            let ctx_clone = ctx.clone();
-            
-            // Mark as pending
-            self.pending_textures.insert(path.clone());
-            
-            // Spawn background thread to load image
-            std::thread::spawn(move || {
-                if let Some((pixels, size)) = load_image_data(&path) {
-                    let _ = success_tx.send((path, pixels, size));
+        let (tx, rx) = channel();
+        let total = sources.len();
+        std::thread::spawn(move || {
+            for (i, source) in sources.into_iter().enumerate() {
+                let file_name = source.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                let target = destination.join(&file_name);
+                let error = if target.exists() {
+                    Some(format!("{}: already exists", file_name))
                 } else {
-                    // Send failure notification
-                    let _ = fail_tx.send(path);
+                    std::fs::copy(&source, &target).err().map(|e| format!("{}: {}", file_name, e))
+                };
+                if let Some(ref error) = error {
+                    eprintln!("[Copy] Failed to copy {}", error);
                 }
-                // Request repaint so the UI updates when texture is ready
-                ctx_clone.request_repaint();
-            });
-        }
+                if tx.send(FileCopyUpdate { error, done: i + 1, total }).is_err() {
+                    break;
+                }
+            }
+        });
         
-        None // Return None while loading, caller should show placeholder
+        self.file_copy_receiver = Some(rx);
+        self.file_copy_progress = Some((0, total));
+        self.file_copy_errors.clear();
     }
     
-    fn draw_thumbnail_placeholder(&mut self, ui: &mut egui::Ui, _ctx: &egui::Context, thumbnail_size: egui::Vec2, video: &VideoFile) {
-        let (rect, response) = ui.allocate_exact_size(thumbnail_size, egui::Sense::click());
+    /// Track progress of the "Copy file to…" worker and report the result
+    fn process_file_copy(&mut self, ctx: &egui::Context) {
+        let Some(ref receiver) = self.file_copy_receiver else {
+            return;
+        };
         
-        let is_selected = self.selected_videos.contains(&video.id) || 
-                         self.selected_video.as_ref() == Some(&video.id);
+        let mut finished = false;
+        loop {
+            match receiver.try_recv() {
+                Ok(update) => {
+                    if let Some(error) = update.error {
+                        self.file_copy_errors.push(error);
+                    }
+                    self.file_copy_progress = Some((update.done, update.total));
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
+                }
+            }
+        }
         
-        if ui.is_rect_visible(rect) {
-            ui.painter().rect_filled(rect, 4.0, egui::Color32::DARK_GRAY);
-            ui.painter().text(
-                rect.center(),
-                egui::Align2::CENTER_CENTER,
-                "🎬",
-                egui::FontId::proportional(48.0 * self.thumbnail_scale),
-                egui::Color32::WHITE,
-            );
-            
-            // Draw selection highlight
-            if is_selected {
-                ui.painter().rect_stroke(
-                    rect,
-                    4.0,
-                    egui::Stroke::new(3.0, egui::Color32::from_rgb(100, 200, 255))
-                );
+        if finished {
+            let (done, _) = self.file_copy_progress.unwrap_or((0, 0));
+            let failed = self.file_copy_errors.len();
+            let mut message = self.i18n.t("files_copied").replace("{}", &done.saturating_sub(failed).to_string());
+            if failed > 0 {
+                message.push_str(&self.i18n.t("files_copy_failed").replace("{}", &self.file_copy_errors.join(", ")));
             }
+            self.status_message = Some(message);
+            self.file_copy_receiver = None;
+            self.file_copy_progress = None;
+            self.file_copy_errors.clear();
+        } else {
+            ctx.request_repaint_after(std::time::Duration::from_millis(250));
         }
+    }
+    
+    /// Apply durations reported by the re-probe worker
+    fn process_duration_probe(&mut self, ctx: &egui::Context) {
+        let Some(ref receiver) = self.duration_probe_receiver else {
+            return;
+        };
         
-        if response.clicked() {
-            self.thumbnail_clicked_this_frame = true;
-            let modifiers = ui.input(|i| i.modifiers.clone());
-            if modifiers.shift {
-                // Shift+Click: range selection (need video_ids context)
-                self.selected_videos.insert(video.id.clone());
-                self.last_selected_video = Some(video.id.clone());
-                // Show profile of first selected video
-                if self.selected_video.is_none() {
-                    self.selected_video = Some(video.id.clone());
-                    self.scene_panel_visible = true;
-                }
-            } else if modifiers.ctrl {
-                self.toggle_video_selection(&video.id);
-                self.last_selected_video = Some(video.id.clone());
-                // Show profile of first selected video
-                if self.selected_video.is_none() && self.selected_videos.contains(&video.id) {
-                    self.selected_video = Some(video.id.clone());
-                    self.scene_panel_visible = true;
+        let mut finished = false;
+        let mut changed = Vec::new();
+        loop {
+            match receiver.try_recv() {
+                Ok(update) => {
+                    if let (Some(duration), Some(video)) = (update.duration, self.database.get_video_mut(&update.video_id)) {
+                        if video.duration.is_none_or(|old| (old - duration).abs() > 0.5) {
+                            eprintln!("[Duration] {:?}: {:?} -> {:.2}", video.path, video.duration, duration);
+                            video.duration = Some(duration);
+                            changed.push(update.video_id);
+                        }
+                    }
+                    self.duration_probe_progress = Some((update.done, update.total));
                 }
-            } else {
-                // Single click: clear multi-select and select only this video
-                // If this video is already the only selected one, keep it selected
-                let already_sole_selected = self.selected_video.as_ref() == Some(&video.id) && 
-                                           self.selected_videos.is_empty();
-                if !already_sole_selected {
-                    self.selected_videos.clear();
-                    self.selected_video = Some(video.id.clone());
-                    self.scene_panel_visible = true;
-                    self.last_selected_video = Some(video.id.clone());
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
                 }
             }
         }
         
-        // Double click: play video
-        if response.double_clicked() {
-            // Select this video before playing (maintain selection in profile panel)
-            self.selected_videos.clear();
-            self.selected_video = Some(video.id.clone());
-            self.scene_panel_visible = true;
-            self.last_selected_video = Some(video.id.clone());
-            
-            let selected_shader = self.selected_shader.as_deref();
-            let use_gpu_hq = self.is_premium && self.use_gpu_hq;
-            let use_custom_shaders = self.is_premium && self.use_custom_shaders;
-            if let Err(e) = video_player::play_video_at_timestamp(&video.path, 0.0, self.mpv_always_on_top, use_gpu_hq, use_custom_shaders, selected_shader, self.mpv_volume) {
-                eprintln!("Video playback error: {}", e);
-            }
+        self.persist_videos(&changed);
+        
+        if finished {
+            self.duration_probe_receiver = None;
+            self.duration_probe_cancel = None;
+            self.duration_probe_progress = None;
+            self.status_message = Some(self.i18n.t("durations_recomputed"));
+        } else {
+            ctx.request_repaint_after(std::time::Duration::from_millis(250));
         }
+    }
+    
+    /// Apply playback positions reported by mpv and persist them once the player closes
+    fn process_playback_status(&mut self, ctx: &egui::Context) {
+        let mut statuses = Vec::new();
+        let mut finished = false;
         
-        response.context_menu(|ui| {
-            if ui.button(&self.i18n.t("show_in_folder")).clicked() {
-                if let Err(e) = video_player::show_in_folder(&video.path) {
-                    eprintln!("Show in folder error: {}", e);
-                }
-                ui.close_menu();
-            }
-            
-            ui.separator();
-            
-            // Refresh profile button - handles both single and multiple selection
-            let video_id_for_refresh = video.id.clone();
-            let refresh_label = if self.selected_videos.len() > 1 {
-                format!("{} ({})", self.i18n.t("refresh_profile"), self.selected_videos.len())
-            } else {
-                self.i18n.t("refresh_profile")
-            };
-            if ui.button(&refresh_label).clicked() {
-                if self.selected_videos.len() > 1 {
-                    // Refresh all selected videos
-                    let selected_ids: Vec<String> = self.selected_videos.iter().cloned().collect();
-                    for video_id in selected_ids {
-                        self.refresh_video_profile(&video_id);
+        if let Some(ref receiver) = self.playback_monitor_receiver {
+            loop {
+                match receiver.try_recv() {
+                    Ok(status) => statuses.push(status),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        finished = true;
+                        break;
                     }
-                } else {
-                    // Refresh single video
-                    self.refresh_video_profile(&video_id_for_refresh);
                 }
-                ui.close_menu();
             }
-            
-            ui.separator();
-            
-            if ui.button(&self.i18n.t("delete")).clicked() {
-                self.delete_confirm_video = Some(video.id.clone());
-                ui.close_menu();
+        } else {
+            return;
+        }
+        
+        for status in statuses {
+            if status.volume.is_some() || status.speed.is_some() {
+                self.player_levels.insert(status.path.clone(), (status.volume, status.speed));
             }
-            
-            // Multiple selection delete option
-            if self.selected_videos.len() > 1 {
-                let delete_multiple_text = self.i18n.t("delete_selected");
-                if ui.button(&delete_multiple_text).clicked() {
-                    self.delete_confirm_video = Some("__MULTI__".to_string());
-                    ui.close_menu();
+            if let Some(video) = self.database.videos.iter_mut().find(|v| v.path == status.path) {
+                // Forget the position near the start or once the video is (almost) finished
+                let near_end = status.duration.or(video.duration)
+                    .map(|d| status.position >= d - 10.0 || status.position >= d * 0.95)
+                    .unwrap_or(false);
+                let position = if near_end || status.position < 5.0 {
+                    None
+                } else {
+                    Some(status.position)
+                };
+                
+                if video.playback_position != position {
+                    video.playback_position = position;
+                    self.playback_dirty_videos.insert(video.id.clone());
+                }
+                
+                // Played past 90%: count as watched
+                let watched = status.duration.or(video.duration)
+                    .is_some_and(|d| d > 0.0 && status.position >= d * 0.9);
+                if watched && !video.watched {
+                    video.watched = true;
+                    self.playback_dirty_videos.insert(video.id.clone());
                 }
             }
-        });
+        }
+        
+        if finished {
+            self.playback_monitor_receiver = None;
+            self.store_player_levels();
+            self.flush_playback_changes();
+        } else {
+            ctx.request_repaint_after(std::time::Duration::from_secs(2));
+        }
     }
     
-    pub fn add_tag_to_video(&mut self, video_id: &str, tag: String) {
-        if let Some(video) = self.database.get_video_mut(video_id) {
-            if !video.tags.contains(&tag) {
-                video.tags.push(tag.clone());
-                self.database.add_tag(tag);
-                let _ = database::save_database(&self.database);
-            }
+    /// Persist the videos changed by playback reports since the last save
+    fn flush_playback_changes(&mut self) {
+        let video_ids: Vec<String> = self.playback_dirty_videos.drain().collect();
+        if !video_ids.is_empty() {
+            self.persist_videos(&video_ids);
         }
     }
     
-    pub fn remove_tag_from_video(&mut self, video_id: &str, tag: &str) {
-        if let Some(video) = self.database.get_video_mut(video_id) {
-            video.tags.retain(|t| t != tag);
-            let _ = database::save_database(&self.database);
-            let _ = database::save_database(&self.database);
+    /// Remember the volume/speed each video was last played at if the user changed it in mpv
+    /// (values equal to the global volume / normal speed clear the per-video setting)
+    fn store_player_levels(&mut self) {
+        let (launch_volume, launch_speed) = self.player_launch_levels;
+        for (path, (volume, speed)) in std::mem::take(&mut self.player_levels) {
+            let Some(video) = self.database.videos.iter_mut().find(|v| v.path == path) else {
+                continue;
+            };
+            if let Some(volume) = volume.map(|v| v.round().clamp(0.0, 130.0) as u8) {
+                if volume != launch_volume {
+                    video.preferred_volume = (volume != self.mpv_volume).then_some(volume);
+                    self.playback_dirty_videos.insert(video.id.clone());
+                }
+            }
+            if let Some(speed) = speed.filter(|s| *s > 0.0) {
+                if (speed - launch_speed).abs() > 0.001 {
+                    video.preferred_speed = ((speed - 1.0).abs() > 0.001).then_some(speed);
+                    self.playback_dirty_videos.insert(video.id.clone());
+                }
+            }
         }
     }
     
-    pub fn set_video_folder(&mut self, video_id: &str, folder: String) {
-        if let Some(video) = self.database.get_video_mut(video_id) {
-            video.folder = Some(folder.clone());
-            self.database.add_folder(folder);
+    /// Mark videos as watched or unwatched
+    pub fn set_videos_watched(&mut self, video_ids: &[String], watched: bool) {
+        for video_id in video_ids {
+            if let Some(video) = self.database.get_video_mut(video_id) {
+                video.watched = watched;
+            }
         }
+        self.persist_videos(video_ids);
     }
     
-    pub fn get_filtered_videos(&self) -> Vec<&VideoFile> {
-        let mut videos: Vec<&VideoFile> = self.database.videos.iter().collect();
+    /// Manual sort: move a video to the position of another one and renumber the library
+    fn move_video_in_manual_order(&mut self, dragged_id: &str, target_id: &str) {
+        let mut order: Vec<&VideoFile> = self.database.videos.iter().collect();
+        order.sort_by_key(|v| manual_order_key(v));
+        let mut ids: Vec<String> = order.into_iter().map(|v| v.id.clone()).collect();
         
-        // 評価フィルタ
-        if self.min_rating_filter > 0 {
-            videos.retain(|v| v.rating >= self.min_rating_filter);
+        let (Some(from), Some(to)) = (
+            ids.iter().position(|id| id == dragged_id),
+            ids.iter().position(|id| id == target_id),
+        ) else {
+            return;
+        };
+        if from == to {
+            return;
         }
+        // Lands after the target when moved forward, before it when moved back
+        let id = ids.remove(from);
+        ids.insert(to, id);
         
-        // フォルダフィルタ（複数選択対応）
-        if !self.selected_folder_filter.is_empty() {
-            videos.retain(|v| {
-                if let Some(folder) = &v.folder {
-                    self.selected_folder_filter.contains(folder)
-                } else {
-                    false
+        let positions: HashMap<&str, i64> = ids.iter().enumerate().map(|(i, id)| (id.as_str(), i as i64 + 1)).collect();
+        let mut changed = Vec::new();
+        for video in &mut self.database.videos {
+            if let Some(&index) = positions.get(video.id.as_str()) {
+                if video.sort_index != index {
+                    video.sort_index = index;
+                    changed.push(video.id.clone());
                 }
-            });
+            }
         }
-        
-        // タグフィルタ（複数選択対応、AND/OR切り替え）
-        if !self.selected_tag_filter.is_empty() {
-            videos.retain(|v| {
-                match self.tag_filter_mode {
-                    FilterMode::Or => {
-                        // OR: 選択されたタグのいずれかを持つ動画を表示
-                        v.tags.iter().any(|t| self.selected_tag_filter.contains(t))
-                    }
-                    FilterMode::And => {
-                        // AND: 選択されたタグをすべて持つ動画のみ表示
-                        self.selected_tag_filter.iter().all(|selected_tag| v.tags.contains(selected_tag))
-                    }
-                }
-            });
+        self.persist_videos(&changed);
+    }
+    
+    /// Save one video's record instead of rewriting the whole database
+    fn persist_video(&self, video_id: &str) {
+        if let Some(video) = self.database.get_video(video_id) {
+            let _ = database::persist_video(video);
         }
-        
-        // 検索クエリ
-        if !self.search_query.is_empty() {
-            let query = self.search_query.to_lowercase();
-            videos.retain(|v| {
-                v.title.to_lowercase().contains(&query)
-                    || v.tags.iter().any(|t| t.to_lowercase().contains(&query))
-            });
+    }
+    
+    /// Save the records of several videos in one transaction
+    fn persist_videos(&self, video_ids: &[String]) {
+        let videos: Vec<&VideoFile> = video_ids.iter()
+            .filter_map(|id| self.database.get_video(id))
+            .collect();
+        let _ = database::persist_videos(&videos);
+    }
+    
+    pub fn set_rating(&mut self, video_id: &str, rating: u8) {
+        if self.apply_rating(video_id, rating) {
+            self.persist_video(video_id);
         }
-        
-        // ソート
-        match self.sort_field {
-            SortField::AddedDate => {
-                videos.sort_by(|a, b| {
-                    let cmp = a.added_date.cmp(&b.added_date);
-                    if self.sort_order == SortOrder::Ascending {
-                        cmp
-                    } else {
-                        cmp.reverse()
-                    }
-                });
-            }
-            SortField::FileName => {
-                videos.sort_by(|a, b| {
-                    let cmp = a.title.to_lowercase().cmp(&b.title.to_lowercase());
-                    if self.sort_order == SortOrder::Ascending {
-                        cmp
-                    } else {
-                        cmp.reverse()
-                    }
-                });
+    }
+    
+    /// Update a video's rating without saving. Returns false if the video doesn't exist.
+    fn apply_rating(&mut self, video_id: &str, rating: u8) -> bool {
+        let is_premium = self.is_premium;
+        let Some(video) = self.database.get_video_mut(video_id) else {
+            return false;
+        };
+        // Free tier: only favorite (1) or no favorite (0)
+        // Premium: 1-5 star ratings
+        if !is_premium && rating > 1 {
+            video.rating = 1; // Cap at favorite for free tier
+        } else {
+            video.rating = rating.min(5); // Cap at 5 stars
+        }
+        true
+    }
+    
+    /// Set rating for multiple videos at once (saved once after the loop)
+    pub fn set_rating_for_selected(&mut self, rating: u8) {
+        let video_ids: Vec<String> = self.selected_videos.iter().cloned().collect();
+        for video_id in video_ids {
+            self.apply_rating(&video_id, rating);
+        }
+        self.persist_videos(&self.selected_videos.iter().cloned().collect::<Vec<_>>());
+    }
+    
+    /// Add (or remove) every selected video to/from a folder and persist them once
+    pub fn set_folder_for_selected(&mut self, folder: String, remove: bool) {
+        let video_ids: Vec<String> = self.selected_videos.iter().cloned().collect();
+        for video_id in &video_ids {
+            if remove {
+                self.remove_video_folder(video_id, &folder);
+            } else {
+                self.add_video_folder(video_id, folder.clone());
             }
-            SortField::Duration => {
-                videos.sort_by(|a, b| {
-                    let cmp = a.duration.unwrap_or(0.0).partial_cmp(&b.duration.unwrap_or(0.0))
-                        .unwrap_or(std::cmp::Ordering::Equal);
-                    if self.sort_order == SortOrder::Ascending {
-                        cmp
-                    } else {
-                        cmp.reverse()
-                    }
-                });
+        }
+        self.persist_videos(&video_ids);
+        eprintln!("[folder] {} {} videos {} '{}'", if remove { "Removed" } else { "Added" },
+            video_ids.len(), if remove { "from" } else { "to" }, folder);
+        let key = if remove { "removed_from_folder" } else { "added_to_folder" };
+        self.status_message = Some(self.i18n.t(key)
+            .replacen("{}", &video_ids.len().to_string(), 1)
+            .replacen("{}", &folder, 1));
+    }
+    
+    /// Activate a license key
+    pub fn activate_license(&mut self, license_key: &str) {
+        match license::verify_license(license_key) {
+            Ok(license) if license.is_premium_active() => {
+                self.current_license = Some(license.clone());
+                self.is_premium = true;
+                self.license_status_message = Some(format!(
+                    "✅ License activated successfully!\nIssued to: {}\nExpires: {}",
+                    license.info.issued_to,
+                    license.info.expires_at.map(|ts| license::format_timestamp(ts))
+                        .unwrap_or_else(|| "Never".to_string())
+                ));
+                
+                // Save license key to settings (premium status derived from license)
+                if let Ok(mut settings) = database::load_settings() {
+                    settings.license_key = Some(license_key.to_string());
+                    let _ = database::save_settings(&settings);
+                }
+                
+                eprintln!("[License] Premium license activated for {}", license.info.issued_to);
+            },
+            Ok(license) if license.is_expired => {
+                self.license_status_message = Some(format!(
+                    "❌ License expired\nExpired on: {}",
+                    license.info.expires_at.map(|ts| license::format_timestamp(ts))
+                        .unwrap_or_else(|| "Unknown".to_string())
+                ));
+            },
+            Ok(license) => {
+                self.license_status_message = Some(format!(
+                    "❌ Invalid license type: {}",
+                    license.info.license_type
+                ));
+            },
+            Err(e) => {
+                self.license_status_message = Some(format!("❌ License verification failed:\n{}", e));
             }
         }
-        
-        videos
     }
-}
-
-impl eframe::App for VideoPlayerApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Reset per-frame flags
-        self.thumbnail_clicked_this_frame = false;
+    
+    /// Remove the license from this machine and fall back to the free tier immediately
+    pub fn deactivate_license(&mut self) {
+        if let Some(license) = self.current_license.take() {
+            eprintln!("[License] License for {} deactivated", license.info.issued_to);
+        }
+        self.is_premium = false;
+        self.license_input.clear();
+        self.license_status_message = None;
+        self.license_expiry_banner_dismissed = false;
         
-        // Handle dropped files (drag & drop)
-        let dropped_files: Vec<egui::DroppedFile> = ctx.input(|i| i.raw.dropped_files.clone());
-        if !dropped_files.is_empty() {
-            self.handle_dropped_files(dropped_files);
+        // Free tier limits: favorites only, single folder/tag filter
+        self.min_rating_filter = self.min_rating_filter.min(1);
+        if self.selected_folder_filter.len() > 1 {
+            self.selected_folder_filter.clear();
+        }
+        if self.selected_tag_filter.len() > 1 {
+            self.selected_tag_filter.clear();
         }
         
-        // Process any completed texture loads from background threads
-        self.process_loaded_textures(ctx);
+        // save_settings() keeps the key only while a license is active
+        self.save_settings();
+        self.status_message = Some(self.i18n.t("license_deactivated"));
+    }
+    
+    pub fn generate_scenes(&mut self, video_id: &str) {
+        if let Some(video) = self.database.get_video_mut(video_id) {
+            let cache_dir = thumbnail::get_cache_dir();
+            let _ = scene_detection::detect_scenes(video, &cache_dir, self.scene_threshold, self.max_scenes, self.scene_thread_count);
+            
+            // Free tier: limit to 5 scenes
+            if !self.is_premium && video.scenes.len() > 5 {
+                // Remove excess scenes and their thumbnails
+                while video.scenes.len() > 5 {
+                    if let Some(scene) = video.scenes.pop() {
+                        let _ = std::fs::remove_file(&scene.thumbnail_path);
+                        self.texture_cache.remove(&scene.thumbnail_path);
+                    }
+                }
+            }
+            
+            let _ = database::persist_video(video);
+        }
+    }
+    
+    /// Add a scene at the timestamp entered in the scene panel
+    pub fn add_scene_from_input(&mut self, video_id: &str) {
+        let timestamp = match scene_detection::parse_timestamp(&self.scene_timestamp_input) {
+            Some(ts) => ts,
+            None => {
+                self.scene_capture_error = Some(self.i18n.t("invalid_timestamp"));
+                return;
+            }
+        };
         
-        // Track window size and position for saving on exit
-        ctx.input(|i| {
-            if let Some(rect) = i.viewport().inner_rect {
-                self.last_window_size = Some((rect.width(), rect.height()));
+        let cache_dir = thumbnail::get_cache_dir();
+        let result = if let Some(video) = self.database.get_video_mut(video_id) {
+            if video.duration.is_some_and(|duration| timestamp > duration) {
+                Err(self.i18n.t("timestamp_exceeds_duration"))
+            } else if scene_detection::add_scene_at_timestamp(video, timestamp, &cache_dir).is_some() {
+                Ok(())
+            } else {
+                Err(self.i18n.t("scene_capture_failed"))
             }
-            if let Some(pos) = i.viewport().outer_rect {
-                self.last_window_pos = Some((pos.min.x, pos.min.y));
+        } else {
+            return;
+        };
+        
+        match result {
+            Ok(()) => {
+                self.scene_timestamp_input.clear();
+                self.scene_capture_error = None;
+                // Scene indices shift after insertion
+                self.selected_scenes.clear();
+                self.last_selected_scene = None;
+                self.persist_video(video_id);
             }
-        });
+            Err(message) => self.scene_capture_error = Some(message),
+        }
+    }
+    
+    /// Capture the frame currently shown in mpv as a scene of the given video
+    pub fn capture_current_frame(&mut self, video_id: &str) {
+        let Some(status) = video_player::query_playback_status() else {
+            self.scene_capture_error = Some(self.i18n.t("mpv_not_running"));
+            return;
+        };
         
-        // Apply theme based on dark_mode setting
-        if self.dark_mode {
-            ctx.set_visuals(egui::Visuals::dark());
+        let cache_dir = thumbnail::get_cache_dir();
+        let result = if let Some(video) = self.database.get_video_mut(video_id) {
+            if status.path != video.path {
+                Err(self.i18n.t("mpv_playing_other_video"))
+            } else if scene_detection::add_scene_at_timestamp(video, status.position, &cache_dir).is_some() {
+                Ok(())
+            } else {
+                Err(self.i18n.t("scene_capture_failed"))
+            }
         } else {
-            ctx.set_visuals(egui::Visuals::light());
+            return;
+        };
+        
+        match result {
+            Ok(()) => {
+                self.scene_capture_error = None;
+                self.scene_panel_visible = true;
+                // Scene indices shift after insertion
+                self.selected_scenes.clear();
+                self.last_selected_scene = None;
+                self.persist_video(video_id);
+            }
+            Err(message) => self.scene_capture_error = Some(message),
         }
+    }
+    
+    /// Replace a video's main thumbnail with a newly created image
+    fn replace_main_thumbnail(&mut self, video_id: &str, new_path: PathBuf) {
+        let Some(video) = self.database.get_video_mut(video_id) else {
+            return;
+        };
+        let old_path = video.thumbnail_path.replace(new_path.clone());
         
-        // Check for file system changes
-        self.check_folder_changes();
+        // The file at new_path may have been rewritten, so drop any cached texture of it too
+        for path in old_path.iter().chain(std::iter::once(&new_path)) {
+            if *path != new_path {
+                let _ = std::fs::remove_file(path);
+            }
+            self.texture_cache.remove(path);
+            self.pending_textures.remove(path);
+            self.failed_textures.remove(path);
+            self.blurred_texture_cache.remove(path);
+        }
+        self.persist_video(video_id);
+    }
+    
+    /// Regenerate the main thumbnails of the given videos; several videos go to the background batch
+    pub fn regenerate_video_thumbnails(&mut self, video_ids: &[String]) {
+        match video_ids {
+            [video_id] => self.regenerate_video_thumbnail(video_id),
+            _ => self.start_thumbnail_batch(video_ids),
+        }
+    }
+    
+    /// Extract the main thumbnail again, even if the file size did not change (e.g. re-encoded files)
+    pub fn regenerate_video_thumbnail(&mut self, video_id: &str) {
+        let Some(video) = self.database.get_video(video_id) else {
+            return;
+        };
+        let cache_dir = thumbnail::get_cache_dir();
+        let video_path = video.path.clone();
         
-        // Check for scene capture requests from mpv
-        self.check_scene_capture_requests();
+        // create_video_thumbnail reuses an existing file, so remove the stale one first
+        let default_path = thumbnail::video_thumbnail_path(&video_path, &cache_dir);
+        let _ = std::fs::remove_file(&default_path);
         
-        // Perform rescan if pending
-        if self.pending_rescan {
-            eprintln!("[app] Triggering automatic rescan due to folder changes");
-            self.rescan_folders();
+        match thumbnail::create_video_thumbnail(&video_path, &cache_dir) {
+            Some(path) => self.replace_main_thumbnail(video_id, path),
+            None => eprintln!("[Thumbnail] Failed to regenerate thumbnail for {:?}", video_path),
+        }
+    }
+    
+    /// Use the frame at the timestamp entered in the dialog as the main thumbnail
+    pub fn set_thumbnail_from_input(&mut self, video_id: &str) {
+        let Some(timestamp) = scene_detection::parse_timestamp(&self.thumbnail_timestamp_input) else {
+            self.thumbnail_timestamp_error = Some(self.i18n.t("invalid_timestamp"));
+            return;
+        };
+        let Some(video) = self.database.get_video(video_id) else {
+            return;
+        };
+        if video.duration.is_some_and(|duration| timestamp > duration) {
+            self.thumbnail_timestamp_error = Some(self.i18n.t("timestamp_exceeds_duration"));
+            return;
         }
         
-        // Initialize folder watcher if not set up yet and we have videos
-        if self.fs_watcher.is_none() && !self.database.videos.is_empty() {
-            self.setup_folder_watcher();
+        match thumbnail::create_thumbnail_at(&video.path, timestamp, &thumbnail::get_cache_dir()) {
+            Some(path) => {
+                self.replace_main_thumbnail(video_id, path);
+                self.thumbnail_timestamp_video = None;
+                self.thumbnail_timestamp_input.clear();
+                self.thumbnail_timestamp_error = None;
+            }
+            None => self.thumbnail_timestamp_error = Some(self.i18n.t("scene_capture_failed")),
         }
+    }
+    
+    /// Promote a scene frame to the video's main thumbnail
+    pub fn use_scene_as_thumbnail(&mut self, video_id: &str, timestamp: f64) {
+        let Some(video) = self.database.get_video(video_id) else {
+            return;
+        };
+        let cache_dir = thumbnail::get_cache_dir();
+        let target = thumbnail::custom_thumbnail_path(&video.path, timestamp, &cache_dir);
         
-        // トップバー
-        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
-            egui::menu::bar(ui, |ui| {
-                // Title with background color
-                let title_frame = egui::Frame::none()
-                    .fill(egui::Color32::from_rgb(60, 130, 60))
-                    .inner_margin(egui::Margin::symmetric(8.0, 4.0));
-                
-                title_frame.show(ui, |ui| {
-                    ui.heading(egui::RichText::new("CicadaGallery - Video Gallery Player").color(egui::Color32::WHITE));
-                });
-                
-                ui.separator();
-                
-                if ui.button(&self.i18n.t("add_videos")).clicked() {
-                    self.add_files();
+        // Copy the scene image; extract the frame again if it was evicted from the cache
+        let copied = video.scenes.iter()
+            .find(|scene| scene.timestamp == timestamp)
+            .is_some_and(|scene| std::fs::copy(&scene.thumbnail_path, &target).is_ok());
+        let new_path = if copied {
+            Some(target)
+        } else {
+            thumbnail::create_thumbnail_at(&video.path, timestamp, &cache_dir)
+        };
+        
+        match new_path {
+            Some(path) => self.replace_main_thumbnail(video_id, path),
+            None => eprintln!("[Thumbnail] Failed to use scene at {}s as thumbnail", timestamp),
+        }
+    }
+    
+    /// Set a custom display title (the file itself is never renamed)
+    pub fn set_video_title(&mut self, video_id: &str, title: &str) {
+        let title = title.trim();
+        if title.is_empty() {
+            return;
+        }
+        if let Some(video) = self.database.get_video_mut(video_id) {
+            video.title = title.to_string();
+            let _ = database::persist_video(video);
+        }
+    }
+    
+    /// Rename a tag everywhere (tag list, videos and the active tag filter)
+    pub fn rename_tag(&mut self, old_name: &str, new_name: &str) {
+        self.database.rename_tag(old_name, new_name);
+        if self.selected_tag_filter.remove(old_name) {
+            self.selected_tag_filter.insert(new_name.to_string());
+        }
+        eprintln!("[tag_management] Renamed tag '{}' to '{}'", old_name, new_name);
+        let _ = database::persist_tag_change(&self.database, &[old_name, new_name]);
+    }
+    
+    /// Rename a content folder label on the folder list and all member videos
+    pub fn rename_folder(&mut self, old_name: &str, new_name: &str) -> Result<(), String> {
+        let new_name = new_name.trim();
+        if new_name.is_empty() {
+            return Err(self.i18n.t("folder_name_empty"));
+        }
+        if new_name == old_name {
+            return Ok(());
+        }
+        if self.database.folders.iter().any(|f| f == new_name) {
+            return Err(self.i18n.t("folder_name_exists").replace("{}", new_name));
+        }
+        
+        self.database.rename_folder(old_name, new_name);
+        if self.selected_folder_filter.remove(old_name) {
+            self.selected_folder_filter.insert(new_name.to_string());
+        }
+        eprintln!("[folder_management] Renamed folder '{}' to '{}'", old_name, new_name);
+        self.save_settings();
+        let _ = database::persist_folder_rename(&self.database, old_name, new_name);
+        Ok(())
+    }
+    
+    /// Reassign all videos from one tag to another and remove the source tag
+    pub fn merge_tag(&mut self, source: &str, target: &str) {
+        self.database.merge_tags(source, target);
+        if self.selected_tag_filter.remove(source) {
+            self.selected_tag_filter.insert(target.to_string());
+        }
+        eprintln!("[tag_management] Merged tag '{}' into '{}'", source, target);
+        let _ = database::persist_tag_change(&self.database, &[source, target]);
+    }
+    
+    /// Save the notes being edited in the details panel and unload them from the editor
+    fn flush_notes(&mut self) {
+        if let Some(video_id) = self.notes_video_id.take() {
+            let notes = std::mem::take(&mut self.notes_buffer);
+            self.set_video_notes(&video_id, &notes);
+        }
+    }
+    
+    /// Set free-text notes for a video
+    pub fn set_video_notes(&mut self, video_id: &str, notes: &str) {
+        if let Some(video) = self.database.get_video_mut(video_id) {
+            if video.notes != notes {
+                video.notes = notes.to_string();
+                let _ = database::persist_video(video);
+            }
+        }
+    }
+    
+    /// Export all scene thumbnails of a video as a single PNG contact sheet
+    pub fn export_contact_sheet(&mut self, video_id: &str) {
+        let Some(video) = self.database.get_video(video_id) else {
+            return;
+        };
+        
+        if video.scenes.is_empty() {
+            self.status_message = Some(self.i18n.t("contact_sheet_no_scenes"));
+            return;
+        }
+        
+        let default_name = format!(
+            "{}_contact_sheet.png",
+            video.path.file_stem().and_then(|s| s.to_str()).unwrap_or("video")
+        );
+        let Some(output_path) = FileDialog::new()
+            .add_filter("PNG", &["png"])
+            .set_file_name(default_name)
+            .save_file()
+        else {
+            return;
+        };
+        
+        self.status_message = Some(match thumbnail::create_contact_sheet(&video.scenes, &output_path, 5) {
+            Ok(()) => format!("{}: {}", self.i18n.t("contact_sheet_saved"), output_path.display()),
+            Err(e) => format!("{}: {}", self.i18n.t("contact_sheet_failed"), e),
+        });
+    }
+    
+    /// Videos targeted by exports: the whole library, or the multi-selection / filtered list
+    fn videos_for_export(&self, all: bool) -> Vec<&VideoFile> {
+        if all {
+            return self.database.videos.iter()
+                .filter(|v| self.protected_unlocked || !v.protected)
+                .collect();
+        }
+        let filtered = self.get_filtered_videos();
+        if self.selected_videos.len() > 1 {
+            filtered.into_iter().filter(|v| self.selected_videos.contains(&v.id)).collect()
+        } else {
+            filtered
+        }
+    }
+    
+    /// Export video metadata to a CSV file chosen by the user
+    pub fn export_csv(&mut self) {
+        let videos = self.videos_for_export(self.export_all_videos);
+        
+        let mut csv = String::from("title,path,duration,resolution,file_size,rating,folder,tags,added_date,last_played\r\n");
+        for video in &videos {
+            let fields = [
+                video.title.clone(),
+                video.path.to_string_lossy().to_string(),
+                video.duration.map(|d| format!("{:.2}", d)).unwrap_or_default(),
+                video.resolution.map(|(w, h)| format!("{}x{}", w, h)).unwrap_or_default(),
+                video.file_size.to_string(),
+                video.rating.to_string(),
+                video.folders.join(";"),
+                video.tags.join(";"),
+                format_export_date(video.added_date),
+                video.last_played.map(format_export_date).unwrap_or_default(),
+            ];
+            let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&row.join(","));
+            csv.push_str("\r\n");
+        }
+        let count = videos.len();
+        
+        let Some(output_path) = FileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .set_file_name("cicada_gallery.csv")
+            .save_file()
+        else {
+            return;
+        };
+        
+        // BOM so spreadsheet applications detect UTF-8 (Japanese titles)
+        let result = std::fs::write(&output_path, format!("\u{feff}{}", csv));
+        self.status_message = Some(match result {
+            Ok(()) => self.i18n.t("export_csv_saved")
+                .replacen("{}", &count.to_string(), 1)
+                .replacen("{}", &output_path.display().to_string(), 1),
+            Err(e) => format!("{}: {}", self.i18n.t("export_failed"), e),
+        });
+    }
+    
+    /// Write the selection (or the clicked video) to an extended M3U playlist, in the displayed order
+    pub fn export_playlist(&mut self, clicked_id: &str) {
+        let use_selection = self.acts_on_selection(clicked_id);
+        let videos: Vec<&VideoFile> = self.get_filtered_videos().into_iter()
+            .filter(|v| if use_selection { self.selected_videos.contains(&v.id) } else { v.id == clicked_id })
+            .collect();
+        
+        let mut playlist = String::from("#EXTM3U\n");
+        for video in &videos {
+            // -1 marks an unknown length in extended M3U
+            let seconds = video.duration.map(|d| d.round() as i64).unwrap_or(-1);
+            let path = std::path::absolute(&video.path).unwrap_or_else(|_| video.path.clone());
+            playlist.push_str(&format!("#EXTINF:{},{}\n{}\n", seconds, video.title, path.display()));
+        }
+        let count = videos.len();
+        
+        let Some(output_path) = FileDialog::new()
+            .add_filter("M3U8", &["m3u8", "m3u"])
+            .set_file_name("playlist.m3u8")
+            .save_file()
+        else {
+            return;
+        };
+        
+        self.status_message = Some(match std::fs::write(&output_path, playlist) {
+            Ok(()) => self.i18n.t("export_playlist_saved")
+                .replacen("{}", &count.to_string(), 1)
+                .replacen("{}", &output_path.display().to_string(), 1),
+            Err(e) => format!("{}: {}", self.i18n.t("export_failed"), e),
+        });
+    }
+    
+    /// Merge tags, rating, folder, title and notes from a JSON file into matching library videos
+    pub fn import_metadata(&mut self) {
+        let Some(input_path) = FileDialog::new()
+            .add_filter("JSON", &["json"])
+            .pick_file()
+        else {
+            return;
+        };
+        
+        let records: Vec<MetadataImportRecord> = match std::fs::read_to_string(&input_path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| serde_json::from_str(&text).map_err(|e| e.to_string()))
+        {
+            Ok(records) => records,
+            Err(e) => {
+                self.status_message = Some(format!("{}: {}", self.i18n.t("import_metadata_failed"), e));
+                return;
+            }
+        };
+        
+        // Save notes being typed first; the editor reloads them afterwards
+        self.flush_notes();
+        
+        // Match by canonical path (falls back to the stored path for files that are offline)
+        let index_by_path: HashMap<PathBuf, String> = self.database.videos.iter()
+            .map(|v| (v.path.canonicalize().unwrap_or_else(|_| v.path.clone()), v.id.clone()))
+            .collect();
+        
+        let mut matched = Vec::new();
+        let mut skipped = 0;
+        for record in records {
+            let key = record.path.canonicalize().unwrap_or_else(|_| record.path.clone());
+            let Some(video_id) = index_by_path.get(&key).cloned() else {
+                eprintln!("[import_metadata] Not in library: {:?}", record.path);
+                skipped += 1;
+                continue;
+            };
+            
+            if let Some(rating) = record.rating {
+                self.apply_rating(&video_id, rating);
+            }
+            for tag in &record.tags {
+                self.database.add_tag(tag.clone());
+            }
+            if let Some(folder) = &record.folder {
+                self.database.add_folder(folder.clone());
+            }
+            if let Some(video) = self.database.get_video_mut(&video_id) {
+                for tag in record.tags {
+                    if !video.tags.contains(&tag) {
+                        video.tags.push(tag);
+                    }
                 }
-                
-                if ui.button(&self.i18n.t("add_folder")).clicked() {
-                    self.add_folder();
+                if let Some(folder) = record.folder {
+                    if !video.folders.contains(&folder) {
+                        video.folders.push(folder);
+                    }
                 }
-                
-                if ui.button(&self.i18n.t("rescan_folders")).clicked() {
-                    self.rescan_folders();
+                if let Some(title) = record.title.filter(|t| !t.trim().is_empty()) {
+                    video.title = title;
                 }
-                
-                ui.separator();
-                
-                let view_button_text = if self.current_view == ViewMode::Grid { 
-                    self.i18n.t("list_view") 
-                } else { 
-                    self.i18n.t("grid_view") 
-                };
-                if ui.button(&view_button_text).clicked() {
-                    self.current_view = if self.current_view == ViewMode::Grid {
-                        ViewMode::List
-                    } else {
-                        ViewMode::Grid
-                    };
+                if let Some(notes) = record.notes {
+                    video.notes = notes;
                 }
-                
-                ui.separator();
-                
-                if ui.button(&self.i18n.t("options")).clicked() {
-                    self.show_options_window = !self.show_options_window;
+            }
+            matched.push(video_id);
+        }
+        
+        self.persist_videos(&matched);
+        
+        eprintln!("[import_metadata] Matched {}, skipped {}", matched.len(), skipped);
+        self.status_message = Some(self.i18n.t("import_metadata_done")
+            .replacen("{}", &matched.len().to_string(), 1)
+            .replacen("{}", &skipped.to_string(), 1));
+    }
+    
+    /// Remove scenes that look nearly identical to the previous kept scene
+    pub fn remove_similar_scenes(&mut self, video_id: &str) {
+        let threshold = self.similar_scene_threshold;
+        let Some(video) = self.database.get_video_mut(video_id) else {
+            return;
+        };
+        
+        let mut removed = Vec::new();
+        let mut kept = Vec::with_capacity(video.scenes.len());
+        let mut last_hash: Option<u64> = None;
+        for (index, scene) in video.scenes.drain(..).enumerate() {
+            let hash = thumbnail::perceptual_hash(&scene.thumbnail_path);
+            match (last_hash, hash) {
+                (Some(previous), Some(current)) if thumbnail::hash_similarity(previous, current) >= threshold => {
+                    removed.push((index, scene));
                 }
-                
-                ui.separator();
-                
-                // Scene panel button (premium only)
-                if self.is_premium {
-                    if ui.button(if self.scene_panel_visible { "Hide Scenes" } else { "Show Scenes" }).clicked() {
-                        self.scene_panel_visible = !self.scene_panel_visible;
+                _ => {
+                    if hash.is_some() {
+                        last_hash = hash;
                     }
-                } else {
-                    ui.add_enabled(false, egui::Button::new("Show Scenes 🔒"));
+                    kept.push(scene);
                 }
+            }
+        }
+        video.scenes = kept;
+        
+        for (_, scene) in &removed {
+            let _ = std::fs::remove_file(&scene.thumbnail_path);
+            self.texture_cache.remove(&scene.thumbnail_path);
+        }
+        
+        let removed_count = removed.len();
+        if !removed.is_empty() {
+            // Scene indices shift after removal
+            self.selected_scenes.clear();
+            self.last_selected_scene = None;
+            // Removed in ascending order, so undo must re-insert them back to front
+            removed.reverse();
+            self.push_undo(UndoEntry::Scenes { video_id: video_id.to_string(), scenes: removed });
+            self.persist_video(video_id);
+        }
+        
+        eprintln!("[Scene] Removed {} similar scenes", removed_count);
+        self.status_message = Some(self.i18n.t("similar_scenes_removed").replace("{}", &removed_count.to_string()));
+    }
+    
+    pub fn delete_scene(&mut self, video_id: &str, scene_timestamp: f64) {
+        if let Some(video) = self.database.get_video_mut(video_id) {
+            // Find and remove the scene
+            if let Some(index) = video.scenes.iter().position(|s| (s.timestamp - scene_timestamp).abs() < 0.01) {
+                let scene = video.scenes.remove(index);
                 
-                ui.separator();
+                // Delete thumbnail file
+                let _ = std::fs::remove_file(&scene.thumbnail_path);
                 
-                // Multi-selection controls
-                let filtered_videos = self.get_filtered_videos();
-                if !filtered_videos.is_empty() {
-                    if ui.button(&self.i18n.t("select_all")).clicked() {
-                        let video_ids: Vec<String> = filtered_videos.iter().map(|v| v.id.clone()).collect();
-                        self.select_all_videos(video_ids);
-                    }
-                    
-                    if !self.selected_videos.is_empty() {
-                        let clear_text = self.i18n.t("clear_selection_count").replace("{}", &self.selected_videos.len().to_string());
-                        if ui.button(&clear_text).clicked() {
-                            self.clear_selection();
-                        }
-                        
-                        if ui.button(&self.i18n.t("add_tag_to_selected")).clicked() {
-                            self.show_tag_add_popup = true;
-                        }
-                        
-                        if ui.button(&self.i18n.t("delete_selected")).clicked() {
-                            self.delete_confirm_video = Some("__MULTI__".to_string());
-                        }
-                    }
-                }
+                // Remove from texture cache
+                self.texture_cache.remove(&scene.thumbnail_path);
                 
-                // Push volume slider to the right
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    ui.add(egui::Slider::new(&mut self.mpv_volume, 0..=100)
-                        .text("🔊")
-                        .suffix("%")
-                        .show_value(true)
-                        .min_decimals(0)
-                        .max_decimals(0));
-                    // ui.label("デフォルト音量");
+                // Save database
+                let _ = database::persist_video(video);
+                
+                self.push_undo(UndoEntry::Scenes { video_id: video_id.to_string(), scenes: vec![(index, scene)] });
+            }
+        }
+        
+        // Clear scene selection
+        self.selected_scenes.clear();
+        self.last_selected_scene = None;
+    }
+    
+    pub fn delete_selected_scenes(&mut self, video_id: &str) {
+        if let Some(video) = self.database.get_video_mut(video_id) {
+            // Sort indices in descending order to remove from back to front
+            let mut indices: Vec<usize> = self.selected_scenes.iter().copied().collect();
+            indices.sort_by(|a, b| b.cmp(a));
+            
+            let mut removed = Vec::new();
+            for index in indices {
+                if index < video.scenes.len() {
+                    let scene = video.scenes.remove(index);
                     
-                    ui.separator();
+                    // Delete thumbnail file
+                    let _ = std::fs::remove_file(&scene.thumbnail_path);
                     
-                    // Show video count and limit for free tier
-                    let video_count = self.database.videos.len();
-                    if self.is_premium {
-                        ui.label(format!("📹 Videos: {}", video_count));
-                    } else {
-                        let count_text = format!("📹 {}/100 (Free)", video_count);
-                        let color = if video_count >= 100 {
-                            egui::Color32::from_rgb(255, 100, 100)
-                        } else if video_count >= 80 {
-                            egui::Color32::from_rgb(255, 200, 100)
-                        } else {
-                            egui::Color32::from_rgb(200, 200, 200)
-                        };
-                        ui.label(egui::RichText::new(count_text).color(color));
-                    }
+                    // Remove from texture cache
+                    self.texture_cache.remove(&scene.thumbnail_path);
                     
-                    if ui.memory(|mem| mem.is_anything_being_dragged()) {
-                        self.save_settings();
-                    }
-                });
-            });
-        });
-        
-        // Sidebar (Filters)
-        egui::SidePanel::left("side_panel").min_width(220.0).show(ctx, |ui| {
-            ui.heading(&self.i18n.t("filters"));
-            ui.separator();
-            
-            // Rating filter
-            ui.label(&self.i18n.t("min_rating"));
-            ui.horizontal_wrapped(|ui| {
-                if ui.radio(self.min_rating_filter == 0, "All").clicked() {
-                    self.min_rating_filter = 0;
-                }
-                
-                if self.is_premium {
-                    // Premium: 1-5 star ratings
-                    for rating in 1..=5 {
-                        let label = format!("{}★+", rating);
-                        if ui.radio(self.min_rating_filter == rating, label).clicked() {
-                            self.min_rating_filter = rating;
-                        }
-                    }
-                } else {
-                    // Free: Favorite only
-                    if ui.radio(self.min_rating_filter == 1, "⭐ Favorites").clicked() {
-                        self.min_rating_filter = 1;
-                    }
+                    removed.push((index, scene));
                 }
-            });
-            ui.separator();
+            }
             
-            // Folder filter
-            ui.label(&self.i18n.t("folders"));
-            if ui.button(&self.i18n.t("all")).clicked() {
-                self.selected_folder_filter.clear();
-                self.last_selected_folder = None;
+            // Save database
+            let _ = database::persist_video(video);
+            
+            if !removed.is_empty() {
+                self.push_undo(UndoEntry::Scenes { video_id: video_id.to_string(), scenes: removed });
+            }
+        }
+        
+        // Clear scene selection
+        self.selected_scenes.clear();
+        self.last_selected_scene = None;
+    }
+    
+    pub fn toggle_scene_selection(&mut self, index: usize) {
+        if self.selected_scenes.contains(&index) {
+            self.selected_scenes.remove(&index);
+        } else {
+            self.selected_scenes.insert(index);
+        }
+    }
+    
+    pub fn select_scene_range(&mut self, start: usize, end: usize) {
+        let (min, max) = if start <= end { (start, end) } else { (end, start) };
+        for i in min..=max {
+            self.selected_scenes.insert(i);
+        }
+    }
+    
+    /// Refresh the video profile: regenerate thumbnail, metadata, and scenes
+    pub fn refresh_video_profile(&mut self, video_id: &str) {
+        let cache_dir = thumbnail::get_cache_dir();
+        
+        // Get video info
+        let video_path = if let Some(video) = self.database.get_video(video_id) {
+            video.path.clone()
+        } else {
+            return;
+        };
+        
+        // Delete existing thumbnail
+        if let Some(video) = self.database.get_video(video_id) {
+            if let Some(ref thumb_path) = video.thumbnail_path {
+                let _ = std::fs::remove_file(thumb_path);
+                self.texture_cache.remove(thumb_path);
+                self.pending_textures.remove(thumb_path);
+                self.failed_textures.remove(thumb_path);
+            }
+            if let Some(ref preview_path) = video.preview_thumbnail_path {
+                let _ = std::fs::remove_file(preview_path);
+                self.texture_cache.remove(preview_path);
+                self.pending_textures.remove(preview_path);
+                self.failed_textures.remove(preview_path);
+            }
+            if let Some(ref sprite_path) = video.sprite_sheet_path {
+                let _ = std::fs::remove_file(sprite_path);
+                self.texture_cache.remove(sprite_path);
+                self.pending_textures.remove(sprite_path);
+                self.failed_textures.remove(sprite_path);
             }
-            let folders_clone = self.database.folders.clone();
-            egui::ScrollArea::vertical()
-                .id_salt("folder_filter_scroll")
-                .max_height(300.0)
-                .show(ui, |ui| {
-                    for (idx, folder) in folders_clone.iter().enumerate() {
-                        let is_selected = self.selected_folder_filter.contains(folder);
-                        let response = ui.selectable_label(is_selected, folder);
-                        
-                        if response.clicked() {
-                            let modifiers = ui.input(|i| i.modifiers);
-                            
-                            if self.is_premium {
-                                // Premium: Ctrl+Click for multiple selection, Shift+Click for range selection
-                                if modifiers.shift && self.last_selected_folder.is_some() {
-                                    // Shift+Click: Range selection
-                                    let last_idx = self.last_selected_folder.unwrap();
-                                    let (start, end) = if idx < last_idx { (idx, last_idx) } else { (last_idx, idx) };
-                                    for i in start..=end {
-                                        if let Some(f) = folders_clone.get(i) {
-                                            self.selected_folder_filter.insert(f.clone());
-                                        }
-                                    }
-                                } else if modifiers.ctrl || modifiers.command {
-                                    // Ctrl+Click: Toggle selection
-                                    if is_selected {
-                                        self.selected_folder_filter.remove(folder);
-                                    } else {
-                                        self.selected_folder_filter.insert(folder.clone());
-                                    }
-                                    self.last_selected_folder = Some(idx);
-                                } else {
-                                    // Normal click: Single selection (clear others)
-                                    self.selected_folder_filter.clear();
-                                    self.selected_folder_filter.insert(folder.clone());
-                                    self.last_selected_folder = Some(idx);
-                                }
-                            } else {
-                                // Free: Single selection only
-                                self.selected_folder_filter.clear();
-                                self.selected_folder_filter.insert(folder.clone());
-                            }
-                        }
-                    }
-                });
             
-            ui.separator();
+            // Delete scene thumbnails
+            let scene_dir = cache_dir.join("scenes").join(&video.id);
+            if scene_dir.exists() {
+                // Remove scene textures from cache
+                for scene in &video.scenes {
+                    self.texture_cache.remove(&scene.thumbnail_path);
+                    self.pending_textures.remove(&scene.thumbnail_path);
+                    self.failed_textures.remove(&scene.thumbnail_path);
+                }
+                let _ = std::fs::remove_dir_all(&scene_dir);
+            }
+        }
+        
+        // Update video profile with fresh data
+        if let Some(video) = self.database.get_video_mut(video_id) {
+            // Regenerate thumbnail (the preview and sprite sheet are regenerated on next use)
+            video.thumbnail_path = thumbnail::create_video_thumbnail(&video_path, &cache_dir);
+            video.preview_thumbnail_path = None;
+            video.sprite_sheet_path = None;
             
-            // Tag filter
-            ui.label(&self.i18n.t("tags_colon"));
-            ui.horizontal(|ui| {
-                if ui.button(&self.i18n.t("all")).clicked() {
-                    self.selected_tag_filter.clear();
-                    self.last_selected_tag = None;
+            // Refresh metadata
+            video.duration = scene_detection::get_video_duration(&video_path);
+            video.resolution = scene_detection::get_video_resolution(&video_path);
+            video.frame_rate = scene_detection::get_video_frame_rate(&video_path);
+            (video.codec, video.container) = scene_detection::get_video_codec(&video_path);
+            video.is_hdr = scene_detection::is_video_hdr(&video_path);
+            video.chapters = scene_detection::get_video_chapters(&video_path);
+            
+            // Update file size
+            if let Ok(metadata) = std::fs::metadata(&video_path) {
+                video.file_size = metadata.len();
+            }
+            video.modified_time = VideoFile::read_modified_time(&video_path);
+            video.content_hash = video_scanner::compute_content_hash(&video_path);
+            
+            // Clear scenes so they will be regenerated
+            video.scenes.clear();
+        }
+        
+        self.persist_video(video_id);
+    }
+    
+    /// Regenerate missing thumbnails for all videos after backup restore
+    /// This checks if thumbnail files exist on disk and regenerates if missing
+    pub fn regenerate_missing_thumbnails(&mut self) {
+        let cache_dir = thumbnail::get_cache_dir();
+        let mut needs_save = false;
+        
+        for video in &mut self.database.videos {
+            // Check if video file exists
+            if !video.path.exists() {
+                eprintln!("[Restore] Skipping missing video file: {:?}", video.path);
+                continue;
+            }
+            
+            // Check main thumbnail
+            let needs_main_thumb = match &video.thumbnail_path {
+                Some(path) => !path.exists(),
+                None => true,
+            };
+            
+            if needs_main_thumb {
+                eprintln!("[Restore] Regenerating thumbnail for: {:?}", video.path);
+                video.thumbnail_path = thumbnail::create_video_thumbnail(&video.path, &cache_dir);
+                needs_save = true;
+            }
+            
+            // Check scene thumbnails
+            let has_missing_scenes = video.scenes.iter().any(|scene| !scene.thumbnail_path.exists());
+            
+            if has_missing_scenes || (!video.scenes.is_empty() && video.scenes.iter().all(|s| !s.thumbnail_path.exists())) {
+                eprintln!("[Restore] Regenerating scene thumbnails for: {:?}", video.path);
+                // Clear existing scenes
+                video.scenes.clear();
+                // Regenerate scenes
+                if let Err(e) = scene_detection::detect_scenes(video, &cache_dir, self.scene_threshold, self.max_scenes, self.scene_thread_count) {
+                    eprintln!("[Restore] Failed to regenerate scenes for {:?}: {}", video.path, e);
                 }
-                // AND/OR toggle (only show when premium and multiple tags selected)
-                if self.is_premium && self.selected_tag_filter.len() > 1 {
-                    ui.separator();
-                    if ui.selectable_label(self.tag_filter_mode == FilterMode::Or, "OR").clicked() {
-                        self.tag_filter_mode = FilterMode::Or;
+                needs_save = true;
+            }
+        }
+        
+        if needs_save {
+            let _ = database::save_database(&self.database);
+            eprintln!("[Restore] Thumbnail regeneration complete");
+        } else {
+            eprintln!("[Restore] All thumbnails are intact, no regeneration needed");
+        }
+    }
+        /// Refresh scene thumbnails for a video: delete existing scenes and regenerate
+    pub fn refresh_scenes(&mut self, video_id: &str) {
+        // Just clear texture caches to force reload from disk
+        // Does NOT regenerate scenes
+        if let Some(video) = self.database.get_video(video_id) {
+            for scene in &video.scenes {
+                self.texture_cache.remove(&scene.thumbnail_path);
+                self.pending_textures.remove(&scene.thumbnail_path);
+                self.failed_textures.remove(&scene.thumbnail_path);
+            }
+        }
+    }
+
+    pub fn delete_video(&mut self, video_id: &str, delete_cache: bool) {
+        if let Some(removed) = self.delete_video_record(video_id, delete_cache) {
+            self.push_undo(UndoEntry::Videos(vec![removed]));
+        }
+    }
+    
+    /// Remove a video from the database, returning its record and index for undo
+    fn delete_video_record(&mut self, video_id: &str, delete_cache: bool) -> Option<(usize, VideoFile)> {
+        let index = self.database.videos.iter().position(|v| v.id == video_id)?;
+        let removed = self.database.videos[index].clone();
+        
+        // Get video info before deletion
+        if let Some(video) = self.database.get_video(video_id) {
+            // Delete cache files if requested
+            if delete_cache {
+                let cache_dir = thumbnail::get_cache_dir();
+                
+                // Delete main thumbnail
+                if let Some(ref thumb_path) = video.thumbnail_path {
+                    let _ = std::fs::remove_file(thumb_path);
+                    // Remove from texture cache
+                    self.texture_cache.remove(thumb_path);
+                }
+                if let Some(ref preview_path) = video.preview_thumbnail_path {
+                    let _ = std::fs::remove_file(preview_path);
+                    self.texture_cache.remove(preview_path);
+                }
+                if let Some(ref sprite_path) = video.sprite_sheet_path {
+                    let _ = std::fs::remove_file(sprite_path);
+                    self.texture_cache.remove(sprite_path);
+                }
+                
+                // Delete scene thumbnails
+                let scene_dir = cache_dir.join("scenes").join(&video.id);
+                if scene_dir.exists() {
+                    let _ = std::fs::remove_dir_all(&scene_dir);
+                }
+                
+                // Remove scene textures from cache
+                for scene in &video.scenes {
+                    self.texture_cache.remove(&scene.thumbnail_path);
+                }
+            }
+            
+            // Remove from database
+            self.database.remove_video(video_id);
+            
+            // Clear selection if deleted video was selected
+            if self.selected_video.as_deref() == Some(video_id) {
+                self.selected_video = None;
+                self.scene_panel_visible = false;
+            }
+            
+            // Remove from multi-selection if selected
+            self.selected_videos.remove(video_id);
+            
+            // Save database
+            let _ = database::persist_deletion(&[video_id]);
+        }
+        
+        Some((index, removed))
+    }
+    
+    /// Remember a deletion for Ctrl+Z, dropping the oldest beyond the cap
+    fn push_undo(&mut self, entry: UndoEntry) {
+        self.undo_stack.push(entry);
+        if self.undo_stack.len() > MAX_UNDO_ENTRIES {
+            self.undo_stack.remove(0);
+        }
+    }
+    
+    /// Restore the most recent deletion. Only database records come back;
+    /// deleted cache files are regenerated on demand when thumbnails are displayed.
+    pub fn undo_last_delete(&mut self) {
+        let Some(entry) = self.undo_stack.pop() else {
+            return;
+        };
+        
+        match entry {
+            UndoEntry::Videos(videos) => {
+                let count = videos.len();
+                let restored_ids: Vec<String> = videos.iter().map(|(_, v)| v.id.clone()).collect();
+                for (index, video) in videos.into_iter().rev() {
+                    // Skip videos that were re-added (e.g. by a rescan) in the meantime
+                    if self.database.get_video(&video.id).is_some() {
+                        continue;
+                    }
+                    let index = index.min(self.database.videos.len());
+                    for folder in &video.folders {
+                        self.database.add_folder(folder.clone());
                     }
-                    if ui.selectable_label(self.tag_filter_mode == FilterMode::And, "AND").clicked() {
-                        self.tag_filter_mode = FilterMode::And;
+                    for tag in &video.tags {
+                        self.database.add_tag(tag.clone());
                     }
+                    self.database.videos.insert(index, video);
                 }
-            });
-            let tags_clone = self.database.tags.clone();
-            egui::ScrollArea::vertical()
-                .id_salt("tag_filter_scroll")
-                .max_height(300.0)
-                .show(ui, |ui| {
-                    for (idx, tag) in tags_clone.iter().enumerate() {
-                        let is_selected = self.selected_tag_filter.contains(tag);
-                        let response = ui.selectable_label(is_selected, tag);
-                        
-                        if response.clicked() {
-                            let modifiers = ui.input(|i| i.modifiers);
-                            
-                            if self.is_premium {
-                                // Premium: Ctrl+Click for multiple selection, Shift+Click for range selection
-                                if modifiers.shift && self.last_selected_tag.is_some() {
-                                    // Shift+Click: Range selection
-                                    let last_idx = self.last_selected_tag.unwrap();
-                                    let (start, end) = if idx < last_idx { (idx, last_idx) } else { (last_idx, idx) };
-                                    for i in start..=end {
-                                        if let Some(t) = tags_clone.get(i) {
-                                            self.selected_tag_filter.insert(t.clone());
-                                        }
-                                    }
-                                } else if modifiers.ctrl || modifiers.command {
-                                    // Ctrl+Click: Toggle selection
-                                    if is_selected {
-                                        self.selected_tag_filter.remove(tag);
-                                    } else {
-                                        self.selected_tag_filter.insert(tag.clone());
-                                    }
-                                    self.last_selected_tag = Some(idx);
-                                } else {
-                                    // Normal click: Single selection (clear others)
-                                    self.selected_tag_filter.clear();
-                                    self.selected_tag_filter.insert(tag.clone());
-                                    self.last_selected_tag = Some(idx);
-                                }
-                            } else {
-                                // Free: Single selection only
-                                self.selected_tag_filter.clear();
-                                self.selected_tag_filter.insert(tag.clone());
-                            }
-                        }
+                self.persist_videos(&restored_ids);
+                eprintln!("[undo] Restored {} deleted video(s)", count);
+                self.status_message = Some(self.i18n.t("undo_videos_restored").replace("{}", &count.to_string()));
+            }
+            UndoEntry::Scenes { video_id, scenes } => {
+                let count = scenes.len();
+                if let Some(video) = self.database.get_video_mut(&video_id) {
+                    for (index, scene) in scenes.into_iter().rev() {
+                        let index = index.min(video.scenes.len());
+                        video.scenes.insert(index, scene);
                     }
-                });
-            
-            ui.separator();
-            
-            // Statistics
-            let total_text = self.i18n.t("total_videos").replace("{}", &self.database.videos.len().to_string());
-            let rated_text = self.i18n.t("favorites_count").replace("{}", &self.database.get_by_rating(1).len().to_string());
-            ui.label(&total_text);
-            ui.label(&rated_text);
-        });
+                }
+                self.selected_scenes.clear();
+                self.last_selected_scene = None;
+                self.persist_video(&video_id);
+                eprintln!("[undo] Restored {} deleted scene(s)", count);
+                self.status_message = Some(self.i18n.t("undo_scenes_restored").replace("{}", &count.to_string()));
+            }
+        }
+    }
+    
+    /// Get the path to the scene capture request file
+    fn get_scene_capture_request_path() -> Option<PathBuf> {
+        dirs::data_local_dir().map(|p| p.join("CicadaGallery").join("scene_capture_request.json"))
+    }
+    
+    /// Check for and process scene capture requests from mpv
+    pub fn check_scene_capture_requests(&mut self) {
+        let request_path = match Self::get_scene_capture_request_path() {
+            Some(p) => p,
+            None => return,
+        };
         
-        // Right side panel for scene thumbnails
-        if self.scene_panel_visible {
-            egui::SidePanel::right("scene_panel").exact_width(300.0).show(ctx, |ui| {
-                if let Some(video_id) = &self.selected_video.clone() {
-                    // Clone video data to avoid borrowing issues
-                    let video_data = self.database.get_video(video_id).cloned();
-                    
-                    if let Some(video) = video_data {
-                        // Check if metadata is complete (both duration and resolution present)
-                        let metadata_complete = video.duration.is_some() && video.resolution.is_some();
-                        
-                        // Only load metadata if incomplete and not yet marked as loaded
-                        // Skip the actual loading to prevent UI blocking
-                        if !metadata_complete && !self.metadata_loaded.contains(video_id) {
-                            // Mark as loaded to prevent repeated checks
-                            self.metadata_loaded.insert(video_id.clone());
-                        } else if metadata_complete && !self.metadata_loaded.contains(video_id) {
-                            // If metadata is already complete, just mark as loaded
-                            self.metadata_loaded.insert(video_id.clone());
-                        }
-                        
-                        // Display selected video information
-                        ui.heading(&self.i18n.t("selected_video"));
-                        ui.separator();
-                        
-                        // Display video thumbnail
-                        if let Some(ref thumb_path) = video.thumbnail_path {
-                            if let Some(texture) = self.load_image_texture(ctx, thumb_path) {
-                                let thumbnail_size = egui::vec2(280.0, 157.0);
-                                ui.add(
-                                    egui::Image::new(&texture)
-                                        .max_size(thumbnail_size)
-                                );
-                            }
-                        } else {
-                            // Placeholder if no thumbnail
-                            let thumbnail_size = egui::vec2(280.0, 157.0);
-                            let (rect, _) = ui.allocate_exact_size(thumbnail_size, egui::Sense::hover());
-                            if ui.is_rect_visible(rect) {
-                                ui.painter().rect_filled(rect, 4.0, egui::Color32::DARK_GRAY);
-                                ui.painter().text(
-                                    rect.center(),
-                                    egui::Align2::CENTER_CENTER,
-                                    &self.i18n.t("no_thumbnail"),
-                                    egui::FontId::proportional(16.0),
-                                    egui::Color32::WHITE,
-                                );
-                            }
+        if !request_path.exists() {
+            return;
+        }
+        
+        // Read the request file
+        let content = match std::fs::read_to_string(&request_path) {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+        
+        // Delete the file immediately to prevent re-processing
+        let _ = std::fs::remove_file(&request_path);
+        
+        // Parse the JSON request
+        #[derive(serde::Deserialize)]
+        struct CaptureRequest {
+            path: String,
+            timestamp: f64,
+        }
+        
+        let request: CaptureRequest = match serde_json::from_str(&content) {
+            Ok(r) => r,
+            Err(_) => return,
+        };
+        
+        // Find the video in the database by path
+        let video_id = self.database.videos.iter()
+            .find(|v| v.path.to_string_lossy() == request.path)
+            .map(|v| v.id.clone());
+        
+        let video_id = match video_id {
+            Some(id) => id,
+            None => return,
+        };
+        
+        // Add scene at the specified timestamp
+        let cache_dir = thumbnail::get_cache_dir();
+        let (success, timestamp_str, video_title) = {
+            if let Some(video) = self.database.get_video_mut(&video_id) {
+                let success = scene_detection::add_scene_at_timestamp(video, request.timestamp, &cache_dir).is_some();
+                let timestamp_str = scene_detection::format_timestamp(request.timestamp);
+                let video_title = video.title.clone();
+                (success, timestamp_str, video_title)
+            } else {
+                return;
+            }
+        };
+        
+        if success {
+            // Save database
+            self.persist_video(&video_id);
+            eprintln!("[Scene Capture] Added scene at {} for video {}", timestamp_str, video_title);
+        }
+    }
+    
+    pub fn toggle_video_selection(&mut self, video_id: &str) {
+        if self.selected_videos.contains(video_id) {
+            self.selected_videos.remove(video_id);
+        } else {
+            self.selected_videos.insert(video_id.to_string());
+        }
+    }
+    
+    pub fn select_all_videos(&mut self, video_ids: Vec<String>) {
+        self.selected_videos.clear();
+        self.selected_videos.extend(video_ids);
+    }
+    
+    /// Select the given (filtered) videos that are not selected now, and deselect the rest
+    pub fn invert_selection(&mut self, video_ids: Vec<String>) {
+        let mut current = std::mem::take(&mut self.selected_videos);
+        if let Some(id) = self.selected_video.take() {
+            current.insert(id);
+        }
+        self.selected_videos = video_ids.into_iter().filter(|id| !current.contains(id)).collect();
+        self.last_selected_video = None;
+    }
+    
+    pub fn clear_selection(&mut self) {
+        self.selected_videos.clear();
+        self.selected_video = None;
+        self.last_selected_video = None;
+    }
+    
+    pub fn select_range(&mut self, video_ids: &[String], from_id: &str, to_id: &str) {
+        // Find indices of from and to
+        let from_idx = video_ids.iter().position(|id| id == from_id);
+        let to_idx = video_ids.iter().position(|id| id == to_id);
+        
+        if let (Some(from), Some(to)) = (from_idx, to_idx) {
+            let start = from.min(to);
+            let end = from.max(to);
+            
+            for id in &video_ids[start..=end] {
+                self.selected_videos.insert(id.clone());
+            }
+        }
+    }
+    
+    /// Move the video files to the OS recycle bin, then remove their records and cache.
+    /// Videos whose file could not be moved are kept in the library.
+    pub fn trash_videos(&mut self, video_ids: &[String]) {
+        let mut trashed = 0;
+        let mut failed = Vec::new();
+        let mut missing = Vec::new();
+        for video_id in video_ids {
+            let Some(video) = self.database.get_video(video_id) else {
+                continue;
+            };
+            let path = video.path.clone();
+            if !path.exists() {
+                // Offline drive or already deleted: keep the record, nothing was trashed
+                eprintln!("[Delete] File not found, record kept: {:?}", path);
+                missing.push(video.title.clone());
+                continue;
+            }
+            if let Err(e) = trash::delete(&path) {
+                eprintln!("[Delete] Failed to move {:?} to the recycle bin: {}", path, e);
+                failed.push(video.title.clone());
+                continue;
+            }
+            eprintln!("[Delete] Moved to the recycle bin: {:?}", path);
+            // The file is gone, so the record is not offered for undo
+            self.delete_video_record(video_id, true);
+            trashed += 1;
+        }
+        
+        let mut message = self.i18n.t("moved_to_trash").replace("{}", &trashed.to_string());
+        if !failed.is_empty() {
+            message.push_str(&self.i18n.t("move_to_trash_failed").replace("{}", &failed.join(", ")));
+        }
+        if !missing.is_empty() {
+            message.push_str(&self.i18n.t("move_to_trash_missing").replace("{}", &missing.join(", ")));
+        }
+        self.status_message = Some(message);
+    }
+    
+    pub fn delete_selected_videos(&mut self, delete_cache: bool) {
+        let video_ids: Vec<String> = self.selected_videos.iter().cloned().collect();
+        let mut removed = Vec::new();
+        for video_id in video_ids {
+            if let Some(record) = self.delete_video_record(&video_id, delete_cache) {
+                removed.push(record);
+            }
+        }
+        if !removed.is_empty() {
+            self.push_undo(UndoEntry::Videos(removed));
+        }
+        self.selected_videos.clear();
+    }
+    
+    /// Setup file system watcher for monitored folders (rebuilds the watcher from `watched_folders`)
+    pub fn setup_folder_watcher(&mut self) {
+        // Dropping the previous watcher stops watching folders that were removed
+        self.fs_watcher = None;
+        self.fs_events = None;
+        self.watcher_unavailable.clear();
+        
+        // Create a channel for receiving events
+        let (tx, rx) = channel();
+        let rx = Arc::new(Mutex::new(rx));
+        
+        // Create a new watcher
+        match notify::recommended_watcher(move |res| {
+            if let Err(e) = tx.send(res) {
+                eprintln!("Error sending watch event: {:?}", e);
+            }
+        }) {
+            Ok(mut watcher) => {
+                // Watch all folders (missing ones are picked up again by maintain_folder_watcher)
+                for folder in &self.watched_folders {
+                    if !folder.exists() {
+                        eprintln!("[watcher] Folder not available: {:?}", folder);
+                        self.watcher_unavailable.push(folder.clone());
+                    } else if let Err(e) = watcher.watch(folder, RecursiveMode::Recursive) {
+                        eprintln!("Failed to watch folder {:?}: {:?}", folder, e);
+                        self.watcher_unavailable.push(folder.clone());
+                    } else {
+                        eprintln!("[watcher] Watching folder: {:?}", folder);
+                    }
+                }
+                
+                self.fs_watcher = Some(watcher);
+                self.fs_events = Some(rx);
+                self.watcher_failed = false;
+            }
+            Err(e) => {
+                eprintln!("Failed to create file system watcher: {:?}", e);
+                self.watcher_failed = true;
+            }
+        }
+    }
+    
+    /// Re-create the watcher after errors (with backoff) and when watched folders appear or disappear
+    /// (e.g. a removable drive is plugged back in)
+    fn maintain_folder_watcher(&mut self, ctx: &egui::Context) {
+        let now = Instant::now();
+        if self.watcher_next_check.is_some_and(|next| now < next) {
+            return;
+        }
+        
+        let reappeared: Vec<PathBuf> = self.watcher_unavailable.iter()
+            .filter(|folder| folder.exists())
+            .cloned()
+            .collect();
+        let disappeared = self.watched_folders.iter()
+            .any(|folder| !self.watcher_unavailable.contains(folder) && !folder.exists());
+        
+        let retry_after_error = self.watcher_failed;
+        if retry_after_error || !reappeared.is_empty() || disappeared {
+            eprintln!("[watcher] Re-creating folder watcher");
+            self.setup_folder_watcher();
+            
+            // Files may have changed while the folder was away
+            for folder in reappeared {
+                if !self.watcher_unavailable.contains(&folder) {
+                    self.pending_rescan_dirs.insert(folder);
+                    self.last_fs_event = Some(now);
+                }
+            }
+        }
+        
+        let delay = if self.watcher_failed { self.watcher_backoff_secs } else { WATCHER_CHECK_INTERVAL_SECS };
+        self.watcher_next_check = Some(now + Duration::from_secs(delay));
+        ctx.request_repaint_after(Duration::from_secs(delay));
+        
+        // Back off further while errors keep coming; a quiet check interval resets the delay
+        self.watcher_backoff_secs = if retry_after_error {
+            (self.watcher_backoff_secs * 2).min(WATCHER_MAX_BACKOFF_SECS)
+        } else {
+            WATCHER_MIN_BACKOFF_SECS
+        };
+    }
+    
+    /// Mark the watcher as failed; it is re-created by maintain_folder_watcher after the backoff delay
+    fn schedule_watcher_retry(&mut self) {
+        if !self.watcher_failed {
+            self.watcher_failed = true;
+            self.watcher_next_check = Some(Instant::now() + Duration::from_secs(self.watcher_backoff_secs));
+        }
+    }
+    
+    /// Stop watching a folder. Videos already imported from it are kept.
+    pub fn unwatch_folder(&mut self, folder: &std::path::Path) {
+        self.watched_folders.remove(folder);
+        self.setup_folder_watcher();
+        self.save_settings();
+        eprintln!("[watcher] Stopped watching folder: {:?}", folder);
+    }
+    
+    /// Collect directories touched by file system events; they are rescanned once events go quiet
+    pub fn check_folder_changes(&mut self) {
+        if let Some(rx) = self.fs_events.clone() {
+            if let Ok(rx_guard) = rx.lock() {
+                // Process all pending events
+                loop {
+                    let result = match rx_guard.try_recv() {
+                        Ok(result) => result,
+                        Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                        Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                            eprintln!("[watcher] Watcher disconnected");
+                            self.schedule_watcher_retry();
+                            break;
                         }
-                        
-                        // Display video title
-                        ui.add_space(5.0);
-                        ui.label(egui::RichText::new(&video.title).strong().size(14.0));
-                        
-                        ui.add_space(5.0);
-                        
-                        // Display video information
-                        ui.group(|ui| {
-                            ui.set_width(ui.available_width());
-                            
-                            // Always visible: Duration, Resolution, Added date
-                            // Duration
-                            ui.horizontal(|ui| {
-                                ui.label(egui::RichText::new("⏱").size(14.0));
-                                if let Some(duration) = video.duration {
-                                    let duration_text = self.i18n.t("duration_label").replace("{}", &scene_detection::format_timestamp(duration));
-                                    ui.label(&duration_text);
+                    };
+                    match result {
+                        Ok(event) => {
+                            // Folders created or moved in are scanned as a whole
+                            let adds_folder = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(notify::event::ModifyKind::Name(_)));
+                            for path in &event.paths {
+                                let dir = if adds_folder && path.is_dir() {
+                                    path.clone()
+                                } else if VideoFile::is_video_file(path, &self.video_extensions) {
+                                    // Created, modified or removed video: rescan its directory
+                                    match path.parent() {
+                                        Some(parent) => parent.to_path_buf(),
+                                        None => continue,
+                                    }
                                 } else {
-                                    ui.label(self.i18n.t("duration_label").replace("{}", "-"));
+                                    continue;
+                                };
+                                if !self.watched_folders.iter().any(|folder| dir.starts_with(folder)) {
+                                    continue;
                                 }
-                            });
-                            
-                            // Resolution
-                            ui.horizontal(|ui| {
-                                ui.label(egui::RichText::new("📐").size(14.0));
-                                if let Some((width, height)) = video.resolution {
-                                    let resolution_text = self.i18n.t("resolution_label").replace("{}", &format!("{}×{}", width, height));
-                                    ui.label(&resolution_text);
-                                } else {
-                                    ui.label(self.i18n.t("resolution_label").replace("{}", "-"));
+                                if self.pending_rescan_dirs.insert(dir) {
+                                    eprintln!("[watcher] Detected change: {:?}", path);
                                 }
-                            });
-                            
-                            // Added date
-                            ui.horizontal(|ui| {
-                                ui.label(egui::RichText::new("📅").size(14.0));
-                                let added_text = self.i18n.t("added_label").replace("{}", &video.added_date.format("%Y-%m-%d %H:%M").to_string());
-                                ui.label(&added_text);
-                            });
-                            
-                            // Expandable section for more details
-                            let expand_text = if self.profile_details_expanded {
-                                self.i18n.t("less_details")
-                            } else {
-                                self.i18n.t("more_details")
-                            };
-                            if ui.button(&expand_text).clicked() {
-                                self.profile_details_expanded = !self.profile_details_expanded;
+                                self.last_fs_event = Some(Instant::now());
                             }
-                            
-                            if self.profile_details_expanded {
-                                // Frame rate
-                                ui.horizontal(|ui| {
-                                    ui.label(egui::RichText::new("🎬").size(14.0));
-                                    if let Some(fps) = video.frame_rate {
-                                        let fps_text = self.i18n.t("framerate_label").replace("{}", &format!("{:.2}", fps));
-                                        ui.label(&fps_text);
-                                    } else {
-                                        ui.label(self.i18n.t("framerate_label").replace("{}", "-"));
-                                    }
-                                });
-                                
-                                // File size
-                                ui.horizontal(|ui| {
-                                    ui.label(egui::RichText::new("💾").size(14.0));
-                                    let size_mb = video.file_size as f64 / 1024.0 / 1024.0;
-                                    let size_text = if size_mb >= 1024.0 {
-                                        self.i18n.t("size_gb").replace("{:.2}", &format!("{:.2}", size_mb / 1024.0))
-                                    } else {
+                        }
+                        Err(e) => {
+                            eprintln!("Watch error: {:?}", e);
+                            self.schedule_watcher_retry();
+                        }
+                    }
+                }
+            }
+        }
+    }
+    
+    /// Initialize the async texture loading channel if not already done
+    fn ensure_texture_loader(&mut self) {
+        if self.texture_load_sender.is_none() {
+            let (tx, rx) = channel();
+            self.texture_load_sender = Some(tx);
+            self.texture_load_receiver = Some(rx);
+        }
+        if self.texture_fail_sender.is_none() {
+            let (tx, rx) = channel();
+            self.texture_fail_sender = Some(tx);
+            self.texture_fail_receiver = Some(rx);
+        }
+    }
+    
+    /// Process any completed texture loads from background threads
+    fn process_loaded_textures(&mut self, ctx: &egui::Context) {
+        // Process successful loads
+        if let Some(ref receiver) = self.texture_load_receiver {
+            // Process up to 10 textures per frame to avoid blocking
+            for _ in 0..10 {
+                match receiver.try_recv() {
+                    Ok((path, pixels, size, true)) => {
+                        self.pending_blurred_textures.remove(&path);
+                        let color_image = egui::ColorImage::from_rgba_unmultiplied(size, &pixels);
+                        let texture = ctx.load_texture(
+                            format!("blurred:{}", path.to_string_lossy()),
+                            color_image,
+                            egui::TextureOptions::LINEAR,
+                        );
+                        self.blurred_texture_cache.insert(path, texture);
+                    }
+                    Ok((path, pixels, size, false)) => {
+                        // Remove from pending set
+                        self.pending_textures.remove(&path);
+                        self.thumbnail_access_log.touch(&path);
+                        
+                        // Create texture from received data
+                        let color_image = egui::ColorImage::from_rgba_unmultiplied(size, &pixels);
+                        let texture = ctx.load_texture(
+                            path.to_string_lossy().to_string(),
+                            color_image,
+                            Default::default(),
+                        );
+                        self.texture_cache.insert(path, texture);
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                        // Channel disconnected, recreate it
+                        self.texture_load_sender = None;
+                        self.texture_load_receiver = None;
+                        self.pending_textures.clear();
+                        self.pending_blurred_textures.clear();
+                        break;
+                    }
+                }
+            }
+        }
+        
+        // Process failed loads
+        if let Some(ref receiver) = self.texture_fail_receiver {
+            for _ in 0..10 {
+                match receiver.try_recv() {
+                    Ok((path, blurred)) => {
+                        // Remove from pending and add to failed
+                        if blurred {
+                            self.pending_blurred_textures.remove(&path);
+                        } else {
+                            self.pending_textures.remove(&path);
+                        }
+                        self.failed_textures.insert(path);
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                        self.texture_fail_sender = None;
+                        self.texture_fail_receiver = None;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    
+    /// Find the video (and scene timestamp) a thumbnail file was generated from
+    fn thumbnail_source(&self, image_path: &std::path::Path) -> Option<thumbnail::ThumbnailSource> {
+        for video in &self.database.videos {
+            if video.thumbnail_path.as_deref() == Some(image_path) {
+                // A thumbnail picked by the user is re-extracted at the same timestamp
+                return Some(match thumbnail::custom_thumbnail_timestamp(image_path) {
+                    Some(timestamp) => thumbnail::ThumbnailSource::Scene(video.path.clone(), timestamp),
+                    None => thumbnail::ThumbnailSource::Video(video.path.clone()),
+                });
+            }
+            if let Some(scene) = video.scenes.iter().find(|s| s.thumbnail_path == image_path) {
+                return Some(thumbnail::ThumbnailSource::Scene(video.path.clone(), scene.timestamp));
+            }
+            if video.preview_thumbnail_path.as_deref() == Some(image_path) {
+                return Some(thumbnail::ThumbnailSource::Preview(video.path.clone(), video.duration));
+            }
+            if video.sprite_sheet_path.as_deref() == Some(image_path) {
+                return Some(thumbnail::ThumbnailSource::Sprite(video.path.clone(), video.duration));
+            }
+        }
+        None
+    }
+    
+    fn load_image_texture(&mut self, ctx: &egui::Context, image_path: &PathBuf) -> Option<egui::TextureHandle> {
+        // Check if already cached - this is fast
+        if let Some(texture) = self.texture_cache.get(image_path) {
+            return Some(texture.clone());
+        }
+        
+        // Check if already failed (don't retry failed images)
+        if self.failed_textures.contains(image_path) {
+            return None;
+        }
+        
+        // Check if already loading
+        if self.pending_textures.contains(image_path) {
+            return None; // Still loading, return None to show placeholder
+        }
+        
+        // Limit concurrent loading threads to prevent resource exhaustion
+        if self.pending_textures.len() >= self.max_concurrent_texture_loads {
+            return None; // Too many pending loads, wait for some to complete
+        }
+        
+        // Ensure loader is initialized
+        self.ensure_texture_loader();
+        
+        // Thumbnails evicted from the disk cache are regenerated on demand
+        let regenerate_from = if image_path.exists() {
+            None
+        } else {
+            self.thumbnail_source(image_path)
+        };
+        
+        // Start async loading
+        if let (Some(ref success_sender), Some(ref fail_sender)) = (&self.texture_load_sender, &self.texture_fail_sender) {
+            let path = image_path.clone();
+            let success_tx = success_sender.clone();
+            let fail_tx = fail_sender.clone();
+            let ctx_clone = ctx.clone();
+            
+            // Mark as pending
+            self.pending_textures.insert(path.clone());
+            
+            // Spawn background thread to load image
+            std::thread::spawn(move || {
+                if let Some(ref source) = regenerate_from {
+                    eprintln!("[Thumbnail] Regenerating evicted thumbnail: {:?}", path);
+                    thumbnail::regenerate_thumbnail(source, &path);
+                }
+                if let Some((pixels, size)) = load_image_data(&path) {
+                    let _ = success_tx.send((path, pixels, size, false));
+                } else {
+                    // Send failure notification
+                    let _ = fail_tx.send((path, false));
+                }
+                // Request repaint so the UI updates when texture is ready
+                ctx_clone.request_repaint();
+            });
+        }
+        
+        None // Return None while loading, caller should show placeholder
+    }
+    
+    /// Load a heavily downscaled copy of an image so it renders blurred (protected videos)
+    fn load_blurred_texture(&mut self, ctx: &egui::Context, image_path: &std::path::Path) -> Option<egui::TextureHandle> {
+        if let Some(texture) = self.blurred_texture_cache.get(image_path) {
+            return Some(texture.clone());
+        }
+        if self.failed_textures.contains(image_path) || self.pending_blurred_textures.contains(image_path) {
+            return None;
+        }
+        if self.pending_textures.len() + self.pending_blurred_textures.len() >= self.max_concurrent_texture_loads {
+            return None;
+        }
+        
+        // Evicted thumbnails are regenerated by the normal loader first
+        if !image_path.exists() {
+            self.load_image_texture(ctx, &image_path.to_path_buf());
+            return None;
+        }
+        
+        self.ensure_texture_loader();
+        
+        // Decode and downscale in the background like other textures
+        if let (Some(ref success_sender), Some(ref fail_sender)) = (&self.texture_load_sender, &self.texture_fail_sender) {
+            let path = image_path.to_path_buf();
+            let success_tx = success_sender.clone();
+            let fail_tx = fail_sender.clone();
+            let ctx_clone = ctx.clone();
+            
+            self.pending_blurred_textures.insert(path.clone());
+            
+            std::thread::spawn(move || {
+                let small = image::ImageReader::open(&path)
+                    .ok()
+                    .and_then(|reader| reader.decode().ok())
+                    .map(|image_data| image_data.thumbnail(12, 12).to_rgba8());
+                match small {
+                    Some(small) => {
+                        let size = [small.width() as usize, small.height() as usize];
+                        let _ = success_tx.send((path, small.into_raw(), size, true));
+                    }
+                    None => {
+                        let _ = fail_tx.send((path, true));
+                    }
+                }
+                ctx_clone.request_repaint();
+            });
+        }
+        
+        None
+    }
+    
+    fn draw_thumbnail_placeholder(&mut self, ui: &mut egui::Ui, _ctx: &egui::Context, thumbnail_size: egui::Vec2, video: &VideoFile) {
+        let (rect, response) = ui.allocate_exact_size(thumbnail_size, egui::Sense::click());
+        
+        let is_selected = self.selected_videos.contains(&video.id) || 
+                         self.selected_video.as_ref() == Some(&video.id);
+        
+        if ui.is_rect_visible(rect) {
+            ui.painter().rect_filled(rect, 4.0, egui::Color32::DARK_GRAY);
+            ui.painter().text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                "🎬",
+                egui::FontId::proportional(48.0 * self.grid_scale),
+                egui::Color32::WHITE,
+            );
+            
+            // Draw selection highlight
+            if is_selected {
+                ui.painter().rect_stroke(
+                    rect,
+                    4.0,
+                    egui::Stroke::new(3.0, self.accent_color32())
+                );
+            }
+        }
+        
+        if response.clicked() {
+            self.thumbnail_clicked_this_frame = true;
+            let modifiers = ui.input(|i| i.modifiers.clone());
+            if modifiers.shift {
+                // Shift+Click: range selection (need video_ids context)
+                self.selected_videos.insert(video.id.clone());
+                self.last_selected_video = Some(video.id.clone());
+                // Show profile of first selected video
+                if self.selected_video.is_none() {
+                    self.selected_video = Some(video.id.clone());
+                    self.scene_panel_visible = true;
+                }
+            } else if modifiers.ctrl {
+                self.toggle_video_selection(&video.id);
+                self.last_selected_video = Some(video.id.clone());
+                // Show profile of first selected video
+                if self.selected_video.is_none() && self.selected_videos.contains(&video.id) {
+                    self.selected_video = Some(video.id.clone());
+                    self.scene_panel_visible = true;
+                }
+            } else {
+                // Single click: clear multi-select and select only this video
+                // If this video is already the only selected one, keep it selected
+                let already_sole_selected = self.selected_video.as_ref() == Some(&video.id) && 
+                                           self.selected_videos.is_empty();
+                if !already_sole_selected {
+                    self.selected_videos.clear();
+                    self.selected_video = Some(video.id.clone());
+                    self.scene_panel_visible = true;
+                    self.last_selected_video = Some(video.id.clone());
+                }
+            }
+        }
+        
+        // Double click: play video
+        if response.double_clicked() {
+            // Select this video before playing (maintain selection in profile panel)
+            self.selected_videos.clear();
+            self.selected_video = Some(video.id.clone());
+            self.scene_panel_visible = true;
+            self.last_selected_video = Some(video.id.clone());
+            
+            self.play_video_at(&video.path, video.playback_position.unwrap_or(0.0));
+        }
+        
+        response.context_menu(|ui| {
+            if ui.button(self.i18n.t("play_all")).on_hover_text(self.i18n.t("play_all_tooltip")).clicked() {
+                self.play_all(&video.id);
+                ui.close_menu();
+            }
+            
+            if ui.button(self.i18n.t("shuffle_play")).on_hover_text(self.i18n.t("shuffle_play_tooltip")).clicked() {
+                self.shuffle_play(&video.id);
+                ui.close_menu();
+            }
+            
+            let watched_label = if video.watched { self.i18n.t("mark_unwatched") } else { self.i18n.t("mark_watched") };
+            if ui.button(watched_label).clicked() {
+                let target_ids = self.target_ids_for(&video.id);
+                self.set_videos_watched(&target_ids, !video.watched);
+                ui.close_menu();
+            }
+            
+            if ui.button(&self.i18n.t("show_in_folder")).clicked() {
+                if let Err(e) = video_player::show_in_folder(&video.path) {
+                    eprintln!("Show in folder error: {}", e);
+                }
+                ui.close_menu();
+            }
+            
+            self.show_copy_menu_items(ui, &video.id);
+            
+            if !video.scenes.is_empty() && ui.button(self.i18n.t("export_contact_sheet")).clicked() {
+                self.export_contact_sheet(&video.id);
+                ui.close_menu();
+            }
+            
+            ui.separator();
+            
+            // Refresh profile button - handles both single and multiple selection
+            let video_id_for_refresh = video.id.clone();
+            let refresh_label = if self.selected_videos.len() > 1 {
+                format!("{} ({})", self.i18n.t("refresh_profile"), self.selected_videos.len())
+            } else {
+                self.i18n.t("refresh_profile")
+            };
+            if ui.button(&refresh_label).clicked() {
+                if self.selected_videos.len() > 1 {
+                    // Refresh all selected videos
+                    let selected_ids: Vec<String> = self.selected_videos.iter().cloned().collect();
+                    for video_id in selected_ids {
+                        self.refresh_video_profile(&video_id);
+                    }
+                } else {
+                    // Refresh single video
+                    self.refresh_video_profile(&video_id_for_refresh);
+                }
+                ui.close_menu();
+            }
+            
+            // Re-probe duration with the accurate (slow) method
+            let reprobe_enabled = self.duration_probe_receiver.is_none();
+            if ui.add_enabled(reprobe_enabled, egui::Button::new(self.i18n.t("reprobe_duration"))).clicked() {
+                let target_ids = self.target_ids_for(&video.id);
+                self.start_duration_probe(&target_ids);
+                ui.close_menu();
+            }
+            
+            if ui.button(self.i18n.t("regenerate_thumbnail")).clicked() {
+                let target_ids = self.target_ids_for(&video.id);
+                self.regenerate_video_thumbnails(&target_ids);
+                ui.close_menu();
+            }
+            
+            if ui.button(self.i18n.t("set_thumbnail_from_timestamp")).clicked() {
+                self.thumbnail_timestamp_video = Some(video.id.clone());
+                self.thumbnail_timestamp_error = None;
+                ui.close_menu();
+            }
+            
+            if ui.button(self.i18n.t("export_playlist")).clicked() {
+                self.export_playlist(&video.id);
+                ui.close_menu();
+            }
+            
+            self.show_move_to_folder_menu(ui, &video.id);
+            self.show_move_to_profile_menu(ui, &video.id);
+            
+            ui.separator();
+            
+            if ui.button(&self.i18n.t("delete")).clicked() {
+                self.delete_confirm_video = Some(video.id.clone());
+                ui.close_menu();
+            }
+            
+            // Multiple selection delete option
+            if self.selected_videos.len() > 1 {
+                let delete_multiple_text = self.i18n.t("delete_selected");
+                if ui.button(&delete_multiple_text).clicked() {
+                    self.delete_confirm_video = Some("__MULTI__".to_string());
+                    ui.close_menu();
+                }
+            }
+        });
+    }
+    
+    pub fn add_tag_to_video(&mut self, video_id: &str, tag: String) {
+        if let Some(video) = self.database.get_video_mut(video_id) {
+            if !video.tags.contains(&tag) {
+                video.tags.push(tag.clone());
+                let _ = database::persist_video(video);
+                self.database.add_tag(tag);
+            }
+        }
+    }
+    
+    pub fn remove_tag_from_video(&mut self, video_id: &str, tag: &str) {
+        if let Some(video) = self.database.get_video_mut(video_id) {
+            video.tags.retain(|t| t != tag);
+            let _ = database::persist_video(video);
+        }
+    }
+    
+    /// Mark or unmark videos as protected
+    pub fn set_videos_protected(&mut self, video_ids: &[String], protected: bool) {
+        for video_id in video_ids {
+            if let Some(video) = self.database.get_video_mut(video_id) {
+                video.protected = protected;
+            }
+        }
+        
+        // Newly protected videos disappear immediately while locked
+        if protected && !self.protected_unlocked {
+            self.hide_protected_selection();
+        }
+        
+        self.persist_videos(video_ids);
+    }
+    
+    /// Unlock protected videos for this session if the PIN matches
+    pub fn unlock_protected(&mut self, pin: &str) -> bool {
+        let (Some(stored), Some(salt)) = (&self.protection_pin_hash, &self.protection_pin_salt) else {
+            return false;
+        };
+        if !crate::pin::verify_pin(pin, salt, stored) {
+            return false;
+        }
+        self.protected_unlocked = true;
+        true
+    }
+    
+    /// Hash of a new protection PIN; the install's salt is created on first use.
+    /// Takes the salt field explicitly so it can be called while the options window borrows `self`.
+    fn salted_pin_hash(salt: &mut Option<String>, pin: &str) -> String {
+        let salt = salt.get_or_insert_with(crate::pin::new_salt);
+        crate::pin::hash_pin(pin, salt)
+    }
+    
+    /// Hide protected videos again
+    pub fn lock_protected(&mut self) {
+        self.protected_unlocked = false;
+        self.hide_protected_selection();
+    }
+    
+    /// Drop protected videos from the current selection
+    fn hide_protected_selection(&mut self) {
+        let protected_ids: HashSet<String> = self.database.videos.iter()
+            .filter(|v| v.protected)
+            .map(|v| v.id.clone())
+            .collect();
+        self.selected_videos.retain(|id| !protected_ids.contains(id));
+        if self.selected_video.as_ref().is_some_and(|id| protected_ids.contains(id)) {
+            self.selected_video = None;
+        }
+    }
+    
+    /// Put a video in a folder (created if new); other folders of the video are kept
+    pub fn add_video_folder(&mut self, video_id: &str, folder: String) {
+        if let Some(video) = self.database.get_video_mut(video_id) {
+            if !video.folders.contains(&folder) {
+                video.folders.push(folder.clone());
+            }
+            self.database.add_folder(folder);
+        }
+    }
+    
+    /// Take a video out of a folder (the folder itself stays in the list)
+    pub fn remove_video_folder(&mut self, video_id: &str, folder: &str) {
+        if let Some(video) = self.database.get_video_mut(video_id) {
+            video.folders.retain(|f| f != folder);
+        }
+    }
+    
+    pub fn get_filtered_videos(&self) -> Vec<&VideoFile> {
+        let mut videos: Vec<&VideoFile> = self.database.videos.iter().collect();
+        
+        // 保護された動画はロック解除されるまで非表示
+        if !self.protected_unlocked {
+            videos.retain(|v| !v.protected);
+        }
+        
+        // 評価フィルタ
+        if self.min_rating_filter > 0 {
+            videos.retain(|v| v.rating >= self.min_rating_filter);
+        }
+        
+        // 視聴済みフィルタ
+        match self.watched_filter {
+            WatchedFilter::All => {}
+            WatchedFilter::Watched => videos.retain(|v| v.watched),
+            WatchedFilter::Unwatched => videos.retain(|v| !v.watched),
+        }
+        
+        // フォルダフィルタ（複数選択対応）
+        if !self.selected_folder_filter.is_empty() {
+            // 選択されたフォルダのいずれかに属する動画を表示
+            videos.retain(|v| v.folders.iter().any(|folder| self.selected_folder_filter.contains(folder)));
+        }
+        
+        // タグフィルタ（複数選択対応、AND/OR切り替え）
+        // 親タグ ("a") を選ぶと子孫タグ ("a/b", "a/b/c") もすべて対象になる
+        if !self.selected_tag_filter.is_empty() {
+            let tag_set = self.resolved_tag_filter();
+            videos.retain(|v| {
+                match self.tag_filter_mode {
+                    FilterMode::Or => {
+                        // OR: 選択されたタグのいずれかを持つ動画を表示
+                        v.tags.iter().any(|t| tag_set.contains(t))
+                    }
+                    FilterMode::And => {
+                        // AND: 選択されたタグをすべて持つ動画のみ表示
+                        tag_set.iter().all(|selected_tag| v.tags.contains(selected_tag))
+                    }
+                }
+            });
+        }
+        
+        // 追加月フィルタ（統計ウィンドウのヒストグラムから選択）
+        if let Some(month) = self.added_month_filter {
+            videos.retain(|v| added_month(v) == month);
+        }
+        
+        // 検索クエリ
+        if !self.search_query.is_empty() {
+            let query = self.search_query.to_lowercase();
+            videos.retain(|v| {
+                v.title.to_lowercase().contains(&query)
+                    || v.tags.iter().any(|t| t.to_lowercase().contains(&query))
+                    || v.path.to_string_lossy().to_lowercase().contains(&query)
+                    || v.folders.iter().any(|f| f.to_lowercase().contains(&query))
+                    || v.notes.to_lowercase().contains(&query)
+            });
+        }
+        
+        // 最近再生した動画（最終再生日時の新しい順で固定）
+        if self.show_recent_only {
+            let cutoff = chrono::Utc::now() - chrono::Duration::days(self.recent_days as i64);
+            videos.retain(|v| v.last_played.is_some_and(|played| played >= cutoff));
+            videos.sort_by_key(|v| std::cmp::Reverse(v.last_played));
+            return videos;
+        }
+        
+        // ソート
+        match self.sort_field {
+            SortField::AddedDate => {
+                videos.sort_by(|a, b| {
+                    let cmp = a.added_date.cmp(&b.added_date);
+                    if self.sort_order == SortOrder::Ascending {
+                        cmp
+                    } else {
+                        cmp.reverse()
+                    }
+                });
+            }
+            SortField::FileName => {
+                videos.sort_by(|a, b| {
+                    let cmp = a.title.to_lowercase().cmp(&b.title.to_lowercase());
+                    if self.sort_order == SortOrder::Ascending {
+                        cmp
+                    } else {
+                        cmp.reverse()
+                    }
+                });
+            }
+            SortField::Duration => {
+                videos.sort_by(|a, b| {
+                    let cmp = a.duration.unwrap_or(0.0).partial_cmp(&b.duration.unwrap_or(0.0))
+                        .unwrap_or(std::cmp::Ordering::Equal);
+                    if self.sort_order == SortOrder::Ascending {
+                        cmp
+                    } else {
+                        cmp.reverse()
+                    }
+                });
+            }
+            SortField::Resolution => {
+                videos.sort_by(|a, b| {
+                    let pixels = |v: &VideoFile| v.resolution.map_or(0, |(w, h)| u64::from(w) * u64::from(h));
+                    let cmp = pixels(a).cmp(&pixels(b));
+                    if self.sort_order == SortOrder::Ascending {
+                        cmp
+                    } else {
+                        cmp.reverse()
+                    }
+                });
+            }
+            SortField::FileSize => {
+                videos.sort_by(|a, b| {
+                    let cmp = a.file_size.cmp(&b.file_size);
+                    if self.sort_order == SortOrder::Ascending {
+                        cmp
+                    } else {
+                        cmp.reverse()
+                    }
+                });
+            }
+            SortField::Rating => {
+                videos.sort_by(|a, b| {
+                    let cmp = a.rating.cmp(&b.rating);
+                    if self.sort_order == SortOrder::Ascending {
+                        cmp
+                    } else {
+                        cmp.reverse()
+                    }
+                });
+            }
+            SortField::Folder => {
+                videos.sort_by(|a, b| {
+                    let folder = |v: &VideoFile| v.folders.join(", ").to_lowercase();
+                    let cmp = folder(a).cmp(&folder(b));
+                    if self.sort_order == SortOrder::Ascending {
+                        cmp
+                    } else {
+                        cmp.reverse()
+                    }
+                });
+            }
+            SortField::Manual => {
+                // 手動の並び順（昇順/降順の切り替えなし）
+                videos.sort_by_key(|v| manual_order_key(v));
+            }
+        }
+        
+        // お気に入りを先頭に固定（ソート順はそれぞれのブロック内で維持）
+        if self.pin_favorites_to_top {
+            let min_rating = if self.is_premium { self.pin_min_rating } else { 1 };
+            videos.sort_by_key(|v| v.rating < min_rating);
+        }
+        
+        videos
+    }
+}
+
+impl eframe::App for VideoPlayerApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Reset per-frame flags
+        self.thumbnail_clicked_this_frame = false;
+        
+        // Handle dropped files (drag & drop)
+        let dropped_files: Vec<egui::DroppedFile> = ctx.input(|i| i.raw.dropped_files.clone());
+        if !dropped_files.is_empty() {
+            self.handle_dropped_files(dropped_files);
+        }
+        
+        // Process any completed texture loads from background threads
+        self.process_loaded_textures(ctx);
+        
+        // Ctrl+Z restores the most recent deletion (text fields keep their own undo)
+        if !ctx.wants_keyboard_input() && ctx.input(|i| i.modifiers.command && !i.modifiers.shift && i.key_pressed(egui::Key::Z)) {
+            self.undo_last_delete();
+        }
+        
+        // Track window size and position for saving on exit
+        ctx.input(|i| {
+            if let Some(rect) = i.viewport().inner_rect {
+                self.last_window_size = Some((rect.width(), rect.height()));
+            }
+            if let Some(pos) = i.viewport().outer_rect {
+                self.last_window_pos = Some((pos.min.x, pos.min.y));
+            }
+        });
+        
+        // Apply theme based on theme_mode setting, with the accent color for selections
+        let visuals = if self.resolve_dark_mode(ctx) {
+            let mut visuals = egui::Visuals::dark();
+            visuals.selection.bg_fill = self.accent_color32().gamma_multiply(0.5);
+            visuals
+        } else {
+            let mut visuals = egui::Visuals::light();
+            visuals.selection.bg_fill = self.accent_color32();
+            visuals
+        };
+        ctx.set_visuals(visuals);
+        
+        // Check for file system changes
+        self.check_folder_changes();
+        
+        // Check for scene capture requests from mpv
+        self.check_scene_capture_requests();
+        
+        // Record playback positions reported by mpv
+        self.process_playback_status(ctx);
+        
+        // Merge results from the batch scene generator
+        self.process_scene_batch(ctx);
+        
+        // Apply re-probed durations
+        self.process_duration_probe(ctx);
+        self.process_duplicate_scan(ctx);
+        self.process_file_copy(ctx);
+        self.process_folder_import(ctx);
+        
+        // Rescan changed folders once file events have been quiet for the debounce interval
+        // (a large copy produces many events, but only one rescan)
+        if !self.pending_rescan_dirs.is_empty() {
+            let debounce = Duration::from_secs(u64::from(self.rescan_debounce_secs));
+            let quiet_for = self.last_fs_event.map_or(debounce, |time| time.elapsed());
+            if quiet_for >= debounce {
+                eprintln!("[app] Triggering automatic rescan due to folder changes");
+                let dirs = std::mem::take(&mut self.pending_rescan_dirs);
+                self.rescan_changed_dirs(dirs);
+            } else {
+                ctx.request_repaint_after(debounce - quiet_for);
+            }
+        }
+        
+        // Initialize folder watcher if not set up yet and we have videos
+        if self.fs_watcher.is_none() && !self.watcher_failed && !self.database.videos.is_empty() {
+            self.setup_folder_watcher();
+        }
+        if self.fs_watcher.is_some() || self.watcher_failed {
+            self.maintain_folder_watcher(ctx);
+        }
+        
+        // トップバー
+        // FFmpeg missing warning (durations, resolutions and scenes depend on it)
+        if self.show_ffmpeg_missing_banner {
+            egui::TopBottomPanel::top("ffmpeg_missing_banner")
+                .frame(egui::Frame::none()
+                    .fill(egui::Color32::from_rgb(120, 80, 20))
+                    .inner_margin(egui::Margin::symmetric(8.0, 4.0)))
+                .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(format!("⚠ {}", self.i18n.t("ffmpeg_missing_banner"))).color(egui::Color32::WHITE));
+                        ui.hyperlink_to(self.i18n.t("ffmpeg_setup_link"), "https://ffmpeg.org/download.html");
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.small_button("✖").on_hover_text(self.i18n.t("dismiss")).clicked() {
+                                self.show_ffmpeg_missing_banner = false;
+                            }
+                        });
+                    });
+                });
+        }
+        
+        // License expiry warning
+        if let Some(expires_at) = self.license_expiry_warning() {
+            egui::TopBottomPanel::top("license_expiry_banner")
+                .frame(egui::Frame::none()
+                    .fill(egui::Color32::from_rgb(120, 80, 20))
+                    .inner_margin(egui::Margin::symmetric(8.0, 4.0)))
+                .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        let text = self.i18n.t("license_expires_banner").replace("{}", &license::format_timestamp(expires_at));
+                        ui.label(egui::RichText::new(format!("⚠ {}", text)).color(egui::Color32::WHITE));
+                        if ui.link(self.i18n.t("enter_license_key")).clicked() {
+                            self.show_license_window = true;
+                        }
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.small_button("✖").on_hover_text(self.i18n.t("dismiss")).clicked() {
+                                self.license_expiry_banner_dismissed = true;
+                            }
+                        });
+                    });
+                });
+        }
+        
+        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
+            egui::menu::bar(ui, |ui| {
+                // Title with background color
+                let title_frame = egui::Frame::none()
+                    .fill(egui::Color32::from_rgb(60, 130, 60))
+                    .inner_margin(egui::Margin::symmetric(8.0, 4.0));
+                
+                title_frame.show(ui, |ui| {
+                    ui.heading(egui::RichText::new("CicadaGallery - Video Gallery Player").color(egui::Color32::WHITE));
+                });
+                
+                ui.separator();
+                
+                if ui.button(&self.i18n.t("add_videos")).clicked() {
+                    self.add_files();
+                }
+                
+                if ui.button(&self.i18n.t("add_folder")).clicked() {
+                    self.add_folder();
+                }
+                
+                if ui.button(&self.i18n.t("rescan_folders")).clicked() {
+                    self.rescan_folders();
+                }
+                
+                ui.separator();
+                
+                // Filters button replaces the side panel on narrow windows
+                if self.is_compact_layout(ctx) {
+                    if ui.selectable_label(self.show_filters_window, self.i18n.t("filters")).clicked() {
+                        self.show_filters_window = !self.show_filters_window;
+                    }
+                    
+                    ui.separator();
+                }
+                
+                let view_button_text = if self.current_view == ViewMode::Grid { 
+                    self.i18n.t("list_view") 
+                } else { 
+                    self.i18n.t("grid_view") 
+                };
+                if ui.button(&view_button_text).clicked() {
+                    self.current_view = if self.current_view == ViewMode::Grid {
+                        ViewMode::List
+                    } else {
+                        ViewMode::Grid
+                    };
+                }
+                
+                if self.current_view == ViewMode::Grid
+                    && ui.selectable_label(self.group_by_folder, self.i18n.t("group_by_folder")).clicked()
+                {
+                    self.group_by_folder = !self.group_by_folder;
+                    self.save_settings();
+                }
+                
+                ui.separator();
+                
+                if ui.button(&self.i18n.t("options")).clicked() {
+                    self.show_options_window = !self.show_options_window;
+                }
+                
+                // Profile switcher
+                let mut switch_to: Option<String> = None;
+                let profile_menu = ui.menu_button(format!("👤 {}", self.current_profile), |ui| {
+                    for (profile_name, video_count) in &self.available_profiles {
+                        let label = format!("{} ({})", profile_name, self.i18n.t("videos_count").replace("{}", &video_count.to_string()));
+                        if ui.selectable_label(*profile_name == self.current_profile, label).clicked() {
+                            switch_to = Some(profile_name.clone());
+                            ui.close_menu();
+                        }
+                    }
+                    ui.separator();
+                    if ui.button(self.i18n.t("manage_profiles")).clicked() {
+                        self.show_options_window = true;
+                        self.selected_options_tab = OptionsTab::Data;
+                        ui.close_menu();
+                    }
+                });
+                if profile_menu.response.clicked() {
+                    // Refresh the list (and video counts) each time the menu opens
+                    self.available_profiles = database::list_profiles().unwrap_or_default();
+                }
+                if let Some(profile_name) = switch_to {
+                    self.switch_profile(&profile_name);
+                }
+                
+                ui.separator();
+                
+                // Scene panel button (premium only)
+                if self.is_premium {
+                    if ui.button(if self.scene_panel_visible { "Hide Scenes" } else { "Show Scenes" }).clicked() {
+                        self.scene_panel_visible = !self.scene_panel_visible;
+                    }
+                } else {
+                    ui.add_enabled(false, egui::Button::new("Show Scenes 🔒"));
+                }
+                
+                ui.separator();
+                
+                // Batch scene generation progress
+                if let Some((done, total)) = self.scene_batch_progress {
+                    let progress_text = self.i18n.t(self.scene_batch_label)
+                        .replacen("{}", &done.to_string(), 1)
+                        .replacen("{}", &total.to_string(), 1);
+                    ui.add(egui::ProgressBar::new(done as f32 / total.max(1) as f32)
+                        .text(progress_text)
+                        .desired_width(180.0));
+                    let cancelling = self.scene_batch_cancel.as_ref().is_some_and(|c| c.load(Ordering::Relaxed));
+                    if ui.add_enabled(!cancelling, egui::Button::new(self.i18n.t("cancel"))).clicked() {
+                        self.cancel_scene_batch();
+                    }
+                    
+                    ui.separator();
+                }
+                
+                // Folder import progress
+                if let Some((found, done, total)) = self.import_progress {
+                    let progress_text = self.i18n.t("import_progress")
+                        .replacen("{}", &found.to_string(), 1)
+                        .replacen("{}", &done.to_string(), 1)
+                        .replacen("{}", &total.to_string(), 1);
+                    ui.add(egui::ProgressBar::new(done as f32 / total.max(1) as f32)
+                        .text(progress_text)
+                        .desired_width(260.0));
+                    let cancelling = self.import_cancel.as_ref().is_some_and(|c| c.load(Ordering::Relaxed));
+                    if ui.add_enabled(!cancelling, egui::Button::new(self.i18n.t("cancel"))).clicked() {
+                        if let Some(ref cancel) = self.import_cancel {
+                            cancel.store(true, Ordering::Relaxed);
+                        }
+                    }
+                    
+                    ui.separator();
+                }
+                
+                // Duration re-probe progress
+                if let Some((done, total)) = self.duration_probe_progress {
+                    let progress_text = self.i18n.t("duration_probe_progress")
+                        .replacen("{}", &done.to_string(), 1)
+                        .replacen("{}", &total.to_string(), 1);
+                    ui.add(egui::ProgressBar::new(done as f32 / total.max(1) as f32)
+                        .text(progress_text)
+                        .desired_width(180.0));
+                    let cancelling = self.duration_probe_cancel.as_ref().is_some_and(|c| c.load(Ordering::Relaxed));
+                    if ui.add_enabled(!cancelling, egui::Button::new(self.i18n.t("cancel"))).clicked() {
+                        if let Some(ref cancel) = self.duration_probe_cancel {
+                            cancel.store(true, Ordering::Relaxed);
+                        }
+                    }
+                    
+                    ui.separator();
+                }
+                
+                // "Copy file to…" progress
+                if let Some((done, total)) = self.file_copy_progress {
+                    let progress_text = self.i18n.t("file_copy_progress")
+                        .replacen("{}", &done.to_string(), 1)
+                        .replacen("{}", &total.to_string(), 1);
+                    ui.add(egui::ProgressBar::new(done as f32 / total.max(1) as f32)
+                        .text(progress_text)
+                        .desired_width(180.0));
+                    
+                    ui.separator();
+                }
+                
+                // Folder watching degraded (watcher error or folders not available)
+                if self.watcher_failed || !self.watcher_unavailable.is_empty() {
+                    let mut details = if self.watcher_failed {
+                        self.i18n.t("watcher_failed")
+                    } else {
+                        self.i18n.t("watcher_folders_unavailable")
+                    };
+                    for folder in &self.watcher_unavailable {
+                        details.push_str(&format!("\n{}", folder.display()));
+                    }
+                    ui.label(egui::RichText::new(self.i18n.t("watcher_degraded")).color(egui::Color32::from_rgb(255, 180, 0)))
+                        .on_hover_text(details);
+                    ui.separator();
+                }
+                
+                // Recently played filter
+                let recent_text = self.i18n.t("recent_filter").replace("{}", &self.recent_days.to_string());
+                if ui.selectable_label(self.show_recent_only, recent_text).clicked() {
+                    self.show_recent_only = !self.show_recent_only;
+                }
+                
+                // Active added-month filter (set from the statistics histogram)
+                if let Some((year, month)) = self.added_month_filter {
+                    if ui.selectable_label(true, format!("📅 {:04}-{:02} ✕", year, month))
+                        .on_hover_text(self.i18n.t("clear_month_filter"))
+                        .clicked()
+                    {
+                        self.added_month_filter = None;
+                    }
+                }
+                
+                if ui.button(self.i18n.t("statistics")).clicked() {
+                    self.show_statistics_window = !self.show_statistics_window;
+                }
+                
+                if ui.button(self.i18n.t("find_duplicates")).clicked() {
+                    self.show_duplicates_window = !self.show_duplicates_window;
+                }
+                
+                if ui.button(self.i18n.t("review_mode")).on_hover_text(self.i18n.t("review_mode_tooltip")).clicked() {
+                    self.show_review_mode = true;
+                    self.review_video_id = None;
+                }
+                
+                ui.separator();
+                
+                // Protected videos lock/unlock
+                if self.protection_pin_hash.is_some() {
+                    if self.protected_unlocked {
+                        if ui.button(self.i18n.t("lock_protected")).clicked() {
+                            self.lock_protected();
+                        }
+                    } else if ui.button(self.i18n.t("unlock_protected")).clicked() {
+                        self.show_unlock_window = true;
+                        self.pin_input.clear();
+                        self.pin_status_message = None;
+                    }
+                    
+                    ui.separator();
+                }
+                
+                // Filtering and sorting the library is costly, so do it once for both uses below
+                let filtered_ids: Vec<String> = self.get_filtered_videos().iter().map(|v| v.id.clone()).collect();
+                
+                // Selected videos that the current filters hide from view
+                let mut hidden_selected = 0;
+                if !self.selected_videos.is_empty() {
+                    let visible_ids: HashSet<&String> = filtered_ids.iter().collect();
+                    if self.prune_hidden_selection {
+                        self.selected_videos.retain(|id| visible_ids.contains(id));
+                    } else {
+                        hidden_selected = self.selected_videos.iter().filter(|id| !visible_ids.contains(*id)).count();
+                    }
+                }
+                
+                // Multi-selection controls
+                if !filtered_ids.is_empty() {
+                    if ui.button(self.i18n.t("select_all")).on_hover_text("Ctrl+A").clicked() {
+                        self.select_all_videos(filtered_ids.clone());
+                    }
+                    
+                    if ui.button(self.i18n.t("invert_selection")).clicked() {
+                        self.invert_selection(filtered_ids);
+                    }
+                    
+                    if !self.selected_videos.is_empty() {
+                        let clear_text = self.i18n.t("clear_selection_count").replace("{}", &self.selected_videos.len().to_string());
+                        if ui.button(&clear_text).on_hover_text("Ctrl+Shift+A").clicked() {
+                            self.clear_selection();
+                        }
+                        
+                        if ui.button(&self.i18n.t("add_tag_to_selected")).clicked() {
+                            self.show_tag_add_popup = true;
+                        }
+                        
+                        if self.selected_videos.len() > 1 && ui.button(self.i18n.t("rate_selected")).clicked() {
+                            self.show_bulk_rating_popup = true;
+                        }
+                        
+                        if self.selected_videos.len() > 1 && ui.button(self.i18n.t("folder_selected")).clicked() {
+                            self.show_bulk_folder_popup = true;
+                        }
+                        
+                        if ui.button(&self.i18n.t("delete_selected")).clicked() {
+                            self.delete_confirm_video = Some("__MULTI__".to_string());
+                        }
+                        
+                        if hidden_selected > 0 {
+                            ui.label(egui::RichText::new(self.i18n.t("selection_hidden_count").replace("{}", &hidden_selected.to_string()))
+                                .color(egui::Color32::from_rgb(255, 170, 60)))
+                                .on_hover_text(self.i18n.t("selection_hidden_hint"));
+                        }
+                    }
+                }
+                
+                // Push volume slider to the right
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.add(egui::Slider::new(&mut self.mpv_volume, 0..=100)
+                        .text("🔊")
+                        .suffix("%")
+                        .show_value(true)
+                        .min_decimals(0)
+                        .max_decimals(0));
+                    // ui.label("デフォルト音量");
+                    
+                    ui.separator();
+                    
+                    // Show video count and limit for free tier
+                    let video_count = self.database.videos.len();
+                    if self.is_premium {
+                        ui.label(format!("📹 Videos: {}", video_count));
+                    } else {
+                        let count_text = format!("📹 {}/100 (Free)", video_count);
+                        let color = if video_count >= 100 {
+                            egui::Color32::from_rgb(255, 100, 100)
+                        } else if video_count >= 80 {
+                            egui::Color32::from_rgb(255, 200, 100)
+                        } else {
+                            egui::Color32::from_rgb(200, 200, 200)
+                        };
+                        ui.label(egui::RichText::new(count_text).color(color));
+                    }
+                    
+                    if ui.memory(|mem| mem.is_anything_being_dragged()) {
+                        self.save_settings();
+                    }
+                });
+            });
+        });
+        
+        // Status bar (only while there is a message to show)
+        if let Some(message) = self.status_message.clone() {
+            egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(message);
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.small_button("✕").clicked() {
+                            self.status_message = None;
+                        }
+                    });
+                });
+            });
+        }
+        
+        // Sidebar (Filters): side panel, or a floating window on narrow windows
+        if self.is_compact_layout(ctx) {
+            if self.show_filters_window {
+                let mut window_open = true;
+                egui::Window::new(self.i18n.t("filters"))
+                    .open(&mut window_open)
+                    .collapsible(false)
+                    .resizable(true)
+                    .default_width(240.0)
+                    .show(ctx, |ui| {
+                        egui::ScrollArea::vertical().show(ui, |ui| {
+                            self.show_filters(ui);
+                        });
+                    });
+                if !window_open {
+                    self.show_filters_window = false;
+                }
+            }
+        } else {
+            // The window is not drawn here, so it must not keep blocking shortcuts
+            self.show_filters_window = false;
+            egui::SidePanel::left("side_panel").min_width(220.0).show(ctx, |ui| {
+                self.show_filters(ui);
+            });
+        }
+        
+        // Right side panel for scene thumbnails
+        if self.scene_panel_visible {
+            let scene_panel = egui::SidePanel::right("scene_panel")
+                .resizable(true)
+                .default_width(self.scene_panel_width)
+                .width_range(240.0..=800.0);
+            let scene_panel_response = scene_panel.show(ctx, |ui| {
+                if let Some(video_id) = &self.selected_video.clone() {
+                    // Clone video data to avoid borrowing issues
+                    let video_data = self.database.get_video(video_id).cloned();
+                    
+                    if let Some(video) = video_data {
+                        // Check if metadata is complete (both duration and resolution present)
+                        let metadata_complete = video.duration.is_some() && video.resolution.is_some();
+                        
+                        // Only load metadata if incomplete and not yet marked as loaded
+                        // Skip the actual loading to prevent UI blocking
+                        if !metadata_complete && !self.metadata_loaded.contains(video_id) {
+                            // Mark as loaded to prevent repeated checks
+                            self.metadata_loaded.insert(video_id.clone());
+                        } else if metadata_complete && !self.metadata_loaded.contains(video_id) {
+                            // If metadata is already complete, just mark as loaded
+                            self.metadata_loaded.insert(video_id.clone());
+                        }
+                        
+                        // Display selected video information
+                        ui.horizontal(|ui| {
+                            ui.heading(self.i18n.t("selected_video"));
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                if ui.button("📷").on_hover_text(self.i18n.t("take_screenshot")).clicked() {
+                                    self.take_player_screenshot();
+                                }
+                            });
+                        });
+                        ui.separator();
+                        
+                        // Larger preview extracted at the mid-point on first selection.
+                        // It is generated in the background by load_image_texture (missing file),
+                        // and the grid thumbnail is shown until it is ready.
+                        let preview_path = match video.preview_thumbnail_path.clone() {
+                            Some(path) => Some(path),
+                            None if video.thumbnail_path.is_some() => {
+                                let path = thumbnail::preview_thumbnail_path(&video.path, &thumbnail::get_cache_dir());
+                                if let Some(video) = self.database.get_video_mut(video_id) {
+                                    video.preview_thumbnail_path = Some(path.clone());
+                                    let _ = database::persist_video(video);
+                                }
+                                Some(path)
+                            }
+                            None => None,
+                        };
+                        let preview_texture = preview_path.and_then(|path| self.load_image_texture(ctx, &path));
+                        
+                        // Display video thumbnail
+                        if let Some(ref thumb_path) = video.thumbnail_path {
+                            if let Some(texture) = preview_texture.or_else(|| self.load_image_texture(ctx, thumb_path)) {
+                                let thumbnail_size = egui::vec2(280.0, 157.0);
+                                ui.add(
+                                    egui::Image::new(&texture)
+                                        .max_size(thumbnail_size)
+                                );
+                            }
+                        } else {
+                            // Placeholder if no thumbnail
+                            let thumbnail_size = egui::vec2(280.0, 157.0);
+                            let (rect, _) = ui.allocate_exact_size(thumbnail_size, egui::Sense::hover());
+                            if ui.is_rect_visible(rect) {
+                                ui.painter().rect_filled(rect, 4.0, egui::Color32::DARK_GRAY);
+                                ui.painter().text(
+                                    rect.center(),
+                                    egui::Align2::CENTER_CENTER,
+                                    &self.i18n.t("no_thumbnail"),
+                                    egui::FontId::proportional(16.0),
+                                    egui::Color32::WHITE,
+                                );
+                            }
+                        }
+                        
+                        // Display video title (pencil button switches to inline editing)
+                        ui.add_space(5.0);
+                        let editing_title = self.title_edit.as_ref().is_some_and(|(id, _)| id == video_id);
+                        if editing_title {
+                            let mut commit = false;
+                            let mut cancel = false;
+                            ui.horizontal(|ui| {
+                                if let Some((_, ref mut buffer)) = self.title_edit {
+                                    let response = ui.text_edit_singleline(buffer);
+                                    if response.lost_focus() {
+                                        if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                                            cancel = true;
+                                        } else {
+                                            commit = true;
+                                        }
+                                    } else {
+                                        response.request_focus();
+                                    }
+                                }
+                                if ui.small_button("✔").clicked() {
+                                    commit = true;
+                                }
+                                if ui.small_button("✕").clicked() {
+                                    cancel = true;
+                                }
+                            });
+                            if cancel {
+                                self.title_edit = None;
+                            } else if commit {
+                                if let Some((id, title)) = self.title_edit.take() {
+                                    self.set_video_title(&id, &title);
+                                }
+                            }
+                        } else {
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new(&video.title).strong().size(14.0));
+                                if ui.small_button("✏").on_hover_text(self.i18n.t("edit_title")).clicked() {
+                                    self.title_edit = Some((video_id.clone(), video.title.clone()));
+                                }
+                            });
+                        }
+                        
+                        ui.add_space(5.0);
+                        
+                        // Display video information
+                        ui.group(|ui| {
+                            ui.set_width(ui.available_width());
+                            
+                            // Always visible: Duration, Resolution, Frame rate, Added date
+                            // Duration
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new("⏱").size(14.0));
+                                if let Some(duration) = video.duration {
+                                    let duration_text = self.i18n.t("duration_label").replace("{}", &scene_detection::format_timestamp(duration));
+                                    ui.label(&duration_text);
+                                } else {
+                                    ui.label(self.i18n.t("duration_label").replace("{}", "-"));
+                                }
+                            });
+                            
+                            // Resolution
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new("📐").size(14.0));
+                                if let Some((width, height)) = video.resolution {
+                                    let resolution_text = self.i18n.t("resolution_label").replace("{}", &format!("{}×{}", width, height));
+                                    ui.label(&resolution_text);
+                                } else {
+                                    ui.label(self.i18n.t("resolution_label").replace("{}", "-"));
+                                }
+                            });
+                            
+                            // Frame rate
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new("🎬").size(14.0));
+                                if let Some(fps) = video.frame_rate {
+                                    let fps_text = self.i18n.t("framerate_label").replace("{}", &format!("{:.2}", fps));
+                                    ui.label(&fps_text);
+                                } else {
+                                    ui.label(self.i18n.t("framerate_label").replace("{}", "-"));
+                                }
+                            });
+                            
+                            // Codec / container
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new("🎞").size(14.0));
+                                let codec = video.codec.as_deref().unwrap_or("-");
+                                let container = video.container.as_deref().unwrap_or("-");
+                                ui.label(self.i18n.t("codec_label").replace("{codec}", codec).replace("{container}", container));
+                            });
+                            
+                            // HDR
+                            if video.is_hdr {
+                                ui.horizontal(|ui| {
+                                    ui.label(egui::RichText::new("🔆").size(14.0));
+                                    ui.label(self.i18n.t("hdr_video"));
+                                });
+                            }
+                            
+                            // Per-video volume / speed remembered from the last playback
+                            if video.preferred_volume.is_some() || video.preferred_speed.is_some() {
+                                ui.horizontal(|ui| {
+                                    ui.label(egui::RichText::new("🔊").size(14.0));
+                                    let volume = video.preferred_volume.unwrap_or(self.mpv_volume);
+                                    let speed = video.preferred_speed.unwrap_or(1.0);
+                                    ui.label(self.i18n.t("player_levels_label")
+                                        .replace("{volume}", &volume.to_string())
+                                        .replace("{speed}", &format!("{:.2}", speed)));
+                                    if ui.small_button("↺").on_hover_text(self.i18n.t("reset_player_levels")).clicked() {
+                                        if let Some(video) = self.database.get_video_mut(video_id) {
+                                            video.preferred_volume = None;
+                                            video.preferred_speed = None;
+                                            let _ = database::persist_video(video);
+                                        }
+                                    }
+                                });
+                            }
+                            
+                            // Added date
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new("📅").size(14.0));
+                                let added_text = self.i18n.t("added_label").replace("{}", &video.added_date.format("%Y-%m-%d %H:%M").to_string());
+                                ui.label(&added_text);
+                            });
+                            
+                            // Expandable section for more details
+                            let expand_text = if self.profile_details_expanded {
+                                self.i18n.t("less_details")
+                            } else {
+                                self.i18n.t("more_details")
+                            };
+                            if ui.button(&expand_text).clicked() {
+                                self.profile_details_expanded = !self.profile_details_expanded;
+                            }
+                            
+                            if self.profile_details_expanded {
+                                // File size
+                                ui.horizontal(|ui| {
+                                    ui.label(egui::RichText::new("💾").size(14.0));
+                                    let size_mb = video.file_size as f64 / 1024.0 / 1024.0;
+                                    let size_text = if size_mb >= 1024.0 {
+                                        self.i18n.t("size_gb").replace("{:.2}", &format!("{:.2}", size_mb / 1024.0))
+                                    } else {
                                         self.i18n.t("size_mb").replace("{:.1}", &format!("{:.1}", size_mb))
                                     };
                                     ui.label(&size_text);
                                 });
                                 
-                                // Folder
-                                if let Some(ref folder) = video.folder {
-                                    ui.horizontal(|ui| {
-                                        ui.label(egui::RichText::new("📁").size(14.0));
-                                        let folder_text = self.i18n.t("folder_label").replace("{}", folder);
-                                        ui.label(&folder_text);
-                                    });
+                                // Folders (a video can be in several)
+                                let mut add_folder: Option<String> = None;
+                                let mut remove_folder: Option<String> = None;
+                                ui.horizontal_wrapped(|ui| {
+                                    ui.label(egui::RichText::new("📁").size(14.0));
+                                    ui.label(self.i18n.t("folders_label"));
+                                    for folder in &video.folders {
+                                        ui.label(folder);
+                                        if ui.small_button("✖").on_hover_text(self.i18n.t("remove_from_folder")).clicked() {
+                                            remove_folder = Some(folder.clone());
+                                        }
+                                    }
+                                    ui.menu_button("➕", |ui| {
+                                        let other_folders: Vec<&String> = self.database.folders.iter()
+                                            .filter(|f| !video.folders.contains(f))
+                                            .collect();
+                                        if other_folders.is_empty() {
+                                            ui.label(egui::RichText::new(self.i18n.t("no_other_folders")).weak());
+                                        }
+                                        for folder in other_folders {
+                                            if ui.button(folder).clicked() {
+                                                add_folder = Some(folder.clone());
+                                                ui.close_menu();
+                                            }
+                                        }
+                                    }).response.on_hover_text(self.i18n.t("add_to_folder"));
+                                });
+                                if let Some(folder) = add_folder {
+                                    self.add_video_folder(video_id, folder);
+                                    self.persist_video(video_id);
+                                }
+                                if let Some(folder) = remove_folder {
+                                    self.remove_video_folder(video_id, &folder);
+                                    self.persist_video(video_id);
+                                }
+                                
+                                // Tags
+                                if !video.tags.is_empty() {
+                                    ui.horizontal(|ui| {
+                                        ui.label(egui::RichText::new("🏷").size(14.0));
+                                        let tags_text = self.i18n.t("tags_label").replace("{}", &video.tags.join(", "));
+                                        ui.label(&tags_text);
+                                    });
+                                }
+                                
+                                // Play count
+                                ui.horizontal(|ui| {
+                                    ui.label(egui::RichText::new("🔁").size(14.0));
+                                    ui.label(self.i18n.t("play_count_label").replace("{}", &video.play_count.to_string()));
+                                });
+                                
+                                // Last played
+                                if let Some(last_played) = video.last_played {
+                                    ui.horizontal(|ui| {
+                                        ui.label(egui::RichText::new("▶").size(14.0));
+                                        let last_played_text = self.i18n.t("last_played_label").replace("{}", &last_played.format("%Y-%m-%d %H:%M").to_string());
+                                        ui.label(&last_played_text);
+                                    });
+                                }
+                            }
+                        });
+                        
+                        // Chapters embedded in the file
+                        if !video.chapters.is_empty() {
+                            ui.add_space(5.0);
+                            egui::CollapsingHeader::new(format!("📑 {} ({})", self.i18n.t("chapters"), video.chapters.len()))
+                                .id_salt("chapter_list")
+                                .show(ui, |ui| {
+                                    for chapter in &video.chapters {
+                                        let label = format!("{}  {}", scene_detection::format_timestamp(chapter.start), chapter.title);
+                                        if ui.selectable_label(false, label)
+                                            .on_hover_text(self.i18n.t("play_from_chapter"))
+                                            .clicked()
+                                        {
+                                            self.play_video_at(&video.path, chapter.start);
+                                        }
+                                    }
+                                });
+                        }
+                        
+                        ui.add_space(5.0);
+                        
+                        // Star rating
+                        let has_multi_selection = !self.selected_videos.is_empty();
+                        ui.horizontal(|ui| {
+                            ui.label(&self.i18n.t("rating"));
+                            let current_rating = video.rating;
+                            for star in 1..=5 {
+                                let star_text = if star <= current_rating {
+                                    "★"
+                                } else {
+                                    "☆"
+                                };
+                                if ui.button(egui::RichText::new(star_text).size(24.0)).clicked() {
+                                    // Toggle: if clicking current rating, set to 0 (no rating)
+                                    let new_rating = if current_rating == star { 0 } else { star };
+                                    
+                                    // Apply to all selected videos if multi-selection exists
+                                    if has_multi_selection {
+                                        self.set_rating_for_selected(new_rating);
+                                    }
+                                    // Also apply to current video (selected_video)
+                                    self.set_rating(video_id, new_rating);
+                                }
+                            }
+                            if current_rating > 0 && ui.small_button("×").clicked() {
+                                if has_multi_selection {
+                                    self.set_rating_for_selected(0);
+                                }
+                                self.set_rating(video_id, 0);
+                            }
+                        });
+                        
+                        // Show multi-selection indicator
+                        if has_multi_selection {
+                            let count = self.selected_videos.len();
+                            ui.label(egui::RichText::new(
+                                format!("({} videos selected - rating applies to all)", count)
+                            ).small().weak());
+                        }
+                        
+                        ui.add_space(10.0);
+                        ui.separator();
+                        
+                        // Tag editing section
+                        ui.heading(&self.i18n.t("tags"));
+                        
+                        let video_tags = video.tags.clone();
+                        let video_id_for_tags = video_id.clone();
+                        
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(&self.i18n.t("tags_colon"));
+                            
+                            // Display existing tags with remove button
+                            for tag in &video_tags {
+                                ui.horizontal(|ui| {
+                                    let _tag_label = ui.label(format!("#{}", tag));
+                                    if ui.small_button("×").clicked() {
+                                        self.remove_tag_from_video(&video_id_for_tags, tag);
+                                    }
+                                });
+                            }
+                            
+                            // Add tag button
+                            if ui.small_button("+").clicked() {
+                                self.show_tag_add_popup = true;
+                            }
+                        });
+                        
+                        // Tag addition popup
+                        if self.show_tag_add_popup {
+                            let screen_rect = ui.ctx().screen_rect();
+                            let popup_pos = egui::pos2(
+                                screen_rect.max.x - 350.0,  // 右から350px
+                                screen_rect.center().y - 150.0  // 中央より少し上
+                            );
+                            
+                            // Determine target videos (single or multiple selection)
+                            let target_videos: Vec<String> = if self.selected_videos.is_empty() {
+                                vec![video_id_for_tags.clone()]
+                            } else {
+                                // 複数選択時はselected_videoも含める
+                                let mut vids: Vec<String> = self.selected_videos.iter().cloned().collect();
+                                if let Some(sel) = &self.selected_video {
+                                    if !vids.contains(sel) {
+                                        vids.push(sel.clone());
+                                    }
+                                }
+                                vids
+                            };
+                            
+                            let is_multi = target_videos.len() > 1;
+                            let title = if is_multi {
+                                format!("{} ({} videos)", self.i18n.t("add_tag"), target_videos.len())
+                            } else {
+                                self.i18n.t("add_tag")
+                            };
+                            
+                            egui::Window::new(&title)
+                                .collapsible(false)
+                                .resizable(false)
+                                .default_pos(popup_pos)
+                                .show(ui.ctx(), |ui| {
+                                    ui.label(&self.i18n.t("select_or_create_tag"));
+                                    
+                                    ui.separator();
+                                    
+                                    // Show existing tags in database
+                                    ui.label(&self.i18n.t("existing_tags"));
+                                    egui::ScrollArea::vertical()
+                                        .max_height(150.0)
+                                        .show(ui, |ui| {
+                                            let all_tags = self.database.tags.clone();
+                                            for tag in &all_tags {
+                                                // For multi-selection, show all tags
+                                                // For single selection, don't show tags already assigned
+                                                let should_show = if is_multi {
+                                                    true
+                                                } else {
+                                                    !video_tags.contains(tag)
+                                                };
+                                                
+                                                if should_show {
+                                                    if ui.button(format!("#{}", tag)).clicked() {
+                                                        // Add tag to all target videos
+                                                        for vid in &target_videos {
+                                                            self.add_tag_to_video(vid, tag.clone());
+                                                        }
+                                                        self.show_tag_add_popup = false;
+                                                    }
+                                                }
+                                            }
+                                        });
+                                    
+                                    ui.separator();
+                                    
+                                    // Create new tag
+                                    ui.label(&self.i18n.t("create_new_tag"));
+                                    ui.horizontal(|ui| {
+                                        ui.text_edit_singleline(&mut self.new_tag_input);
+                                        if ui.button(&self.i18n.t("create_tag")).clicked() && !self.new_tag_input.is_empty() {
+                                            let new_tag = self.new_tag_input.trim().to_string();
+                                            if !new_tag.is_empty() {
+                                                // Add tag to all target videos
+                                                for vid in &target_videos {
+                                                    self.add_tag_to_video(vid, new_tag.clone());
+                                                }
+                                                self.new_tag_input.clear();
+                                                self.show_tag_add_popup = false;
+                                            }
+                                        }
+                                    });
+                                    
+                                    ui.separator();
+                                    
+                                    if ui.button(&self.i18n.t("cancel")).clicked() {
+                                        self.show_tag_add_popup = false;
+                                        self.new_tag_input.clear();
+                                    }
+                                });
+                        }
+                        
+                        // Notes (saved when the text field loses focus)
+                        if self.notes_video_id.as_ref() != Some(video_id) {
+                            // Keep unsaved edits of the previously selected video
+                            self.flush_notes();
+                            self.notes_video_id = Some(video_id.clone());
+                            self.notes_buffer = video.notes.clone();
+                        }
+                        ui.add_space(10.0);
+                        ui.label(self.i18n.t("notes"));
+                        let notes_response = ui.add(
+                            egui::TextEdit::multiline(&mut self.notes_buffer)
+                                .desired_rows(3)
+                                .desired_width(f32::INFINITY)
+                                .hint_text(self.i18n.t("notes_hint"))
+                        );
+                        if notes_response.lost_focus() {
+                            let notes = self.notes_buffer.clone();
+                            self.set_video_notes(video_id, &notes);
+                        }
+                        
+                        ui.add_space(10.0);
+                        ui.separator();
+                        
+                        // Scene thumbnails section
+                        ui.horizontal(|ui| {
+                            ui.heading(&self.i18n.t("scene_thumbnails"));
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                let video_id_for_refresh = video_id.clone();
+                                if ui.button(&self.i18n.t("refresh_scenes")).clicked() {
+                                    self.refresh_scenes(&video_id_for_refresh);
+                                }
+                                if !video.scenes.is_empty() && ui.button(self.i18n.t("view_scenes")).clicked() {
+                                    self.show_scene_lightbox = true;
+                                    self.lightbox_focused_scene = 0;
+                                }
+                                if video.scenes.len() > 1 && ui.button("🧹")
+                                    .on_hover_text(self.i18n.t("remove_similar_scenes"))
+                                    .clicked()
+                                {
+                                    self.remove_similar_scenes(&video_id_for_refresh);
+                                }
+                            });
+                        });
+                        
+                        // Scene order toggle (only meaningful when scores were detected)
+                        if video.scenes.iter().any(|s| s.score.is_some()) {
+                            ui.horizontal(|ui| {
+                                ui.label(self.i18n.t("scene_order"));
+                                if ui.selectable_label(!self.scene_sort_by_score, self.i18n.t("scene_order_time")).clicked() {
+                                    self.scene_sort_by_score = false;
+                                }
+                                if ui.selectable_label(self.scene_sort_by_score, self.i18n.t("scene_order_score")).clicked() {
+                                    self.scene_sort_by_score = true;
+                                }
+                            });
+                        }
+                        
+                        // Manual scene capture by timestamp (premium)
+                        if self.is_premium {
+                            ui.horizontal(|ui| {
+                                ui.label(self.i18n.t("add_scene_at"));
+                                let response = ui.add(
+                                    egui::TextEdit::singleline(&mut self.scene_timestamp_input)
+                                        .hint_text("00:01:30")
+                                        .desired_width(80.0)
+                                );
+                                let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                                if ui.button(self.i18n.t("add")).clicked() || submitted {
+                                    self.add_scene_from_input(video_id);
+                                }
+                            });
+                            if let Some(ref error) = self.scene_capture_error {
+                                ui.label(egui::RichText::new(error).color(egui::Color32::from_rgb(255, 100, 100)));
+                            }
+                        }
+                        
+                        // Show selection controls if scenes are selected
+                        if !self.selected_scenes.is_empty() {
+                            ui.horizontal(|ui| {
+                                let selected_text = self.i18n.t("selected_count").replace("{}", &self.selected_scenes.len().to_string());
+                                ui.label(&selected_text);
+                                if ui.button(&self.i18n.t("clear_selection")).clicked() {
+                                    self.selected_scenes.clear();
+                                    self.last_selected_scene = None;
+                                }
+                                if ui.button(&self.i18n.t("delete_selected")).clicked() {
+                                    self.delete_selected_scenes(video_id);
+                                }
+                            });
+                        }
+                        
+                        ui.separator();
+                        
+                        // Show generate button if no scenes exist
+                        if video.scenes.is_empty() {
+                            ui.label(&self.i18n.t("no_scenes_yet"));
+                            // Free tier: show limit info
+                            if !self.is_premium {
+                                ui.label(&self.i18n.t("free_tier_scene_limit"));
+                            }
+                            if ui.button(&self.i18n.t("generate_scenes")).clicked() {
+                                self.generate_scenes(video_id);
+                            }
+                        } else {
+                            // Display scene thumbnails in a scrollable area
+                            egui::ScrollArea::vertical()
+                                .auto_shrink([false, false])
+                                .show(ui, |ui| {
+                                    let scenes = video.scenes.clone();
+                                    let video_path = video.path.clone();
+                                    let video_id_clone = video_id.clone();
+                                    
+                                    // Scene indices always refer to the time-ordered list
+                                    let mut ordered_scenes: Vec<(usize, &crate::models::SceneInfo)> = scenes.iter().enumerate().collect();
+                                    if self.scene_sort_by_score {
+                                        ordered_scenes.sort_by(|a, b| {
+                                            b.1.score.unwrap_or(-1.0).partial_cmp(&a.1.score.unwrap_or(-1.0))
+                                                .unwrap_or(std::cmp::Ordering::Equal)
+                                        });
+                                    }
+                                    
+                                    for (scene_index, scene) in ordered_scenes {
+                                        let is_selected = self.selected_scenes.contains(&scene_index);
+                                        
+                                        ui.group(|ui| {
+                                            let thumbnail_size = egui::vec2(180.0, 101.0);
+                                            let (rect, response) = ui.allocate_exact_size(thumbnail_size, egui::Sense::click());
+                                            
+                                            // Check if this is a manually captured scene
+                                            let is_manual_scene = scene.thumbnail_path.to_string_lossy().contains("scene_manual_");
+                                            
+                                            // Only load texture if visible (optimization)
+                                            if ui.is_rect_visible(rect) {
+                                                // Draw background for manually captured scenes (purple-pink color)
+                                                if is_manual_scene {
+                                                    ui.painter().rect_filled(
+                                                        rect.expand(3.0),
+                                                        6.0,
+                                                        egui::Color32::from_rgb(180, 100, 160) // Purple-pink
+                                                    );
+                                                }
+                                                
+                                                // Load and display actual thumbnail image
+                                                if let Some(texture) = self.load_image_texture(ctx, &scene.thumbnail_path) {
+                                                    // Draw thumbnail
+                                                    ui.put(rect, egui::Image::new(&texture).fit_to_exact_size(thumbnail_size));
+                                                    
+                                                    // Draw selection highlight
+                                                    if is_selected {
+                                                        ui.painter().rect_stroke(
+                                                            rect,
+                                                            4.0,
+                                                            egui::Stroke::new(3.0, self.accent_color32())
+                                                        );
+                                                    } else if is_manual_scene {
+                                                        // Always show border for manual scenes
+                                                        ui.painter().rect_stroke(
+                                                            rect,
+                                                            4.0,
+                                                            egui::Stroke::new(2.0, egui::Color32::from_rgb(180, 100, 160))
+                                                        );
+                                                    }
+                                                }
+                                            }
+                                            
+                                            // Handle click with modifiers (always handle regardless of visibility)
+                                            if response.clicked() {
+                                                let modifiers = ui.input(|i| i.modifiers.clone());
+                                                if modifiers.shift {
+                                                    // Shift+Click: range selection
+                                                    if let Some(last_idx) = self.last_selected_scene {
+                                                        self.select_scene_range(last_idx, scene_index);
+                                                    } else {
+                                                        self.selected_scenes.insert(scene_index);
+                                                    }
+                                                    self.last_selected_scene = Some(scene_index);
+                                                } else if modifiers.ctrl {
+                                                    // Ctrl+Click: toggle selection
+                                                    self.toggle_scene_selection(scene_index);
+                                                    self.last_selected_scene = Some(scene_index);
+                                                } else {
+                                                    // Normal click: play video
+                                                    self.selected_scenes.clear();
+                                                    self.last_selected_scene = None;
+                                                    self.play_video_at(&video_path, scene.timestamp);
+                                                }
+                                            }
+                                            
+                                            // Right-click menu for scene operations
+                                            let scene_ts = scene.timestamp;
+                                            response.context_menu(|ui| {
+                                                if ui.button(&self.i18n.t("play_from_scene")).clicked() {
+                                                    self.play_video_at(&video_path, scene_ts);
+                                                    ui.close_menu();
+                                                }
+                                                
+                                                if ui.button(self.i18n.t("use_scene_as_thumbnail")).clicked() {
+                                                    self.use_scene_as_thumbnail(&video_id_clone, scene_ts);
+                                                    ui.close_menu();
+                                                }
+                                                
+                                                ui.separator();
+                                                
+                                                if ui.button(&self.i18n.t("delete_scene")).clicked() {
+                                                    self.delete_scene(&video_id_clone, scene_ts);
+                                                    ui.close_menu();
+                                                }
+                                            });
+                                            
+                                            response.on_hover_text(&self.i18n.t("click_play_ctrl_select"));
+                                            
+                                            // Timestamp label (with score when sorting by score)
+                                            match scene.score {
+                                                Some(score) if self.scene_sort_by_score => {
+                                                    ui.label(format!("{}  ({:.2})", scene_detection::format_timestamp(scene.timestamp), score));
+                                                }
+                                                _ => {
+                                                    ui.label(scene_detection::format_timestamp(scene.timestamp));
+                                                }
+                                            }
+                                        });
+                                        
+                                        ui.add_space(5.0);
+                                    }
+                                    
+                                    // Free tier: show premium promotion after scenes
+                                    if !self.is_premium {
+                                        ui.add_space(10.0);
+                                        ui.separator();
+                                        ui.vertical_centered(|ui| {
+                                            ui.label(egui::RichText::new(&self.i18n.t("free_tier_scene_limit_reached")).strong());
+                                            ui.add_space(5.0);
+                                            ui.label(&self.i18n.t("premium_unlimited_scenes"));
+                                            ui.add_space(5.0);
+                                            if ui.button(&self.i18n.t("purchase_premium")).clicked() {
+                                                #[cfg(target_os = "windows")]
+                                                {
+                                                    let _ = std::process::Command::new("cmd")
+                                                        .args(["/C", "start", "", "https://tetdarth.gumroad.com/l/jmjty"])
+                                                        .spawn();
+                                                }
+                                                #[cfg(target_os = "macos")]
+                                                {
+                                                    let _ = std::process::Command::new("open")
+                                                        .arg("https://tetdarth.gumroad.com/l/jmjty")
+                                                        .spawn();
+                                                }
+                                                #[cfg(target_os = "linux")]
+                                                {
+                                                    let _ = std::process::Command::new("xdg-open")
+                                                        .arg("https://tetdarth.gumroad.com/l/jmjty")
+                                                        .spawn();
+                                                }
+                                            }
+                                        });
+                                    }
+                                });
+                        }
+                    }
+                } else {
+                    // No video selected
+                    ui.vertical_centered(|ui| {
+                        ui.add_space(50.0);
+                        ui.heading(&self.i18n.t("selected_video"));
+                        ui.add_space(10.0);
+                        ui.label(&self.i18n.t("no_video_selected"));
+                    });
+                }
+            });
+            // Remember the dragged width (saved with the other window state on exit)
+            self.scene_panel_width = scene_panel_response.response.rect.width();
+        }
+        
+        // Main content area
+        egui::CentralPanel::default().show(ctx, |ui| {
+            // Search bar with sort buttons
+            let popup_open = self.any_popup_open();
+            ui.horizontal(|ui| {
+                ui.label("Search:");
+                let search_response = ui.text_edit_singleline(&mut self.search_query);
+                if !self.search_query.is_empty() && ui.small_button("×").on_hover_text(self.i18n.t("clear_search")).clicked() {
+                    self.search_query.clear();
+                }
+                
+                // Ctrl+F focuses the search box, Escape in it clears the query.
+                // Escape elsewhere clears the selection.
+                if !popup_open {
+                    if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::F)) {
+                        search_response.request_focus();
+                    }
+                    if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+                        if search_response.has_focus() || search_response.lost_focus() {
+                            self.search_query.clear();
+                        } else if !ctx.wants_keyboard_input() {
+                            self.clear_selection();
+                        }
+                    }
+                }
+                
+                ui.separator();
+                ui.label(self.i18n.t("sort"));
+                
+                // 作成日時ボタン
+                let added_date_text = match self.sort_field {
+                    SortField::AddedDate => {
+                        if self.sort_order == SortOrder::Ascending {
+                            self.i18n.t("sort_added_date_asc")
+                        } else {
+                            self.i18n.t("sort_added_date_desc")
+                        }
+                    }
+                    _ => self.i18n.t("sort_added_date")
+                };
+                if ui.button(added_date_text).clicked() {
+                    if self.sort_field == SortField::AddedDate {
+                        // 同じフィールドの場合は昇順降順を切り替え
+                        self.sort_order = if self.sort_order == SortOrder::Ascending {
+                            SortOrder::Descending
+                        } else {
+                            SortOrder::Ascending
+                        };
+                    } else {
+                        // 違うフィールドの場合は昇順で開始
+                        self.sort_field = SortField::AddedDate;
+                        self.sort_order = SortOrder::Ascending;
+                    }
+                }
+                
+                // ファイル名ボタン
+                let file_name_text = match self.sort_field {
+                    SortField::FileName => {
+                        if self.sort_order == SortOrder::Ascending {
+                            self.i18n.t("sort_filename_asc")
+                        } else {
+                            self.i18n.t("sort_filename_desc")
+                        }
+                    }
+                    _ => self.i18n.t("sort_filename")
+                };
+                if ui.button(file_name_text).clicked() {
+                    if self.sort_field == SortField::FileName {
+                        self.sort_order = if self.sort_order == SortOrder::Ascending {
+                            SortOrder::Descending
+                        } else {
+                            SortOrder::Ascending
+                        };
+                    } else {
+                        self.sort_field = SortField::FileName;
+                        self.sort_order = SortOrder::Ascending;
+                    }
+                }
+                
+                // 動画時間ボタン
+                let duration_text = match self.sort_field {
+                    SortField::Duration => {
+                        if self.sort_order == SortOrder::Ascending {
+                            self.i18n.t("sort_duration_asc")
+                        } else {
+                            self.i18n.t("sort_duration_desc")
+                        }
+                    }
+                    _ => self.i18n.t("sort_duration")
+                };
+                if ui.button(duration_text).clicked() {
+                    if self.sort_field == SortField::Duration {
+                        self.sort_order = if self.sort_order == SortOrder::Ascending {
+                            SortOrder::Descending
+                        } else {
+                            SortOrder::Ascending
+                        };
+                    } else {
+                        self.sort_field = SortField::Duration;
+                        self.sort_order = SortOrder::Ascending;
+                    }
+                }
+                
+                // 手動並び替えボタン
+                if ui.selectable_label(self.sort_field == SortField::Manual, self.i18n.t("sort_manual"))
+                    .on_hover_text(self.i18n.t("sort_manual_hint"))
+                    .clicked()
+                {
+                    self.sort_field = SortField::Manual;
+                }
+            });
+            
+            ui.separator();
+            
+            // Display video list
+            let current_view = self.current_view.clone();
+            let videos_to_show: Vec<VideoFile> = self.get_filtered_videos()
+                .into_iter()
+                .cloned()
+                .collect();
+            
+            // Remember the selection state BEFORE drawing UI
+            let selection_before = self.selected_video.clone();
+            let selections_before = self.selected_videos.clone();
+            
+            // Recently played row (independent of the current filter)
+            if self.show_recent_row {
+                self.show_recent_row(ui, ctx);
+            }
+            
+            // Keep the scroll position while selecting, opening panels or switching views;
+            // only a different filter/search result starts again from the top
+            let filter_key = self.filter_state_key();
+            if self.scroll_filter_key != Some(filter_key) {
+                self.scroll_filter_key = Some(filter_key);
+                self.grid_scroll_offset = 0.0;
+                self.list_scroll_offset = 0.0;
+            }
+            
+            // Create a scrollable area for content
+            // The grid virtualizes its rows inside its own scroll area
+            if videos_to_show.is_empty() {
+                self.show_empty_message(ui);
+            } else {
+                match current_view {
+                    ViewMode::Grid => self.show_grid_view(ui, ctx, &videos_to_show),
+                    ViewMode::List => {
+                        let output = egui::ScrollArea::vertical()
+                            .id_salt("video_list")
+                            .vertical_scroll_offset(self.list_scroll_offset)
+                            .show(ui, |ui| {
+                                self.show_list_view(ui, &videos_to_show);
+                            });
+                        self.list_scroll_offset = output.state.offset.y;
+                    }
+                }
+            }
+            
+            // Check for background click: if mouse was clicked but selection didn't change,
+            // it means user clicked on empty area
+            let left_clicked = ctx.input(|i| i.pointer.button_clicked(egui::PointerButton::Primary));
+            let double_clicked = ctx.input(|i| i.pointer.button_double_clicked(egui::PointerButton::Primary));
+            let selection_changed = selection_before != self.selected_video || 
+                                   selections_before != self.selected_videos;
+            
+            // Check if any popup, window, or context menu is open
+            let any_popup_open = self.any_popup_open();
+            
+            // Delete key opens the delete confirmation for the selection (like a file manager)
+            // Ignored while typing in a text field or when a window is open
+            if !any_popup_open && !ctx.wants_keyboard_input() && ctx.input(|i| i.key_pressed(egui::Key::Delete)) {
+                if self.selected_videos.len() > 1 {
+                    self.delete_confirm_video = Some("__MULTI__".to_string());
+                } else if let Some(video_id) = self.selected_video.clone()
+                    .or_else(|| self.selected_videos.iter().next().cloned())
+                {
+                    self.delete_confirm_video = Some(video_id);
+                }
+            }
+            
+            // Ctrl+A selects all filtered videos, Ctrl+Shift+A selects none
+            if !any_popup_open && !ctx.wants_keyboard_input() {
+                if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::A)) {
+                    self.clear_selection();
+                } else if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::A)) {
+                    let video_ids: Vec<String> = videos_to_show.iter().map(|v| v.id.clone()).collect();
+                    self.select_all_videos(video_ids);
+                }
+            }
+            
+            // Also check if something is being interacted with (context menu, etc.)
+            let is_using_pointer = ctx.is_using_pointer();
+            
+            // If clicked in this panel area and selection didn't change, clear selection
+            // But skip if any popup/window is open, context menu is active, double-clicked (playing video),
+            // or a thumbnail was clicked (even if selection didn't change)
+            if left_clicked && !double_clicked && !selection_changed && !any_popup_open && !is_using_pointer && !self.thumbnail_clicked_this_frame {
+                // User clicked somewhere but not on a thumbnail
+                // Check if click was in our panel area
+                let pointer_pos = ctx.input(|i| i.pointer.interact_pos());
+                if let Some(pos) = pointer_pos {
+                    let panel_rect = ui.min_rect();
+                    if panel_rect.contains(pos) {
+                        // Clicked in our panel but not on any thumbnail
+                        if self.selected_video.is_some() || !self.selected_videos.is_empty() {
+                            self.clear_selection();
+                        }
+                    }
+                }
+            }
+        });
+        
+        // Options window
+        let options_window_was_open = self.show_options_window;
+        let mut settings_changed = false;
+        let mut start_scene_batch = false;
+        let mut recompute_all_durations = false;
+        let mut switch_profile_to: Option<String> = None;
+        let mut clean_cache = false;
+        let mut export_csv = false;
+        let mut import_metadata = false;
+        let mut add_watched_folder = false;
+        let mut unwatch_folder: Option<PathBuf> = None;
+        let mut clear_all_cache = false;
+        let mut regenerate_thumbnails = false;
+        let mut rebuild_tag_rules = false;
+        let mut preview_tag_rules = false;
+        let mut apply_tag_rules = false;
+        
+        if self.show_options_window {
+            egui::Window::new(&self.i18n.t("options"))
+                .open(&mut self.show_options_window)
+                .resizable(true)
+                .default_width(450.0)
+                .default_height(400.0)
+                .show(ctx, |ui| {
+                    // Tab bar
+                    ui.horizontal(|ui| {
+                        if ui.selectable_label(self.selected_options_tab == OptionsTab::Display, &self.i18n.t("options_display")).clicked() {
+                            self.selected_options_tab = OptionsTab::Display;
+                        }
+                        ui.separator();
+                        if ui.selectable_label(self.selected_options_tab == OptionsTab::Player, &self.i18n.t("options_player")).clicked() {
+                            self.selected_options_tab = OptionsTab::Player;
+                        }
+                        ui.separator();
+                        if ui.selectable_label(self.selected_options_tab == OptionsTab::Data, &self.i18n.t("options_data")).clicked() {
+                            self.selected_options_tab = OptionsTab::Data;
+                        }
+                        ui.separator();
+                        if ui.selectable_label(self.selected_options_tab == OptionsTab::License, &self.i18n.t("options_license")).clicked() {
+                            self.selected_options_tab = OptionsTab::License;
+                        }
+                    });
+                    ui.separator();
+                    
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        match self.selected_options_tab {
+                            OptionsTab::Display => {
+                                // Display settings tab
+                                ui.heading(&self.i18n.t("display_settings"));
+                                ui.add_space(5.0);
+                                
+                                ui.add_enabled_ui(!self.fixed_grid_columns, |ui| {
+                                    ui.horizontal(|ui| {
+                                        ui.label(self.i18n.t("thumbnail_scale"));
+                                        if ui.add(egui::Slider::new(&mut self.thumbnail_scale, 0.5..=3.0)
+                                            .suffix("x")).changed() {
+                                            settings_changed = true;
+                                        }
+                                    });
+                                });
+                                
+                                // Fixed column count (thumbnails are scaled to fit)
+                                ui.horizontal(|ui| {
+                                    if ui.checkbox(&mut self.fixed_grid_columns, self.i18n.t("fixed_grid_columns")).changed() {
+                                        settings_changed = true;
+                                    }
+                                    if ui.add_enabled(self.fixed_grid_columns, egui::Slider::new(&mut self.grid_columns, 2..=10)).changed() {
+                                        settings_changed = true;
+                                    }
+                                });
+                                
+                                ui.label(format!("Current scale: {:.0}%", self.thumbnail_scale * 100.0));
+                                
+                                ui.add_space(10.0);
+                                
+                                if ui.checkbox(&mut self.show_full_filename, &self.i18n.t("show_full_filename")).changed() {
+                                    settings_changed = true;
+                                }
+                                if ui.checkbox(&mut self.show_tags_in_grid, &self.i18n.t("show_tags_in_grid")).changed() {
+                                    settings_changed = true;
+                                }
+                                
+                                if ui.checkbox(&mut self.show_recent_row, self.i18n.t("show_recent_row")).changed() {
+                                    settings_changed = true;
+                                }
+                                
+                                if ui.checkbox(&mut self.prune_hidden_selection, self.i18n.t("prune_hidden_selection")).changed() {
+                                    settings_changed = true;
+                                }
+                                
+                                if ui.checkbox(&mut self.show_quality_badge, self.i18n.t("show_quality_badge")).changed() {
+                                    settings_changed = true;
                                 }
                                 
-                                // Tags
-                                if !video.tags.is_empty() {
-                                    ui.horizontal(|ui| {
-                                        ui.label(egui::RichText::new("🏷").size(14.0));
-                                        let tags_text = self.i18n.t("tags_label").replace("{}", &video.tags.join(", "));
-                                        ui.label(&tags_text);
-                                    });
+                                if ui.checkbox(&mut self.show_duration_badge, self.i18n.t("show_duration_badge")).changed() {
+                                    settings_changed = true;
                                 }
                                 
-                                // Last played
-                                if let Some(last_played) = video.last_played {
-                                    ui.horizontal(|ui| {
-                                        ui.label(egui::RichText::new("▶").size(14.0));
-                                        let last_played_text = self.i18n.t("last_played_label").replace("{}", &last_played.format("%Y-%m-%d %H:%M").to_string());
-                                        ui.label(&last_played_text);
-                                    });
+                                ui.horizontal(|ui| {
+                                    if ui.checkbox(&mut self.pin_favorites_to_top, self.i18n.t("pin_favorites_to_top")).changed() {
+                                        settings_changed = true;
+                                    }
+                                    // Free tier only has favorites (rating 1), so the threshold is premium-only
+                                    if self.is_premium {
+                                        ui.add_enabled_ui(self.pin_favorites_to_top, |ui| {
+                                            if ui.add(egui::DragValue::new(&mut self.pin_min_rating).range(1..=5).suffix("★+")).changed() {
+                                                settings_changed = true;
+                                            }
+                                        });
+                                    }
+                                });
+                                
+                                if ui.checkbox(&mut self.auto_collapse_filters, self.i18n.t("auto_collapse_filters")).changed() {
+                                    settings_changed = true;
+                                }
+                                
+                                ui.horizontal(|ui| {
+                                    ui.label(self.i18n.t("max_texture_cache"));
+                                    if ui.add(egui::DragValue::new(&mut self.max_texture_cache).range(100..=5000)).changed() {
+                                        self.texture_cache.set_capacity(self.max_texture_cache);
+                                        settings_changed = true;
+                                    }
+                                });
+                                
+                                ui.horizontal(|ui| {
+                                    ui.label(self.i18n.t("max_concurrent_texture_loads"));
+                                    let max_loads = cpu_count().max(models::DEFAULT_MAX_CONCURRENT_TEXTURE_LOADS);
+                                    if ui.add(egui::DragValue::new(&mut self.max_concurrent_texture_loads).range(1..=max_loads)).changed() {
+                                        settings_changed = true;
+                                    }
+                                });
+                                
+                                ui.horizontal(|ui| {
+                                    ui.label(self.i18n.t("recent_days"));
+                                    if ui.add(egui::DragValue::new(&mut self.recent_days).range(1..=365)).changed() {
+                                        settings_changed = true;
+                                    }
+                                });
+                                
+                                ui.add_space(10.0);
+                                ui.separator();
+                                ui.heading(&self.i18n.t("theme"));
+                                ui.add_space(5.0);
+                                
+                                ui.horizontal(|ui| {
+                                    for (mode, key) in [(ThemeMode::System, "theme_system"), (ThemeMode::Light, "theme_light"), (ThemeMode::Dark, "theme_dark")] {
+                                        if ui.radio_value(&mut self.theme_mode, mode, self.i18n.t(key)).changed() {
+                                            settings_changed = true;
+                                        }
+                                    }
+                                });
+                                
+                                ui.horizontal(|ui| {
+                                    ui.label(self.i18n.t("accent_color"));
+                                    if ui.color_edit_button_srgb(&mut self.accent_color).changed() {
+                                        settings_changed = true;
+                                    }
+                                    if self.accent_color != models::default_accent_color() && ui.small_button(self.i18n.t("reset_to_default")).clicked() {
+                                        self.accent_color = models::default_accent_color();
+                                        settings_changed = true;
+                                    }
+                                });
+                                
+                                ui.add_space(10.0);
+                                ui.separator();
+                                ui.label(&self.i18n.t("language"));
+                                let current_language = self.i18n.get_language();
+                                for lang in self.i18n.available_languages() {
+                                    let name = self.i18n.language_name(&lang);
+                                    if ui.radio(current_language == lang, name).clicked() {
+                                        self.i18n.set_language(lang);
+                                        settings_changed = true;
+                                    }
                                 }
                             }
-                        });
-                        
-                        ui.add_space(5.0);
-                        
-                        // Star rating
-                        let has_multi_selection = !self.selected_videos.is_empty();
-                        ui.horizontal(|ui| {
-                            ui.label(&self.i18n.t("rating"));
-                            let current_rating = video.rating;
-                            for star in 1..=5 {
-                                let star_text = if star <= current_rating {
-                                    "★"
-                                } else {
-                                    "☆"
-                                };
-                                if ui.button(egui::RichText::new(star_text).size(24.0)).clicked() {
-                                    // Toggle: if clicking current rating, set to 0 (no rating)
-                                    let new_rating = if current_rating == star { 0 } else { star };
+                            
+                            OptionsTab::Player => {
+                                // Player settings tab
+                                ui.heading(&self.i18n.t("player_settings"));
+                                ui.add_space(5.0);
+                                
+                                if ui.checkbox(&mut self.mpv_always_on_top, &self.i18n.t("always_on_top")).changed() {
+                                    settings_changed = true;
+                                }
+                                
+                                if ui.checkbox(&mut self.loop_playback, self.i18n.t("loop_playback")).changed() {
+                                    settings_changed = true;
+                                }
+                                
+                                if ui.checkbox(&mut self.fullscreen_on_launch, self.i18n.t("fullscreen_on_launch")).changed() {
+                                    settings_changed = true;
+                                }
+                                
+                                if ui.checkbox(&mut self.mark_watched_on_play, self.i18n.t("mark_watched_on_play"))
+                                    .on_hover_text(self.i18n.t("mark_watched_on_play_hint"))
+                                    .changed()
+                                {
+                                    settings_changed = true;
+                                }
+                                
+                                if ui.checkbox(&mut self.hdr_colorspace_hint, self.i18n.t("hdr_colorspace_hint"))
+                                    .on_hover_text(self.i18n.t("hdr_colorspace_hint_tooltip"))
+                                    .changed()
+                                {
+                                    settings_changed = true;
+                                }
+                                
+                                // Preferred tracks (mpv language codes)
+                                ui.add_space(10.0);
+                                egui::Grid::new("track_preferences").num_columns(2).show(ui, |ui| {
+                                    ui.label(self.i18n.t("default_subtitle"));
+                                    if ui.add(egui::TextEdit::singleline(&mut self.default_subtitle).hint_text("ja,en / off").desired_width(120.0)).changed() {
+                                        settings_changed = true;
+                                    }
+                                    ui.end_row();
                                     
-                                    // Apply to all selected videos if multi-selection exists
-                                    if has_multi_selection {
-                                        self.set_rating_for_selected(new_rating);
+                                    ui.label(self.i18n.t("default_audio_lang"));
+                                    if ui.add(egui::TextEdit::singleline(&mut self.default_audio_lang).hint_text("jpn,eng").desired_width(120.0)).changed() {
+                                        settings_changed = true;
+                                    }
+                                    ui.end_row();
+                                });
+                                ui.label(egui::RichText::new(self.i18n.t("track_preferences_hint")).weak().small());
+                                
+                                // Screenshot folder
+                                ui.add_space(10.0);
+                                let screenshot_dir = self.screenshot_dir.clone().unwrap_or_else(video_player::default_screenshot_dir);
+                                ui.horizontal(|ui| {
+                                    ui.label(self.i18n.t("screenshot_dir"));
+                                    ui.label(egui::RichText::new(screenshot_dir.display().to_string()).monospace().small());
+                                });
+                                ui.horizontal(|ui| {
+                                    if ui.button(self.i18n.t("browse")).clicked() {
+                                        if let Some(folder) = FileDialog::new().pick_folder() {
+                                            self.screenshot_dir = Some(folder);
+                                            settings_changed = true;
+                                        }
+                                    }
+                                    if self.screenshot_dir.is_some() && ui.button(self.i18n.t("reset_to_default")).clicked() {
+                                        self.screenshot_dir = None;
+                                        settings_changed = true;
+                                    }
+                                    if ui.button(self.i18n.t("open_mpv_config_dir")).clicked() {
+                                        let _ = std::fs::create_dir_all(&screenshot_dir);
+                                        if let Err(e) = video_player::open_directory(&screenshot_dir) {
+                                            eprintln!("[mpv] Failed to open screenshot dir: {}", e);
+                                        }
+                                    }
+                                });
+                                
+                                // GPU settings - only show for premium users
+                                if self.is_premium {
+                                    ui.add_space(10.0);
+                                    ui.separator();
+                                    ui.label("GPU Rendering");
+                                    ui.add_space(5.0);
+                                    
+                                    if ui.checkbox(&mut self.use_gpu_hq, &self.i18n.t("use_gpu_hq")).changed() {
+                                        settings_changed = true;
+                                    }
+                                    ui.label("  GPU high-quality mode uses advanced shaders");
+                                    
+                                    ui.add_space(10.0);
+                                    
+                                    if ui.checkbox(&mut self.use_custom_shaders, &self.i18n.t("use_custom_shaders")).changed() {
+                                        settings_changed = true;
+                                    }
+                                    
+                                    // Button to open shader management window
+                                    if self.use_custom_shaders {
+                                        if ui.button(&self.i18n.t("manage_shaders")).clicked() {
+                                            self.show_shader_management_window = true;
+                                        }
+                                    } else {
+                                        ui.label("  Place .glsl shader files in mpv/glsl_shaders");
+                                    }
+                                    
+                                    ui.add_space(10.0);
+                                    ui.separator();
+                                    ui.label(self.i18n.t("scene_detection"));
+                                    ui.add_space(5.0);
+                                    
+                                    if ui.add(egui::Slider::new(&mut self.scene_threshold, 0.05..=0.9)
+                                        .text(self.i18n.t("scene_threshold"))
+                                        .fixed_decimals(2)).changed() {
+                                        settings_changed = true;
+                                    }
+                                    ui.label(format!("  {}", self.i18n.t("scene_threshold_hint")));
+                                    
+                                    if ui.add(egui::Slider::new(&mut self.max_scenes, 5..=500)
+                                        .text(self.i18n.t("max_scenes"))).changed() {
+                                        settings_changed = true;
+                                    }
+                                    
+                                    let max_threads = cpu_count().max(models::DEFAULT_SCENE_THREAD_COUNT);
+                                    if ui.add(egui::Slider::new(&mut self.scene_thread_count, 1..=max_threads)
+                                        .text(self.i18n.t("scene_thread_count"))).changed() {
+                                        settings_changed = true;
+                                    }
+                                    
+                                    if ui.add(egui::Slider::new(&mut self.similar_scene_threshold, 0.5..=1.0)
+                                        .text(self.i18n.t("similar_scene_threshold"))
+                                        .fixed_decimals(2)).changed() {
+                                        settings_changed = true;
+                                    }
+                                }
+                                
+                                // External tools (custom FFmpeg/FFprobe location)
+                                ui.add_space(10.0);
+                                ui.separator();
+                                ui.label(self.i18n.t("external_tools"));
+                                ui.add_space(5.0);
+                                
+                                let mut tool_path_change: Option<(bool, Option<PathBuf>)> = None; // (is_ffprobe, new path)
+                                egui::Grid::new("external_tools").num_columns(4).show(ui, |ui| {
+                                    for is_ffprobe in [false, true] {
+                                        let (label, current) = if is_ffprobe {
+                                            ("FFprobe", &self.ffprobe_path)
+                                        } else {
+                                            ("FFmpeg", &self.ffmpeg_path)
+                                        };
+                                        ui.label(label);
+                                        let shown = current.as_ref()
+                                            .map(|p| p.display().to_string())
+                                            .unwrap_or_else(|| self.i18n.t("tool_path_auto"));
+                                        ui.label(egui::RichText::new(shown).monospace().small());
+                                        if ui.button(self.i18n.t("browse")).clicked() {
+                                            if let Some(path) = FileDialog::new().pick_file() {
+                                                tool_path_change = Some((is_ffprobe, Some(path)));
+                                            }
+                                        }
+                                        if current.is_some() && ui.small_button(self.i18n.t("reset_to_default")).clicked() {
+                                            tool_path_change = Some((is_ffprobe, None));
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                                
+                                if let Some((is_ffprobe, path)) = tool_path_change {
+                                    match path {
+                                        Some(path) if !thumbnail::is_valid_tool_binary(&path) => {
+                                            self.external_tools_error = Some(self.i18n.t("invalid_tool_binary").replace("{}", &path.display().to_string()));
+                                        }
+                                        path => {
+                                            if is_ffprobe {
+                                                if path.is_some() {
+                                                    self.show_ffmpeg_missing_banner = false;
+                                                }
+                                                self.ffprobe_path = path;
+                                            } else {
+                                                self.ffmpeg_path = path;
+                                            }
+                                            self.external_tools_error = None;
+                                            thumbnail::set_tool_paths(self.ffmpeg_path.clone(), self.ffprobe_path.clone());
+                                            settings_changed = true;
+                                        }
                                     }
-                                    // Also apply to current video (selected_video)
-                                    self.set_rating(video_id, new_rating);
+                                }
+                                if let Some(ref error) = self.external_tools_error {
+                                    ui.label(egui::RichText::new(error).color(egui::Color32::from_rgb(255, 100, 100)));
+                                }
+                                ui.label(egui::RichText::new(self.i18n.t("external_tools_hint")).weak().small());
+                                
+                                ui.add_space(10.0);
+                                ui.separator();
+                                
+                                // Button to open/show MPV shortcuts panel
+                                if ui.button("Show MPV Shortcuts").clicked() {
+                                    self.mpv_shortcuts_open = true;
+                                    settings_changed = true;
                                 }
                             }
-                            if current_rating > 0 && ui.small_button("×").clicked() {
-                                if has_multi_selection {
-                                    self.set_rating_for_selected(0);
+                            
+                            OptionsTab::Data => {
+                                // Data management tab
+                                
+                                // Profile management section
+                                ui.heading(&self.i18n.t("profiles"));
+                                ui.add_space(5.0);
+                                
+                                // Current profile
+                                ui.horizontal(|ui| {
+                                    ui.label(format!("{}: ", self.i18n.t("current_profile")));
+                                    ui.label(egui::RichText::new(&self.current_profile).strong());
+                                });
+                                
+                                ui.add_space(10.0);
+                                
+                                // Profile list
+                                let mut delete_profile_name: Option<String> = None;
+                                
+                                // Determine which profile is selected (pending or current)
+                                let selected_profile = self.profile_switch_pending.clone().unwrap_or_else(|| self.current_profile.clone());
+                                
+                                egui::ScrollArea::vertical()
+                                    .max_height(120.0)
+                                    .show(ui, |ui| {
+                                        for (profile_name, video_count) in &self.available_profiles.clone() {
+                                            let is_selected = profile_name == &selected_profile;
+                                            let is_actual_current = profile_name == &self.current_profile;
+                                            let label = format!("{} ({})", profile_name, self.i18n.t("videos_count").replace("{}", &video_count.to_string()));
+                                            
+                                            ui.horizontal(|ui| {
+                                                // Use selectable_label for radio-like behavior
+                                                let response = ui.selectable_label(is_selected, &label);
+                                                if response.clicked() && !is_actual_current {
+                                                    eprintln!("[Profile] Clicked on: {} -> setting pending", profile_name);
+                                                    self.profile_switch_pending = Some(profile_name.clone());
+                                                }
+                                                
+                                                // Rename button (only for non-default profiles)
+                                                if profile_name != "default" {
+                                                    if ui.small_button("✏").on_hover_text(&self.i18n.t("rename_profile")).clicked() {
+                                                        self.profile_rename_target = Some(profile_name.clone());
+                                                        self.profile_rename_new_name = profile_name.clone();
+                                                    }
+                                                }
+                                                
+                                                // Delete button (only for non-current, non-default profiles)
+                                                if !is_actual_current && profile_name != "default" {
+                                                    if ui.small_button("🗑").on_hover_text(&self.i18n.t("delete_profile")).clicked() {
+                                                        delete_profile_name = Some(profile_name.clone());
+                                                    }
+                                                }
+                                            });
+                                        }
+                                    });
+                                
+                                // Handle profile delete
+                                if let Some(profile_name) = delete_profile_name {
+                                    self.profile_delete_confirm = Some(profile_name);
                                 }
-                                self.set_rating(video_id, 0);
-                            }
-                        });
-                        
-                        // Show multi-selection indicator
-                        if has_multi_selection {
-                            let count = self.selected_videos.len();
-                            ui.label(egui::RichText::new(
-                                format!("({} videos selected - rating applies to all)", count)
-                            ).small().weak());
-                        }
-                        
-                        ui.add_space(10.0);
-                        ui.separator();
-                        
-                        // Tag editing section
-                        ui.heading(&self.i18n.t("tags"));
-                        
-                        let video_tags = video.tags.clone();
-                        let video_id_for_tags = video_id.clone();
-                        
-                        ui.horizontal_wrapped(|ui| {
-                            ui.label(&self.i18n.t("tags_colon"));
-                            
-                            // Display existing tags with remove button
-                            for tag in &video_tags {
+                                
+                                ui.add_space(10.0);
+                                
+                                // New profile creation
                                 ui.horizontal(|ui| {
-                                    let _tag_label = ui.label(format!("#{}", tag));
-                                    if ui.small_button("×").clicked() {
-                                        self.remove_tag_from_video(&video_id_for_tags, tag);
+                                    ui.label(&self.i18n.t("new_profile"));
+                                    ui.text_edit_singleline(&mut self.new_profile_name)
+                                        .on_hover_text(&self.i18n.t("enter_profile_name"));
+                                    
+                                    if ui.button(&self.i18n.t("create_profile")).clicked() && !self.new_profile_name.is_empty() {
+                                        match database::create_profile(&self.new_profile_name) {
+                                            Ok(_) => {
+                                                self.profile_status_message = Some(self.i18n.t("profile_created"));
+                                                self.available_profiles = database::list_profiles().unwrap_or_default();
+                                                self.new_profile_name.clear();
+                                            }
+                                            Err(e) => {
+                                                self.profile_status_message = Some(format!("{}: {}", self.i18n.t("profile_create_failed"), e));
+                                            }
+                                        }
                                     }
                                 });
-                            }
-                            
-                            // Add tag button
-                            if ui.small_button("+").clicked() {
-                                self.show_tag_add_popup = true;
-                            }
-                        });
-                        
-                        // Tag addition popup
-                        if self.show_tag_add_popup {
-                            let screen_rect = ui.ctx().screen_rect();
-                            let popup_pos = egui::pos2(
-                                screen_rect.max.x - 350.0,  // 右から350px
-                                screen_rect.center().y - 150.0  // 中央より少し上
-                            );
-                            
-                            // Determine target videos (single or multiple selection)
-                            let target_videos: Vec<String> = if self.selected_videos.is_empty() {
-                                vec![video_id_for_tags.clone()]
-                            } else {
-                                // 複数選択時はselected_videoも含める
-                                let mut vids: Vec<String> = self.selected_videos.iter().cloned().collect();
-                                if let Some(sel) = &self.selected_video {
-                                    if !vids.contains(sel) {
-                                        vids.push(sel.clone());
-                                    }
+                                
+                                // Show profile status message
+                                if let Some(ref msg) = self.profile_status_message {
+                                    ui.add_space(5.0);
+                                    ui.label(msg);
                                 }
-                                vids
-                            };
-                            
-                            let is_multi = target_videos.len() > 1;
-                            let title = if is_multi {
-                                format!("{} ({} videos)", self.i18n.t("add_tag"), target_videos.len())
-                            } else {
-                                self.i18n.t("add_tag")
-                            };
-                            
-                            egui::Window::new(&title)
-                                .collapsible(false)
-                                .resizable(false)
-                                .default_pos(popup_pos)
-                                .show(ui.ctx(), |ui| {
-                                    ui.label(&self.i18n.t("select_or_create_tag"));
-                                    
+                                
+                                // Profile switch pending notice
+                                if let Some(ref pending_profile) = self.profile_switch_pending.clone() {
+                                    ui.add_space(10.0);
                                     ui.separator();
+                                    ui.add_space(5.0);
                                     
-                                    // Show existing tags in database
-                                    ui.label(&self.i18n.t("existing_tags"));
-                                    egui::ScrollArea::vertical()
-                                        .max_height(150.0)
-                                        .show(ui, |ui| {
-                                            let all_tags = self.database.tags.clone();
-                                            for tag in &all_tags {
-                                                // For multi-selection, show all tags
-                                                // For single selection, don't show tags already assigned
-                                                let should_show = if is_multi {
-                                                    true
-                                                } else {
-                                                    !video_tags.contains(tag)
-                                                };
-                                                
-                                                if should_show {
-                                                    if ui.button(format!("#{}", tag)).clicked() {
-                                                        // Add tag to all target videos
-                                                        for vid in &target_videos {
-                                                            self.add_tag_to_video(vid, tag.clone());
-                                                        }
-                                                        self.show_tag_add_popup = false;
-                                                    }
-                                                }
-                                            }
-                                        });
+                                    // Show which profile will be switched to
+                                    ui.horizontal(|ui| {
+                                        ui.label(&self.i18n.t("switch_to_profile"));
+                                        ui.label(egui::RichText::new(pending_profile.as_str()).strong().color(egui::Color32::LIGHT_BLUE));
+                                    });
                                     
+                                    ui.label(egui::RichText::new(&self.i18n.t("profile_switch_notice")).color(egui::Color32::YELLOW));
+                                    
+                                    ui.add_space(5.0);
+                                    ui.horizontal(|ui| {
+                                        if ui.button(&self.i18n.t("ok")).clicked() {
+                                            // Switched after the options window is drawn
+                                            switch_profile_to = Some(pending_profile.clone());
+                                        }
+                                        if ui.button(&self.i18n.t("cancel")).clicked() {
+                                            self.profile_switch_pending = None;
+                                        }
+                                    });
+                                }
+                                
+                                // Profile delete confirmation
+                                if let Some(ref delete_profile) = self.profile_delete_confirm.clone() {
+                                    ui.add_space(10.0);
                                     ui.separator();
+                                    ui.label(egui::RichText::new(&self.i18n.t("confirm_delete_profile")).color(egui::Color32::RED));
+                                    
+                                    // Show video count for the profile
+                                    if let Some((_, count)) = self.available_profiles.iter().find(|(n, _)| n == delete_profile) {
+                                        ui.label(self.i18n.t("profile_has_videos").replace("{}", &count.to_string()));
+                                    }
                                     
-                                    // Create new tag
-                                    ui.label(&self.i18n.t("create_new_tag"));
                                     ui.horizontal(|ui| {
-                                        ui.text_edit_singleline(&mut self.new_tag_input);
-                                        if ui.button(&self.i18n.t("create_tag")).clicked() && !self.new_tag_input.is_empty() {
-                                            let new_tag = self.new_tag_input.trim().to_string();
-                                            if !new_tag.is_empty() {
-                                                // Add tag to all target videos
-                                                for vid in &target_videos {
-                                                    self.add_tag_to_video(vid, new_tag.clone());
+                                        if ui.button(&self.i18n.t("yes_delete")).clicked() {
+                                            match database::delete_profile(delete_profile) {
+                                                Ok(_) => {
+                                                    self.profile_watched_folders.remove(delete_profile.as_str());
+                                                    self.profile_status_message = Some(self.i18n.t("profile_deleted"));
+                                                    self.available_profiles = database::list_profiles().unwrap_or_default();
+                                                }
+                                                Err(e) => {
+                                                    self.profile_status_message = Some(format!("{}: {}", self.i18n.t("profile_delete_failed"), e));
                                                 }
-                                                self.new_tag_input.clear();
-                                                self.show_tag_add_popup = false;
                                             }
+                                            self.profile_delete_confirm = None;
+                                        }
+                                        if ui.button(&self.i18n.t("cancel")).clicked() {
+                                            self.profile_delete_confirm = None;
                                         }
                                     });
-                                    
+                                }
+                                
+                                // Profile rename dialog
+                                if let Some(ref rename_profile) = self.profile_rename_target.clone() {
+                                    ui.add_space(10.0);
                                     ui.separator();
+                                    ui.add_space(5.0);
                                     
-                                    if ui.button(&self.i18n.t("cancel")).clicked() {
-                                        self.show_tag_add_popup = false;
-                                        self.new_tag_input.clear();
-                                    }
-                                });
-                        }
-                        
-                        ui.add_space(10.0);
-                        ui.separator();
-                        
-                        // Scene thumbnails section
-                        ui.horizontal(|ui| {
-                            ui.heading(&self.i18n.t("scene_thumbnails"));
-                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                                let video_id_for_refresh = video_id.clone();
-                                if ui.button(&self.i18n.t("refresh_scenes")).clicked() {
-                                    self.refresh_scenes(&video_id_for_refresh);
-                                }
-                            });
-                        });
-                        
-                        // Show selection controls if scenes are selected
-                        if !self.selected_scenes.is_empty() {
-                            ui.horizontal(|ui| {
-                                let selected_text = self.i18n.t("selected_count").replace("{}", &self.selected_scenes.len().to_string());
-                                ui.label(&selected_text);
-                                if ui.button(&self.i18n.t("clear_selection")).clicked() {
-                                    self.selected_scenes.clear();
-                                    self.last_selected_scene = None;
-                                }
-                                if ui.button(&self.i18n.t("delete_selected")).clicked() {
-                                    self.delete_selected_scenes(video_id);
-                                }
-                            });
-                        }
-                        
-                        ui.separator();
-                        
-                        // Show generate button if no scenes exist
-                        if video.scenes.is_empty() {
-                            ui.label(&self.i18n.t("no_scenes_yet"));
-                            // Free tier: show limit info
-                            if !self.is_premium {
-                                ui.label(&self.i18n.t("free_tier_scene_limit"));
-                            }
-                            if ui.button(&self.i18n.t("generate_scenes")).clicked() {
-                                self.generate_scenes(video_id);
-                            }
-                        } else {
-                            // Display scene thumbnails in a scrollable area
-                            egui::ScrollArea::vertical()
-                                .auto_shrink([false, false])
-                                .show(ui, |ui| {
-                                    let scenes = video.scenes.clone();
-                                    let video_path = video.path.clone();
-                                    let video_id_clone = video_id.clone();
+                                    ui.horizontal(|ui| {
+                                        ui.label(&self.i18n.t("rename_profile"));
+                                        ui.label(egui::RichText::new(rename_profile.as_str()).strong());
+                                    });
+                                    
+                                    ui.horizontal(|ui| {
+                                        ui.label(&self.i18n.t("new_name"));
+                                        ui.text_edit_singleline(&mut self.profile_rename_new_name);
+                                    });
                                     
-                                    for (scene_index, scene) in scenes.iter().enumerate() {
-                                        let is_selected = self.selected_scenes.contains(&scene_index);
+                                    ui.horizontal(|ui| {
+                                        let new_name = self.profile_rename_new_name.trim().to_string();
+                                        let can_rename = !new_name.is_empty() && new_name != *rename_profile;
                                         
-                                        ui.group(|ui| {
-                                            let thumbnail_size = egui::vec2(180.0, 101.0);
-                                            let (rect, response) = ui.allocate_exact_size(thumbnail_size, egui::Sense::click());
-                                            
-                                            // Check if this is a manually captured scene
-                                            let is_manual_scene = scene.thumbnail_path.to_string_lossy().contains("scene_manual_");
+                                        if ui.add_enabled(can_rename, egui::Button::new(&self.i18n.t("ok"))).clicked() {
+                                            // If renaming current profile, update settings
+                                            let is_current = rename_profile == &self.current_profile;
                                             
-                                            // Only load texture if visible (optimization)
-                                            if ui.is_rect_visible(rect) {
-                                                // Draw background for manually captured scenes (purple-pink color)
-                                                if is_manual_scene {
-                                                    ui.painter().rect_filled(
-                                                        rect.expand(3.0),
-                                                        6.0,
-                                                        egui::Color32::from_rgb(180, 100, 160) // Purple-pink
-                                                    );
-                                                }
-                                                
-                                                // Load and display actual thumbnail image
-                                                if let Some(texture) = self.load_image_texture(ctx, &scene.thumbnail_path) {
-                                                    // Draw thumbnail
-                                                    ui.put(rect, egui::Image::new(&texture).fit_to_exact_size(thumbnail_size));
+                                            match database::rename_profile(rename_profile, &new_name) {
+                                                Ok(_) => {
+                                                    if let Some(folders) = self.profile_watched_folders.remove(rename_profile.as_str()) {
+                                                        self.profile_watched_folders.insert(new_name.clone(), folders);
+                                                    }
+                                                    self.profile_status_message = Some(self.i18n.t("profile_renamed"));
+                                                    self.available_profiles = database::list_profiles().unwrap_or_default();
                                                     
-                                                    // Draw selection highlight
-                                                    if is_selected {
-                                                        ui.painter().rect_stroke(
-                                                            rect,
-                                                            4.0,
-                                                            egui::Stroke::new(3.0, egui::Color32::from_rgb(100, 200, 255))
-                                                        );
-                                                    } else if is_manual_scene {
-                                                        // Always show border for manual scenes
-                                                        ui.painter().rect_stroke(
-                                                            rect,
-                                                            4.0,
-                                                            egui::Stroke::new(2.0, egui::Color32::from_rgb(180, 100, 160))
-                                                        );
+                                                    // Update current profile name if it was renamed
+                                                    if is_current {
+                                                        // Keep database writes pointed at the renamed profile
+                                                        database::set_current_profile(&new_name);
+                                                        self.current_profile = new_name.clone();
+                                                        if let Ok(mut settings) = database::load_settings() {
+                                                            settings.current_profile = new_name.clone();
+                                                            let _ = database::save_settings(&settings);
+                                                        }
+                                                    }
+                                                    
+                                                    // Update pending switch if it was targeting the renamed profile
+                                                    if self.profile_switch_pending.as_ref() == Some(rename_profile) {
+                                                        self.profile_switch_pending = Some(new_name);
                                                     }
                                                 }
+                                                Err(e) => {
+                                                    self.profile_status_message = Some(format!("{}: {}", self.i18n.t("profile_rename_failed"), e));
+                                                }
                                             }
-                                            
-                                            // Handle click with modifiers (always handle regardless of visibility)
-                                            if response.clicked() {
-                                                let modifiers = ui.input(|i| i.modifiers.clone());
-                                                if modifiers.shift {
-                                                    // Shift+Click: range selection
-                                                    if let Some(last_idx) = self.last_selected_scene {
-                                                        self.select_scene_range(last_idx, scene_index);
-                                                    } else {
-                                                        self.selected_scenes.insert(scene_index);
+                                            self.profile_rename_target = None;
+                                            self.profile_rename_new_name.clear();
+                                        }
+                                        if ui.button(&self.i18n.t("cancel")).clicked() {
+                                            self.profile_rename_target = None;
+                                            self.profile_rename_new_name.clear();
+                                        }
+                                    });
+                                }
+                                
+                                ui.add_space(15.0);
+                                ui.separator();
+                                
+                                ui.heading(&self.i18n.t("management"));
+                                ui.add_space(5.0);
+                                
+                                // Button to open folder management window
+                                if ui.button(&self.i18n.t("manage_folders")).clicked() {
+                                    self.show_folder_management_window = true;
+                                }
+                                
+                                ui.add_space(5.0);
+                                
+                                // Button to open tag management window
+                                if ui.button(&self.i18n.t("manage_tags")).clicked() {
+                                    self.show_tag_management_window = true;
+                                }
+                                
+                                // Re-probe all durations with the accurate (slow) method
+                                ui.add_space(5.0);
+                                if ui.add_enabled(self.duration_probe_receiver.is_none(), egui::Button::new(self.i18n.t("recompute_durations")))
+                                    .on_hover_text(self.i18n.t("recompute_durations_hint"))
+                                    .clicked()
+                                {
+                                    recompute_all_durations = true;
+                                }
+                                
+                                // Export metadata for spreadsheets
+                                ui.add_space(5.0);
+                                ui.horizontal(|ui| {
+                                    if ui.button(self.i18n.t("export_csv")).clicked() {
+                                        export_csv = true;
+                                    }
+                                    ui.checkbox(&mut self.export_all_videos, self.i18n.t("export_all_videos"))
+                                        .on_hover_text(self.i18n.t("export_all_videos_hint"));
+                                });
+                                
+                                // Merge metadata from another machine
+                                ui.add_space(5.0);
+                                if ui.button(self.i18n.t("import_metadata"))
+                                    .on_hover_text(self.i18n.t("import_metadata_hint"))
+                                    .clicked()
+                                {
+                                    import_metadata = true;
+                                }
+                                
+                                // Generate scenes for all videos (premium)
+                                if self.is_premium {
+                                    ui.add_space(5.0);
+                                    let running = self.scene_batch_receiver.is_some();
+                                    if ui.add_enabled(!running, egui::Button::new(self.i18n.t("generate_all_scenes"))).clicked() {
+                                        start_scene_batch = true;
+                                    }
+                                }
+                                
+                                ui.add_space(15.0);
+                                ui.separator();
+                                ui.heading(self.i18n.t("watched_folders"));
+                                ui.add_space(5.0);
+                                
+                                if self.watched_folders.is_empty() {
+                                    ui.label(egui::RichText::new(self.i18n.t("no_watched_folders")).weak());
+                                } else {
+                                    let mut folders: Vec<&PathBuf> = self.watched_folders.iter().collect();
+                                    folders.sort();
+                                    egui::ScrollArea::vertical()
+                                        .id_salt("watched_folders")
+                                        .max_height(150.0)
+                                        .show(ui, |ui| {
+                                            for folder in folders {
+                                                ui.horizontal(|ui| {
+                                                    if ui.small_button("✕").on_hover_text(self.i18n.t("unwatch_folder_tooltip")).clicked() {
+                                                        unwatch_folder = Some(folder.clone());
                                                     }
-                                                    self.last_selected_scene = Some(scene_index);
-                                                } else if modifiers.ctrl {
-                                                    // Ctrl+Click: toggle selection
-                                                    self.toggle_scene_selection(scene_index);
-                                                    self.last_selected_scene = Some(scene_index);
-                                                } else {
-                                                    // Normal click: play video
-                                                    self.selected_scenes.clear();
-                                                    self.last_selected_scene = None;
-                                                    let selected_shader = self.selected_shader.as_deref();
-                                                    let use_gpu_hq = self.is_premium && self.use_gpu_hq;
-                                                    let use_custom_shaders = self.is_premium && self.use_custom_shaders;
-                                                    if let Err(e) = video_player::play_video_at_timestamp(&video_path, scene.timestamp, self.mpv_always_on_top, use_gpu_hq, use_custom_shaders, selected_shader, self.mpv_volume) {
-                                                        eprintln!("Video playback error: {}", e);
+                                                    let text = egui::RichText::new(folder.to_string_lossy());
+                                                    if folder.exists() {
+                                                        ui.label(text);
+                                                    } else {
+                                                        ui.label(text.strikethrough()).on_hover_text(self.i18n.t("folder_not_found"));
                                                     }
-                                                }
+                                                });
                                             }
-                                            
-                                            // Right-click menu for scene operations
-                                            let scene_ts = scene.timestamp;
-                                            response.context_menu(|ui| {
-                                                if ui.button(&self.i18n.t("play_from_scene")).clicked() {
-                                                    let selected_shader = self.selected_shader.as_deref();
-                                                    let use_gpu_hq = self.is_premium && self.use_gpu_hq;
-                                                    let use_custom_shaders = self.is_premium && self.use_custom_shaders;
-                                                    if let Err(e) = video_player::play_video_at_timestamp(&video_path, scene_ts, self.mpv_always_on_top, use_gpu_hq, use_custom_shaders, selected_shader, self.mpv_volume) {
-                                                        eprintln!("Video playback error: {}", e);
-                                                    }
-                                                    ui.close_menu();
-                                                }
-                                                
-                                                ui.separator();
-                                                
-                                                if ui.button(&self.i18n.t("delete_scene")).clicked() {
-                                                    self.delete_scene(&video_id_clone, scene_ts);
-                                                    ui.close_menu();
-                                                }
-                                            });
-                                            
-                                            response.on_hover_text(&self.i18n.t("click_play_ctrl_select"));
-                                            
-                                            // Timestamp label
-                                            ui.label(scene_detection::format_timestamp(scene.timestamp));
                                         });
-                                        
-                                        ui.add_space(5.0);
+                                }
+                                
+                                if ui.button(self.i18n.t("add_watched_folder")).clicked() {
+                                    add_watched_folder = true;
+                                }
+                                ui.label(egui::RichText::new(self.i18n.t("watched_folders_hint")).weak().small());
+                                
+                                ui.add_space(15.0);
+                                ui.separator();
+                                ui.heading(self.i18n.t("video_extensions"));
+                                ui.add_space(5.0);
+                                
+                                // Extensions picked up by scans, file dialogs, drag & drop and the folder watcher
+                                let mut remove_extension: Option<usize> = None;
+                                ui.horizontal_wrapped(|ui| {
+                                    for (index, ext) in self.video_extensions.iter().enumerate() {
+                                        // Keep at least one extension so scanning still finds something
+                                        let removable = self.video_extensions.len() > 1;
+                                        if ui.add_enabled(removable, egui::Button::new(format!(".{} ✕", ext)).small()).clicked() {
+                                            remove_extension = Some(index);
+                                        }
                                     }
-                                    
-                                    // Free tier: show premium promotion after scenes
-                                    if !self.is_premium {
-                                        ui.add_space(10.0);
-                                        ui.separator();
-                                        ui.vertical_centered(|ui| {
-                                            ui.label(egui::RichText::new(&self.i18n.t("free_tier_scene_limit_reached")).strong());
-                                            ui.add_space(5.0);
-                                            ui.label(&self.i18n.t("premium_unlimited_scenes"));
+                                });
+                                if let Some(index) = remove_extension {
+                                    self.video_extensions.remove(index);
+                                    settings_changed = true;
+                                }
+                                
+                                ui.horizontal(|ui| {
+                                    let response = ui.add(egui::TextEdit::singleline(&mut self.new_extension_input)
+                                        .hint_text("ts")
+                                        .desired_width(80.0));
+                                    let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                                    if ui.button(self.i18n.t("add_extension")).clicked() || submitted {
+                                        let ext = self.new_extension_input.trim().trim_start_matches('.').to_lowercase();
+                                        if !ext.is_empty() && !self.video_extensions.iter().any(|e| e.eq_ignore_ascii_case(&ext)) {
+                                            self.video_extensions.push(ext);
+                                            settings_changed = true;
+                                        }
+                                        self.new_extension_input.clear();
+                                    }
+                                    if ui.button(self.i18n.t("reset_extensions")).clicked() {
+                                        self.video_extensions = crate::models::default_video_extensions();
+                                        settings_changed = true;
+                                    }
+                                });
+                                ui.label(egui::RichText::new(self.i18n.t("video_extensions_hint")).weak().small());
+                                
+                                // Exclude patterns applied when scanning folders
+                                ui.add_space(10.0);
+                                ui.label(egui::RichText::new(self.i18n.t("exclude_patterns")).strong());
+                                let mut remove_pattern: Option<usize> = None;
+                                for (index, pattern) in self.exclude_patterns.iter().enumerate() {
+                                    ui.horizontal(|ui| {
+                                        if ui.small_button("✕").clicked() {
+                                            remove_pattern = Some(index);
+                                        }
+                                        ui.monospace(pattern);
+                                    });
+                                }
+                                if let Some(index) = remove_pattern {
+                                    self.exclude_patterns.remove(index);
+                                    settings_changed = true;
+                                }
+                                
+                                ui.horizontal(|ui| {
+                                    let response = ui.add(egui::TextEdit::singleline(&mut self.new_exclude_pattern_input)
+                                        .hint_text("*sample*")
+                                        .desired_width(200.0));
+                                    let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                                    if ui.button(self.i18n.t("add_extension")).clicked() || submitted {
+                                        let pattern = self.new_exclude_pattern_input.trim().to_string();
+                                        if !pattern.is_empty() && !self.exclude_patterns.contains(&pattern) {
+                                            self.exclude_patterns.push(pattern);
+                                            settings_changed = true;
+                                        }
+                                        self.new_exclude_pattern_input.clear();
+                                    }
+                                });
+                                ui.label(egui::RichText::new(self.i18n.t("exclude_patterns_hint")).weak().small());
+                                
+                                // Embedded title / recording date
+                                ui.add_space(10.0);
+                                if ui.checkbox(&mut self.use_embedded_metadata, self.i18n.t("use_embedded_metadata"))
+                                    .on_hover_text(self.i18n.t("use_embedded_metadata_hint"))
+                                    .changed()
+                                {
+                                    video_scanner::set_use_embedded_metadata(self.use_embedded_metadata);
+                                    settings_changed = true;
+                                }
+                                
+                                if ui.checkbox(&mut self.auto_tag_from_folder, self.i18n.t("auto_tag_from_folder"))
+                                    .on_hover_text(self.i18n.t("auto_tag_from_folder_hint"))
+                                    .changed()
+                                {
+                                    settings_changed = true;
+                                }
+                                
+                                // Regex auto-tagging rules
+                                ui.add_space(10.0);
+                                ui.label(egui::RichText::new(self.i18n.t("tag_rules")).strong());
+                                let mut rules_changed = false;
+                                let mut remove_rule: Option<usize> = None;
+                                egui::Grid::new("tag_rules_grid").num_columns(3).show(ui, |ui| {
+                                    ui.label(self.i18n.t("tag_rule_pattern"));
+                                    ui.label(self.i18n.t("tag_rule_tag"));
+                                    ui.end_row();
+                                    for (index, rule) in self.tag_rules.iter_mut().enumerate() {
+                                        let pattern_edit = ui.add(egui::TextEdit::singleline(&mut rule.pattern)
+                                            .desired_width(200.0)
+                                            .font(egui::TextStyle::Monospace)
+                                            .hint_text("(\\d{4})-\\d{2}-\\d{2}"));
+                                        let tag_edit = ui.add(egui::TextEdit::singleline(&mut rule.tag)
+                                            .desired_width(120.0)
+                                            .hint_text("$1"));
+                                        rules_changed |= pattern_edit.changed() || tag_edit.changed();
+                                        if ui.small_button("✕").clicked() {
+                                            remove_rule = Some(index);
+                                        }
+                                        ui.end_row();
+                                        if let Some(Some(error)) = self.tag_rule_errors.get(index) {
+                                            ui.label(egui::RichText::new(format!("⚠ {}", self.i18n.t("tag_rule_invalid")))
+                                                .color(egui::Color32::RED)
+                                                .small())
+                                                .on_hover_text(error);
+                                            ui.end_row();
+                                        }
+                                    }
+                                });
+                                if let Some(index) = remove_rule {
+                                    self.tag_rules.remove(index);
+                                    rules_changed = true;
+                                }
+                                ui.horizontal(|ui| {
+                                    if ui.button(self.i18n.t("add_tag_rule")).clicked() {
+                                        self.tag_rules.push(models::TagRule::default());
+                                        rules_changed = true;
+                                    }
+                                    if ui.add_enabled(!self.compiled_tag_rules.is_empty(), egui::Button::new(self.i18n.t("apply_tag_rules_to_all"))).clicked() {
+                                        preview_tag_rules = true;
+                                    }
+                                });
+                                if rules_changed {
+                                    rebuild_tag_rules = true;
+                                    settings_changed = true;
+                                }
+                                if let Some((videos, tags)) = self.tag_rules_preview {
+                                    ui.horizontal(|ui| {
+                                        ui.label(self.i18n.t("tag_rules_preview")
+                                            .replace("{videos}", &videos.to_string())
+                                            .replace("{tags}", &tags.to_string()));
+                                        if videos > 0 && ui.button(self.i18n.t("apply")).clicked() {
+                                            apply_tag_rules = true;
+                                        }
+                                        if ui.button(self.i18n.t("cancel")).clicked() {
+                                            self.tag_rules_preview = None;
+                                        }
+                                    });
+                                }
+                                ui.label(egui::RichText::new(self.i18n.t("tag_rules_hint")).weak().small());
+                                
+                                // Quiet time before the folder watcher rescans
+                                ui.horizontal(|ui| {
+                                    ui.label(self.i18n.t("rescan_debounce"));
+                                    if ui.add(egui::DragValue::new(&mut self.rescan_debounce_secs).range(1..=60).suffix(" s")).changed() {
+                                        settings_changed = true;
+                                    }
+                                }).response.on_hover_text(self.i18n.t("rescan_debounce_hint"));
+                                
+                                ui.add_space(15.0);
+                                ui.separator();
+                                ui.heading(self.i18n.t("thumbnail_cache"));
+                                ui.add_space(5.0);
+                                
+                                // Size and quality of newly generated thumbnails
+                                ui.horizontal(|ui| {
+                                    ui.label(self.i18n.t("thumbnail_max_width"));
+                                    if ui.add(egui::DragValue::new(&mut self.thumbnail_max_width).range(160..=1920).suffix(" px")).changed() {
+                                        thumbnail::set_thumbnail_options(self.thumbnail_max_width, self.thumbnail_quality);
+                                        settings_changed = true;
+                                    }
+                                });
+                                ui.horizontal(|ui| {
+                                    ui.label(self.i18n.t("thumbnail_quality"));
+                                    if ui.add(egui::DragValue::new(&mut self.thumbnail_quality).range(2..=31)).changed() {
+                                        thumbnail::set_thumbnail_options(self.thumbnail_max_width, self.thumbnail_quality);
+                                        settings_changed = true;
+                                    }
+                                });
+                                ui.label(egui::RichText::new(self.i18n.t("thumbnail_quality_hint")).weak().small());
+                                if ui.add_enabled(self.scene_batch_receiver.is_none(), egui::Button::new(self.i18n.t("regenerate_thumbnails")))
+                                    .on_hover_text(self.i18n.t("regenerate_thumbnails_hint"))
+                                    .clicked()
+                                {
+                                    regenerate_thumbnails = true;
+                                }
+                                ui.add_space(5.0);
+                                
+                                if ui.checkbox(&mut self.thumbnail_eviction_enabled, self.i18n.t("thumbnail_eviction_enabled")).changed() {
+                                    settings_changed = true;
+                                }
+                                ui.add_enabled_ui(self.thumbnail_eviction_enabled, |ui| {
+                                    ui.horizontal(|ui| {
+                                        ui.label(self.i18n.t("thumbnail_eviction_days"));
+                                        if ui.add(egui::DragValue::new(&mut self.thumbnail_eviction_days).range(1..=3650)).changed() {
+                                            settings_changed = true;
+                                        }
+                                    });
+                                    ui.horizontal(|ui| {
+                                        ui.label(self.i18n.t("thumbnail_cache_budget"));
+                                        if ui.add(egui::DragValue::new(&mut self.thumbnail_cache_budget_mb).range(50..=102400).suffix(" MB")).changed() {
+                                            settings_changed = true;
+                                        }
+                                    });
+                                    ui.label(egui::RichText::new(self.i18n.t("thumbnail_eviction_hint")).weak().small());
+                                });
+                                
+                                // Manual cleanup (disabled while scenes are being generated)
+                                ui.add_space(5.0);
+                                ui.horizontal(|ui| {
+                                    let idle = self.scene_batch_receiver.is_none();
+                                    if ui.add_enabled(idle, egui::Button::new(self.i18n.t("clean_cache")))
+                                        .on_hover_text(self.i18n.t("clean_cache_hint"))
+                                        .clicked()
+                                    {
+                                        clean_cache = true;
+                                    }
+                                    if ui.add_enabled(idle, egui::Button::new(self.i18n.t("clear_all_cache"))).clicked() {
+                                        self.clear_cache_confirm = true;
+                                    }
+                                });
+                                
+                                if self.clear_cache_confirm {
+                                    ui.label(egui::RichText::new(self.i18n.t("confirm_clear_all_cache")).color(egui::Color32::RED));
+                                    ui.horizontal(|ui| {
+                                        if ui.button(self.i18n.t("yes_delete")).clicked() {
+                                            clear_all_cache = true;
+                                            self.clear_cache_confirm = false;
+                                        }
+                                        if ui.button(self.i18n.t("cancel")).clicked() {
+                                            self.clear_cache_confirm = false;
+                                        }
+                                    });
+                                }
+                                
+                                if let Some(ref msg) = self.cache_status_message {
+                                    ui.label(msg);
+                                }
+                                
+                                ui.add_space(15.0);
+                                ui.separator();
+                                ui.heading(&self.i18n.t("backup"));
+                                ui.add_space(5.0);
+                                
+                                // Show last backup date
+                                if let Ok(settings) = database::load_settings() {
+                                    if let Some(ref last_backup) = settings.last_backup_date {
+                                        if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(last_backup) {
+                                            // Convert to local timezone
+                                            let local_dt = dt.with_timezone(&chrono::Local);
+                                            let formatted = local_dt.format("%Y/%m/%d %H:%M:%S").to_string();
+                                            ui.label(format!("{}: {}", self.i18n.t("last_backup"), formatted));
                                             ui.add_space(5.0);
-                                            if ui.button(&self.i18n.t("purchase_premium")).clicked() {
-                                                #[cfg(target_os = "windows")]
-                                                {
-                                                    let _ = std::process::Command::new("cmd")
-                                                        .args(["/C", "start", "", "https://tetdarth.gumroad.com/l/jmjty"])
-                                                        .spawn();
-                                                }
-                                                #[cfg(target_os = "macos")]
-                                                {
-                                                    let _ = std::process::Command::new("open")
-                                                        .arg("https://tetdarth.gumroad.com/l/jmjty")
-                                                        .spawn();
-                                                }
-                                                #[cfg(target_os = "linux")]
-                                                {
-                                                    let _ = std::process::Command::new("xdg-open")
-                                                        .arg("https://tetdarth.gumroad.com/l/jmjty")
-                                                        .spawn();
-                                                }
-                                            }
-                                        });
+                                        }
+                                    }
+                                }
+                                
+                                // Number of backups to keep
+                                ui.horizontal(|ui| {
+                                    ui.label(self.i18n.t("backups_to_keep"));
+                                    if ui.add(egui::DragValue::new(&mut self.backups_to_keep).range(0..=100)).changed() {
+                                        settings_changed = true;
+                                    }
+                                    if self.backups_to_keep == 0 {
+                                        ui.label(egui::RichText::new(self.i18n.t("unlimited")).weak());
                                     }
                                 });
-                        }
-                    }
-                } else {
-                    // No video selected
-                    ui.vertical_centered(|ui| {
-                        ui.add_space(50.0);
-                        ui.heading(&self.i18n.t("selected_video"));
-                        ui.add_space(10.0);
-                        ui.label(&self.i18n.t("no_video_selected"));
-                    });
-                }
-            });
-        }
-        
-        // Main content area
-        egui::CentralPanel::default().show(ctx, |ui| {
-            // Search bar with sort buttons
-            ui.horizontal(|ui| {
-                ui.label("Search:");
-                ui.text_edit_singleline(&mut self.search_query);
-                
-                ui.separator();
-                ui.label(self.i18n.t("sort"));
-                
-                // 作成日時ボタン
-                let added_date_text = match self.sort_field {
-                    SortField::AddedDate => {
-                        if self.sort_order == SortOrder::Ascending {
-                            self.i18n.t("sort_added_date_asc")
-                        } else {
-                            self.i18n.t("sort_added_date_desc")
-                        }
-                    }
-                    _ => self.i18n.t("sort_added_date")
-                };
-                if ui.button(added_date_text).clicked() {
-                    if self.sort_field == SortField::AddedDate {
-                        // 同じフィールドの場合は昇順降順を切り替え
-                        self.sort_order = if self.sort_order == SortOrder::Ascending {
-                            SortOrder::Descending
-                        } else {
-                            SortOrder::Ascending
-                        };
-                    } else {
-                        // 違うフィールドの場合は昇順で開始
-                        self.sort_field = SortField::AddedDate;
-                        self.sort_order = SortOrder::Ascending;
-                    }
-                }
-                
-                // ファイル名ボタン
-                let file_name_text = match self.sort_field {
-                    SortField::FileName => {
-                        if self.sort_order == SortOrder::Ascending {
-                            self.i18n.t("sort_filename_asc")
-                        } else {
-                            self.i18n.t("sort_filename_desc")
-                        }
-                    }
-                    _ => self.i18n.t("sort_filename")
-                };
-                if ui.button(file_name_text).clicked() {
-                    if self.sort_field == SortField::FileName {
-                        self.sort_order = if self.sort_order == SortOrder::Ascending {
-                            SortOrder::Descending
-                        } else {
-                            SortOrder::Ascending
-                        };
-                    } else {
-                        self.sort_field = SortField::FileName;
-                        self.sort_order = SortOrder::Ascending;
-                    }
-                }
-                
-                // 動画時間ボタン
-                let duration_text = match self.sort_field {
-                    SortField::Duration => {
-                        if self.sort_order == SortOrder::Ascending {
-                            self.i18n.t("sort_duration_asc")
-                        } else {
-                            self.i18n.t("sort_duration_desc")
-                        }
-                    }
-                    _ => self.i18n.t("sort_duration")
-                };
-                if ui.button(duration_text).clicked() {
-                    if self.sort_field == SortField::Duration {
-                        self.sort_order = if self.sort_order == SortOrder::Ascending {
-                            SortOrder::Descending
-                        } else {
-                            SortOrder::Ascending
-                        };
-                    } else {
-                        self.sort_field = SortField::Duration;
-                        self.sort_order = SortOrder::Ascending;
-                    }
-                }
-            });
-            
-            ui.separator();
-            
-            // Display video list
-            let current_view = self.current_view.clone();
-            let videos_to_show: Vec<VideoFile> = self.get_filtered_videos()
-                .into_iter()
-                .cloned()
-                .collect();
-            
-            // Remember the selection state BEFORE drawing UI
-            let selection_before = self.selected_video.clone();
-            let selections_before = self.selected_videos.clone();
-            
-            // Create a scrollable area for content
-            egui::ScrollArea::vertical().show(ui, |ui| {
-                match current_view {
-                    ViewMode::Grid => self.show_grid_view(ui, ctx, &videos_to_show),
-                    ViewMode::List => self.show_list_view(ui, &videos_to_show),
-                }
-            });
-            
-            // Check for background click: if mouse was clicked but selection didn't change,
-            // it means user clicked on empty area
-            let left_clicked = ctx.input(|i| i.pointer.button_clicked(egui::PointerButton::Primary));
-            let double_clicked = ctx.input(|i| i.pointer.button_double_clicked(egui::PointerButton::Primary));
-            let selection_changed = selection_before != self.selected_video || 
-                                   selections_before != self.selected_videos;
-            
-            // Check if any popup, window, or context menu is open
-            let any_popup_open = self.show_options_window || 
-                                self.show_folder_management_window || 
-                                self.show_tag_management_window ||
-                                self.show_shader_management_window ||
-                                self.show_license_window ||
-                                self.show_premium_promotion_window ||
-                                self.show_tag_add_popup ||
-                                self.delete_confirm_video.is_some() ||
-                                self.folder_delete_confirm.is_some() ||
-                                self.tag_delete_confirm.is_some();
-            
-            // Also check if something is being interacted with (context menu, etc.)
-            let is_using_pointer = ctx.is_using_pointer();
-            
-            // If clicked in this panel area and selection didn't change, clear selection
-            // But skip if any popup/window is open, context menu is active, double-clicked (playing video),
-            // or a thumbnail was clicked (even if selection didn't change)
-            if left_clicked && !double_clicked && !selection_changed && !any_popup_open && !is_using_pointer && !self.thumbnail_clicked_this_frame {
-                // User clicked somewhere but not on a thumbnail
-                // Check if click was in our panel area
-                let pointer_pos = ctx.input(|i| i.pointer.interact_pos());
-                if let Some(pos) = pointer_pos {
-                    let panel_rect = ui.min_rect();
-                    if panel_rect.contains(pos) {
-                        // Clicked in our panel but not on any thumbnail
-                        if self.selected_video.is_some() || !self.selected_videos.is_empty() {
-                            self.clear_selection();
-                        }
-                    }
-                }
-            }
-        });
-        
-        // Options window
-        let options_window_was_open = self.show_options_window;
-        let mut settings_changed = false;
-        
-        if self.show_options_window {
-            egui::Window::new(&self.i18n.t("options"))
-                .open(&mut self.show_options_window)
-                .resizable(true)
-                .default_width(450.0)
-                .default_height(400.0)
-                .show(ctx, |ui| {
-                    // Tab bar
-                    ui.horizontal(|ui| {
-                        if ui.selectable_label(self.selected_options_tab == OptionsTab::Display, &self.i18n.t("options_display")).clicked() {
-                            self.selected_options_tab = OptionsTab::Display;
-                        }
-                        ui.separator();
-                        if ui.selectable_label(self.selected_options_tab == OptionsTab::Player, &self.i18n.t("options_player")).clicked() {
-                            self.selected_options_tab = OptionsTab::Player;
-                        }
-                        ui.separator();
-                        if ui.selectable_label(self.selected_options_tab == OptionsTab::Data, &self.i18n.t("options_data")).clicked() {
-                            self.selected_options_tab = OptionsTab::Data;
-                        }
-                        ui.separator();
-                        if ui.selectable_label(self.selected_options_tab == OptionsTab::License, &self.i18n.t("options_license")).clicked() {
-                            self.selected_options_tab = OptionsTab::License;
-                        }
-                    });
-                    ui.separator();
-                    
-                    egui::ScrollArea::vertical().show(ui, |ui| {
-                        match self.selected_options_tab {
-                            OptionsTab::Display => {
-                                // Display settings tab
-                                ui.heading(&self.i18n.t("display_settings"));
+                                
+                                // Automatic backup interval
+                                ui.horizontal(|ui| {
+                                    ui.label(self.i18n.t("backup_interval_days"));
+                                    if ui.add(egui::DragValue::new(&mut self.backup_interval_days).range(1..=365)).changed() {
+                                        settings_changed = true;
+                                    }
+                                });
+                                ui.add_space(5.0);
+                                
+                                // Create backup button
+                                if ui.button(&self.i18n.t("create_backup")).clicked() {
+                                    match database::create_backup(self.backups_to_keep) {
+                                        Ok(path) => {
+                                            // Update last backup date in settings
+                                            if let Ok(mut settings) = database::load_settings() {
+                                                settings.last_backup_date = Some(chrono::Utc::now().to_rfc3339());
+                                                let _ = database::save_settings(&settings);
+                                            }
+                                            self.backup_status_message = Some(format!("{}: {:?}", self.i18n.t("backup_created"), path));
+                                        }
+                                        Err(e) => {
+                                            self.backup_status_message = Some(format!("{}: {}", self.i18n.t("backup_failed"), e));
+                                        }
+                                    }
+                                }
+                                
                                 ui.add_space(5.0);
                                 
-                                ui.horizontal(|ui| {
-                                    ui.label(&self.i18n.t("thumbnail_scale"));
-                                    if ui.add(egui::Slider::new(&mut self.thumbnail_scale, 0.5..=3.0)
-                                        .suffix("x")).changed() {
-                                        settings_changed = true;
+                                // Restore from backup button
+                                if ui.button(&self.i18n.t("restore_from_backup")).clicked() {
+                                    // Load available backups
+                                    match database::list_backups() {
+                                        Ok(backups) => {
+                                            self.available_backups = backups;
+                                            self.show_backup_restore_window = true;
+                                        }
+                                        Err(e) => {
+                                            self.backup_status_message = Some(format!("{}: {}", self.i18n.t("restore_failed"), e));
+                                        }
                                     }
-                                });
+                                }
                                 
-                                ui.label(format!("Current scale: {:.0}%", self.thumbnail_scale * 100.0));
+                                // Show backup status message
+                                if let Some(ref msg) = self.backup_status_message {
+                                    ui.add_space(5.0);
+                                    ui.label(msg);
+                                }
                                 
-                                ui.add_space(10.0);
+                                ui.add_space(15.0);
+                                ui.separator();
+                                ui.heading(self.i18n.t("protected_videos"));
+                                ui.add_space(5.0);
                                 
-                                if ui.checkbox(&mut self.show_full_filename, &self.i18n.t("show_full_filename")).changed() {
-                                    settings_changed = true;
+                                // The PIN can only be changed while protected videos are unlocked
+                                let can_change_pin = self.protection_pin_hash.is_none() || self.protected_unlocked;
+                                if can_change_pin {
+                                    ui.horizontal(|ui| {
+                                        ui.label(self.i18n.t("protection_pin"));
+                                        ui.add(egui::TextEdit::singleline(&mut self.pin_input).password(true).desired_width(120.0));
+                                        if ui.button(self.i18n.t("set_pin")).clicked() && !self.pin_input.trim().is_empty() {
+                                            self.protection_pin_hash = Some(Self::salted_pin_hash(&mut self.protection_pin_salt, self.pin_input.trim()));
+                                            self.pin_input.clear();
+                                            self.pin_status_message = Some(self.i18n.t("pin_saved"));
+                                            settings_changed = true;
+                                        }
+                                    });
+                                } else {
+                                    ui.label(self.i18n.t("unlock_to_change_pin"));
                                 }
-                                if ui.checkbox(&mut self.show_tags_in_grid, &self.i18n.t("show_tags_in_grid")).changed() {
-                                    settings_changed = true;
+                                
+                                if let Some(ref message) = self.pin_status_message {
+                                    ui.label(message);
                                 }
                                 
-                                ui.add_space(10.0);
+                                ui.add_space(15.0);
                                 ui.separator();
-                                ui.heading(&self.i18n.t("theme"));
+                                ui.heading(&self.i18n.t("reset_to_default"));
                                 ui.add_space(5.0);
                                 
-                                if ui.checkbox(&mut self.dark_mode, &self.i18n.t("dark_mode")).changed() {
+                                if ui.button(&self.i18n.t("reset_to_default")).clicked() {
+                                    self.thumbnail_scale = 1.0;
+                                    self.mpv_always_on_top = true;
+                                    self.show_full_filename = false;
+                                    self.show_tags_in_grid = true;
+                                    self.theme_mode = ThemeMode::System;
+                                    self.accent_color = models::default_accent_color();
+                                    self.use_gpu_hq = false;
+                                    self.use_custom_shaders = false;
+                                    self.selected_shader = None;
                                     settings_changed = true;
                                 }
-                                
-                                ui.add_space(10.0);
-                                ui.separator();
-                                ui.label(&self.i18n.t("language"));
-                                let current_language = self.i18n.get_language();
-                                for lang in Language::all() {
-                                    if ui.radio(current_language == lang, lang.name()).clicked() {
-                                        self.i18n.set_language(lang);
-                                        settings_changed = true;
-                                    }
-                                }
                             }
                             
-                            OptionsTab::Player => {
-                                // Player settings tab
-                                ui.heading(&self.i18n.t("player_settings"));
+                            OptionsTab::License => {
+                                // License section
+                                ui.heading("Premium License");
                                 ui.add_space(5.0);
                                 
-                                if ui.checkbox(&mut self.mpv_always_on_top, &self.i18n.t("always_on_top")).changed() {
-                                    settings_changed = true;
-                                }
-                                
-                                // GPU settings - only show for premium users
-                                if self.is_premium {
-                                    ui.add_space(10.0);
-                                    ui.separator();
-                                    ui.label("GPU Rendering");
-                                    ui.add_space(5.0);
+                                if let Some(ref license) = self.current_license {
+                                    // Show license info
+                                    ui.horizontal(|ui| {
+                                        ui.label(&self.i18n.t("issued_to"));
+                                        ui.label(&license.info.issued_to);
+                                    });
                                     
-                                    if ui.checkbox(&mut self.use_gpu_hq, &self.i18n.t("use_gpu_hq")).changed() {
-                                        settings_changed = true;
+                                    ui.horizontal(|ui| {
+                                        ui.label(&self.i18n.t("expires"));
+                                        let expires_text = license.info.expires_at
+                                            .map(|ts| license::format_timestamp(ts))
+                                            .unwrap_or_else(|| self.i18n.t("never_expires"));
+                                        ui.label(&expires_text);
+                                    });
+                                    
+                                    ui.horizontal(|ui| {
+                                        ui.label(&self.i18n.t("license_status"));
+                                        if license.is_expired {
+                                            ui.label(egui::RichText::new("❌ Expired").color(egui::Color32::RED));
+                                        } else if license.is_valid {
+                                            ui.label(egui::RichText::new("✅ Active").color(egui::Color32::GREEN));
+                                        } else {
+                                            ui.label(egui::RichText::new("❌ Invalid").color(egui::Color32::RED));
+                                        }
+                                    });
+                                    
+                                    if license.info.expires_at.is_some() {
+                                        ui.horizontal(|ui| {
+                                            ui.label(self.i18n.t("license_expiry_warning_days"));
+                                            if ui.add(egui::DragValue::new(&mut self.license_expiry_warning_days).range(0..=90)).changed() {
+                                                self.license_expiry_banner_dismissed = false;
+                                                settings_changed = true;
+                                            }
+                                        });
                                     }
-                                    ui.label("  GPU high-quality mode uses advanced shaders");
                                     
                                     ui.add_space(10.0);
+                                    ui.horizontal(|ui| {
+                                        if ui.button(self.i18n.t("enter_license_key")).clicked() {
+                                            self.show_license_window = true;
+                                        }
+                                        if ui.button(self.i18n.t("deactivate_license")).clicked() {
+                                            self.show_deactivate_license_confirm = true;
+                                        }
+                                    });
+                                } else {
+                                    // Premium promotion for free users
+                                    ui.label(egui::RichText::new(&self.i18n.t("premium_benefits_title")).strong());
+                                    ui.add_space(5.0);
                                     
-                                    if ui.checkbox(&mut self.use_custom_shaders, &self.i18n.t("use_custom_shaders")).changed() {
-                                        settings_changed = true;
-                                    }
+                                    ui.label(&self.i18n.t("premium_benefit_1")); // 5つ星レーティング
+                                    ui.label(&self.i18n.t("premium_benefit_2")); // 複数タグ/フォルダ選択
+                                    ui.label(&self.i18n.t("premium_benefit_3")); // GPU高画質レンダリング
+                                    ui.label(&self.i18n.t("premium_benefit_4")); // カスタムシェーダー
+                                    ui.label(&self.i18n.t("premium_benefit_5")); // 無制限の動画プロファイル
                                     
-                                    // Button to open shader management window
-                                    if self.use_custom_shaders {
-                                        if ui.button(&self.i18n.t("manage_shaders")).clicked() {
-                                            self.show_shader_management_window = true;
+                                    ui.add_space(15.0);
+                                    
+                                    ui.horizontal(|ui| {
+                                        if ui.button(&self.i18n.t("purchase_premium")).clicked() {
+                                            // Open purchase page using platform-specific command
+                                            #[cfg(target_os = "windows")]
+                                            {
+                                                let _ = std::process::Command::new("cmd")
+                                                    .args(["/C", "start", "", "https://tetdarth.gumroad.com/l/jmjty"])
+                                                    .spawn();
+                                            }
+                                            #[cfg(target_os = "macos")]
+                                            {
+                                                let _ = std::process::Command::new("open")
+                                                    .arg("https://tetdarth.gumroad.com/l/jmjty")
+                                                    .spawn();
+                                            }
+                                            #[cfg(target_os = "linux")]
+                                            {
+                                                let _ = std::process::Command::new("xdg-open")
+                                                    .arg("https://tetdarth.gumroad.com/l/jmjty")
+                                                    .spawn();
+                                            }
                                         }
-                                    } else {
-                                        ui.label("  Place .glsl shader files in mpv/glsl_shaders");
-                                    }
-                                }
-                                
-                                ui.add_space(10.0);
-                                ui.separator();
-                                
-                                // Button to open/show MPV shortcuts panel
-                                if ui.button("Show MPV Shortcuts").clicked() {
-                                    self.mpv_shortcuts_open = true;
-                                    settings_changed = true;
-                                }
-                            }
-                            
-                            OptionsTab::Data => {
-                                // Data management tab
-                                
-                                // Profile management section
-                                ui.heading(&self.i18n.t("profiles"));
-                                ui.add_space(5.0);
-                                
-                                // Current profile
-                                ui.horizontal(|ui| {
-                                    ui.label(format!("{}: ", self.i18n.t("current_profile")));
-                                    ui.label(egui::RichText::new(&self.current_profile).strong());
-                                });
-                                
-                                ui.add_space(10.0);
-                                
-                                // Profile list
-                                let mut delete_profile_name: Option<String> = None;
-                                
-                                // Determine which profile is selected (pending or current)
-                                let selected_profile = self.profile_switch_pending.clone().unwrap_or_else(|| self.current_profile.clone());
-                                
-                                egui::ScrollArea::vertical()
-                                    .max_height(120.0)
-                                    .show(ui, |ui| {
-                                        for (profile_name, video_count) in &self.available_profiles.clone() {
-                                            let is_selected = profile_name == &selected_profile;
-                                            let is_actual_current = profile_name == &self.current_profile;
-                                            let label = format!("{} ({})", profile_name, self.i18n.t("videos_count").replace("{}", &video_count.to_string()));
-                                            
-                                            ui.horizontal(|ui| {
-                                                // Use selectable_label for radio-like behavior
-                                                let response = ui.selectable_label(is_selected, &label);
-                                                if response.clicked() && !is_actual_current {
-                                                    eprintln!("[Profile] Clicked on: {} -> setting pending", profile_name);
-                                                    self.profile_switch_pending = Some(profile_name.clone());
-                                                }
-                                                
-                                                // Rename button (only for non-default profiles)
-                                                if profile_name != "default" {
-                                                    if ui.small_button("✏").on_hover_text(&self.i18n.t("rename_profile")).clicked() {
-                                                        self.profile_rename_target = Some(profile_name.clone());
-                                                        self.profile_rename_new_name = profile_name.clone();
-                                                    }
-                                                }
-                                                
-                                                // Delete button (only for non-current, non-default profiles)
-                                                if !is_actual_current && profile_name != "default" {
-                                                    if ui.small_button("🗑").on_hover_text(&self.i18n.t("delete_profile")).clicked() {
-                                                        delete_profile_name = Some(profile_name.clone());
-                                                    }
-                                                }
-                                            });
+                                        
+                                        if ui.button(&self.i18n.t("enter_license_key")).clicked() {
+                                            self.show_license_window = true;
                                         }
                                     });
-                                
-                                // Handle profile delete
-                                if let Some(profile_name) = delete_profile_name {
-                                    self.profile_delete_confirm = Some(profile_name);
                                 }
-                                
-                                ui.add_space(10.0);
-                                
-                                // New profile creation
+                            }
+                        }
+                    });
+                });
+        }
+        
+        // Save settings when changed or when options window is closed
+        if start_scene_batch {
+            self.start_scene_batch();
+        }
+        
+        if recompute_all_durations {
+            let all_ids: Vec<String> = self.database.videos.iter().map(|v| v.id.clone()).collect();
+            self.start_duration_probe(&all_ids);
+        }
+        
+        if let Some(profile_name) = switch_profile_to {
+            self.switch_profile(&profile_name);
+        }
+        
+        if clean_cache {
+            self.clean_thumbnail_cache();
+        }
+        
+        if export_csv {
+            self.export_csv();
+        }
+        
+        if import_metadata {
+            self.import_metadata();
+        }
+        
+        if add_watched_folder {
+            self.add_folder();
+        }
+        
+        if let Some(folder) = unwatch_folder {
+            self.unwatch_folder(&folder);
+        }
+        
+        if clear_all_cache {
+            self.clear_all_thumbnail_cache();
+        }
+        
+        if regenerate_thumbnails {
+            self.regenerate_thumbnails();
+        }
+        
+        if rebuild_tag_rules {
+            self.rebuild_tag_rules();
+        }
+        
+        if preview_tag_rules {
+            self.tag_rules_preview = Some(self.preview_tag_rules());
+        }
+        
+        if apply_tag_rules {
+            self.apply_tag_rules_to_all();
+            self.tag_rules_preview = None;
+        }
+        
+        if settings_changed || (options_window_was_open && !self.show_options_window) {
+            self.save_settings();
+        }
+        
+        // Backup Restore Window
+        if self.show_backup_restore_window {
+            let select_backup_title = self.i18n.t("select_backup");
+            let restore_warning = self.i18n.t("restore_warning");
+            let no_backups_text = self.i18n.t("no_backups_available");
+            let restore_success_text = self.i18n.t("restore_success");
+            let restore_failed_text = self.i18n.t("restore_failed");
+            let restore_in_progress_text = self.i18n.t("restore_in_progress");
+            let regenerating_text = self.i18n.t("regenerating_thumbnails");
+            
+            let mut window_open = self.show_backup_restore_window;
+            let mut restore_path: Option<PathBuf> = None;
+            
+            egui::Window::new(&select_backup_title)
+                .open(&mut window_open)
+                .resizable(true)
+                .default_width(400.0)
+                .show(ctx, |ui| {
+                    // Show processing indicator if restore or thumbnail regeneration is in progress
+                    if self.restore_in_progress || self.thumbnail_regen_in_progress {
+                        ui.vertical_centered(|ui| {
+                            ui.add_space(20.0);
+                            ui.spinner();
+                            ui.add_space(10.0);
+                            if self.thumbnail_regen_in_progress {
+                                ui.label(egui::RichText::new(&regenerating_text).strong());
+                            } else {
+                                ui.label(egui::RichText::new(&restore_in_progress_text).strong());
+                            }
+                            ui.add_space(20.0);
+                        });
+                        return;
+                    }
+                    
+                    ui.label(egui::RichText::new(&restore_warning).color(egui::Color32::YELLOW));
+                    ui.add_space(10.0);
+                    
+                    if self.available_backups.is_empty() {
+                        ui.label(&no_backups_text);
+                    } else {
+                        egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                            for (path, timestamp) in &self.available_backups {
+                                let display_name = database::format_backup_timestamp(timestamp);
                                 ui.horizontal(|ui| {
-                                    ui.label(&self.i18n.t("new_profile"));
-                                    ui.text_edit_singleline(&mut self.new_profile_name)
-                                        .on_hover_text(&self.i18n.t("enter_profile_name"));
-                                    
-                                    if ui.button(&self.i18n.t("create_profile")).clicked() && !self.new_profile_name.is_empty() {
-                                        match database::create_profile(&self.new_profile_name) {
-                                            Ok(_) => {
-                                                self.profile_status_message = Some(self.i18n.t("profile_created"));
-                                                self.available_profiles = database::list_profiles().unwrap_or_default();
-                                                self.new_profile_name.clear();
-                                            }
-                                            Err(e) => {
-                                                self.profile_status_message = Some(format!("{}: {}", self.i18n.t("profile_create_failed"), e));
-                                            }
+                                    ui.label(&display_name);
+                                    // Disable button while restore is in progress
+                                    ui.add_enabled_ui(!self.restore_in_progress, |ui| {
+                                        if ui.button("Restore").clicked() {
+                                            restore_path = Some(path.clone());
                                         }
-                                    }
+                                    });
                                 });
-                                
-                                // Show profile status message
-                                if let Some(ref msg) = self.profile_status_message {
-                                    ui.add_space(5.0);
-                                    ui.label(msg);
-                                }
-                                
-                                // Profile switch pending notice
-                                if let Some(ref pending_profile) = self.profile_switch_pending.clone() {
-                                    ui.add_space(10.0);
-                                    ui.separator();
-                                    ui.add_space(5.0);
+                                ui.separator();
+                            }
+                        });
+                    }
+                });
+            
+            self.show_backup_restore_window = window_open;
+            
+            // Start restore in background thread
+            if let Some(path) = restore_path {
+                self.restore_in_progress = true;
+                self.backup_status_message = Some(restore_in_progress_text.clone());
+                
+                // Create channel for result
+                let (tx, rx) = channel();
+                self.restore_result_receiver = Some(rx);
+                
+                // Spawn background thread for restore
+                std::thread::spawn(move || {
+                    let result = database::restore_from_backup(&path);
+                    let _ = tx.send(result.map_err(|e| e.to_string()));
+                });
+            }
+            
+            // Check for restore completion
+            if self.restore_in_progress {
+                if let Some(ref receiver) = self.restore_result_receiver {
+                    if let Ok(result) = receiver.try_recv() {
+                        match result {
+                            Ok(()) => {
+                                // Reload database and start thumbnail regeneration in background
+                                if let Ok(new_db) = database::load_database() {
+                                    // Start thumbnail regeneration in background thread
+                                    let (tx, rx) = channel();
+                                    self.thumbnail_regen_receiver = Some(rx);
+                                    self.thumbnail_regen_in_progress = true;
+                                    let (scene_threshold, max_scenes, scene_thread_count) = (self.scene_threshold, self.max_scenes, self.scene_thread_count);
                                     
-                                    // Show which profile will be switched to
-                                    ui.horizontal(|ui| {
-                                        ui.label(&self.i18n.t("switch_to_profile"));
-                                        ui.label(egui::RichText::new(pending_profile.as_str()).strong().color(egui::Color32::LIGHT_BLUE));
+                                    std::thread::spawn(move || {
+                                        let result = regenerate_missing_thumbnails_async(new_db, scene_threshold, max_scenes, scene_thread_count);
+                                        let _ = tx.send(result);
                                     });
-                                    
-                                    ui.label(egui::RichText::new(&self.i18n.t("profile_switch_notice")).color(egui::Color32::YELLOW));
-                                    
-                                    ui.add_space(5.0);
+                                }
+                            }
+                            Err(e) => {
+                                self.backup_status_message = Some(format!("{}: {}", restore_failed_text, e));
+                            }
+                        }
+                        self.restore_in_progress = false;
+                        self.restore_result_receiver = None;
+                    }
+                }
+                // Request repaint to keep checking
+                ctx.request_repaint();
+            }
+            
+            // Check for thumbnail regeneration completion
+            if self.thumbnail_regen_in_progress {
+                if let Some(ref receiver) = self.thumbnail_regen_receiver {
+                    if let Ok(result) = receiver.try_recv() {
+                        match result {
+                            Ok(updated_db) => {
+                                self.database = updated_db;
+                                self.backup_status_message = Some(restore_success_text.clone());
+                                self.show_backup_restore_window = false;
+                            }
+                            Err(e) => {
+                                self.backup_status_message = Some(format!("{}: {}", restore_failed_text, e));
+                            }
+                        }
+                        self.thumbnail_regen_in_progress = false;
+                        self.thumbnail_regen_receiver = None;
+                    }
+                }
+                // Request repaint to keep checking
+                ctx.request_repaint();
+            }
+        }
+        
+        // Folder Management Window
+        if self.show_folder_management_window {
+            let folder_management_title = self.i18n.t("folder_management");
+            let registered_folders_text = self.i18n.t("registered_folders");
+            let new_folder_name_text = self.i18n.t("new_folder_name");
+            let add_folder_name_text = self.i18n.t("add_folder_name");
+            
+            let mut window_open = self.show_folder_management_window;
+            
+            egui::Window::new(&folder_management_title)
+                .open(&mut window_open)
+                .resizable(true)
+                .default_width(500.0)
+                .show(ctx, |ui| {
+                    ui.label(&registered_folders_text);
+                    ui.separator();
+                    
+                    let mut folder_to_confirm: Option<(String, usize)> = None;
+                    let mut folders_changed = false;
+                    let mut rename_request: Option<(String, String)> = None;
+                    let mut cancel_rename = false;
+                    
+                    // Display folders with rename and delete buttons
+                    egui::ScrollArea::vertical()
+                        .max_height(300.0)
+                        .show(ui, |ui| {
+                            let folders = self.database.folders.clone();
+                            for folder in folders {
+                                // Inline rename editor
+                                if let Some((original, new_name)) = self.folder_rename.as_mut().filter(|(original, _)| *original == folder) {
+                                    let original = original.clone();
                                     ui.horizontal(|ui| {
-                                        if ui.button(&self.i18n.t("ok")).clicked() {
-                                            // Save the profile switch to settings
-                                            if let Ok(mut settings) = database::load_settings() {
-                                                settings.current_profile = pending_profile.clone();
-                                                let _ = database::save_settings(&settings);
-                                            }
-                                            // Update current_profile in UI to maintain selection state
-                                            self.current_profile = pending_profile.clone();
-                                            self.profile_switch_pending = None;
-                                            
-                                            // Restart the application
-                                            if let Ok(exe_path) = std::env::current_exe() {
-                                                let _ = std::process::Command::new(&exe_path).spawn();
-                                                std::process::exit(0);
-                                            }
+                                        let response = ui.text_edit_singleline(new_name);
+                                        response.request_focus();
+                                        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                                        if ui.button("✔").clicked() || submitted {
+                                            rename_request = Some((original, new_name.clone()));
                                         }
-                                        if ui.button(&self.i18n.t("cancel")).clicked() {
-                                            self.profile_switch_pending = None;
+                                        if ui.button("✕").clicked() || ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                                            cancel_rename = true;
                                         }
                                     });
+                                    if let Some(error) = &self.folder_rename_error {
+                                        ui.label(egui::RichText::new(error).color(egui::Color32::RED).small());
+                                    }
+                                    continue;
                                 }
                                 
-                                // Profile delete confirmation
-                                if let Some(ref delete_profile) = self.profile_delete_confirm.clone() {
-                                    ui.add_space(10.0);
-                                    ui.separator();
-                                    ui.label(egui::RichText::new(&self.i18n.t("confirm_delete_profile")).color(egui::Color32::RED));
-                                    
-                                    // Show video count for the profile
-                                    if let Some((_, count)) = self.available_profiles.iter().find(|(n, _)| n == delete_profile) {
-                                        ui.label(self.i18n.t("profile_has_videos").replace("{}", &count.to_string()));
-                                    }
-                                    
+                                ui.horizontal(|ui| {
+                                    ui.label(&folder);
+                                    if ui.button("✏").on_hover_text(self.i18n.t("rename_folder")).clicked() {
+                                        self.folder_rename = Some((folder.clone(), folder.clone()));
+                                        self.folder_rename_error = None;
+                                    }
+                                    if ui.button("❌").clicked() {
+                                        // Count videos in this folder
+                                        let video_count = self.database.videos.iter()
+                                            .filter(|v| v.folders.contains(&folder))
+                                            .count();
+                                        folder_to_confirm = Some((folder.clone(), video_count));
+                                    }
+                                });
+                            }
+                        });
+                    
+                    // Set confirmation if requested
+                    if let Some((folder, count)) = folder_to_confirm {
+                        self.folder_delete_confirm = Some((folder, count));
+                    }
+                    
+                    if cancel_rename {
+                        self.folder_rename = None;
+                        self.folder_rename_error = None;
+                    }
+                    if let Some((old_name, new_name)) = rename_request {
+                        // Keep the editor open with the error so the name can be corrected
+                        match self.rename_folder(&old_name, &new_name) {
+                            Ok(()) => {
+                                self.folder_rename = None;
+                                self.folder_rename_error = None;
+                            }
+                            Err(error) => self.folder_rename_error = Some(error),
+                        }
+                    }
+                    
+                    ui.separator();
+                    
+                    // Add new folder
+                    ui.label(&new_folder_name_text);
+                    ui.horizontal(|ui| {
+                        ui.text_edit_singleline(&mut self.new_folder_input);
+                        if ui.button(&add_folder_name_text).clicked() && !self.new_folder_input.trim().is_empty() {
+                            let new_folder = self.new_folder_input.trim().to_string();
+                            self.database.add_folder(new_folder);
+                            self.new_folder_input.clear();
+                            folders_changed = true;
+                        }
+                    });
+                    
+                    if folders_changed {
+                        self.save_settings();
+                        let _ = database::save_database(&self.database);
+                    }
+                });
+            
+            self.show_folder_management_window = window_open;
+        }
+        
+        // Tag Management Window
+        if self.show_tag_management_window {
+            let tag_management_title = self.i18n.t("tag_management");
+            let registered_tags_text = self.i18n.t("registered_tags");
+            
+            let mut window_open = self.show_tag_management_window;
+            
+            egui::Window::new(&tag_management_title)
+                .open(&mut window_open)
+                .resizable(true)
+                .default_width(400.0)
+                .show(ctx, |ui| {
+                    ui.label(&registered_tags_text);
+                    ui.separator();
+                    
+                    let mut tag_to_confirm: Option<(String, usize)> = None;
+                    let mut rename_request: Option<(String, String)> = None;
+                    let mut merge_request: Option<(String, String)> = None;
+                    let mut cancel_rename = false;
+                    
+                    // Display tags with rename, merge and delete buttons
+                    egui::ScrollArea::vertical()
+                        .max_height(300.0)
+                        .show(ui, |ui| {
+                            let tags = self.database.tags.clone();
+                            for tag in &tags {
+                                let tag = tag.clone();
+                                
+                                // Inline rename editor
+                                if let Some((original, new_name)) = self.tag_rename.as_mut().filter(|(original, _)| *original == tag) {
+                                    let trimmed = new_name.trim().to_string();
+                                    let conflicts = trimmed != *original && tags.contains(&trimmed);
+                                    let original = original.clone();
                                     ui.horizontal(|ui| {
-                                        if ui.button(&self.i18n.t("yes_delete")).clicked() {
-                                            match database::delete_profile(delete_profile) {
-                                                Ok(_) => {
-                                                    self.profile_status_message = Some(self.i18n.t("profile_deleted"));
-                                                    self.available_profiles = database::list_profiles().unwrap_or_default();
-                                                }
-                                                Err(e) => {
-                                                    self.profile_status_message = Some(format!("{}: {}", self.i18n.t("profile_delete_failed"), e));
-                                                }
+                                        let response = ui.text_edit_singleline(new_name);
+                                        response.request_focus();
+                                        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                                        if conflicts {
+                                            // Renaming onto an existing tag is a merge
+                                            if ui.button(self.i18n.t("merge_instead")).clicked() {
+                                                merge_request = Some((original.clone(), trimmed.clone()));
                                             }
-                                            self.profile_delete_confirm = None;
+                                        } else if (ui.button("✔").clicked() || submitted) && !trimmed.is_empty() {
+                                            rename_request = Some((original.clone(), trimmed.clone()));
                                         }
-                                        if ui.button(&self.i18n.t("cancel")).clicked() {
-                                            self.profile_delete_confirm = None;
+                                        if ui.button("✕").clicked() || ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                                            cancel_rename = true;
                                         }
                                     });
+                                    if conflicts {
+                                        ui.label(egui::RichText::new(self.i18n.t("tag_name_exists")).color(egui::Color32::YELLOW).small());
+                                    }
+                                    continue;
                                 }
                                 
-                                // Profile rename dialog
-                                if let Some(ref rename_profile) = self.profile_rename_target.clone() {
-                                    ui.add_space(10.0);
-                                    ui.separator();
-                                    ui.add_space(5.0);
-                                    
-                                    ui.horizontal(|ui| {
-                                        ui.label(&self.i18n.t("rename_profile"));
-                                        ui.label(egui::RichText::new(rename_profile.as_str()).strong());
-                                    });
-                                    
-                                    ui.horizontal(|ui| {
-                                        ui.label(&self.i18n.t("new_name"));
-                                        ui.text_edit_singleline(&mut self.profile_rename_new_name);
-                                    });
-                                    
-                                    ui.horizontal(|ui| {
-                                        let new_name = self.profile_rename_new_name.trim().to_string();
-                                        let can_rename = !new_name.is_empty() && new_name != *rename_profile;
-                                        
-                                        if ui.add_enabled(can_rename, egui::Button::new(&self.i18n.t("ok"))).clicked() {
-                                            // If renaming current profile, update settings
-                                            let is_current = rename_profile == &self.current_profile;
-                                            
-                                            match database::rename_profile(rename_profile, &new_name) {
-                                                Ok(_) => {
-                                                    self.profile_status_message = Some(self.i18n.t("profile_renamed"));
-                                                    self.available_profiles = database::list_profiles().unwrap_or_default();
-                                                    
-                                                    // Update current profile name if it was renamed
-                                                    if is_current {
-                                                        self.current_profile = new_name.clone();
-                                                        if let Ok(mut settings) = database::load_settings() {
-                                                            settings.current_profile = new_name.clone();
-                                                            let _ = database::save_settings(&settings);
-                                                        }
-                                                    }
-                                                    
-                                                    // Update pending switch if it was targeting the renamed profile
-                                                    if self.profile_switch_pending.as_ref() == Some(rename_profile) {
-                                                        self.profile_switch_pending = Some(new_name);
-                                                    }
-                                                }
-                                                Err(e) => {
-                                                    self.profile_status_message = Some(format!("{}: {}", self.i18n.t("profile_rename_failed"), e));
-                                                }
+                                ui.horizontal(|ui| {
+                                    ui.label(format!("🏷 {}", &tag));
+                                    if ui.button("✏").on_hover_text(self.i18n.t("rename_tag")).clicked() {
+                                        self.tag_rename = Some((tag.clone(), tag.clone()));
+                                    }
+                                    ui.menu_button("⤵", |ui| {
+                                        ui.label(egui::RichText::new(self.i18n.t("merge_tag_into")).weak());
+                                        for target in tags.iter().filter(|t| **t != tag) {
+                                            if ui.button(format!("🏷 {}", target)).clicked() {
+                                                merge_request = Some((tag.clone(), target.clone()));
+                                                ui.close_menu();
                                             }
-                                            self.profile_rename_target = None;
-                                            self.profile_rename_new_name.clear();
                                         }
-                                        if ui.button(&self.i18n.t("cancel")).clicked() {
-                                            self.profile_rename_target = None;
-                                            self.profile_rename_new_name.clear();
-                                        }
-                                    });
-                                }
-                                
-                                ui.add_space(15.0);
-                                ui.separator();
-                                
-                                ui.heading(&self.i18n.t("management"));
-                                ui.add_space(5.0);
-                                
-                                // Button to open folder management window
-                                if ui.button(&self.i18n.t("manage_folders")).clicked() {
-                                    self.show_folder_management_window = true;
-                                }
-                                
-                                ui.add_space(5.0);
-                                
-                                // Button to open tag management window
-                                if ui.button(&self.i18n.t("manage_tags")).clicked() {
-                                    self.show_tag_management_window = true;
+                                    }).response.on_hover_text(self.i18n.t("merge_tag_into"));
+                                    if ui.button("❌").clicked() {
+                                        // Count videos using this tag
+                                        let video_count = self.database.videos.iter()
+                                            .filter(|v| v.tags.contains(&tag))
+                                            .count();
+                                        tag_to_confirm = Some((tag.clone(), video_count));
+                                    }
+                                });
+                            }
+                            
+                            if self.database.tags.is_empty() {
+                                ui.label("(No tags registered)");
+                            }
+                        });
+                    
+                    // Set confirmation if requested
+                    if let Some((tag, count)) = tag_to_confirm {
+                        self.tag_delete_confirm = Some((tag, count));
+                    }
+                    
+                    if cancel_rename {
+                        self.tag_rename = None;
+                    }
+                    if let Some((old_name, new_name)) = rename_request {
+                        if old_name != new_name {
+                            self.rename_tag(&old_name, &new_name);
+                        }
+                        self.tag_rename = None;
+                    }
+                    if let Some((source, target)) = merge_request {
+                        self.merge_tag(&source, &target);
+                        self.tag_rename = None;
+                    }
+                });
+            
+            self.show_tag_management_window = window_open;
+        }
+        
+        // Tag Delete Confirmation Window
+        if self.tag_delete_confirm.is_some() {
+            let confirm_title = self.i18n.t("confirm_tag_delete_title");
+            let confirm_msg = self.i18n.t("confirm_tag_delete");
+            let cancel_text = self.i18n.t("cancel");
+            
+            let mut should_close = false;
+            let mut delete_tag = false;
+            
+            // Keyboard shortcuts: 1 to confirm, 2 or Escape to cancel
+            if ctx.input(|i| i.key_pressed(egui::Key::Num1)) {
+                delete_tag = true;
+                should_close = true;
+            }
+            if ctx.input(|i| i.key_pressed(egui::Key::Num2) || i.key_pressed(egui::Key::Escape)) {
+                should_close = true;
+            }
+            
+            egui::Window::new(&confirm_title)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    if let Some((tag_name, video_count)) = &self.tag_delete_confirm {
+                        ui.label(format!("🏷 {}", tag_name));
+                        ui.separator();
+                        ui.label(&confirm_msg);
+                        
+                        if *video_count > 0 {
+                            ui.add_space(8.0);
+                            let videos_msg = self.i18n.t("tag_used_in_videos")
+                                .replace("{}", &video_count.to_string());
+                            ui.label(egui::RichText::new(videos_msg).color(egui::Color32::YELLOW));
+                            ui.label(self.i18n.t("tag_will_be_removed"));
+                        }
+                        
+                        ui.add_space(16.0);
+                        
+                        // Keyboard hint
+                        ui.label(egui::RichText::new("1: OK | 2: Cancel").weak().small());
+                        ui.add_space(4.0);
+                        
+                        ui.horizontal(|ui| {
+                            if ui.button(egui::RichText::new("[1] OK").color(egui::Color32::RED)).clicked() {
+                                delete_tag = true;
+                                should_close = true;
+                            }
+                            if ui.button(format!("[2] {}", &cancel_text)).clicked() {
+                                should_close = true;
+                            }
+                        });
+                    }
+                });
+            
+            // Process deletion after window is closed
+            if should_close {
+                if let Some((tag, _video_count)) = self.tag_delete_confirm.take() {
+                    if delete_tag {
+                        // Remove tag from tags list
+                        self.database.tags.retain(|t| t != &tag);
+                        
+                        // Remove tag from all videos
+                        for video in &mut self.database.videos {
+                            video.tags.retain(|t| t != &tag);
+                        }
+                        
+                        // Also remove from selected tag filter
+                        self.selected_tag_filter.remove(&tag);
+                        
+                        eprintln!("[tag_management] Removed tag '{}' from database and all videos", tag);
+                        
+                        self.save_settings();
+                        let _ = database::persist_tag_change(&self.database, &[tag.as_str()]);
+                    }
+                }
+            }
+        }
+        
+        // Folder Delete Confirmation Window
+        if self.folder_delete_confirm.is_some() {
+            let confirm_title = self.i18n.t("confirm_folder_delete_title");
+            let confirm_msg = self.i18n.t("confirm_folder_delete");
+            let cancel_text = self.i18n.t("cancel");
+            
+            let mut should_close = false;
+            let mut delete_with_videos = false;
+            let mut delete_folder_only = false;
+            
+            // Check if there are videos in this folder
+            let has_videos = self.folder_delete_confirm.as_ref()
+                .map(|(_, count)| *count > 0)
+                .unwrap_or(false);
+            
+            // Keyboard shortcuts
+            if ctx.input(|i| i.key_pressed(egui::Key::Escape) || i.key_pressed(egui::Key::Num3)) {
+                should_close = true;
+            }
+            if has_videos {
+                // 1: Delete with videos, 2: Folder only, 3: Cancel
+                if ctx.input(|i| i.key_pressed(egui::Key::Num1)) {
+                    delete_with_videos = true;
+                    should_close = true;
+                }
+                if ctx.input(|i| i.key_pressed(egui::Key::Num2)) {
+                    delete_folder_only = true;
+                    should_close = true;
+                }
+            } else {
+                // 1: OK, 2: Cancel
+                if ctx.input(|i| i.key_pressed(egui::Key::Num1)) {
+                    delete_folder_only = true;
+                    should_close = true;
+                }
+                if ctx.input(|i| i.key_pressed(egui::Key::Num2)) {
+                    should_close = true;
+                }
+            }
+            
+            egui::Window::new(&confirm_title)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    if let Some((folder_name, video_count)) = &self.folder_delete_confirm {
+                        ui.label(format!("📁 {}", folder_name));
+                        ui.separator();
+                        ui.label(&confirm_msg);
+                        
+                        if *video_count > 0 {
+                            ui.add_space(8.0);
+                            let videos_msg = self.i18n.t("folder_contains_videos")
+                                .replace("{}", &video_count.to_string());
+                            ui.label(egui::RichText::new(videos_msg).color(egui::Color32::YELLOW));
+                        }
+                        
+                        ui.add_space(16.0);
+                        
+                        // Keyboard hint
+                        if *video_count > 0 {
+                            ui.label(egui::RichText::new("1: Delete All | 2: Folder Only | 3: Cancel").weak().small());
+                        } else {
+                            ui.label(egui::RichText::new("1: OK | 2: Cancel").weak().small());
+                        }
+                        ui.add_space(4.0);
+                        
+                        ui.horizontal(|ui| {
+                            if *video_count > 0 {
+                                // Show both options when there are videos
+                                if ui.button(egui::RichText::new(format!("[1] {}", self.i18n.t("delete_videos_too"))).color(egui::Color32::RED)).clicked() {
+                                    delete_with_videos = true;
+                                    should_close = true;
                                 }
-                                
-                                ui.add_space(15.0);
-                                ui.separator();
-                                ui.heading(&self.i18n.t("backup"));
-                                ui.add_space(5.0);
-                                
-                                // Show last backup date
-                                if let Ok(settings) = database::load_settings() {
-                                    if let Some(ref last_backup) = settings.last_backup_date {
-                                        if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(last_backup) {
-                                            // Convert to local timezone
-                                            let local_dt = dt.with_timezone(&chrono::Local);
-                                            let formatted = local_dt.format("%Y/%m/%d %H:%M:%S").to_string();
-                                            ui.label(format!("{}: {}", self.i18n.t("last_backup"), formatted));
-                                            ui.add_space(5.0);
-                                        }
-                                    }
+                                if ui.button(format!("[2] {}", self.i18n.t("folder_only"))).clicked() {
+                                    delete_folder_only = true;
+                                    should_close = true;
                                 }
-                                
-                                // Create backup button
-                                if ui.button(&self.i18n.t("create_backup")).clicked() {
-                                    match database::create_backup() {
-                                        Ok(path) => {
-                                            // Update last backup date in settings
-                                            if let Ok(mut settings) = database::load_settings() {
-                                                settings.last_backup_date = Some(chrono::Utc::now().to_rfc3339());
-                                                let _ = database::save_settings(&settings);
-                                            }
-                                            self.backup_status_message = Some(format!("{}: {:?}", self.i18n.t("backup_created"), path));
-                                        }
-                                        Err(e) => {
-                                            self.backup_status_message = Some(format!("{}: {}", self.i18n.t("backup_failed"), e));
-                                        }
-                                    }
+                            } else {
+                                // No videos, just confirm folder removal
+                                if ui.button("[1] OK").clicked() {
+                                    delete_folder_only = true;
+                                    should_close = true;
                                 }
-                                
-                                ui.add_space(5.0);
-                                
-                                // Restore from backup button
-                                if ui.button(&self.i18n.t("restore_from_backup")).clicked() {
-                                    // Load available backups
-                                    match database::list_backups() {
-                                        Ok(backups) => {
-                                            self.available_backups = backups;
-                                            self.show_backup_restore_window = true;
-                                        }
-                                        Err(e) => {
-                                            self.backup_status_message = Some(format!("{}: {}", self.i18n.t("restore_failed"), e));
-                                        }
+                            }
+                            if ui.button(format!("[{}] {}", if *video_count > 0 { "3" } else { "2" }, &cancel_text)).clicked() {
+                                should_close = true;
+                            }
+                        });
+                    }
+                });
+            
+            // Process deletion after window is closed
+            if should_close {
+                if let Some((folder, _video_count)) = self.folder_delete_confirm.take() {
+                    if delete_with_videos || delete_folder_only {
+                        // Remove folder from list
+                        self.database.folders.retain(|f| f != &folder);
+                        
+                        // Remove from watched_folders
+                        self.watched_folders.retain(|path| {
+                            path.file_name()
+                                .and_then(|n| n.to_str())
+                                .map(|name| name != folder)
+                                .unwrap_or(true)
+                        });
+                        
+                        if delete_with_videos {
+                            // Also delete video profiles
+                            self.database.videos.retain(|v| !v.folders.contains(&folder));
+                            eprintln!("[folder_management] Removed folder '{}' and its video profiles", folder);
+                        } else {
+                            for video in &mut self.database.videos {
+                                video.folders.retain(|f| f != &folder);
+                            }
+                            eprintln!("[folder_management] Removed folder '{}' (kept video profiles)", folder);
+                        }
+                        
+                        self.save_settings();
+                        let _ = database::save_database(&self.database);
+                    }
+                }
+            }
+        }
+        
+        // Shader Management Window
+        if self.show_shader_management_window {
+            let shader_management_title = self.i18n.t("shader_management");
+            let select_shader_text = self.i18n.t("select_shader_to_use");
+            
+            let mut window_open = self.show_shader_management_window;
+            
+            egui::Window::new(&shader_management_title)
+                .open(&mut window_open)
+                .resizable(true)
+                .default_width(400.0)
+                .show(ctx, |ui| {
+                    ui.label(&select_shader_text);
+                    ui.separator();
+                    
+                    let mut shader_changed = false;
+                    let available_shaders = video_player::get_available_shaders();
+                    
+                    if available_shaders.is_empty() {
+                        ui.label("⚠ No shaders found in mpv/glsl_shaders directory");
+                        ui.label("📁 Place .glsl shader files in the mpv/glsl_shaders directory");
+                    } else {
+                        // Add "None" option
+                        if ui.radio(self.selected_shader.is_none(), "None").clicked() {
+                            self.selected_shader = None;
+                            shader_changed = true;
+                        }
+                        
+                        ui.separator();
+                        
+                        // Add radio button for each shader
+                        egui::ScrollArea::vertical()
+                            .max_height(400.0)
+                            .show(ui, |ui| {
+                                for shader_name in &available_shaders {
+                                    let is_selected = self.selected_shader.as_ref() == Some(shader_name);
+                                    if ui.radio(is_selected, shader_name).clicked() {
+                                        self.selected_shader = Some(shader_name.clone());
+                                        shader_changed = true;
                                     }
                                 }
-                                
-                                // Show backup status message
-                                if let Some(ref msg) = self.backup_status_message {
-                                    ui.add_space(5.0);
-                                    ui.label(msg);
-                                }
-                                
-                                ui.add_space(15.0);
-                                ui.separator();
-                                ui.heading(&self.i18n.t("reset_to_default"));
-                                ui.add_space(5.0);
-                                
-                                if ui.button(&self.i18n.t("reset_to_default")).clicked() {
-                                    self.thumbnail_scale = 1.0;
-                                    self.mpv_always_on_top = true;
-                                    self.show_full_filename = false;
-                                    self.show_tags_in_grid = true;
-                                    self.dark_mode = false;
-                                    self.use_gpu_hq = false;
-                                    self.use_custom_shaders = false;
-                                    self.selected_shader = None;
-                                    settings_changed = true;
-                                }
+                            });
+                    }
+                    
+                    if shader_changed {
+                        self.save_settings();
+                    }
+                    
+                    // User mpv.conf precedence
+                    ui.separator();
+                    ui.label(egui::RichText::new(self.i18n.t("mpv_conf_precedence")).weak().small());
+                    // Only created when opened, not on every frame the window is shown
+                    let config_dir = database::get_mpv_config_dir_path();
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(config_dir.display().to_string()).monospace().small());
+                        if ui.small_button(self.i18n.t("open_mpv_config_dir")).clicked() {
+                            if let Err(e) = video_player::open_directory(&database::get_mpv_config_dir()) {
+                                eprintln!("[mpv] Failed to open config dir: {}", e);
                             }
-                            
-                            OptionsTab::License => {
-                                // License section
-                                ui.heading("Premium License");
-                                ui.add_space(5.0);
-                                
-                                if let Some(ref license) = self.current_license {
-                                    // Show license info
-                                    ui.horizontal(|ui| {
-                                        ui.label(&self.i18n.t("issued_to"));
-                                        ui.label(&license.info.issued_to);
-                                    });
-                                    
-                                    ui.horizontal(|ui| {
-                                        ui.label(&self.i18n.t("expires"));
-                                        let expires_text = license.info.expires_at
-                                            .map(|ts| license::format_timestamp(ts))
-                                            .unwrap_or_else(|| self.i18n.t("never_expires"));
-                                        ui.label(&expires_text);
-                                    });
-                                    
-                                    ui.horizontal(|ui| {
-                                        ui.label(&self.i18n.t("license_status"));
-                                        if license.is_expired {
-                                            ui.label(egui::RichText::new("❌ Expired").color(egui::Color32::RED));
-                                        } else if license.is_valid {
-                                            ui.label(egui::RichText::new("✅ Active").color(egui::Color32::GREEN));
-                                        } else {
-                                            ui.label(egui::RichText::new("❌ Invalid").color(egui::Color32::RED));
-                                        }
-                                    });
-                                    
-                                    ui.add_space(10.0);
-                                    if ui.button(&self.i18n.t("enter_license_key")).clicked() {
-                                        self.show_license_window = true;
-                                    }
+                        }
+                    });
+                });
+            
+            self.show_shader_management_window = window_open;
+        }
+        
+        // Delete confirmation dialog
+        if let Some(video_id) = self.delete_confirm_video.clone() {
+            let is_multi = video_id == "__MULTI__";
+            let title = if is_multi { 
+                self.i18n.t("delete_selected_videos")
+            } else { 
+                self.i18n.t("delete_video")
+            };
+            
+            egui::Window::new(&title)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    if is_multi {
+                        let confirm_text = self.i18n.t("confirm_delete_videos").replace("{}", &self.selected_videos.len().to_string());
+                        ui.label(&confirm_text);
+                    } else {
+                        ui.label(&self.i18n.t("confirm_delete_video"));
+                        
+                        if let Some(video) = self.database.get_video(&video_id) {
+                            let title_text = self.i18n.t("title_label").replace("{}", &video.title);
+                            ui.label(&title_text);
+                        }
+                    }
+                    
+                    ui.separator();
+                    
+                    // Deleting the actual files needs a second, explicit confirmation
+                    if self.delete_to_trash_confirm {
+                        ui.label(egui::RichText::new(self.i18n.t("confirm_move_to_trash")).color(egui::Color32::from_rgb(255, 100, 100)));
+                        ui.add_space(5.0);
+                        ui.horizontal(|ui| {
+                            let trash_button = egui::Button::new(egui::RichText::new(self.i18n.t("move_to_trash")).color(egui::Color32::WHITE))
+                                .fill(egui::Color32::from_rgb(170, 40, 40));
+                            if ui.add(trash_button).clicked() {
+                                let target_ids: Vec<String> = if is_multi {
+                                    self.selected_videos.iter().cloned().collect()
                                 } else {
-                                    // Premium promotion for free users
-                                    ui.label(egui::RichText::new(&self.i18n.t("premium_benefits_title")).strong());
-                                    ui.add_space(5.0);
-                                    
-                                    ui.label(&self.i18n.t("premium_benefit_1")); // 5つ星レーティング
-                                    ui.label(&self.i18n.t("premium_benefit_2")); // 複数タグ/フォルダ選択
-                                    ui.label(&self.i18n.t("premium_benefit_3")); // GPU高画質レンダリング
-                                    ui.label(&self.i18n.t("premium_benefit_4")); // カスタムシェーダー
-                                    ui.label(&self.i18n.t("premium_benefit_5")); // 無制限の動画プロファイル
-                                    
-                                    ui.add_space(15.0);
-                                    
-                                    ui.horizontal(|ui| {
-                                        if ui.button(&self.i18n.t("purchase_premium")).clicked() {
-                                            // Open purchase page using platform-specific command
-                                            #[cfg(target_os = "windows")]
-                                            {
-                                                let _ = std::process::Command::new("cmd")
-                                                    .args(["/C", "start", "", "https://tetdarth.gumroad.com/l/jmjty"])
-                                                    .spawn();
-                                            }
-                                            #[cfg(target_os = "macos")]
-                                            {
-                                                let _ = std::process::Command::new("open")
-                                                    .arg("https://tetdarth.gumroad.com/l/jmjty")
-                                                    .spawn();
-                                            }
-                                            #[cfg(target_os = "linux")]
-                                            {
-                                                let _ = std::process::Command::new("xdg-open")
-                                                    .arg("https://tetdarth.gumroad.com/l/jmjty")
-                                                    .spawn();
-                                            }
-                                        }
-                                        
-                                        if ui.button(&self.i18n.t("enter_license_key")).clicked() {
-                                            self.show_license_window = true;
-                                        }
-                                    });
+                                    vec![video_id.clone()]
+                                };
+                                self.trash_videos(&target_ids);
+                                if is_multi {
+                                    self.selected_videos.clear();
                                 }
+                                self.delete_confirm_video = None;
+                            }
+                            if ui.button(self.i18n.t("cancel")).clicked() {
+                                self.delete_to_trash_confirm = false;
+                            }
+                        });
+                        return;
+                    }
+                    
+                    ui.horizontal(|ui| {
+                        if ui.button(&self.i18n.t("delete_keep_cache")).clicked() {
+                            if is_multi {
+                                self.delete_selected_videos(false);
+                            } else {
+                                self.delete_video(&video_id, false);
+                            }
+                            self.delete_confirm_video = None;
+                        }
+                        
+                        if ui.button(&self.i18n.t("delete_remove_all")).clicked() {
+                            if is_multi {
+                                self.delete_selected_videos(true);
+                            } else {
+                                self.delete_video(&video_id, true);
+                            }
+                            self.delete_confirm_video = None;
+                        }
+                        
+                        if ui.button(&self.i18n.t("cancel")).clicked() {
+                            self.delete_confirm_video = None;
+                        }
+                    });
+                    
+                    // Real file deletion, kept apart from the library-only options above
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.label(egui::RichText::new(self.i18n.t("delete_file_hint")).weak().small());
+                    if ui.button(egui::RichText::new(self.i18n.t("delete_file_to_trash")).color(egui::Color32::from_rgb(255, 100, 100))).clicked() {
+                        self.delete_to_trash_confirm = true;
+                    }
+                });
+            
+            if self.delete_confirm_video.is_none() {
+                self.delete_to_trash_confirm = false;
+            }
+        }
+        
+        // MPV shortcuts panel at bottom left
+        if self.show_mpv_shortcuts {
+            let mut window_open = self.mpv_shortcuts_open;
+            let window_was_open = window_open;
+            
+            // Use saved position or default to bottom-left
+            let default_pos = egui::pos2(10.0, ctx.screen_rect().max.y - 280.0);
+            let initial_pos = self.mpv_shortcuts_pos.unwrap_or(default_pos);
+            
+            let response = egui::Window::new("MPV Shortcuts")
+                .default_pos(initial_pos)
+                .resizable(false)
+                .collapsible(true)
+                .open(&mut window_open)
+                .show(ctx, |ui| {
+                    ui.set_width(180.0);
+                    
+                    ui.label(egui::RichText::new("⌨ Keyboard Shortcuts").strong());
+                    ui.separator();
+                    
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Space").code().strong());
+                        ui.label("Play/Pause");
+                    });
+                    
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("F").code().strong());
+                        ui.label("Toggle Fullscreen");
+                    });
+                    
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("←/→").code().strong());
+                        ui.label("Seek -5/+5 sec");
+                    });
+                    
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("↑/↓").code().strong());
+                        ui.label("Seek -60/+60 sec");
+                    });
+                    
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("9/0").code().strong());
+                        ui.label("Volume Down/Up");
+                    });
+                    
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("M").code().strong());
+                        ui.label("Mute/Unmute");
+                    });
+                    
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("[/]").code().strong());
+                        ui.label("Speed -10%/+10%");
+                    });
+                    
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("{/}").code().strong());
+                        ui.label("Speed x0.5/x2.0");
+                    });
+                    
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Q").code().strong());
+                        ui.label("Quit Player");
+                    });
+                });
+            
+            // Track window position after drag
+            if let Some(inner_response) = response {
+                let new_pos = inner_response.response.rect.min;
+                let pos_changed = self.mpv_shortcuts_pos.map(|p| (p.x - new_pos.x).abs() > 1.0 || (p.y - new_pos.y).abs() > 1.0).unwrap_or(true);
+                if pos_changed {
+                    self.mpv_shortcuts_pos = Some(new_pos);
+                    self.save_settings();
+                }
+            }
+            
+            // Save state if changed
+            if window_open != window_was_open {
+                self.mpv_shortcuts_open = window_open;
+                self.save_settings();
+            } else {
+                self.mpv_shortcuts_open = window_open;
+            }
+        }
+        
+        // Unlock protected videos window
+        if self.show_unlock_window {
+            let mut window_open = true;
+            egui::Window::new(self.i18n.t("unlock_protected"))
+                .open(&mut window_open)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(self.i18n.t("enter_protection_pin"));
+                    ui.add_space(5.0);
+                    
+                    let response = ui.add(egui::TextEdit::singleline(&mut self.pin_input).password(true));
+                    response.request_focus();
+                    let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    
+                    if let Some(ref message) = self.pin_status_message {
+                        ui.label(egui::RichText::new(message).color(egui::Color32::from_rgb(255, 100, 100)));
+                    }
+                    
+                    ui.add_space(5.0);
+                    ui.horizontal(|ui| {
+                        if ui.button(self.i18n.t("unlock")).clicked() || submitted {
+                            let pin = std::mem::take(&mut self.pin_input);
+                            if self.unlock_protected(&pin) {
+                                self.show_unlock_window = false;
+                                self.pin_status_message = None;
+                            } else {
+                                self.pin_status_message = Some(self.i18n.t("wrong_pin"));
                             }
                         }
+                        if ui.button(self.i18n.t("cancel")).clicked() {
+                            self.show_unlock_window = false;
+                        }
+                    });
+                });
+            
+            if !window_open {
+                self.show_unlock_window = false;
+            }
+            if !self.show_unlock_window {
+                self.pin_input.clear();
+            }
+        }
+        
+        // Pick the main thumbnail by timestamp
+        if let Some(video_id) = self.thumbnail_timestamp_video.clone() {
+            let mut window_open = true;
+            egui::Window::new(self.i18n.t("set_thumbnail_from_timestamp"))
+                .open(&mut window_open)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    if let Some(video) = self.database.get_video(&video_id) {
+                        ui.label(self.i18n.t("title_label").replace("{}", &video.title));
+                        if let Some(duration) = video.duration {
+                            ui.label(egui::RichText::new(scene_detection::format_timestamp(duration)).weak());
+                        }
+                    }
+                    ui.add_space(5.0);
+                    
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut self.thumbnail_timestamp_input)
+                            .hint_text("00:01:30")
+                            .desired_width(120.0)
+                    );
+                    response.request_focus();
+                    let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    
+                    if let Some(ref error) = self.thumbnail_timestamp_error {
+                        ui.label(egui::RichText::new(error).color(egui::Color32::from_rgb(255, 100, 100)));
+                    }
+                    
+                    ui.add_space(5.0);
+                    ui.horizontal(|ui| {
+                        if ui.button(self.i18n.t("set_thumbnail")).clicked() || submitted {
+                            self.set_thumbnail_from_input(&video_id);
+                        }
+                        if ui.button(self.i18n.t("cancel")).clicked() {
+                            self.thumbnail_timestamp_video = None;
+                        }
+                    });
+                });
+            
+            if !window_open {
+                self.thumbnail_timestamp_video = None;
+            }
+        }
+        
+        // Video player could not be launched
+        if let Some(message) = self.player_error_message.clone() {
+            let mut window_open = true;
+            egui::Window::new(self.i18n.t("player_error_title"))
+                .open(&mut window_open)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_max_width(420.0);
+                    ui.label(&message);
+                    ui.add_space(5.0);
+                    ui.label(self.i18n.t("player_install_hint"));
+                    ui.add_space(10.0);
+                    ui.horizontal(|ui| {
+                        ui.hyperlink_to(self.i18n.t("player_install_instructions"), "https://mpv.io/installation/");
+                        if ui.button(self.i18n.t("close")).clicked() {
+                            self.player_error_message = None;
+                        }
+                    });
+                });
+            
+            if !window_open {
+                self.player_error_message = None;
+            }
+        }
+        
+        // Premium promotion window (shown when video limit reached)
+        if self.show_premium_promotion_window {
+            egui::Window::new(&self.i18n.t("premium_promotion_title"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_width(500.0);
+                    
+                    ui.label(egui::RichText::new(&self.i18n.t("premium_limit_reached")).size(16.0));
+                    ui.add_space(10.0);
+                    
+                    ui.separator();
+                    ui.add_space(5.0);
+                    
+                    ui.label(egui::RichText::new(&self.i18n.t("premium_unlock_features")).strong().size(14.0));
+                    ui.add_space(5.0);
+                    
+                    // List premium features
+                    ui.label(&self.i18n.t("premium_unlimited_storage"));
+                    ui.label(&self.i18n.t("premium_scene_generation"));
+                    ui.label(&self.i18n.t("premium_star_ratings"));
+                    ui.label(&self.i18n.t("premium_glsl_shaders"));
+                    ui.label(&self.i18n.t("premium_gpu_rendering"));
+                    ui.label(&self.i18n.t("premium_multi_select"));
+                    
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(5.0);
+                    
+                    // Buttons
+                    ui.horizontal(|ui| {
+                        // License activation button
+                        if ui.button(&self.i18n.t("enter_license_key")).clicked() {
+                            self.show_license_window = true;
+                            self.show_premium_promotion_window = false;
+                        }
+                        
+                        // Close button
+                        if ui.button(&self.i18n.t("got_it")).clicked() {
+                            self.show_premium_promotion_window = false;
+                        }
                     });
                 });
         }
         
-        // Save settings when changed or when options window is closed
-        if settings_changed || (options_window_was_open && !self.show_options_window) {
-            self.save_settings();
+        // License deactivation confirmation
+        if self.show_deactivate_license_confirm {
+            let mut should_close = ctx.input(|i| i.key_pressed(egui::Key::Escape));
+            let mut deactivate = false;
+            egui::Window::new(self.i18n.t("deactivate_license"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(self.i18n.t("confirm_deactivate_license"));
+                    ui.add_space(16.0);
+                    ui.horizontal(|ui| {
+                        if ui.button(egui::RichText::new(self.i18n.t("deactivate_license")).color(egui::Color32::RED)).clicked() {
+                            deactivate = true;
+                            should_close = true;
+                        }
+                        if ui.button(self.i18n.t("cancel")).clicked() {
+                            should_close = true;
+                        }
+                    });
+                });
+            
+            if deactivate {
+                self.deactivate_license();
+            }
+            if should_close {
+                self.show_deactivate_license_confirm = false;
+            }
         }
         
-        // Backup Restore Window
-        if self.show_backup_restore_window {
-            let select_backup_title = self.i18n.t("select_backup");
-            let restore_warning = self.i18n.t("restore_warning");
-            let no_backups_text = self.i18n.t("no_backups_available");
-            let restore_success_text = self.i18n.t("restore_success");
-            let restore_failed_text = self.i18n.t("restore_failed");
-            let restore_in_progress_text = self.i18n.t("restore_in_progress");
-            let regenerating_text = self.i18n.t("regenerating_thumbnails");
-            
-            let mut window_open = self.show_backup_restore_window;
-            let mut restore_path: Option<PathBuf> = None;
-            
-            egui::Window::new(&select_backup_title)
+        // License activation window
+        if self.show_license_window {
+            let mut window_open = true;
+            egui::Window::new(&self.i18n.t("activate_license"))
                 .open(&mut window_open)
-                .resizable(true)
-                .default_width(400.0)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
                 .show(ctx, |ui| {
-                    // Show processing indicator if restore or thumbnail regeneration is in progress
-                    if self.restore_in_progress || self.thumbnail_regen_in_progress {
-                        ui.vertical_centered(|ui| {
-                            ui.add_space(20.0);
-                            ui.spinner();
-                            ui.add_space(10.0);
-                            if self.thumbnail_regen_in_progress {
-                                ui.label(egui::RichText::new(&regenerating_text).strong());
-                            } else {
-                                ui.label(egui::RichText::new(&restore_in_progress_text).strong());
-                            }
-                            ui.add_space(20.0);
-                        });
-                        return;
+                    ui.set_width(500.0);
+                    
+                    ui.label(egui::RichText::new(&self.i18n.t("license_key_label")).strong());
+                    ui.add_space(5.0);
+                    
+                    // License key input
+                    let response = ui.add(
+                        egui::TextEdit::multiline(&mut self.license_input)
+                            .desired_width(480.0)
+                            .desired_rows(4)
+                            .hint_text(&self.i18n.t("paste_license_key"))
+                    );
+                    
+                    // Auto-focus input on first show
+                    if response.has_focus() || response.gained_focus() {
+                        // Input is focused
                     }
                     
-                    ui.label(egui::RichText::new(&restore_warning).color(egui::Color32::YELLOW));
                     ui.add_space(10.0);
                     
-                    if self.available_backups.is_empty() {
-                        ui.label(&no_backups_text);
-                    } else {
-                        egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
-                            for (path, timestamp) in &self.available_backups {
-                                let display_name = database::format_backup_timestamp(timestamp);
-                                ui.horizontal(|ui| {
-                                    ui.label(&display_name);
-                                    // Disable button while restore is in progress
-                                    ui.add_enabled_ui(!self.restore_in_progress, |ui| {
-                                        if ui.button("Restore").clicked() {
-                                            restore_path = Some(path.clone());
-                                        }
-                                    });
-                                });
-                                ui.separator();
+                    // Status message
+                    if let Some(ref message) = self.license_status_message {
+                        ui.label(egui::RichText::new(message).size(12.0));
+                        ui.add_space(10.0);
+                    }
+                    
+                    // Buttons
+                    ui.horizontal(|ui| {
+                        if ui.button(&self.i18n.t("activate")).clicked() {
+                            let license_key = self.license_input.trim().to_string();
+                            if !license_key.is_empty() {
+                                self.activate_license(&license_key);
+                            }
+                        }
+                        
+                        if ui.button(&self.i18n.t("cancel")).clicked() {
+                            self.show_license_window = false;
+                            self.license_input.clear();
+                            self.license_status_message = None;
+                        }
+                    });
+                    
+                    // Show current license info if available
+                    if let Some(ref license) = self.current_license {
+                        ui.add_space(10.0);
+                        ui.separator();
+                        ui.add_space(5.0);
+                        
+                        ui.label(egui::RichText::new(&self.i18n.t("license_info")).strong());
+                        ui.add_space(5.0);
+                        
+                        ui.horizontal(|ui| {
+                            ui.label(&self.i18n.t("issued_to"));
+                            ui.label(&license.info.issued_to);
+                        });
+                        
+                        ui.horizontal(|ui| {
+                            ui.label(&self.i18n.t("expires"));
+                            let expires_text = license.info.expires_at
+                                .map(|ts| license::format_timestamp(ts))
+                                .unwrap_or_else(|| self.i18n.t("never_expires"));
+                            ui.label(&expires_text);
+                        });
+                        
+                        ui.horizontal(|ui| {
+                            ui.label(&self.i18n.t("license_status"));
+                            if license.is_expired {
+                                ui.label(egui::RichText::new("❌ Expired").color(egui::Color32::RED));
+                            } else if license.is_valid {
+                                ui.label(egui::RichText::new("✅ Active").color(egui::Color32::GREEN));
+                            } else {
+                                ui.label(egui::RichText::new("❌ Invalid").color(egui::Color32::RED));
                             }
                         });
                     }
                 });
             
-            self.show_backup_restore_window = window_open;
-            
-            // Start restore in background thread
-            if let Some(path) = restore_path {
-                self.restore_in_progress = true;
-                self.backup_status_message = Some(restore_in_progress_text.clone());
-                
-                // Create channel for result
-                let (tx, rx) = channel();
-                self.restore_result_receiver = Some(rx);
-                
-                // Spawn background thread for restore
-                std::thread::spawn(move || {
-                    let result = database::restore_from_backup(&path);
-                    let _ = tx.send(result.map_err(|e| e.to_string()));
-                });
+            // Handle window close button (X)
+            if !window_open {
+                self.show_license_window = false;
+                self.license_input.clear();
+                self.license_status_message = None;
             }
+        }
+        
+        // Bulk rating popup for the multi-selection
+        if self.show_bulk_rating_popup {
+            let count = self.selected_videos.len();
+            let mut chosen_rating: Option<u8> = None;
+            let mut close = count < 2 || ctx.input(|i| i.key_pressed(egui::Key::Escape));
             
-            // Check for restore completion
-            if self.restore_in_progress {
-                if let Some(ref receiver) = self.restore_result_receiver {
-                    if let Ok(result) = receiver.try_recv() {
-                        match result {
-                            Ok(()) => {
-                                // Reload database and start thumbnail regeneration in background
-                                if let Ok(new_db) = database::load_database() {
-                                    // Start thumbnail regeneration in background thread
-                                    let (tx, rx) = channel();
-                                    self.thumbnail_regen_receiver = Some(rx);
-                                    self.thumbnail_regen_in_progress = true;
-                                    
-                                    std::thread::spawn(move || {
-                                        let result = regenerate_missing_thumbnails_async(new_db);
-                                        let _ = tx.send(result);
-                                    });
-                                }
-                            }
-                            Err(e) => {
-                                self.backup_status_message = Some(format!("{}: {}", restore_failed_text, e));
+            egui::Window::new(format!("{} ({} videos)", self.i18n.t("rate_selected"), count))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    // Free tier can only mark favorites (set_rating caps higher values)
+                    let max_stars = if self.is_premium { 5 } else { 1 };
+                    ui.horizontal(|ui| {
+                        for stars in 1..=max_stars {
+                            if ui.button(egui::RichText::new("★".repeat(stars as usize)).size(20.0)).clicked() {
+                                chosen_rating = Some(stars);
                             }
                         }
-                        self.restore_in_progress = false;
-                        self.restore_result_receiver = None;
-                    }
-                }
-                // Request repaint to keep checking
-                ctx.request_repaint();
-            }
-            
-            // Check for thumbnail regeneration completion
-            if self.thumbnail_regen_in_progress {
-                if let Some(ref receiver) = self.thumbnail_regen_receiver {
-                    if let Ok(result) = receiver.try_recv() {
-                        match result {
-                            Ok(updated_db) => {
-                                self.database = updated_db;
-                                self.backup_status_message = Some(restore_success_text.clone());
-                                self.show_backup_restore_window = false;
-                            }
-                            Err(e) => {
-                                self.backup_status_message = Some(format!("{}: {}", restore_failed_text, e));
-                            }
+                    });
+                    
+                    ui.separator();
+                    
+                    ui.horizontal(|ui| {
+                        if ui.button(self.i18n.t("clear_rating")).clicked() {
+                            chosen_rating = Some(0);
                         }
-                        self.thumbnail_regen_in_progress = false;
-                        self.thumbnail_regen_receiver = None;
-                    }
-                }
-                // Request repaint to keep checking
-                ctx.request_repaint();
-            }
-        }
-        
-        // Folder Management Window
-        if self.show_folder_management_window {
-            let folder_management_title = self.i18n.t("folder_management");
-            let registered_folders_text = self.i18n.t("registered_folders");
-            let new_folder_name_text = self.i18n.t("new_folder_name");
-            let add_folder_name_text = self.i18n.t("add_folder_name");
+                        if ui.button(self.i18n.t("cancel")).clicked() {
+                            close = true;
+                        }
+                    });
+                });
             
-            let mut window_open = self.show_folder_management_window;
+            if let Some(rating) = chosen_rating {
+                self.set_rating_for_selected(rating);
+                close = true;
+            }
+            if close {
+                self.show_bulk_rating_popup = false;
+            }
+        }
+        
+        // Bulk folder popup for the multi-selection
+        if self.show_bulk_folder_popup {
+            let count = self.selected_videos.len();
+            let mut chosen: Option<(String, bool)> = None; // (folder, remove)
+            let mut close = count < 2 || ctx.input(|i| i.key_pressed(egui::Key::Escape));
             
-            egui::Window::new(&folder_management_title)
-                .open(&mut window_open)
-                .resizable(true)
-                .default_width(500.0)
+            egui::Window::new(format!("{} ({} videos)", self.i18n.t("folder_selected"), count))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
                 .show(ctx, |ui| {
-                    ui.label(&registered_folders_text);
-                    ui.separator();
-                    
-                    let mut folder_to_confirm: Option<(String, usize)> = None;
-                    let mut folders_changed = false;
-                    
-                    // Display folders with delete buttons
+                    ui.label(self.i18n.t("existing_folders"));
                     egui::ScrollArea::vertical()
-                        .max_height(300.0)
+                        .max_height(200.0)
                         .show(ui, |ui| {
-                            let folders = self.database.folders.clone();
-                            for folder in folders {
-                                ui.horizontal(|ui| {
-                                    ui.label(&folder);
-                                    if ui.button("❌").clicked() {
-                                        // Count videos in this folder
-                                        let video_count = self.database.videos.iter()
-                                            .filter(|v| v.folder.as_ref() == Some(&folder))
-                                            .count();
-                                        folder_to_confirm = Some((folder.clone(), video_count));
+                            egui::Grid::new("bulk_folder_grid").num_columns(3).striped(true).show(ui, |ui| {
+                                for folder in &self.database.folders {
+                                    // How many of the selected videos are already in this folder
+                                    let in_folder = self.selected_videos.iter()
+                                        .filter(|id| self.database.get_video(id).is_some_and(|v| v.folders.contains(folder)))
+                                        .count();
+                                    ui.label(format!("📁 {} ({}/{})", folder, in_folder, count));
+                                    if ui.add_enabled(in_folder < count, egui::Button::new(self.i18n.t("add_to_folder"))).clicked() {
+                                        chosen = Some((folder.clone(), false));
                                     }
-                                });
-                            }
+                                    if ui.add_enabled(in_folder > 0, egui::Button::new(self.i18n.t("remove_from_folder"))).clicked() {
+                                        chosen = Some((folder.clone(), true));
+                                    }
+                                    ui.end_row();
+                                }
+                            });
                         });
                     
-                    // Set confirmation if requested
-                    if let Some((folder, count)) = folder_to_confirm {
-                        self.folder_delete_confirm = Some((folder, count));
-                    }
-                    
                     ui.separator();
                     
-                    // Add new folder
-                    ui.label(&new_folder_name_text);
+                    ui.label(self.i18n.t("create_new_folder"));
                     ui.horizontal(|ui| {
-                        ui.text_edit_singleline(&mut self.new_folder_input);
-                        if ui.button(&add_folder_name_text).clicked() && !self.new_folder_input.trim().is_empty() {
-                            let new_folder = self.new_folder_input.trim().to_string();
-                            self.database.add_folder(new_folder);
-                            self.new_folder_input.clear();
-                            folders_changed = true;
+                        let response = ui.text_edit_singleline(&mut self.bulk_folder_input);
+                        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                        let name = self.bulk_folder_input.trim().to_string();
+                        if (ui.button(self.i18n.t("add_to_folder")).clicked() || submitted) && !name.is_empty() {
+                            chosen = Some((name, false));
                         }
                     });
                     
-                    if folders_changed {
-                        self.save_settings();
-                        let _ = database::save_database(&self.database);
+                    ui.separator();
+                    
+                    if ui.button(self.i18n.t("cancel")).clicked() {
+                        close = true;
                     }
                 });
             
-            self.show_folder_management_window = window_open;
+            if let Some((folder, remove)) = chosen {
+                self.set_folder_for_selected(folder, remove);
+                close = true;
+            }
+            if close {
+                self.show_bulk_folder_popup = false;
+                self.bulk_folder_input.clear();
+            }
         }
         
-        // Tag Management Window
-        if self.show_tag_management_window {
-            let tag_management_title = self.i18n.t("tag_management");
-            let registered_tags_text = self.i18n.t("registered_tags");
-            
-            let mut window_open = self.show_tag_management_window;
-            
-            egui::Window::new(&tag_management_title)
-                .open(&mut window_open)
-                .resizable(true)
-                .default_width(400.0)
-                .show(ctx, |ui| {
-                    ui.label(&registered_tags_text);
-                    ui.separator();
-                    
-                    let mut tag_to_confirm: Option<(String, usize)> = None;
-                    
-                    // Display tags with delete buttons
-                    egui::ScrollArea::vertical()
-                        .max_height(300.0)
-                        .show(ui, |ui| {
-                            let tags = self.database.tags.clone();
-                            for tag in tags {
-                                ui.horizontal(|ui| {
-                                    ui.label(format!("🏷 {}", &tag));
-                                    if ui.button("❌").clicked() {
-                                        // Count videos using this tag
-                                        let video_count = self.database.videos.iter()
-                                            .filter(|v| v.tags.contains(&tag))
-                                            .count();
-                                        tag_to_confirm = Some((tag.clone(), video_count));
+        // Library statistics window
+        if self.show_statistics_window {
+            self.show_statistics_window(ctx);
+        } else {
+            self.library_stats = None;
+        }
+        
+        // Expired license notice (once per license)
+        if self.show_license_expired_notice {
+            self.show_license_expired_notice(ctx);
+        }
+        
+        // Duplicate finder window
+        if self.show_duplicates_window {
+            self.show_duplicates_window(ctx);
+        }
+        
+        // Files skipped or incomplete in the last import/rescan
+        if self.show_import_issues_window {
+            self.show_import_issues_window(ctx);
+        }
+        
+        // Review mode overlay
+        if self.show_review_mode {
+            self.show_review_mode(ctx);
+        }
+        
+        // Scene lightbox overlay
+        if self.show_scene_lightbox {
+            self.show_scene_lightbox(ctx);
+        }
+    }
+    
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        // Persist playback positions reported while mpv was still running
+        self.flush_playback_changes();
+        self.flush_notes();
+        
+        // Persist thumbnail access times for the disk cache eviction policy
+        self.thumbnail_access_log.save();
+        
+        // Save window state on exit (size and position only, not maximized state)
+        let mut settings = database::load_settings().unwrap_or_default();
+        
+        settings.window_size = self.last_window_size;
+        settings.window_position = self.last_window_pos;
+        settings.scene_panel_width = self.scene_panel_width;
+        
+        if let Err(e) = database::save_settings(&settings) {
+            eprintln!("[app] Failed to save window state: {}", e);
+        } else {
+            eprintln!("[app] Window state saved: size={:?}, pos={:?}", 
+                self.last_window_size, self.last_window_pos);
+        }
+    }
+}
+
+impl VideoPlayerApp {
+    /// Horizontal "Continue watching" row listing the most recently played videos
+    fn show_recent_row(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        const MAX_RECENT: usize = 12;
+        
+        let mut recent: Vec<VideoFile> = self.database.videos.iter()
+            .filter(|v| v.last_played.is_some())
+            .filter(|v| self.protected_unlocked || !v.protected)
+            .cloned()
+            .collect();
+        if recent.is_empty() {
+            return;
+        }
+        recent.sort_by_key(|v| std::cmp::Reverse(v.last_played));
+        recent.truncate(MAX_RECENT);
+        
+        ui.label(egui::RichText::new(self.i18n.t("continue_watching")).strong());
+        
+        let thumbnail_size = egui::vec2(160.0, 90.0);
+        egui::ScrollArea::horizontal()
+            .id_salt("recent_row")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    for video in &recent {
+                        ui.vertical(|ui| {
+                            ui.set_width(thumbnail_size.x);
+                            let (rect, response) = ui.allocate_exact_size(thumbnail_size, egui::Sense::click());
+                            
+                            if ui.is_rect_visible(rect) {
+                                let texture = match video.thumbnail_path {
+                                    Some(ref path) if video.protected => self.load_blurred_texture(ctx, path),
+                                    Some(ref path) => self.load_image_texture(ctx, path),
+                                    None => None,
+                                };
+                                if let Some(texture) = texture {
+                                    ui.put(rect, egui::Image::new(&texture).fit_to_exact_size(thumbnail_size));
+                                } else {
+                                    ui.painter().rect_filled(rect, 4.0, egui::Color32::DARK_GRAY);
+                                }
+                                
+                                // Resume progress bar along the bottom edge
+                                if let (Some(position), Some(duration)) = (video.playback_position, video.duration) {
+                                    if duration > 0.0 {
+                                        let progress = (position / duration).clamp(0.0, 1.0) as f32;
+                                        let bar = egui::Rect::from_min_size(
+                                            egui::pos2(rect.min.x, rect.max.y - 4.0),
+                                            egui::vec2(rect.width() * progress, 4.0),
+                                        );
+                                        ui.painter().rect_filled(bar, 0.0, egui::Color32::from_rgb(220, 50, 50));
                                     }
-                                });
+                                }
+                                
+                                if response.hovered() {
+                                    ui.painter().rect_stroke(rect, 4.0, egui::Stroke::new(2.0, self.accent_color32()));
+                                }
                             }
                             
-                            if self.database.tags.is_empty() {
-                                ui.label("(No tags registered)");
+                            if response.clicked() {
+                                self.thumbnail_clicked_this_frame = true;
+                                self.selected_videos.clear();
+                                self.selected_video = Some(video.id.clone());
+                                self.last_selected_video = Some(video.id.clone());
+                                self.play_video_at(&video.path, video.playback_position.unwrap_or(0.0));
                             }
+                            response.on_hover_text(&video.title);
+                            
+                            ui.add(egui::Label::new(egui::RichText::new(&video.title).small()).truncate());
                         });
-                    
-                    // Set confirmation if requested
-                    if let Some((tag, count)) = tag_to_confirm {
-                        self.tag_delete_confirm = Some((tag, count));
                     }
                 });
-            
-            self.show_tag_management_window = window_open;
-        }
+            });
         
-        // Tag Delete Confirmation Window
-        if self.tag_delete_confirm.is_some() {
-            let confirm_title = self.i18n.t("confirm_tag_delete_title");
-            let confirm_msg = self.i18n.t("confirm_tag_delete");
-            let cancel_text = self.i18n.t("cancel");
-            
-            let mut should_close = false;
-            let mut delete_tag = false;
-            
-            // Keyboard shortcuts: 1 to confirm, 2 or Escape to cancel
-            if ctx.input(|i| i.key_pressed(egui::Key::Num1)) {
-                delete_tag = true;
-                should_close = true;
+        ui.separator();
+    }
+    
+    /// Full-window overlay showing all scenes of the selected video in a large grid
+    fn show_scene_lightbox(&mut self, ctx: &egui::Context) {
+        let video = match self.selected_video.as_ref().and_then(|id| self.database.get_video(id)) {
+            Some(video) if !video.scenes.is_empty() => video.clone(),
+            _ => {
+                self.show_scene_lightbox = false;
+                return;
             }
-            if ctx.input(|i| i.key_pressed(egui::Key::Num2) || i.key_pressed(egui::Key::Escape)) {
-                should_close = true;
+        };
+        
+        let screen_rect = ctx.screen_rect();
+        let thumbnail_size = egui::vec2(320.0, 180.0);
+        let item_spacing = 12.0;
+        let columns = (((screen_rect.width() - 40.0) / (thumbnail_size.x + item_spacing)).floor() as usize).max(1);
+        let last_index = video.scenes.len() - 1;
+        
+        // Keyboard navigation
+        let mut focus = self.lightbox_focused_scene.min(last_index);
+        let mut focus_changed = false;
+        let mut play_index: Option<usize> = None;
+        let mut close = false;
+        ctx.input(|i| {
+            if i.key_pressed(egui::Key::Escape) {
+                close = true;
             }
-            
-            egui::Window::new(&confirm_title)
-                .collapsible(false)
-                .resizable(false)
-                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
-                .show(ctx, |ui| {
-                    if let Some((tag_name, video_count)) = &self.tag_delete_confirm {
-                        ui.label(format!("🏷 {}", tag_name));
-                        ui.separator();
-                        ui.label(&confirm_msg);
-                        
-                        if *video_count > 0 {
-                            ui.add_space(8.0);
-                            let videos_msg = self.i18n.t("tag_used_in_videos")
-                                .replace("{}", &video_count.to_string());
-                            ui.label(egui::RichText::new(videos_msg).color(egui::Color32::YELLOW));
-                            ui.label(self.i18n.t("tag_will_be_removed"));
+            if i.key_pressed(egui::Key::ArrowRight) {
+                focus = (focus + 1).min(last_index);
+                focus_changed = true;
+            }
+            if i.key_pressed(egui::Key::ArrowLeft) {
+                focus = focus.saturating_sub(1);
+                focus_changed = true;
+            }
+            if i.key_pressed(egui::Key::ArrowDown) {
+                focus = (focus + columns).min(last_index);
+                focus_changed = true;
+            }
+            if i.key_pressed(egui::Key::ArrowUp) && focus >= columns {
+                focus -= columns;
+                focus_changed = true;
+            }
+            if i.key_pressed(egui::Key::Home) {
+                focus = 0;
+                focus_changed = true;
+            }
+            if i.key_pressed(egui::Key::End) {
+                focus = last_index;
+                focus_changed = true;
+            }
+            if i.key_pressed(egui::Key::Enter) {
+                play_index = Some(focus);
+            }
+        });
+        
+        egui::Area::new(egui::Id::new("scene_lightbox"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(screen_rect.min)
+            .show(ctx, |ui| {
+                // Dim the background and block clicks to the panels underneath
+                ui.painter().rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(230));
+                ui.interact(screen_rect, ui.id().with("lightbox_background"), egui::Sense::click());
+                ui.set_min_size(screen_rect.size());
+                
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.add_space(20.0);
+                    ui.label(egui::RichText::new(&video.title).strong().size(18.0).color(egui::Color32::WHITE));
+                    ui.label(egui::RichText::new(format!("({})", video.scenes.len())).color(egui::Color32::LIGHT_GRAY));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.add_space(20.0);
+                        if ui.button("✕").clicked() {
+                            close = true;
                         }
-                        
-                        ui.add_space(16.0);
-                        
-                        // Keyboard hint
-                        ui.label(egui::RichText::new("1: OK | 2: Cancel").weak().small());
-                        ui.add_space(4.0);
-                        
-                        ui.horizontal(|ui| {
-                            if ui.button(egui::RichText::new("[1] OK").color(egui::Color32::RED)).clicked() {
-                                delete_tag = true;
-                                should_close = true;
-                            }
-                            if ui.button(format!("[2] {}", &cancel_text)).clicked() {
-                                should_close = true;
-                            }
-                        });
+                        ui.label(egui::RichText::new(self.i18n.t("lightbox_hint")).color(egui::Color32::LIGHT_GRAY));
+                    });
+                });
+                ui.add_space(10.0);
+                
+                egui::ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .max_height(screen_rect.height() - 60.0)
+                    .show(ui, |ui| {
+                        for (row_index, row) in video.scenes.chunks(columns).enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.add_space(20.0);
+                                for (col_index, scene) in row.iter().enumerate() {
+                                    let scene_index = row_index * columns + col_index;
+                                    ui.vertical(|ui| {
+                                        let (rect, response) = ui.allocate_exact_size(thumbnail_size, egui::Sense::click());
+                                        
+                                        if ui.is_rect_visible(rect) {
+                                            if let Some(texture) = self.load_image_texture(ctx, &scene.thumbnail_path) {
+                                                ui.put(rect, egui::Image::new(&texture).fit_to_exact_size(thumbnail_size));
+                                            } else {
+                                                ui.painter().rect_filled(rect, 4.0, egui::Color32::DARK_GRAY);
+                                            }
+                                            
+                                            if scene_index == focus {
+                                                ui.painter().rect_stroke(rect, 4.0, egui::Stroke::new(3.0, self.accent_color32()));
+                                            } else if response.hovered() {
+                                                ui.painter().rect_stroke(rect, 4.0, egui::Stroke::new(1.0, egui::Color32::WHITE));
+                                            }
+                                        }
+                                        
+                                        if focus_changed && scene_index == focus {
+                                            response.scroll_to_me(None);
+                                        }
+                                        
+                                        if response.clicked() {
+                                            focus = scene_index;
+                                            play_index = Some(scene_index);
+                                        }
+                                        
+                                        ui.label(egui::RichText::new(scene_detection::format_timestamp(scene.timestamp)).color(egui::Color32::WHITE));
+                                    });
+                                    ui.add_space(item_spacing);
+                                }
+                            });
+                            ui.add_space(item_spacing);
+                        }
+                    });
+            });
+        
+        self.lightbox_focused_scene = focus;
+        
+        if let Some(index) = play_index {
+            self.play_video_at(&video.path, video.scenes[index].timestamp);
+        }
+        
+        if close {
+            self.show_scene_lightbox = false;
+        }
+    }
+    
+    /// Full-window review mode: rate the filtered videos one at a time with the keyboard
+    fn show_review_mode(&mut self, ctx: &egui::Context) {
+        let video_ids: Vec<String> = self.get_filtered_videos().iter().map(|v| v.id.clone()).collect();
+        
+        // Start at the selected video or the first unrated one
+        let current_index = self.review_video_id.as_ref()
+            .and_then(|id| video_ids.iter().position(|v| v == id))
+            .or_else(|| {
+                let unrated = |id: &String| self.database.get_video(id).is_some_and(|v| v.rating == 0);
+                video_ids.iter().position(unrated).or(if video_ids.is_empty() { None } else { Some(0) })
+            });
+        let video = match current_index.and_then(|i| self.database.get_video(&video_ids[i])) {
+            Some(video) => video.clone(),
+            None => {
+                self.show_review_mode = false;
+                return;
+            }
+        };
+        let current_index = current_index.unwrap_or(0);
+        self.review_video_id = Some(video.id.clone());
+        
+        // Take keyboard focus away from text fields behind the overlay (e.g. the search box)
+        ctx.memory_mut(|m| {
+            if let Some(id) = m.focused() {
+                m.surrender_focus(id);
+            }
+        });
+        
+        // Keyboard handling: 0-5 rate, arrows move, Enter/Space play, Esc exits
+        let mut new_rating: Option<u8> = None;
+        let mut next = false;
+        let mut previous = false;
+        let mut play = false;
+        let mut close = false;
+        ctx.input(|i| {
+            let rating_keys = [
+                egui::Key::Num0, egui::Key::Num1, egui::Key::Num2,
+                egui::Key::Num3, egui::Key::Num4, egui::Key::Num5,
+            ];
+            for (rating, key) in rating_keys.iter().enumerate() {
+                if i.key_pressed(*key) {
+                    new_rating = Some(rating as u8);
+                }
+            }
+            if i.key_pressed(egui::Key::ArrowRight) || i.key_pressed(egui::Key::ArrowDown) {
+                next = true;
+            }
+            if i.key_pressed(egui::Key::ArrowLeft) || i.key_pressed(egui::Key::ArrowUp) {
+                previous = true;
+            }
+            if i.key_pressed(egui::Key::Enter) || i.key_pressed(egui::Key::Space) {
+                play = true;
+            }
+            if i.key_pressed(egui::Key::Escape) {
+                close = true;
+            }
+        });
+        
+        let screen_rect = ctx.screen_rect();
+        let unrated_count = video_ids.iter()
+            .filter(|id| self.database.get_video(id).is_some_and(|v| v.rating == 0))
+            .count();
+        
+        egui::Area::new(egui::Id::new("review_mode"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(screen_rect.min)
+            .show(ctx, |ui| {
+                // Dim the background and block clicks to the panels underneath
+                ui.painter().rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(235));
+                ui.interact(screen_rect, ui.id().with("review_background"), egui::Sense::click());
+                ui.set_min_size(screen_rect.size());
+                
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.add_space(20.0);
+                    ui.label(egui::RichText::new(format!("{} / {}", current_index + 1, video_ids.len())).color(egui::Color32::WHITE));
+                    ui.label(egui::RichText::new(self.i18n.t("review_unrated").replace("{}", &unrated_count.to_string())).color(egui::Color32::LIGHT_GRAY));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.add_space(20.0);
+                        if ui.button("✕").clicked() {
+                            close = true;
+                        }
+                        ui.label(egui::RichText::new(self.i18n.t("review_mode_hint")).color(egui::Color32::LIGHT_GRAY));
+                    });
+                });
+                
+                ui.vertical_centered(|ui| {
+                    ui.add_space(20.0);
+                    ui.label(egui::RichText::new(&video.title).strong().size(20.0).color(egui::Color32::WHITE));
+                    ui.add_space(10.0);
+                    
+                    // Large thumbnail (click to play)
+                    let max_width = (screen_rect.width() - 80.0).max(160.0);
+                    let max_height = (screen_rect.height() - 220.0).max(90.0);
+                    let thumbnail_size = if max_width / max_height > 16.0 / 9.0 {
+                        egui::vec2(max_height * 16.0 / 9.0, max_height)
+                    } else {
+                        egui::vec2(max_width, max_width * 9.0 / 16.0)
+                    };
+                    let texture = video.thumbnail_path.as_ref().and_then(|path| self.load_image_texture(ctx, path));
+                    let (rect, response) = ui.allocate_exact_size(thumbnail_size, egui::Sense::click());
+                    if let Some(texture) = texture {
+                        ui.put(rect, egui::Image::new(&texture).fit_to_exact_size(thumbnail_size));
+                    } else {
+                        ui.painter().rect_filled(rect, 4.0, egui::Color32::DARK_GRAY);
+                    }
+                    if response.hovered() {
+                        ui.painter().rect_stroke(rect, 4.0, egui::Stroke::new(1.0, egui::Color32::WHITE));
+                    }
+                    if response.on_hover_text(self.i18n.t("review_play_hint")).clicked() {
+                        play = true;
+                    }
+                    
+                    ui.add_space(10.0);
+                    let stars = if self.is_premium {
+                        format!("{}{}", "★".repeat(video.rating as usize), "☆".repeat(5 - video.rating.min(5) as usize))
+                    } else if video.rating > 0 {
+                        "★".to_string()
+                    } else {
+                        "☆".to_string()
+                    };
+                    ui.label(egui::RichText::new(stars).size(28.0).color(egui::Color32::GOLD));
+                    if let Some(duration) = video.duration {
+                        ui.label(egui::RichText::new(scene_detection::format_timestamp(duration)).color(egui::Color32::LIGHT_GRAY));
                     }
                 });
-            
-            // Process deletion after window is closed
-            if should_close {
-                if let Some((tag, _video_count)) = self.tag_delete_confirm.take() {
-                    if delete_tag {
-                        // Remove tag from tags list
-                        self.database.tags.retain(|t| t != &tag);
-                        
-                        // Remove tag from all videos
-                        for video in &mut self.database.videos {
-                            video.tags.retain(|t| t != &tag);
-                        }
-                        
-                        // Also remove from selected tag filter
-                        self.selected_tag_filter.remove(&tag);
-                        
-                        eprintln!("[tag_management] Removed tag '{}' from database and all videos", tag);
-                        
-                        self.save_settings();
-                        let _ = database::save_database(&self.database);
+            });
+        
+        if play {
+            self.play_video_at(&video.path, 0.0);
+        }
+        
+        if let Some(rating) = new_rating {
+            self.set_rating(&video.id, rating);
+            next = true;
+        }
+        
+        if next {
+            // Advance to the next unrated video after the current one
+            let next_unrated = video_ids.iter().skip(current_index + 1)
+                .find(|id| self.database.get_video(id).is_some_and(|v| v.rating == 0));
+            match next_unrated {
+                Some(id) => self.review_video_id = Some(id.clone()),
+                None if new_rating.is_some() && unrated_count <= 1 => close = true, // Everything is rated
+                None => {
+                    if let Some(id) = video_ids.get(current_index + 1) {
+                        self.review_video_id = Some(id.clone());
                     }
                 }
             }
+        } else if previous && current_index > 0 {
+            self.review_video_id = Some(video_ids[current_index - 1].clone());
         }
         
-        // Folder Delete Confirmation Window
-        if self.folder_delete_confirm.is_some() {
-            let confirm_title = self.i18n.t("confirm_folder_delete_title");
-            let confirm_msg = self.i18n.t("confirm_folder_delete");
-            let cancel_text = self.i18n.t("cancel");
-            
-            let mut should_close = false;
-            let mut delete_with_videos = false;
-            let mut delete_folder_only = false;
-            
-            // Check if there are videos in this folder
-            let has_videos = self.folder_delete_confirm.as_ref()
-                .map(|(_, count)| *count > 0)
-                .unwrap_or(false);
-            
-            // Keyboard shortcuts
-            if ctx.input(|i| i.key_pressed(egui::Key::Escape) || i.key_pressed(egui::Key::Num3)) {
-                should_close = true;
+        if close {
+            // Return to the grid with the last reviewed video selected
+            self.show_review_mode = false;
+            if let Some(id) = self.review_video_id.take() {
+                self.selected_videos.clear();
+                self.selected_videos.insert(id.clone());
+                self.selected_video = Some(id);
             }
-            if has_videos {
-                // 1: Delete with videos, 2: Folder only, 3: Cancel
-                if ctx.input(|i| i.key_pressed(egui::Key::Num1)) {
-                    delete_with_videos = true;
-                    should_close = true;
-                }
-                if ctx.input(|i| i.key_pressed(egui::Key::Num2)) {
-                    delete_folder_only = true;
-                    should_close = true;
-                }
-            } else {
-                // 1: OK, 2: Cancel
-                if ctx.input(|i| i.key_pressed(egui::Key::Num1)) {
-                    delete_folder_only = true;
-                    should_close = true;
+        }
+    }
+    
+    /// Filter controls (rating, folders, tags) shown in the side panel or the filters window
+    fn show_filters(&mut self, ui: &mut egui::Ui) {
+        ui.heading(&self.i18n.t("filters"));
+        ui.separator();
+        
+        // Filter presets
+        ui.label(self.i18n.t("filter_presets"));
+        let mut apply_preset = None;
+        egui::ComboBox::from_id_salt("filter_preset_combo")
+            .selected_text(self.i18n.t("apply_preset"))
+            .show_ui(ui, |ui| {
+                if self.filter_presets.is_empty() {
+                    ui.weak(self.i18n.t("no_presets"));
                 }
-                if ctx.input(|i| i.key_pressed(egui::Key::Num2)) {
-                    should_close = true;
+                for (idx, preset) in self.filter_presets.iter().enumerate() {
+                    if ui.selectable_label(false, &preset.name).clicked() {
+                        apply_preset = Some(idx);
+                    }
                 }
+            });
+        if let Some(idx) = apply_preset {
+            self.apply_filter_preset(idx);
+        }
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.new_preset_name)
+                .hint_text(self.i18n.t("preset_name"))
+                .desired_width(120.0));
+            let name = self.new_preset_name.trim().to_string();
+            if ui.add_enabled(!name.is_empty(), egui::Button::new(self.i18n.t("save_current_filter"))).clicked() {
+                self.save_filter_preset(&name);
+                self.new_preset_name.clear();
             }
-            
-            egui::Window::new(&confirm_title)
-                .collapsible(false)
-                .resizable(false)
-                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
-                .show(ctx, |ui| {
-                    if let Some((folder_name, video_count)) = &self.folder_delete_confirm {
-                        ui.label(format!("📁 {}", folder_name));
-                        ui.separator();
-                        ui.label(&confirm_msg);
-                        
-                        if *video_count > 0 {
-                            ui.add_space(8.0);
-                            let videos_msg = self.i18n.t("folder_contains_videos")
-                                .replace("{}", &video_count.to_string());
-                            ui.label(egui::RichText::new(videos_msg).color(egui::Color32::YELLOW));
-                        }
-                        
-                        ui.add_space(16.0);
-                        
-                        // Keyboard hint
-                        if *video_count > 0 {
-                            ui.label(egui::RichText::new("1: Delete All | 2: Folder Only | 3: Cancel").weak().small());
-                        } else {
-                            ui.label(egui::RichText::new("1: OK | 2: Cancel").weak().small());
-                        }
-                        ui.add_space(4.0);
-                        
+        });
+        if !self.filter_presets.is_empty() {
+            egui::CollapsingHeader::new(self.i18n.t("manage_presets"))
+                .id_salt("manage_filter_presets")
+                .show(ui, |ui| {
+                    let mut delete_idx = None;
+                    let mut rename_done = false;
+                    let mut rename_cancel = false;
+                    let mut start_rename = None;
+                    for (idx, preset) in self.filter_presets.iter().enumerate() {
                         ui.horizontal(|ui| {
-                            if *video_count > 0 {
-                                // Show both options when there are videos
-                                if ui.button(egui::RichText::new(format!("[1] {}", self.i18n.t("delete_videos_too"))).color(egui::Color32::RED)).clicked() {
-                                    delete_with_videos = true;
-                                    should_close = true;
-                                }
-                                if ui.button(format!("[2] {}", self.i18n.t("folder_only"))).clicked() {
-                                    delete_folder_only = true;
-                                    should_close = true;
+                            match &mut self.renaming_preset {
+                                Some((rename_idx, new_name)) if *rename_idx == idx => {
+                                    let response = ui.add(egui::TextEdit::singleline(new_name).desired_width(120.0));
+                                    if ui.small_button("✔").clicked()
+                                        || (response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter))) {
+                                        rename_done = true;
+                                    }
+                                    if ui.small_button("✖").clicked() {
+                                        rename_cancel = true;
+                                    }
                                 }
-                            } else {
-                                // No videos, just confirm folder removal
-                                if ui.button("[1] OK").clicked() {
-                                    delete_folder_only = true;
-                                    should_close = true;
+                                _ => {
+                                    ui.label(&preset.name);
+                                    if ui.small_button("✏").on_hover_text(self.i18n.t("rename")).clicked() {
+                                        start_rename = Some(idx);
+                                    }
+                                    if ui.small_button("🗑").on_hover_text(self.i18n.t("delete")).clicked() {
+                                        delete_idx = Some(idx);
+                                    }
                                 }
                             }
-                            if ui.button(format!("[{}] {}", if *video_count > 0 { "3" } else { "2" }, &cancel_text)).clicked() {
-                                should_close = true;
-                            }
                         });
                     }
-                });
-            
-            // Process deletion after window is closed
-            if should_close {
-                if let Some((folder, _video_count)) = self.folder_delete_confirm.take() {
-                    if delete_with_videos || delete_folder_only {
-                        // Remove folder from list
-                        self.database.folders.retain(|f| f != &folder);
-                        
-                        // Remove from watched_folders
-                        self.watched_folders.retain(|path| {
-                            path.file_name()
-                                .and_then(|n| n.to_str())
-                                .map(|name| name != folder)
-                                .unwrap_or(true)
-                        });
-                        
-                        if delete_with_videos {
-                            // Also delete video profiles
-                            self.database.videos.retain(|v| v.folder.as_ref() != Some(&folder));
-                            eprintln!("[folder_management] Removed folder '{}' and its video profiles", folder);
-                        } else {
-                            eprintln!("[folder_management] Removed folder '{}' (kept video profiles)", folder);
-                        }
-                        
-                        self.save_settings();
-                        let _ = database::save_database(&self.database);
+                    if let Some(idx) = start_rename {
+                        self.renaming_preset = Some((idx, self.filter_presets[idx].name.clone()));
                     }
-                }
-            }
-        }
-        
-        // Shader Management Window
-        if self.show_shader_management_window {
-            let shader_management_title = self.i18n.t("shader_management");
-            let select_shader_text = self.i18n.t("select_shader_to_use");
-            
-            let mut window_open = self.show_shader_management_window;
-            
-            egui::Window::new(&shader_management_title)
-                .open(&mut window_open)
-                .resizable(true)
-                .default_width(400.0)
-                .show(ctx, |ui| {
-                    ui.label(&select_shader_text);
-                    ui.separator();
-                    
-                    let mut shader_changed = false;
-                    let available_shaders = video_player::get_available_shaders();
-                    
-                    if available_shaders.is_empty() {
-                        ui.label("⚠ No shaders found in mpv/glsl_shaders directory");
-                        ui.label("📁 Place .glsl shader files in the mpv/glsl_shaders directory");
-                    } else {
-                        // Add "None" option
-                        if ui.radio(self.selected_shader.is_none(), "None").clicked() {
-                            self.selected_shader = None;
-                            shader_changed = true;
+                    if rename_cancel {
+                        self.renaming_preset = None;
+                    }
+                    if rename_done {
+                        if let Some((idx, new_name)) = self.renaming_preset.take() {
+                            let new_name = new_name.trim().to_string();
+                            let taken = self.filter_presets.iter().enumerate()
+                                .any(|(i, p)| i != idx && p.name == new_name);
+                            if !new_name.is_empty() && !taken {
+                                self.filter_presets[idx].name = new_name;
+                                self.save_settings();
+                            }
                         }
-                        
-                        ui.separator();
-                        
-                        // Add radio button for each shader
-                        egui::ScrollArea::vertical()
-                            .max_height(400.0)
-                            .show(ui, |ui| {
-                                for shader_name in &available_shaders {
-                                    let is_selected = self.selected_shader.as_ref() == Some(shader_name);
-                                    if ui.radio(is_selected, shader_name).clicked() {
-                                        self.selected_shader = Some(shader_name.clone());
-                                        shader_changed = true;
-                                    }
-                                }
-                            });
                     }
-                    
-                    if shader_changed {
+                    if let Some(idx) = delete_idx {
+                        self.filter_presets.remove(idx);
+                        self.renaming_preset = None;
                         self.save_settings();
                     }
                 });
-            
-            self.show_shader_management_window = window_open;
         }
+        ui.separator();
         
-        // Delete confirmation dialog
-        if let Some(video_id) = self.delete_confirm_video.clone() {
-            let is_multi = video_id == "__MULTI__";
-            let title = if is_multi { 
-                self.i18n.t("delete_selected_videos")
-            } else { 
-                self.i18n.t("delete_video")
-            };
+        // Rating filter
+        ui.label(&self.i18n.t("min_rating"));
+        ui.horizontal_wrapped(|ui| {
+            if ui.radio(self.min_rating_filter == 0, "All").clicked() {
+                self.min_rating_filter = 0;
+            }
             
-            egui::Window::new(&title)
-                .collapsible(false)
-                .resizable(false)
-                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
-                .show(ctx, |ui| {
-                    if is_multi {
-                        let confirm_text = self.i18n.t("confirm_delete_videos").replace("{}", &self.selected_videos.len().to_string());
-                        ui.label(&confirm_text);
-                    } else {
-                        ui.label(&self.i18n.t("confirm_delete_video"));
-                        
-                        if let Some(video) = self.database.get_video(&video_id) {
-                            let title_text = self.i18n.t("title_label").replace("{}", &video.title);
-                            ui.label(&title_text);
+            if self.is_premium {
+                // Premium: 1-5 star ratings
+                for rating in 1..=5 {
+                    let label = format!("{}★+", rating);
+                    if ui.radio(self.min_rating_filter == rating, label).clicked() {
+                        self.min_rating_filter = rating;
+                    }
+                }
+            } else {
+                // Free: Favorite only
+                if ui.radio(self.min_rating_filter == 1, "⭐ Favorites").clicked() {
+                    self.min_rating_filter = 1;
+                }
+            }
+        });
+        ui.separator();
+        
+        // Watched filter
+        ui.label(self.i18n.t("watched_filter"));
+        ui.horizontal_wrapped(|ui| {
+            for (filter, key) in [
+                (WatchedFilter::All, "all"),
+                (WatchedFilter::Watched, "watched"),
+                (WatchedFilter::Unwatched, "unwatched"),
+            ] {
+                if ui.radio(self.watched_filter == filter, self.i18n.t(key)).clicked() {
+                    self.watched_filter = filter;
+                }
+            }
+        });
+        ui.separator();
+        
+        // Folder filter
+        ui.label(&self.i18n.t("folders"));
+        if ui.button(&self.i18n.t("all")).clicked() {
+            self.selected_folder_filter.clear();
+            self.last_selected_folder = None;
+        }
+        let folders_clone = self.database.folders.clone();
+        egui::ScrollArea::vertical()
+            .id_salt("folder_filter_scroll")
+            .max_height(300.0)
+            .show(ui, |ui| {
+                for (idx, folder) in folders_clone.iter().enumerate() {
+                    let is_selected = self.selected_folder_filter.contains(folder);
+                    let response = ui.selectable_label(is_selected, folder);
+                    
+                    if response.clicked() {
+                        let modifiers = ui.input(|i| i.modifiers);
+                        
+                        if self.is_premium {
+                            // Premium: Ctrl+Click for multiple selection, Shift+Click for range selection
+                            if modifiers.shift && self.last_selected_folder.is_some() {
+                                // Shift+Click: Range selection
+                                let last_idx = self.last_selected_folder.unwrap();
+                                let (start, end) = if idx < last_idx { (idx, last_idx) } else { (last_idx, idx) };
+                                for i in start..=end {
+                                    if let Some(f) = folders_clone.get(i) {
+                                        self.selected_folder_filter.insert(f.clone());
+                                    }
+                                }
+                            } else if modifiers.ctrl || modifiers.command {
+                                // Ctrl+Click: Toggle selection
+                                if is_selected {
+                                    self.selected_folder_filter.remove(folder);
+                                } else {
+                                    self.selected_folder_filter.insert(folder.clone());
+                                }
+                                self.last_selected_folder = Some(idx);
+                            } else {
+                                // Normal click: Single selection (clear others)
+                                self.selected_folder_filter.clear();
+                                self.selected_folder_filter.insert(folder.clone());
+                                self.last_selected_folder = Some(idx);
+                            }
+                        } else {
+                            // Free: Single selection only
+                            self.selected_folder_filter.clear();
+                            self.selected_folder_filter.insert(folder.clone());
                         }
                     }
                     
-                    ui.separator();
-                    
-                    ui.horizontal(|ui| {
-                        if ui.button(&self.i18n.t("delete_keep_cache")).clicked() {
-                            if is_multi {
-                                self.delete_selected_videos(false);
-                            } else {
-                                self.delete_video(&video_id, false);
+                    response.context_menu(|ui| {
+                        if ui.button(self.i18n.t("select_all_in_folder")).clicked() {
+                            // 表示中（検索・フィルタ適用後）の動画のみ選択
+                            let ids: Vec<String> = self.get_filtered_videos()
+                                .into_iter()
+                                .filter(|v| v.folders.contains(folder))
+                                .map(|v| v.id.clone())
+                                .collect();
+                            self.select_all_videos(ids);
+                            ui.close_menu();
+                        }
+                    });
+                }
+            });
+        
+        ui.separator();
+        
+        // Tag filter
+        ui.label(&self.i18n.t("tags_colon"));
+        ui.horizontal(|ui| {
+            if ui.button(&self.i18n.t("all")).clicked() {
+                self.selected_tag_filter.clear();
+                self.last_selected_tag = None;
+            }
+            // AND/OR toggle (only show when premium and multiple tags selected)
+            if self.is_premium && self.selected_tag_filter.len() > 1 {
+                ui.separator();
+                if ui.selectable_label(self.tag_filter_mode == FilterMode::Or, "OR").clicked() {
+                    self.tag_filter_mode = FilterMode::Or;
+                }
+                if ui.selectable_label(self.tag_filter_mode == FilterMode::And, "AND").clicked() {
+                    self.tag_filter_mode = FilterMode::And;
+                }
+            }
+        });
+        // "parent/child" tags are shown as a tree; rows hidden under collapsed parents are skipped
+        let tag_rows = tag_tree_rows(&self.database.tags);
+        let visible_rows: Vec<&TagTreeRow> = tag_rows.iter()
+            .filter(|row| row.ancestors().all(|ancestor| self.expanded_tag_nodes.contains(ancestor)))
+            .collect();
+        let tags_clone: Vec<String> = visible_rows.iter().map(|row| row.path.clone()).collect();
+        egui::ScrollArea::vertical()
+            .id_salt("tag_filter_scroll")
+            .max_height(300.0)
+            .show(ui, |ui| {
+                for (idx, row) in visible_rows.iter().enumerate() {
+                    let tag = &row.path;
+                    let is_selected = self.selected_tag_filter.contains(tag);
+                    let response = ui.horizontal(|ui| {
+                        ui.add_space(row.depth as f32 * 14.0);
+                        if row.has_children {
+                            let expanded = self.expanded_tag_nodes.contains(tag);
+                            if ui.small_button(if expanded { "▼" } else { "▶" }).clicked() {
+                                if expanded {
+                                    self.expanded_tag_nodes.remove(tag);
+                                } else {
+                                    self.expanded_tag_nodes.insert(tag.clone());
+                                }
                             }
-                            self.delete_confirm_video = None;
                         }
+                        ui.selectable_label(is_selected, row.name())
+                    }).inner;
+                    
+                    if response.clicked() {
+                        let modifiers = ui.input(|i| i.modifiers);
                         
-                        if ui.button(&self.i18n.t("delete_remove_all")).clicked() {
-                            if is_multi {
-                                self.delete_selected_videos(true);
+                        if self.is_premium {
+                            // Premium: Ctrl+Click for multiple selection, Shift+Click for range selection
+                            // The anchor is a path, so expanding/collapsing nodes in between keeps it valid
+                            let anchor_idx = self.last_selected_tag.as_ref()
+                                .and_then(|anchor| tags_clone.iter().position(|t| t == anchor));
+                            if let (true, Some(last_idx)) = (modifiers.shift, anchor_idx) {
+                                // Shift+Click: Range selection
+                                let (start, end) = if idx < last_idx { (idx, last_idx) } else { (last_idx, idx) };
+                                for i in start..=end {
+                                    if let Some(t) = tags_clone.get(i) {
+                                        self.selected_tag_filter.insert(t.clone());
+                                    }
+                                }
+                            } else if modifiers.ctrl || modifiers.command {
+                                // Ctrl+Click: Toggle selection
+                                if is_selected {
+                                    self.selected_tag_filter.remove(tag);
+                                } else {
+                                    self.selected_tag_filter.insert(tag.clone());
+                                }
+                                self.last_selected_tag = Some(tag.clone());
                             } else {
-                                self.delete_video(&video_id, true);
+                                // Normal click: Single selection (clear others)
+                                self.selected_tag_filter.clear();
+                                self.selected_tag_filter.insert(tag.clone());
+                                self.last_selected_tag = Some(tag.clone());
                             }
-                            self.delete_confirm_video = None;
+                        } else {
+                            // Free: Single selection only
+                            self.selected_tag_filter.clear();
+                            self.selected_tag_filter.insert(tag.clone());
                         }
-                        
-                        if ui.button(&self.i18n.t("cancel")).clicked() {
-                            self.delete_confirm_video = None;
+                    }
+                    
+                    response.context_menu(|ui| {
+                        if ui.button(self.i18n.t("select_all_with_tag")).clicked() {
+                            // 表示中（検索・フィルタ適用後）の動画のみ選択
+                            let ids: Vec<String> = self.get_filtered_videos()
+                                .into_iter()
+                                .filter(|v| v.tags.iter().any(|t| tag_in_subtree(t, tag)))
+                                .map(|v| v.id.clone())
+                                .collect();
+                            self.select_all_videos(ids);
+                            ui.close_menu();
                         }
                     });
-                });
+                }
+            });
+        
+        ui.separator();
+        
+        // Statistics
+        let total_text = self.i18n.t("total_videos").replace("{}", &self.database.videos.len().to_string());
+        let rated_text = self.i18n.t("favorites_count").replace("{}", &self.database.get_by_rating(1).len().to_string());
+        ui.label(&total_text);
+        ui.label(&rated_text);
+    }
+    
+    /// Selected tags expanded to the tags they stand for: a parent tag covers the leaves below it
+    /// (and itself, when it is also applied to videos directly)
+    fn resolved_tag_filter(&self) -> HashSet<String> {
+        self.selected_tag_filter.iter()
+            .flat_map(|selected| tag_filter_targets(&self.database.tags, selected))
+            .collect()
+    }
+    
+    /// Narrow window: filters move from the side panel into a window opened from the top bar
+    fn is_compact_layout(&self, ctx: &egui::Context) -> bool {
+        self.auto_collapse_filters && ctx.screen_rect().width() < COMPACT_LAYOUT_WIDTH
+    }
+    
+    fn show_grid_view(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, videos: &[VideoFile]) {
+        if self.group_by_folder {
+            self.show_grouped_grid_view(ui, ctx, videos);
+            return;
         }
         
-        // MPV shortcuts panel at bottom left
-        if self.show_mpv_shortcuts {
-            let mut window_open = self.mpv_shortcuts_open;
-            let window_was_open = window_open;
-            
-            // Use saved position or default to bottom-left
-            let default_pos = egui::pos2(10.0, ctx.screen_rect().max.y - 280.0);
-            let initial_pos = self.mpv_shortcuts_pos.unwrap_or(default_pos);
-            
-            let response = egui::Window::new("MPV Shortcuts")
-                .default_pos(initial_pos)
-                .resizable(false)
-                .collapsible(true)
-                .open(&mut window_open)
-                .show(ctx, |ui| {
-                    ui.set_width(180.0);
-                    
-                    ui.label(egui::RichText::new("⌨ Keyboard Shortcuts").strong());
-                    ui.separator();
-                    
-                    ui.horizontal(|ui| {
-                        ui.label(egui::RichText::new("Space").code().strong());
-                        ui.label("Play/Pause");
-                    });
-                    
-                    ui.horizontal(|ui| {
-                        ui.label(egui::RichText::new("F").code().strong());
-                        ui.label("Toggle Fullscreen");
-                    });
-                    
-                    ui.horizontal(|ui| {
-                        ui.label(egui::RichText::new("←/→").code().strong());
-                        ui.label("Seek -5/+5 sec");
-                    });
-                    
-                    ui.horizontal(|ui| {
-                        ui.label(egui::RichText::new("↑/↓").code().strong());
-                        ui.label("Seek -60/+60 sec");
-                    });
-                    
-                    ui.horizontal(|ui| {
-                        ui.label(egui::RichText::new("9/0").code().strong());
-                        ui.label("Volume Down/Up");
-                    });
-                    
-                    ui.horizontal(|ui| {
-                        ui.label(egui::RichText::new("M").code().strong());
-                        ui.label("Mute/Unmute");
-                    });
-                    
-                    ui.horizontal(|ui| {
-                        ui.label(egui::RichText::new("[/]").code().strong());
-                        ui.label("Speed -10%/+10%");
-                    });
-                    
-                    ui.horizontal(|ui| {
-                        ui.label(egui::RichText::new("{/}").code().strong());
-                        ui.label("Speed x0.5/x2.0");
-                    });
-                    
-                    ui.horizontal(|ui| {
-                        ui.label(egui::RichText::new("Q").code().strong());
-                        ui.label("Quit Player");
-                    });
-                });
-            
-            // Track window position after drag
-            if let Some(inner_response) = response {
-                let new_pos = inner_response.response.rect.min;
-                let pos_changed = self.mpv_shortcuts_pos.map(|p| (p.x - new_pos.x).abs() > 1.0 || (p.y - new_pos.y).abs() > 1.0).unwrap_or(true);
-                if pos_changed {
-                    self.mpv_shortcuts_pos = Some(new_pos);
-                    self.save_settings();
+        // Width inside the scroll area (excluding the scroll bar)
+        let available_width = ui.available_width() - ui.spacing().scroll.allocated_width();
+        let (items_per_row, scale) = self.grid_layout(available_width);
+        self.grid_scale = scale;
+        let row_count = videos.len().div_ceil(items_per_row);
+        let row_height = self.grid_row_height(ui, items_per_row);
+        let videos: Vec<&VideoFile> = videos.iter().collect();
+        let mut tallest_row: f32 = 0.0;
+        let mut sprite_requests: Vec<(String, PathBuf)> = Vec::new();
+        
+        let scroll_area = egui::ScrollArea::vertical()
+            .id_salt("video_grid")
+            .vertical_scroll_offset(self.grid_scroll_offset);
+        let output = scroll_area.show_rows(ui, row_height, row_count, |ui, row_range| {
+            for row in row_range {
+                let start = row * items_per_row;
+                let row_items = start..(start + items_per_row).min(videos.len());
+                let used_height = self.show_grid_row(ui, ctx, &videos, row_items, row_height, &mut sprite_requests);
+                tallest_row = tallest_row.max(used_height);
+            }
+        });
+        self.grid_scroll_offset = output.state.offset.y;
+        self.apply_sprite_requests(ctx, &sprite_requests);
+        
+        if tallest_row > row_height + 0.5 {
+            self.grid_row_height = tallest_row;
+            ctx.request_repaint();
+        }
+    }
+    
+    /// Rows share one height so only the visible rows need to be laid out.
+    /// Start from an estimate and grow it when a row (e.g. a wrapped full filename) is taller.
+    fn grid_row_height(&mut self, ui: &egui::Ui, items_per_row: usize) -> f32 {
+        let layout_key = (self.grid_scale, self.show_full_filename, self.show_tags_in_grid, items_per_row);
+        if self.grid_row_layout != Some(layout_key) {
+            self.grid_row_layout = Some(layout_key);
+            self.grid_row_height = 0.0;
+        }
+        let text_height = ui.text_style_height(&egui::TextStyle::Body);
+        let item_spacing = ui.spacing().item_spacing.y;
+        let mut estimated_height = 142.0 * self.grid_scale + item_spacing + text_height;
+        if self.show_tags_in_grid {
+            estimated_height += item_spacing + text_height;
+        }
+        self.grid_row_height.max(estimated_height)
+    }
+    
+    /// Columns of the grid and the thumbnail scale to draw them with.
+    /// In fixed-column mode the thumbnails are scaled so that many columns fill the width.
+    fn grid_layout(&self, available_width: f32) -> (usize, f32) {
+        if self.fixed_grid_columns {
+            let columns = self.grid_columns.max(1);
+            let item_width = (available_width + GRID_SPACING) / columns as f32 - GRID_SPACING;
+            (columns, (item_width / GRID_ITEM_WIDTH).clamp(0.3, 4.0))
+        } else {
+            let item_width = GRID_ITEM_WIDTH * self.thumbnail_scale;
+            let columns = ((available_width + GRID_SPACING) / (item_width + GRID_SPACING)).floor().max(1.0) as usize;
+            (columns, self.thumbnail_scale)
+        }
+    }
+    
+    /// Grid split into collapsible sections per folder, in folder-list order.
+    /// Videos without a folder go into a final "Ungrouped" section.
+    fn show_grouped_grid_view(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, videos: &[VideoFile]) {
+        // One line per section header or grid row; only the lines in view are laid out
+        enum GridLine {
+            Header(usize),
+            Row(std::ops::Range<usize>),
+        }
+        
+        let available_width = ui.available_width() - ui.spacing().scroll.allocated_width();
+        let (items_per_row, scale) = self.grid_layout(available_width);
+        self.grid_scale = scale;
+        let row_height = self.grid_row_height(ui, items_per_row);
+        
+        // Videos in several folders are listed under their first one
+        let mut by_folder: HashMap<Option<&String>, Vec<usize>> = HashMap::new();
+        for (index, video) in videos.iter().enumerate() {
+            by_folder.entry(video.folders.first()).or_default().push(index);
+        }
+        let mut sections: Vec<(Option<&String>, Vec<usize>)> = by_folder.into_iter().collect();
+        sections.sort_by_cached_key(|(folder, _)| match folder {
+            Some(name) => (0, self.database.folders.iter().position(|f| f == *name).unwrap_or(usize::MAX), (*name).clone()),
+            None => (1, 0, String::new()),
+        });
+        
+        // Display order across all sections, for Shift+Click range selection
+        let ordered: Vec<&VideoFile> = sections.iter()
+            .flat_map(|(_, indices)| indices.iter().map(|&index| &videos[index]))
+            .collect();
+        
+        // Top offset of every line inside the scroll area
+        let spacing = ui.spacing().item_spacing.y;
+        let header_height = ui.spacing().interact_size.y;
+        let mut lines: Vec<(f32, GridLine)> = Vec::new();
+        let mut top = 0.0;
+        let mut start = 0;
+        for (section_index, (folder, indices)) in sections.iter().enumerate() {
+            lines.push((top, GridLine::Header(section_index)));
+            top += header_height + spacing;
+            let end = start + indices.len();
+            if !self.collapsed_grid_folders.contains(&folder.cloned()) {
+                for row_start in (start..end).step_by(items_per_row) {
+                    lines.push((top, GridLine::Row(row_start..(row_start + items_per_row).min(end))));
+                    top += row_height + spacing;
                 }
             }
-            
-            // Save state if changed
-            if window_open != window_was_open {
-                self.mpv_shortcuts_open = window_open;
-                self.save_settings();
-            } else {
-                self.mpv_shortcuts_open = window_open;
+            start = end;
+        }
+        let total_height = (top - spacing).max(0.0);
+        
+        let mut sprite_requests: Vec<(String, PathBuf)> = Vec::new();
+        let mut tallest_row: f32 = 0.0;
+        let mut toggled: Option<Option<String>> = None;
+        let output = egui::ScrollArea::vertical()
+            .id_salt("video_grid_grouped")
+            .vertical_scroll_offset(self.grid_scroll_offset)
+            .show_viewport(ui, |ui, viewport| {
+                ui.set_height(total_height);
+                let first = lines.partition_point(|(top, _)| *top <= viewport.min.y).saturating_sub(1);
+                let last = lines.partition_point(|(top, _)| *top < viewport.max.y);
+                let Some((first_top, _)) = lines.get(first) else {
+                    return;
+                };
+                let rect = egui::Rect::from_x_y_ranges(
+                    ui.max_rect().x_range(),
+                    (ui.max_rect().top() + first_top)..=ui.max_rect().bottom(),
+                );
+                ui.allocate_new_ui(egui::UiBuilder::new().max_rect(rect), |ui| {
+                    ui.skip_ahead_auto_ids(first); // Keep widget IDs stable while scrolling
+                    for (_, line) in &lines[first..last] {
+                        match line {
+                            GridLine::Header(section_index) => {
+                                let (folder, indices) = &sections[*section_index];
+                                let name = folder.cloned().unwrap_or_else(|| self.i18n.t("ungrouped"));
+                                let icon = if self.collapsed_grid_folders.contains(&folder.cloned()) { "▶" } else { "▼" };
+                                ui.horizontal(|ui| {
+                                    ui.set_height(header_height);
+                                    let label = egui::RichText::new(format!("{} {} ({})", icon, name, indices.len())).strong();
+                                    if ui.add(egui::Button::new(label).frame(false)).clicked() {
+                                        toggled = Some(folder.cloned());
+                                    }
+                                });
+                            }
+                            GridLine::Row(row_items) => {
+                                let used_height = self.show_grid_row(ui, ctx, &ordered, row_items.clone(), row_height, &mut sprite_requests);
+                                tallest_row = tallest_row.max(used_height);
+                            }
+                        }
+                    }
+                });
+            });
+        self.grid_scroll_offset = output.state.offset.y;
+        self.apply_sprite_requests(ctx, &sprite_requests);
+        
+        if let Some(folder) = toggled {
+            if !self.collapsed_grid_folders.remove(&folder) {
+                self.collapsed_grid_folders.insert(folder);
+            }
+        }
+        if tallest_row > row_height + 0.5 {
+            self.grid_row_height = tallest_row;
+            ctx.request_repaint();
+        }
+    }
+    
+    /// Remember sprite sheets requested by hovering; their textures load from the next frame
+    fn apply_sprite_requests(&mut self, ctx: &egui::Context, sprite_requests: &[(String, PathBuf)]) {
+        if sprite_requests.is_empty() {
+            return;
+        }
+        for (video_id, sprite_path) in sprite_requests {
+            if let Some(video) = self.database.get_video_mut(video_id) {
+                video.sprite_sheet_path = Some(sprite_path.clone());
             }
+            self.persist_video(video_id);
         }
+        ctx.request_repaint();
+    }
+    
+    /// Draw `videos[row_items]` as one grid row and return the height it actually took.
+    /// `videos` is the whole list in display order (used for Shift+Click range selection).
+    fn show_grid_row(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, videos: &[&VideoFile], row_items: std::ops::Range<usize>, row_height: f32, sprite_requests: &mut Vec<(String, PathBuf)>) -> f32 {
+        let item_width = GRID_ITEM_WIDTH * self.grid_scale;
+        let row_videos = &videos[row_items];
         
-        // Premium promotion window (shown when video limit reached)
-        if self.show_premium_promotion_window {
-            egui::Window::new(&self.i18n.t("premium_promotion_title"))
-                .collapsible(false)
-                .resizable(false)
-                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
-                .show(ctx, |ui| {
-                    ui.set_width(500.0);
-                    
-                    ui.label(egui::RichText::new(&self.i18n.t("premium_limit_reached")).size(16.0));
-                    ui.add_space(10.0);
+        let row_response = ui.horizontal(|ui| {
+            ui.set_min_height(row_height);
+            for video in row_videos {
+                ui.vertical(|ui| {
+                    ui.set_width(item_width);
+            
+                    let is_selected = self.selected_videos.contains(&video.id) || 
+                                     self.selected_video.as_ref() == Some(&video.id);
+            
+                    // Thumbnail with selection highlight
+                    let base_thumbnail_size = egui::vec2(185.0, 142.0);
+                    let thumbnail_size = base_thumbnail_size * self.grid_scale;
+            
+                    // Try to load actual thumbnail image
+                    if let Some(ref thumb_path) = video.thumbnail_path {
+                        // Thumbnails can be dragged only while sorting manually
+                        let sense = if self.sort_field == SortField::Manual {
+                            egui::Sense::click_and_drag()
+                        } else {
+                            egui::Sense::click()
+                        };
+                        let (rect, response) = ui.allocate_exact_size(thumbnail_size, sense);
+                
+                        // Only load texture if the rect is actually visible (optimization)
+                        if ui.is_rect_visible(rect) {
+                            let texture = if video.protected {
+                                self.load_blurred_texture(ctx, thumb_path)
+                            } else {
+                                self.load_image_texture(ctx, thumb_path)
+                            };
+                            
+                            // Hover-scrub (premium): show the sprite sheet frame under the cursor
+                            let mut scrub_frame = None;
+                            if self.is_premium && !video.protected && video.duration.is_some() {
+                                if let Some(pointer) = response.hover_pos() {
+                                    match video.sprite_sheet_path {
+                                        Some(ref sprite_path) => {
+                                            if let Some(sprite) = self.load_image_texture(ctx, sprite_path) {
+                                                let frames = thumbnail::SPRITE_FRAMES as f32;
+                                                let index = ((pointer.x - rect.min.x) / rect.width() * frames)
+                                                    .floor()
+                                                    .clamp(0.0, frames - 1.0);
+                                                scrub_frame = Some((sprite, index / frames, (index + 1.0) / frames));
+                                            }
+                                        }
+                                        None => {
+                                            // Generated on first hover, by the background thumbnail loader
+                                            let sprite_path = thumbnail::sprite_sheet_path(&video.path, &thumbnail::get_cache_dir());
+                                            sprite_requests.push((video.id.clone(), sprite_path));
+                                        }
+                                    }
+                                }
+                            }
+                            
+                            if let Some(texture) = texture {
+                                // Letterbox inside the cell so portrait videos keep their aspect ratio;
+                                // overlays below are aligned to the image itself
+                                if (texture.aspect_ratio() - thumbnail_size.x / thumbnail_size.y).abs() > 0.01 {
+                                    ui.painter().rect_filled(rect, 4.0, egui::Color32::from_gray(20));
+                                }
+                                let rect = letterbox_rect(rect, texture.size_vec2());
+                                
+                                // Draw thumbnail (or the hovered sprite frame)
+                                if let Some((sprite, u_min, u_max)) = scrub_frame {
+                                    ui.painter().image(
+                                        sprite.id(),
+                                        rect,
+                                        egui::Rect::from_min_max(egui::pos2(u_min, 0.0), egui::pos2(u_max, 1.0)),
+                                        egui::Color32::WHITE,
+                                    );
+                                } else {
+                                    let _img_response = ui.put(rect, egui::Image::new(&texture).fit_to_exact_size(rect.size()));
+                                }
+                        
+                                let painter = ui.painter();
+                                
+                                // Dim watched videos (full brightness while hovered) and mark them
+                                if video.watched {
+                                    if !response.hovered() {
+                                        painter.rect_filled(rect, 0.0, egui::Color32::from_black_alpha(110));
+                                    }
+                                    draw_thumbnail_badge(
+                                        painter,
+                                        egui::pos2(rect.center().x, rect.min.y + 4.0),
+                                        egui::Align2::CENTER_TOP,
+                                        &self.i18n.t("watched_badge"),
+                                        11.0 * self.grid_scale,
+                                    );
+                                }
+                        
+                                // Draw lock icon for protected videos in top-left corner
+                                if video.protected {
+                                    painter.text(
+                                        egui::pos2(rect.min.x + 5.0, rect.min.y + 5.0),
+                                        egui::Align2::LEFT_TOP,
+                                        "🔒",
+                                        egui::FontId::proportional(16.0 * self.grid_scale),
+                                        egui::Color32::WHITE,
+                                    );
+                                }
+                        
+                                // Draw rating stars overlay in top-right corner
+                                if video.rating > 0 {
+                                    let star_text = "★".repeat(video.rating as usize);
+                                    let star_pos = egui::pos2(rect.max.x - 5.0, rect.min.y + 5.0);
+                                    painter.text(
+                                        star_pos,
+                                        egui::Align2::RIGHT_TOP,
+                                        star_text,
+                                        egui::FontId::proportional(16.0 * self.grid_scale),
+                                        egui::Color32::from_rgb(255, 215, 0),
+                                    );
+                                }
+                        
+                                // Draw resolution badge in bottom-left corner
+                                if self.show_quality_badge {
+                                    if let Some(label) = video.resolution.map(quality_label) {
+                                        draw_thumbnail_badge(
+                                            painter,
+                                            egui::pos2(rect.min.x + 4.0, rect.max.y - 4.0),
+                                            egui::Align2::LEFT_BOTTOM,
+                                            &label,
+                                            11.0 * self.grid_scale,
+                                        );
+                                    }
+                                }
+                                
+                                // Draw HDR badge in top-left corner (right of the lock icon)
+                                if video.is_hdr {
+                                    let lock_width = if video.protected { 22.0 * self.grid_scale } else { 0.0 };
+                                    draw_thumbnail_badge(
+                                        painter,
+                                        egui::pos2(rect.min.x + 4.0 + lock_width, rect.min.y + 4.0),
+                                        egui::Align2::LEFT_TOP,
+                                        "HDR",
+                                        11.0 * self.grid_scale,
+                                    );
+                                }
+                                
+                                // Draw duration badge in bottom-right corner
+                                if self.show_duration_badge {
+                                    if let Some(duration) = video.duration {
+                                        draw_thumbnail_badge(
+                                            painter,
+                                            egui::pos2(rect.max.x - 4.0, rect.max.y - 4.0),
+                                            egui::Align2::RIGHT_BOTTOM,
+                                            &scene_detection::format_timestamp(duration),
+                                            11.0 * self.grid_scale,
+                                        );
+                                    }
+                                }
+                                
+                                // Draw selection highlight (blue border, on top of favorite if both)
+                                if is_selected {
+                                    painter.rect_stroke(
+                                        rect,
+                                        4.0,
+                                        egui::Stroke::new(3.0, self.accent_color32())
+                                    );
+                                }
+                            } else {
+                                // Fallback to placeholder if image can't be loaded
+                                ui.painter().rect_filled(rect, 4.0, egui::Color32::DARK_GRAY);
+                                ui.painter().text(
+                                    rect.center(),
+                                    egui::Align2::CENTER_CENTER,
+                                    "🎬",
+                                    egui::FontId::proportional(48.0 * self.grid_scale),
+                                    egui::Color32::WHITE,
+                                );
+                        
+                                if is_selected {
+                                    ui.painter().rect_stroke(
+                                        rect,
+                                        4.0,
+                                        egui::Stroke::new(3.0, self.accent_color32())
+                                    );
+                                }
+                            }
+                        }
+                
+                        // Manual order: drop a thumbnail onto another to move it there
+                        if self.sort_field == SortField::Manual {
+                            response.dnd_set_drag_payload(ManualOrderDrag(video.id.clone()));
+                            if response.dnd_hover_payload::<ManualOrderDrag>().is_some_and(|dragged| dragged.0 != video.id) {
+                                ui.painter().rect_stroke(
+                                    rect.expand(2.0),
+                                    4.0,
+                                    egui::Stroke::new(3.0, egui::Color32::from_rgb(255, 180, 60))
+                                );
+                            }
+                            if let Some(dragged) = response.dnd_release_payload::<ManualOrderDrag>() {
+                                self.move_video_in_manual_order(&dragged.0, &video.id);
+                            }
+                        }
+                
+                        // Handle click events (always handle regardless of visibility)
+                        if response.clicked() {
+                            self.thumbnail_clicked_this_frame = true;
+                            let modifiers = ui.input(|i| i.modifiers);
+                            if modifiers.shift {
+                                // Shift+Click: range selection
+                                if let Some(ref last_selected) = self.last_selected_video.clone() {
+                                    let video_ids: Vec<String> = videos.iter().map(|v| v.id.clone()).collect();
+                                    self.select_range(&video_ids, last_selected, &video.id);
+                                } else {
+                                    self.selected_videos.insert(video.id.clone());
+                                }
+                                self.last_selected_video = Some(video.id.clone());
+                                // Show profile of first selected video
+                                if self.selected_video.is_none() {
+                                    self.selected_video = Some(video.id.clone());
+                                    self.scene_panel_visible = true;
+                                }
+                            } else if modifiers.ctrl {
+                                // Ctrl+Click: toggle selection
+                                self.toggle_video_selection(&video.id);
+                                self.last_selected_video = Some(video.id.clone());
+                                // Show profile of first selected video
+                                if self.selected_video.is_none() && self.selected_videos.contains(&video.id) {
+                                    self.selected_video = Some(video.id.clone());
+                                    self.scene_panel_visible = true;
+                                }
+                            } else {
+                                // Single click: clear multi-select and select only this video
+                                // If this video is already the only selected one, keep it selected
+                                let already_sole_selected = self.selected_video.as_ref() == Some(&video.id) && 
+                                                           self.selected_videos.is_empty();
+                                if !already_sole_selected {
+                                    self.selected_videos.clear();
+                                    self.selected_video = Some(video.id.clone());
+                                    self.scene_panel_visible = true;
+                                    self.last_selected_video = Some(video.id.clone());
+                                }
+                            }
+                        }
+                
+                        // Double click: play video
+                        if response.double_clicked() {
+                            // Select this video before playing (maintain selection in profile panel)
+                            self.selected_videos.clear();
+                            self.selected_video = Some(video.id.clone());
+                            self.scene_panel_visible = true;
+                            self.last_selected_video = Some(video.id.clone());
                     
-                    ui.separator();
-                    ui.add_space(5.0);
+                            self.play_video_at(&video.path, video.playback_position.unwrap_or(0.0));
+                        }
+                
+                        response.context_menu(|ui| {
+                            if ui.button(self.i18n.t("play_video")).clicked() {
+                                self.play_video_at(&video.path, video.playback_position.unwrap_or(0.0));
+                                ui.close_menu();
+                            }
                     
-                    ui.label(egui::RichText::new(&self.i18n.t("premium_unlock_features")).strong().size(14.0));
-                    ui.add_space(5.0);
+                            if video.playback_position.is_some() && ui.button(self.i18n.t("play_from_beginning")).clicked() {
+                                self.play_video_at(&video.path, 0.0);
+                                ui.close_menu();
+                            }
+                            
+                            if ui.button(self.i18n.t("play_all")).on_hover_text(self.i18n.t("play_all_tooltip")).clicked() {
+                                self.play_all(&video.id);
+                                ui.close_menu();
+                            }
+                            
+                            if ui.button(self.i18n.t("shuffle_play")).on_hover_text(self.i18n.t("shuffle_play_tooltip")).clicked() {
+                                self.shuffle_play(&video.id);
+                                ui.close_menu();
+                            }
                     
-                    // List premium features
-                    ui.label(&self.i18n.t("premium_unlimited_storage"));
-                    ui.label(&self.i18n.t("premium_scene_generation"));
-                    ui.label(&self.i18n.t("premium_star_ratings"));
-                    ui.label(&self.i18n.t("premium_glsl_shaders"));
-                    ui.label(&self.i18n.t("premium_gpu_rendering"));
-                    ui.label(&self.i18n.t("premium_multi_select"));
+                            // Bookmark the frame currently shown in mpv (premium)
+                            if self.is_premium && ui.button(self.i18n.t("capture_current_frame")).clicked() {
+                                self.selected_video = Some(video.id.clone());
+                                self.capture_current_frame(&video.id);
+                                ui.close_menu();
+                            }
+                            
+                            let watched_label = if video.watched { self.i18n.t("mark_unwatched") } else { self.i18n.t("mark_watched") };
+                            if ui.button(watched_label).clicked() {
+                                let target_ids = self.target_ids_for(&video.id);
+                                self.set_videos_watched(&target_ids, !video.watched);
+                                ui.close_menu();
+                            }
                     
-                    ui.add_space(10.0);
-                    ui.separator();
-                    ui.add_space(5.0);
+                            ui.separator();
                     
-                    // Buttons
-                    ui.horizontal(|ui| {
-                        // License activation button
-                        if ui.button(&self.i18n.t("enter_license_key")).clicked() {
-                            self.show_license_window = true;
-                            self.show_premium_promotion_window = false;
-                        }
-                        
-                        // Close button
-                        if ui.button(&self.i18n.t("got_it")).clicked() {
-                            self.show_premium_promotion_window = false;
-                        }
-                    });
-                });
-        }
-        
-        // License activation window
-        if self.show_license_window {
-            let mut window_open = true;
-            egui::Window::new(&self.i18n.t("activate_license"))
-                .open(&mut window_open)
-                .collapsible(false)
-                .resizable(false)
-                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
-                .show(ctx, |ui| {
-                    ui.set_width(500.0);
+                            if ui.button(self.i18n.t("show_in_folder")).clicked() {
+                                if let Err(e) = video_player::show_in_folder(&video.path) {
+                                    eprintln!("Show in folder error: {}", e);
+                                }
+                                ui.close_menu();
+                            }
+                            
+                            self.show_copy_menu_items(ui, &video.id);
                     
-                    ui.label(egui::RichText::new(&self.i18n.t("license_key_label")).strong());
-                    ui.add_space(5.0);
+                            if !video.scenes.is_empty() && ui.button(self.i18n.t("export_contact_sheet")).clicked() {
+                                self.export_contact_sheet(&video.id);
+                                ui.close_menu();
+                            }
                     
-                    // License key input
-                    let response = ui.add(
-                        egui::TextEdit::multiline(&mut self.license_input)
-                            .desired_width(480.0)
-                            .desired_rows(4)
-                            .hint_text(&self.i18n.t("paste_license_key"))
-                    );
+                            ui.separator();
                     
-                    // Auto-focus input on first show
-                    if response.has_focus() || response.gained_focus() {
-                        // Input is focused
-                    }
+                            // Refresh profile button - handles both single and multiple selection
+                            let video_id_for_refresh = video.id.clone();
+                            let refresh_label = if self.selected_videos.len() > 1 {
+                                format!("{} ({})", self.i18n.t("refresh_profile"), self.selected_videos.len())
+                            } else {
+                                self.i18n.t("refresh_profile")
+                            };
+                            if ui.button(&refresh_label).clicked() {
+                                if self.selected_videos.len() > 1 {
+                                    // Refresh all selected videos
+                                    let selected_ids: Vec<String> = self.selected_videos.iter().cloned().collect();
+                                    for video_id in selected_ids {
+                                        self.refresh_video_profile(&video_id);
+                                    }
+                                } else {
+                                    // Refresh single video
+                                    self.refresh_video_profile(&video_id_for_refresh);
+                                }
+                                ui.close_menu();
+                            }
+                            
+                            // Re-probe duration with the accurate (slow) method
+                            let reprobe_enabled = self.duration_probe_receiver.is_none();
+                            if ui.add_enabled(reprobe_enabled, egui::Button::new(self.i18n.t("reprobe_duration"))).clicked() {
+                                let target_ids = self.target_ids_for(&video.id);
+                                self.start_duration_probe(&target_ids);
+                                ui.close_menu();
+                            }
+                            
+                            if ui.button(self.i18n.t("regenerate_thumbnail")).clicked() {
+                                let target_ids = self.target_ids_for(&video.id);
+                                self.regenerate_video_thumbnails(&target_ids);
+                                ui.close_menu();
+                            }
+                            
+                            if ui.button(self.i18n.t("set_thumbnail_from_timestamp")).clicked() {
+                                self.thumbnail_timestamp_video = Some(video.id.clone());
+                                self.thumbnail_timestamp_error = None;
+                                ui.close_menu();
+                            }
                     
-                    ui.add_space(10.0);
+                            // Protect / unprotect (requires a protection PIN)
+                            let protect_label = if video.protected {
+                                self.i18n.t("unprotect_video")
+                            } else {
+                                self.i18n.t("protect_video")
+                            };
+                            let protect_response = ui.add_enabled(
+                                self.protection_pin_hash.is_some(),
+                                egui::Button::new(protect_label),
+                            );
+                            if protect_response.clicked() {
+                                let target_ids = self.target_ids_for(&video.id);
+                                self.set_videos_protected(&target_ids, !video.protected);
+                                ui.close_menu();
+                            }
+                            protect_response.on_disabled_hover_text(self.i18n.t("protection_pin_required"));
+                            
+                            if ui.button(self.i18n.t("export_playlist")).clicked() {
+                                self.export_playlist(&video.id);
+                                ui.close_menu();
+                            }
+                            
+                            self.show_move_to_folder_menu(ui, &video.id);
+                            self.show_move_to_profile_menu(ui, &video.id);
                     
-                    // Status message
-                    if let Some(ref message) = self.license_status_message {
-                        ui.label(egui::RichText::new(message).size(12.0));
-                        ui.add_space(10.0);
-                    }
+                            ui.separator();
                     
-                    // Buttons
-                    ui.horizontal(|ui| {
-                        if ui.button(&self.i18n.t("activate")).clicked() {
-                            let license_key = self.license_input.trim().to_string();
-                            if !license_key.is_empty() {
-                                self.activate_license(&license_key);
+                            if ui.button(self.i18n.t("delete")).clicked() {
+                                self.delete_confirm_video = Some(video.id.clone());
+                                ui.close_menu();
                             }
-                        }
-                        
-                        if ui.button(&self.i18n.t("cancel")).clicked() {
-                            self.show_license_window = false;
-                            self.license_input.clear();
-                            self.license_status_message = None;
-                        }
-                    });
                     
-                    // Show current license info if available
-                    if let Some(ref license) = self.current_license {
-                        ui.add_space(10.0);
-                        ui.separator();
-                        ui.add_space(5.0);
-                        
-                        ui.label(egui::RichText::new(&self.i18n.t("license_info")).strong());
-                        ui.add_space(5.0);
-                        
-                        ui.horizontal(|ui| {
-                            ui.label(&self.i18n.t("issued_to"));
-                            ui.label(&license.info.issued_to);
+                            // Multiple selection delete option
+                            if self.selected_videos.len() > 1 {
+                                let delete_multiple_text = self.i18n.t("delete_selected");
+                                if ui.button(&delete_multiple_text).clicked() {
+                                    self.delete_confirm_video = Some("__MULTI__".to_string());
+                                    ui.close_menu();
+                                }
+                            }
                         });
-                        
+                    } else {
+                        // No thumbnail, show placeholder
+                        self.draw_thumbnail_placeholder(ui, ctx, thumbnail_size, video);
+                    }
+            
+                    // Title
+                    if self.show_full_filename {
+                        ui.label(&video.title);
+                    } else {
+                        // Truncate if text exceeds available width
+                        ui.add(egui::Label::new(&video.title).truncate());
+                    }
+            
+                    // Display tags if enabled
+                    if self.show_tags_in_grid {
                         ui.horizontal(|ui| {
-                            ui.label(&self.i18n.t("expires"));
-                            let expires_text = license.info.expires_at
-                                .map(|ts| license::format_timestamp(ts))
-                                .unwrap_or_else(|| self.i18n.t("never_expires"));
-                            ui.label(&expires_text);
-                        });
+                            let video_id = video.id.clone();
+                            for tag in video.tags.iter().take(2) {
+                                let tag_text = format!("#{}", tag);
+                                let tag_label = ui.label(tag_text);
                         
-                        ui.horizontal(|ui| {
-                            ui.label(&self.i18n.t("license_status"));
-                            if license.is_expired {
-                                ui.label(egui::RichText::new("❌ Expired").color(egui::Color32::RED));
-                            } else if license.is_valid {
-                                ui.label(egui::RichText::new("✅ Active").color(egui::Color32::GREEN));
-                            } else {
-                                ui.label(egui::RichText::new("❌ Invalid").color(egui::Color32::RED));
+                                // Right-click on tag to remove
+                                tag_label.context_menu(|ui| {
+                                    if ui.button("Remove Tag").clicked() {
+                                        self.remove_tag_from_video(&video_id, tag);
+                                        ui.close_menu();
+                                    }
+                                });
                             }
                         });
                     }
                 });
-            
-            // Handle window close button (X)
-            if !window_open {
-                self.show_license_window = false;
-                self.license_input.clear();
-                self.license_status_message = None;
+        
+                ui.add_space(GRID_SPACING);
             }
-        }
+        });
+        row_response.response.rect.height()
     }
     
-    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
-        // Save window state on exit (size and position only, not maximized state)
-        let mut settings = database::load_settings().unwrap_or_default();
-        
-        settings.window_size = self.last_window_size;
-        settings.window_position = self.last_window_pos;
-        
-        if let Err(e) = database::save_settings(&settings) {
-            eprintln!("[app] Failed to save window state: {}", e);
+    /// Column header of the list view; clicking sorts by the column (again: reverse the order)
+    fn list_sort_header(&mut self, ui: &mut egui::Ui, label: String, field: SortField) {
+        let is_current = self.sort_field == field;
+        let text = if !is_current {
+            label
+        } else if self.sort_order == SortOrder::Ascending {
+            format!("{} ▲", label)
         } else {
-            eprintln!("[app] Window state saved: size={:?}, pos={:?}", 
-                self.last_window_size, self.last_window_pos);
+            format!("{} ▼", label)
+        };
+        if ui.selectable_label(is_current, egui::RichText::new(text).strong()).clicked() {
+            if is_current {
+                self.sort_order = if self.sort_order == SortOrder::Ascending {
+                    SortOrder::Descending
+                } else {
+                    SortOrder::Ascending
+                };
+            } else {
+                self.sort_field = field;
+                self.sort_order = SortOrder::Ascending;
+            }
         }
     }
-}
-
-impl VideoPlayerApp {
-    fn show_grid_view(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, videos: &[VideoFile]) {
-        let available_width = ui.available_width();
-        let base_item_width = 205.0;
-        let item_width = base_item_width * self.thumbnail_scale;
-        let spacing = 10.0;
-        let items_per_row = ((available_width + spacing) / (item_width + spacing)).floor().max(1.0) as usize;
-        
-        for row_videos in videos.chunks(items_per_row) {
-            ui.horizontal(|ui| {
-                for video in row_videos {
-                    ui.vertical(|ui| {
-                        ui.set_width(item_width);
-                        
-                        let is_selected = self.selected_videos.contains(&video.id) || 
-                                         self.selected_video.as_ref() == Some(&video.id);
-                        
-                        // Thumbnail with selection highlight
-                        let base_thumbnail_size = egui::vec2(185.0, 142.0);
-                        let thumbnail_size = base_thumbnail_size * self.thumbnail_scale;
-                        
-                        // Try to load actual thumbnail image
-                        if let Some(ref thumb_path) = video.thumbnail_path {
-                            let (rect, response) = ui.allocate_exact_size(thumbnail_size, egui::Sense::click());
-                            
-                            // Only load texture if the rect is actually visible (optimization)
-                            if ui.is_rect_visible(rect) {
-                                if let Some(texture) = self.load_image_texture(ctx, thumb_path) {
-                                    // Draw thumbnail
-                                    let _img_response = ui.put(rect, egui::Image::new(&texture).fit_to_exact_size(thumbnail_size));
-                                    
-                                    let painter = ui.painter();
-                                    
-                                    // Draw rating stars overlay in top-right corner
-                                    if video.rating > 0 {
-                                        let star_text = "★".repeat(video.rating as usize);
-                                        let star_pos = egui::pos2(rect.max.x - 5.0, rect.min.y + 5.0);
-                                        painter.text(
-                                            star_pos,
-                                            egui::Align2::RIGHT_TOP,
-                                            star_text,
-                                            egui::FontId::proportional(16.0 * self.thumbnail_scale),
-                                            egui::Color32::from_rgb(255, 215, 0),
-                                        );
-                                    }
-                                    
-                                    // Draw selection highlight (blue border, on top of favorite if both)
-                                    if is_selected {
-                                        painter.rect_stroke(
-                                            rect,
-                                            4.0,
-                                            egui::Stroke::new(3.0, egui::Color32::from_rgb(100, 200, 255))
-                                        );
-                                    }
-                                } else {
-                                    // Fallback to placeholder if image can't be loaded
-                                    ui.painter().rect_filled(rect, 4.0, egui::Color32::DARK_GRAY);
-                                    ui.painter().text(
-                                        rect.center(),
-                                        egui::Align2::CENTER_CENTER,
-                                        "🎬",
-                                        egui::FontId::proportional(48.0 * self.thumbnail_scale),
-                                        egui::Color32::WHITE,
-                                    );
-                                    
-                                    if is_selected {
-                                        ui.painter().rect_stroke(
-                                            rect,
-                                            4.0,
-                                            egui::Stroke::new(3.0, egui::Color32::from_rgb(100, 200, 255))
-                                        );
-                                    }
-                                }
+    
+    fn show_list_view(&mut self, ui: &mut egui::Ui, videos: &[VideoFile]) {
+        egui::Grid::new("video_list_table")
+            .striped(true)
+            .num_columns(9)
+            .spacing(egui::vec2(16.0, 6.0))
+            .show(ui, |ui| {
+                // Header row
+                self.list_sort_header(ui, self.i18n.t("title"), SortField::FileName);
+                self.list_sort_header(ui, self.i18n.t("duration"), SortField::Duration);
+                self.list_sort_header(ui, self.i18n.t("resolution"), SortField::Resolution);
+                self.list_sort_header(ui, self.i18n.t("file_size"), SortField::FileSize);
+                self.list_sort_header(ui, self.i18n.t("rating"), SortField::Rating);
+                self.list_sort_header(ui, self.i18n.t("folder"), SortField::Folder);
+                self.list_sort_header(ui, self.i18n.t("added_date"), SortField::AddedDate);
+                ui.label(egui::RichText::new(self.i18n.t("tags")).strong());
+                ui.label("");
+                ui.end_row();
+                
+                for video in videos {
+                    let is_selected = self.selected_videos.contains(&video.id) || 
+                                     self.selected_video.as_ref() == Some(&video.id);
+                    
+                    // Title with selection highlight
+                    let title_response = ui.selectable_label(is_selected, &video.title)
+                        .on_hover_text(video.path.display().to_string());
+                    if title_response.clicked() {
+                        self.thumbnail_clicked_this_frame = true;
+                        let modifiers = ui.input(|i| i.modifiers);
+                        if modifiers.shift {
+                            // Shift+Click: range selection
+                            if let Some(ref last_selected) = self.last_selected_video.clone() {
+                                let video_ids: Vec<String> = videos.iter().map(|v| v.id.clone()).collect();
+                                self.select_range(&video_ids, last_selected, &video.id);
+                            } else {
+                                self.selected_videos.insert(video.id.clone());
                             }
-                            
-                            // Handle click events (always handle regardless of visibility)
-                            if response.clicked() {
-                                self.thumbnail_clicked_this_frame = true;
-                                let modifiers = ui.input(|i| i.modifiers.clone());
-                                if modifiers.shift {
-                                    // Shift+Click: range selection
-                                    if let Some(ref last_selected) = self.last_selected_video.clone() {
-                                        let video_ids: Vec<String> = videos.iter().map(|v| v.id.clone()).collect();
-                                        self.select_range(&video_ids, last_selected, &video.id);
-                                    } else {
-                                        self.selected_videos.insert(video.id.clone());
-                                    }
-                                    self.last_selected_video = Some(video.id.clone());
-                                    // Show profile of first selected video
-                                    if self.selected_video.is_none() {
-                                        self.selected_video = Some(video.id.clone());
-                                        self.scene_panel_visible = true;
-                                    }
-                                } else if modifiers.ctrl {
-                                    // Ctrl+Click: toggle selection
-                                    self.toggle_video_selection(&video.id);
-                                    self.last_selected_video = Some(video.id.clone());
-                                    // Show profile of first selected video
-                                    if self.selected_video.is_none() && self.selected_videos.contains(&video.id) {
-                                        self.selected_video = Some(video.id.clone());
-                                        self.scene_panel_visible = true;
-                                    }
-                                } else {
-                                    // Single click: clear multi-select and select only this video
-                                    // If this video is already the only selected one, keep it selected
-                                    let already_sole_selected = self.selected_video.as_ref() == Some(&video.id) && 
-                                                               self.selected_videos.is_empty();
-                                    if !already_sole_selected {
-                                        self.selected_videos.clear();
-                                        self.selected_video = Some(video.id.clone());
-                                        self.scene_panel_visible = true;
-                                        self.last_selected_video = Some(video.id.clone());
-                                    }
-                                }
+                            self.last_selected_video = Some(video.id.clone());
+                            // Show profile of first selected video
+                            if self.selected_video.is_none() {
+                                self.selected_video = Some(video.id.clone());
+                                self.scene_panel_visible = true;
                             }
-                            
-                            // Double click: play video
-                            if response.double_clicked() {
-                                // Select this video before playing (maintain selection in profile panel)
+                        } else if modifiers.ctrl {
+                            self.toggle_video_selection(&video.id);
+                            self.last_selected_video = Some(video.id.clone());
+                            // Show profile of first selected video
+                            if self.selected_video.is_none() && self.selected_videos.contains(&video.id) {
+                                self.selected_video = Some(video.id.clone());
+                                self.scene_panel_visible = true;
+                            }
+                        } else {
+                            // Single click: clear multi-select and select only this video
+                            // If this video is already the only selected one, keep it selected
+                            let already_sole_selected = self.selected_video.as_ref() == Some(&video.id) && 
+                                                       self.selected_videos.is_empty();
+                            if !already_sole_selected {
                                 self.selected_videos.clear();
                                 self.selected_video = Some(video.id.clone());
                                 self.scene_panel_visible = true;
                                 self.last_selected_video = Some(video.id.clone());
-                                
-                                let selected_shader = self.selected_shader.as_deref();
-                                let use_gpu_hq = self.is_premium && self.use_gpu_hq;
-                                let use_custom_shaders = self.is_premium && self.use_custom_shaders;
-                                if let Err(e) = video_player::play_video_at_timestamp(&video.path, 0.0, self.mpv_always_on_top, use_gpu_hq, use_custom_shaders, selected_shader, self.mpv_volume) {
-                                    eprintln!("Video playback error: {}", e);
-                                }
                             }
-                            
-                            response.context_menu(|ui| {
-                                if ui.button(&self.i18n.t("play_video")).clicked() {
-                                    let selected_shader = self.selected_shader.as_deref();
-                                    let use_gpu_hq = self.is_premium && self.use_gpu_hq;
-                                    let use_custom_shaders = self.is_premium && self.use_custom_shaders;
-                                    if let Err(e) = video_player::play_video_at_timestamp(&video.path, 0.0, self.mpv_always_on_top, use_gpu_hq, use_custom_shaders, selected_shader, self.mpv_volume) {
-                                        eprintln!("Video playback error: {}", e);
-                                    }
-                                    ui.close_menu();
-                                }
-                                
-                                ui.separator();
-                                
-                                if ui.button(&self.i18n.t("show_in_folder")).clicked() {
-                                    if let Err(e) = video_player::show_in_folder(&video.path) {
-                                        eprintln!("Show in folder error: {}", e);
-                                    }
-                                    ui.close_menu();
-                                }
-                                
-                                ui.separator();
-                                
-                                // Refresh profile button - handles both single and multiple selection
-                                let video_id_for_refresh = video.id.clone();
-                                let refresh_label = if self.selected_videos.len() > 1 {
-                                    format!("{} ({})", self.i18n.t("refresh_profile"), self.selected_videos.len())
-                                } else {
-                                    self.i18n.t("refresh_profile")
-                                };
-                                if ui.button(&refresh_label).clicked() {
-                                    if self.selected_videos.len() > 1 {
-                                        // Refresh all selected videos
-                                        let selected_ids: Vec<String> = self.selected_videos.iter().cloned().collect();
-                                        for video_id in selected_ids {
-                                            self.refresh_video_profile(&video_id);
-                                        }
-                                    } else {
-                                        // Refresh single video
-                                        self.refresh_video_profile(&video_id_for_refresh);
-                                    }
-                                    ui.close_menu();
-                                }
-                                
-                                ui.separator();
-                                
-                                if ui.button(&self.i18n.t("delete")).clicked() {
-                                    self.delete_confirm_video = Some(video.id.clone());
-                                    ui.close_menu();
-                                }
-                                
-                                // Multiple selection delete option
-                                if self.selected_videos.len() > 1 {
-                                    let delete_multiple_text = self.i18n.t("delete_selected");
-                                    if ui.button(&delete_multiple_text).clicked() {
-                                        self.delete_confirm_video = Some("__MULTI__".to_string());
-                                        ui.close_menu();
-                                    }
-                                }
-                            });
-                        } else {
-                            // No thumbnail, show placeholder
-                            self.draw_thumbnail_placeholder(ui, ctx, thumbnail_size, video);
-                        }
-                        
-                        // Title
-                        if self.show_full_filename {
-                            ui.label(&video.title);
-                        } else {
-                            // Truncate if text exceeds available width
-                            ui.add(egui::Label::new(&video.title).truncate());
                         }
+                    }
+                    
+                    title_response.context_menu(|ui| {
+                        self.show_move_to_folder_menu(ui, &video.id);
+                        self.show_copy_menu_items(ui, &video.id);
+                    });
+                    
+                    // Double click: play video
+                    if title_response.double_clicked() {
+                        // Select this video before playing (maintain selection in profile panel)
+                        self.selected_videos.clear();
+                        self.selected_video = Some(video.id.clone());
+                        self.scene_panel_visible = true;
+                        self.last_selected_video = Some(video.id.clone());
                         
-                        // Display tags if enabled
-                        if self.show_tags_in_grid {
-                            ui.horizontal(|ui| {
-                                let video_id = video.id.clone();
-                                for tag in video.tags.iter().take(2) {
-                                    let tag_text = format!("#{}", tag);
-                                    let tag_label = ui.label(tag_text);
-                                    
-                                    // Right-click on tag to remove
-                                    tag_label.context_menu(|ui| {
-                                        if ui.button("Remove Tag").clicked() {
-                                            self.remove_tag_from_video(&video_id, tag);
-                                            ui.close_menu();
-                                        }
-                                    });
+                        self.play_video_at(&video.path, video.playback_position.unwrap_or(0.0));
+                    }
+                    
+                    // Duration
+                    ui.label(video.duration.map(scene_detection::format_timestamp).unwrap_or_else(|| "-".to_string()));
+                    
+                    // Resolution
+                    ui.label(video.resolution.map(|(w, h)| format!("{}×{}", w, h)).unwrap_or_else(|| "-".to_string()));
+                    
+                    // File size
+                    let size_mb = video.file_size as f64 / 1024.0 / 1024.0;
+                    ui.label(format!("{:.1} MB", size_mb));
+                    
+                    // Rating stars
+                    if video.rating > 0 {
+                        let stars = "★".repeat(video.rating as usize) + &"☆".repeat((5 - video.rating) as usize);
+                        ui.label(egui::RichText::new(stars).color(egui::Color32::from_rgb(255, 215, 0)));
+                    } else {
+                        ui.label("");
+                    }
+                    
+                    // Folder
+                    ui.label(video.folders.join(", "));
+                    
+                    // Added date
+                    ui.label(video.added_date.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string());
+                    
+                    // Tags
+                    let video_id = video.id.clone();
+                    ui.horizontal(|ui| {
+                        for tag in &video.tags {
+                            let tag_text = format!("#{}", tag);
+                            let tag_label = ui.label(tag_text);
+                            
+                            // Right-click on tag to remove
+                            tag_label.context_menu(|ui| {
+                                if ui.button("Remove Tag").clicked() {
+                                    self.remove_tag_from_video(&video_id, tag);
+                                    ui.close_menu();
                                 }
                             });
                         }
                     });
                     
-                    ui.add_space(spacing);
+                    // Delete button
+                    if ui.button("X").clicked() {
+                        self.delete_confirm_video = Some(video.id.clone());
+                    }
+                    ui.end_row();
                 }
             });
+    }
+    
+    /// expires_at of the active license if the expiry banner should be shown now
+    fn license_expiry_warning(&self) -> Option<i64> {
+        if !self.is_premium || self.license_expiry_banner_dismissed || self.license_expiry_warning_days == 0 {
+            return None;
         }
+        let license = self.current_license.as_ref()?;
+        let days_left = license.days_until_expiry()?;
+        (days_left < i64::from(self.license_expiry_warning_days))
+            .then_some(license.info.expires_at)
+            .flatten()
     }
     
-    fn show_list_view(&mut self, ui: &mut egui::Ui, videos: &[VideoFile]) {
-        for video in videos {
-            ui.horizontal(|ui| {
-                let is_selected = self.selected_videos.contains(&video.id) || 
-                                 self.selected_video.as_ref() == Some(&video.id);
+    fn show_license_expired_notice(&mut self, ctx: &egui::Context) {
+        let expires_at = self.current_license.as_ref().and_then(|license| license.info.expires_at);
+        let mut close = false;
+        egui::Window::new(self.i18n.t("license_expired_title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                let date = expires_at.map(license::format_timestamp).unwrap_or_else(|| "-".to_string());
+                ui.label(self.i18n.t("license_expired_notice").replace("{}", &date));
+                ui.add_space(5.0);
+                ui.label(self.i18n.t("license_expired_features"));
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button(self.i18n.t("enter_license_key")).clicked() {
+                        self.show_license_window = true;
+                        close = true;
+                    }
+                    if ui.button(self.i18n.t("close")).clicked() {
+                        close = true;
+                    }
+                });
+            });
+        
+        if close {
+            self.show_license_expired_notice = false;
+            self.license_expired_notice_shown = expires_at;
+            self.save_settings();
+        }
+    }
+    
+    fn show_import_issues_window(&mut self, ctx: &egui::Context) {
+        let mut window_open = true;
+        let mut close = false;
+        egui::Window::new(self.i18n.t("import_issues").replace("{}", &self.import_issues.len().to_string()))
+            .id(egui::Id::new("import_issues_window")) // The title changes with the count
+            .open(&mut window_open)
+            .collapsible(true)
+            .resizable(true)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                ui.label(self.i18n.t("import_issues_hint"));
+                ui.add_space(5.0);
+                egui::ScrollArea::vertical()
+                    .max_height(320.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("import_issues_grid").striped(true).num_columns(2).show(ui, |ui| {
+                            for (path, reason) in &self.import_issues {
+                                let file_name = path.file_name()
+                                    .map(|n| n.to_string_lossy().to_string())
+                                    .unwrap_or_else(|| path.display().to_string());
+                                ui.label(file_name).on_hover_text(path.display().to_string());
+                                ui.label(reason);
+                                ui.end_row();
+                            }
+                        });
+                    });
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    if ui.button(self.i18n.t("copy_paths")).clicked() {
+                        let text = self.import_issues.iter()
+                            .map(|(path, reason)| format!("{}\t{}", path.display(), reason))
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        ui.ctx().copy_text(text);
+                    }
+                    if ui.button(self.i18n.t("close")).clicked() {
+                        close = true;
+                    }
+                });
+            });
+        
+        if !window_open || close {
+            self.show_import_issues_window = false;
+        }
+    }
+    
+    /// Totals and breakdowns of the visible library for the statistics window
+    fn compute_library_stats(&self) -> LibraryStats {
+        let videos: Vec<&VideoFile> = self.database.videos.iter()
+            .filter(|v| self.protected_unlocked || !v.protected)
+            .collect();
+        
+        let mut rating_counts = [0usize; 6];
+        let mut tag_counts: HashMap<&str, usize> = HashMap::new();
+        let mut resolution_classes: HashMap<String, (u32, usize)> = HashMap::new(); // label -> (short side, count)
+        for video in &videos {
+            rating_counts[usize::from(video.rating.min(5))] += 1;
+            for tag in &video.tags {
+                *tag_counts.entry(tag.as_str()).or_insert(0) += 1;
+            }
+            let (label, short_side) = match video.resolution {
+                Some(resolution) => (quality_label(resolution), resolution.0.min(resolution.1)),
+                None => (self.i18n.t("unknown"), 0),
+            };
+            let entry = resolution_classes.entry(label).or_insert((short_side, 0));
+            entry.0 = entry.0.max(short_side);
+            entry.1 += 1;
+        }
+        
+        let mut top_tags: Vec<(String, usize)> = tag_counts.into_iter()
+            .map(|(tag, count)| (tag.to_string(), count))
+            .collect();
+        top_tags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_tags.truncate(10);
+        
+        let mut resolution_counts: Vec<(u32, String, usize)> = resolution_classes.into_iter()
+            .map(|(label, (short_side, count))| (short_side, label, count))
+            .collect();
+        resolution_counts.sort_by_key(|(short_side, _, _)| std::cmp::Reverse(*short_side));
+        
+        LibraryStats {
+            video_count: videos.len(),
+            total_duration: videos.iter().filter_map(|v| v.duration).sum(),
+            total_size: videos.iter().map(|v| v.file_size).sum(),
+            rating_counts,
+            top_tags,
+            resolution_counts: resolution_counts.into_iter().map(|(_, label, count)| (label, count)).collect(),
+        }
+    }
+    
+    /// Library statistics window with a histogram of videos added per month
+    fn show_statistics_window(&mut self, ctx: &egui::Context) {
+        // Computed once per opening; the library rarely changes while the window is open
+        let stats = match self.library_stats.clone() {
+            Some(stats) => stats,
+            None => {
+                let stats = self.compute_library_stats();
+                self.library_stats = Some(stats.clone());
+                stats
+            }
+        };
+        
+        let mut window_open = true;
+        egui::Window::new(self.i18n.t("statistics"))
+            .open(&mut window_open)
+            .collapsible(false)
+            .resizable(true)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                ui.label(self.i18n.t("total_videos").replace("{}", &stats.video_count.to_string()));
+                ui.label(self.i18n.t("total_duration").replace("{}", &scene_detection::format_timestamp(stats.total_duration)));
+                ui.label(self.i18n.t("total_storage").replace("{}", &format!("{:.2}", stats.total_size as f64 / 1024.0 / 1024.0 / 1024.0)));
                 
-                // Title with selection highlight
-                let title_response = ui.selectable_label(is_selected, &video.title);
-                if title_response.clicked() {
-                    self.thumbnail_clicked_this_frame = true;
-                    let modifiers = ui.input(|i| i.modifiers.clone());
-                    if modifiers.shift {
-                        // Shift+Click: range selection
-                        if let Some(ref last_selected) = self.last_selected_video.clone() {
-                            let video_ids: Vec<String> = videos.iter().map(|v| v.id.clone()).collect();
-                            self.select_range(&video_ids, last_selected, &video.id);
-                        } else {
-                            self.selected_videos.insert(video.id.clone());
-                        }
-                        self.last_selected_video = Some(video.id.clone());
-                        // Show profile of first selected video
-                        if self.selected_video.is_none() {
-                            self.selected_video = Some(video.id.clone());
-                            self.scene_panel_visible = true;
-                        }
-                    } else if modifiers.ctrl {
-                        self.toggle_video_selection(&video.id);
-                        self.last_selected_video = Some(video.id.clone());
-                        // Show profile of first selected video
-                        if self.selected_video.is_none() && self.selected_videos.contains(&video.id) {
-                            self.selected_video = Some(video.id.clone());
-                            self.scene_panel_visible = true;
-                        }
-                    } else {
-                        // Single click: clear multi-select and select only this video
-                        // If this video is already the only selected one, keep it selected
-                        let already_sole_selected = self.selected_video.as_ref() == Some(&video.id) && 
-                                                   self.selected_videos.is_empty();
-                        if !already_sole_selected {
-                            self.selected_videos.clear();
-                            self.selected_video = Some(video.id.clone());
-                            self.scene_panel_visible = true;
-                            self.last_selected_video = Some(video.id.clone());
-                        }
+                ui.add_space(10.0);
+                ui.columns(3, |columns| {
+                    // Rating histogram
+                    columns[0].label(egui::RichText::new(self.i18n.t("videos_by_rating")).strong());
+                    let max_rating_count = stats.rating_counts.iter().copied().max().unwrap_or(1).max(1);
+                    for (rating, count) in stats.rating_counts.iter().enumerate().rev() {
+                        let label = if rating == 0 { self.i18n.t("unrated") } else { "★".repeat(rating) };
+                        columns[0].add(
+                            egui::ProgressBar::new(*count as f32 / max_rating_count as f32)
+                                .text(format!("{}  {}", label, count))
+                        );
+                    }
+                    
+                    columns[1].label(egui::RichText::new(self.i18n.t("top_tags")).strong());
+                    if stats.top_tags.is_empty() {
+                        columns[1].label(egui::RichText::new(self.i18n.t("no_tags")).weak());
+                    }
+                    for (tag, count) in &stats.top_tags {
+                        columns[1].label(format!("#{}  {}", tag, count));
+                    }
+                    
+                    columns[2].label(egui::RichText::new(self.i18n.t("videos_by_resolution")).strong());
+                    for (label, count) in &stats.resolution_counts {
+                        columns[2].label(format!("{}  {}", label, count));
                     }
+                });
+                
+                ui.add_space(10.0);
+                ui.separator();
+                ui.label(egui::RichText::new(self.i18n.t("additions_per_month")).strong());
+                ui.add_space(5.0);
+                
+                // Group videos by the month they were added
+                let mut counts: std::collections::BTreeMap<(i32, u32), usize> = std::collections::BTreeMap::new();
+                for video in &self.database.videos {
+                    *counts.entry(added_month(video)).or_insert(0) += 1;
                 }
                 
-                // Double click: play video
-                if title_response.double_clicked() {
-                    // Select this video before playing (maintain selection in profile panel)
-                    self.selected_videos.clear();
-                    self.selected_video = Some(video.id.clone());
-                    self.scene_panel_visible = true;
-                    self.last_selected_video = Some(video.id.clone());
-                    
-                    let selected_shader = self.selected_shader.as_deref();
-                    let use_gpu_hq = self.is_premium && self.use_gpu_hq;
-                    let use_custom_shaders = self.is_premium && self.use_custom_shaders;
-                    if let Err(e) = video_player::play_video_at_timestamp(&video.path, 0.0, self.mpv_always_on_top, use_gpu_hq, use_custom_shaders, selected_shader, self.mpv_volume) {
-                        eprintln!("Video playback error: {}", e);
+                let (Some(&first), Some(&last)) = (counts.keys().next(), counts.keys().next_back()) else {
+                    ui.label(self.i18n.t("no_videos_found"));
+                    return;
+                };
+                
+                // Fill empty months so gaps are visible
+                let mut months = Vec::new();
+                let (mut year, mut month) = first;
+                while (year, month) <= last {
+                    months.push(((year, month), counts.get(&(year, month)).copied().unwrap_or(0)));
+                    if month == 12 {
+                        year += 1;
+                        month = 1;
+                    } else {
+                        month += 1;
                     }
                 }
                 
-                // Tags
-                let video_id = video.id.clone();
-                for tag in &video.tags {
-                    let tag_text = format!("#{}", tag);
-                    let tag_label = ui.label(tag_text);
-                    
-                    // Right-click on tag to remove
-                    tag_label.context_menu(|ui| {
-                        if ui.button("Remove Tag").clicked() {
-                            self.remove_tag_from_video(&video_id, tag);
-                            ui.close_menu();
+                let max_count = months.iter().map(|(_, count)| *count).max().unwrap_or(1).max(1);
+                let chart_height = 140.0;
+                let bar_width = (ui.available_width() / months.len() as f32).clamp(3.0, 40.0);
+                let chart_size = egui::vec2(bar_width * months.len() as f32, chart_height);
+                
+                egui::ScrollArea::horizontal()
+                    .id_salt("additions_histogram")
+                    .show(ui, |ui| {
+                        let (chart_rect, _) = ui.allocate_exact_size(chart_size, egui::Sense::hover());
+                        
+                        for (i, (key, count)) in months.iter().enumerate() {
+                            let slot = egui::Rect::from_min_size(
+                                egui::pos2(chart_rect.min.x + i as f32 * bar_width, chart_rect.min.y),
+                                egui::vec2(bar_width, chart_height),
+                            );
+                            let response = ui.interact(slot, ui.id().with(("month_bar", *key)), egui::Sense::click());
+                            
+                            let bar_height = chart_height * (*count as f32 / max_count as f32);
+                            let bar = egui::Rect::from_min_max(
+                                egui::pos2(slot.min.x + 1.0, slot.max.y - bar_height),
+                                egui::pos2(slot.max.x - 1.0, slot.max.y),
+                            );
+                            let color = if self.added_month_filter == Some(*key) {
+                                egui::Color32::from_rgb(255, 180, 60)
+                            } else if response.hovered() {
+                                egui::Color32::from_rgb(130, 210, 255)
+                            } else {
+                                egui::Color32::from_rgb(100, 160, 230)
+                            };
+                            ui.painter().rect_filled(bar, 1.0, color);
+                            
+                            let response = response.on_hover_text(format!("{:04}-{:02}: {}", key.0, key.1, count));
+                            if response.clicked() {
+                                // Click again to clear the filter
+                                self.added_month_filter = if self.added_month_filter == Some(*key) {
+                                    None
+                                } else {
+                                    Some(*key)
+                                };
+                            }
                         }
                     });
-                }
                 
-                // Rating stars
-                if video.rating > 0 {
-                    let stars = "★".repeat(video.rating as usize) + &"☆".repeat((5 - video.rating) as usize);
-                    ui.label(egui::RichText::new(stars).color(egui::Color32::from_rgb(255, 215, 0)));
-                }
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(format!("{:04}-{:02}", first.0, first.1)).weak().small());
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(egui::RichText::new(format!("{:04}-{:02}", last.0, last.1)).weak().small());
+                    });
+                });
+                ui.label(egui::RichText::new(self.i18n.t("histogram_hint")).weak().small());
+            });
+        
+        if !window_open {
+            self.show_statistics_window = false;
+        }
+    }
+    
+    /// Window listing groups of likely duplicate videos, with keep/delete actions
+    fn show_duplicates_window(&mut self, ctx: &egui::Context) {
+        let mut window_open = true;
+        let mut scan = false;
+        let mut to_delete: Vec<String> = Vec::new();
+        
+        egui::Window::new(self.i18n.t("find_duplicates"))
+            .open(&mut window_open)
+            .collapsible(false)
+            .resizable(true)
+            .default_width(620.0)
+            .default_height(500.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(self.i18n.t("duplicate_sensitivity"));
+                    ui.add(egui::Slider::new(&mut self.duplicate_max_distance, 0..=16));
+                    let scanning = self.duplicate_scan_receiver.is_some();
+                    if ui.add_enabled(!scanning, egui::Button::new(self.i18n.t("scan_duplicates"))).clicked() {
+                        scan = true;
+                    }
+                    if scanning {
+                        ui.spinner();
+                    }
+                });
+                ui.label(egui::RichText::new(self.i18n.t("duplicate_hint")).weak().small());
+                ui.separator();
                 
-                // Folder
-                if let Some(folder) = &video.folder {
-                    ui.label(format!("Folder: {}", folder));
+                if !self.duplicates_scanned {
+                    return;
+                }
+                if self.duplicate_groups.is_empty() {
+                    ui.label(self.i18n.t("no_duplicates_found"));
+                    return;
                 }
                 
-                // File size
-                let size_mb = video.file_size as f64 / 1024.0 / 1024.0;
-                ui.label(format!("{:.1} MB", size_mb));
+                ui.label(self.i18n.t("duplicate_groups_found").replace("{}", &self.duplicate_groups.len().to_string()));
                 
-                // Delete button
-                if ui.button("X").clicked() {
-                    self.delete_confirm_video = Some(video.id.clone());
-                }
+                let groups = self.duplicate_groups.clone();
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for (group_index, group) in groups.iter().enumerate() {
+                        ui.push_id(group_index, |ui| {
+                            ui.group(|ui| {
+                                for video_id in group {
+                                    let Some(video) = self.database.get_video(video_id).cloned() else {
+                                        continue;
+                                    };
+                                    ui.horizontal(|ui| {
+                                        let texture = video.thumbnail_path.as_ref()
+                                            .and_then(|path| self.load_image_texture(ctx, path));
+                                        if let Some(texture) = texture {
+                                            let size = texture.size_vec2();
+                                            let scale = 96.0 / size.x.max(1.0);
+                                            ui.image((texture.id(), size * scale));
+                                        }
+                                        
+                                        ui.vertical(|ui| {
+                                            ui.label(egui::RichText::new(&video.title).strong());
+                                            ui.label(egui::RichText::new(video.path.to_string_lossy()).weak().small());
+                                            
+                                            let resolution = video.resolution
+                                                .map(|(w, h)| format!("{}x{} ({})", w, h, quality_label((w, h))))
+                                                .unwrap_or_else(|| "-".to_string());
+                                            let duration = video.duration
+                                                .map(scene_detection::format_timestamp)
+                                                .unwrap_or_else(|| "-".to_string());
+                                            ui.label(format!("{}  |  {:.1} MB  |  {}", resolution, video.file_size as f64 / 1024.0 / 1024.0, duration));
+                                            
+                                            ui.horizontal(|ui| {
+                                                if ui.button(self.i18n.t("keep_this")).on_hover_text(self.i18n.t("keep_this_tooltip")).clicked() {
+                                                    to_delete.extend(group.iter().filter(|id| *id != video_id).cloned());
+                                                }
+                                                if ui.button(self.i18n.t("delete")).clicked() {
+                                                    to_delete.push(video_id.clone());
+                                                }
+                                            });
+                                        });
+                                    });
+                                }
+                            });
+                        });
+                        ui.add_space(4.0);
+                    }
+                });
             });
+        
+        if scan {
+            self.find_duplicate_videos();
+        }
+        
+        if !to_delete.is_empty() {
+            let mut removed = Vec::new();
+            for video_id in &to_delete {
+                // Keep the cache so undo brings back thumbnails and scenes as well
+                if let Some(entry) = self.delete_video_record(video_id, false) {
+                    removed.push(entry);
+                }
+            }
+            // One undo step for the whole action
+            if !removed.is_empty() {
+                self.push_undo(UndoEntry::Videos(removed));
+            }
             
-            ui.separator();
+            // Drop deleted videos from the groups, and groups with nothing left to compare
+            for group in &mut self.duplicate_groups {
+                group.retain(|id| !to_delete.contains(id));
+            }
+            self.duplicate_groups.retain(|group| group.len() > 1);
+        }
+        
+        if !window_open {
+            self.show_duplicates_window = false;
+        }
+    }
+}
+
+/// Short quality label for a resolution, based on the shorter side (e.g. "1080p", "4K")
+fn quality_label((width, height): (u32, u32)) -> String {
+    match width.min(height) {
+        h if h >= 2160 => "4K".to_string(),
+        h if h >= 1440 => "1440p".to_string(),
+        h if h >= 1080 => "1080p".to_string(),
+        h if h >= 720 => "720p".to_string(),
+        h if h >= 480 => "480p".to_string(),
+        h => format!("{}p", h),
+    }
+}
+
+/// Number of logical CPUs (upper bound of the thread count settings)
+fn cpu_count() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Folder watcher retry delays (doubling after each failed attempt) and the folder availability check interval
+const WATCHER_MIN_BACKOFF_SECS: u64 = 5;
+const WATCHER_MAX_BACKOFF_SECS: u64 = 300;
+const WATCHER_CHECK_INTERVAL_SECS: u64 = 10;
+
+/// Width of a grid item at thumbnail scale 1.0, and the gap between items
+const GRID_ITEM_WIDTH: f32 = 205.0;
+const GRID_SPACING: f32 = 10.0;
+
+/// Draw a small text badge with a translucent background on a thumbnail
+fn draw_thumbnail_badge(painter: &egui::Painter, anchor: egui::Pos2, align: egui::Align2, text: &str, font_size: f32) {
+    let galley = painter.layout_no_wrap(text.to_string(), egui::FontId::proportional(font_size), egui::Color32::WHITE);
+    let padding = egui::vec2(4.0, 2.0);
+    let rect = align.anchor_size(anchor, galley.size() + padding * 2.0);
+    painter.rect_filled(rect, 3.0, egui::Color32::from_black_alpha(170));
+    painter.galley(rect.min + padding, galley, egui::Color32::WHITE);
+}
+
+/// Sort key of the manual order; videos never placed by hand follow, oldest first
+fn manual_order_key(video: &VideoFile) -> (bool, i64, chrono::DateTime<chrono::Utc>) {
+    (video.sort_index == 0, video.sort_index, video.added_date)
+}
+
+/// Largest rect with the image's aspect ratio that fits centered inside `cell`
+fn letterbox_rect(cell: egui::Rect, image_size: egui::Vec2) -> egui::Rect {
+    if image_size.x <= 0.0 || image_size.y <= 0.0 {
+        return cell;
+    }
+    let scale = (cell.width() / image_size.x).min(cell.height() / image_size.y);
+    egui::Rect::from_center_size(cell.center(), image_size * scale)
+}
+
+/// Whether `tag` is `node` itself or one of its "node/..." descendants
+fn tag_in_subtree(tag: &str, node: &str) -> bool {
+    tag.strip_prefix(node)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Tags a selected tree node filters on: the leaf tags below it, plus `node` itself if it is a tag.
+/// A node that is not a tag and has no tags below it stands for itself.
+fn tag_filter_targets(tags: &[String], node: &str) -> Vec<String> {
+    let parents: HashSet<&str> = tags.iter()
+        .flat_map(|tag| tag.match_indices('/').map(move |(i, _)| &tag[..i]))
+        .collect();
+    let leaves: Vec<String> = tags.iter()
+        .filter(|tag| tag_in_subtree(tag, node) && (tag.as_str() == node || !parents.contains(tag.as_str())))
+        .cloned()
+        .collect();
+    if leaves.is_empty() {
+        vec![node.to_string()]
+    } else {
+        leaves
+    }
+}
+
+/// One row of the sidebar tag tree
+struct TagTreeRow {
+    path: String, // Full "parent/child" tag
+    depth: usize,
+    has_children: bool,
+}
+
+impl TagTreeRow {
+    /// Last path segment, shown as the label
+    fn name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+    
+    /// Paths of the parent nodes, nearest last ("a", "a/b" for "a/b/c")
+    fn ancestors(&self) -> impl Iterator<Item = &str> {
+        self.path.match_indices('/').map(|(i, _)| &self.path[..i])
+    }
+}
+
+/// Tag tree rows in display order (depth-first, siblings in first-seen order).
+/// Parents that are not tags themselves get a row too.
+fn tag_tree_rows(tags: &[String]) -> Vec<TagTreeRow> {
+    let mut roots: Vec<String> = Vec::new();
+    let mut children: HashMap<String, Vec<String>> = HashMap::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    for tag in tags {
+        let mut parent: Option<&str> = None;
+        for (end, _) in tag.match_indices('/').chain(std::iter::once((tag.len(), ""))) {
+            let path = &tag[..end];
+            if seen.insert(path.to_string()) {
+                match parent {
+                    Some(parent) => children.entry(parent.to_string()).or_default().push(path.to_string()),
+                    None => roots.push(path.to_string()),
+                }
+            }
+            parent = Some(path);
         }
     }
+    
+    fn push_rows(path: String, depth: usize, children: &HashMap<String, Vec<String>>, rows: &mut Vec<TagTreeRow>) {
+        let kids = children.get(&path);
+        rows.push(TagTreeRow { path, depth, has_children: kids.is_some() });
+        for child in kids.into_iter().flatten() {
+            push_rows(child.clone(), depth + 1, children, rows);
+        }
+    }
+    
+    let mut rows = Vec::new();
+    for root in roots {
+        push_rows(root, 0, &children, &mut rows);
+    }
+    rows
+}
+
+/// (year, month) a video was added, in local time
+fn added_month(video: &VideoFile) -> (i32, u32) {
+    use chrono::Datelike;
+    let local = video.added_date.with_timezone(&chrono::Local);
+    (local.year(), local.month())
+}
+
+/// Quote a CSV field when it contains separators, quotes or line breaks
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Local date/time in a spreadsheet-friendly format
+fn format_export_date(date: chrono::DateTime<chrono::Utc>) -> String {
+    date.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
 /// Load image data from file in a format suitable for creating a texture
@@ -4262,7 +10397,7 @@ fn load_image_data(image_path: &PathBuf) -> Option<(Vec<u8>, [usize; 2])> {
 
 /// Async version of regenerate_missing_thumbnails for running in a background thread
 /// Takes ownership of database and returns the updated database
-fn regenerate_missing_thumbnails_async(mut database: VideoDatabase) -> Result<VideoDatabase, String> {
+fn regenerate_missing_thumbnails_async(mut database: VideoDatabase, scene_threshold: f32, max_scenes: usize, scene_thread_count: usize) -> Result<VideoDatabase, String> {
     let cache_dir = thumbnail::get_cache_dir();
     let mut needs_save = false;
     
@@ -4293,7 +10428,7 @@ fn regenerate_missing_thumbnails_async(mut database: VideoDatabase) -> Result<Vi
             // Clear existing scenes
             video.scenes.clear();
             // Regenerate scenes
-            if let Err(e) = scene_detection::detect_scenes(video, &cache_dir) {
+            if let Err(e) = scene_detection::detect_scenes(video, &cache_dir, scene_threshold, max_scenes, scene_thread_count) {
                 eprintln!("[Restore] Failed to regenerate scenes for {:?}: {}", video.path, e);
             }
             needs_save = true;
@@ -4311,3 +10446,54 @@ fn regenerate_missing_thumbnails_async(mut database: VideoDatabase) -> Result<Vi
     
     Ok(database)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn tags(list: &[&str]) -> Vec<String> {
+        list.iter().map(|tag| tag.to_string()).collect()
+    }
+    
+    #[test]
+    fn test_tag_in_subtree() {
+        assert!(tag_in_subtree("a", "a"));
+        assert!(tag_in_subtree("a/b", "a"));
+        assert!(tag_in_subtree("a/b/c", "a/b"));
+        assert!(!tag_in_subtree("ab", "a"));
+        assert!(!tag_in_subtree("a", "a/b"));
+    }
+    
+    #[test]
+    fn test_tag_filter_targets() {
+        let all = tags(&["a", "a/b", "a/b/c", "a/d", "ab"]);
+        let mut targets = tag_filter_targets(&all, "a");
+        targets.sort();
+        // "a" is a real tag, so videos tagged only "a" still match; "a/b" is an inner node
+        assert_eq!(targets, tags(&["a", "a/b/c", "a/d"]));
+        let mut targets = tag_filter_targets(&all, "a/b");
+        targets.sort();
+        assert_eq!(targets, tags(&["a/b", "a/b/c"]));
+        assert_eq!(tag_filter_targets(&all, "ab"), tags(&["ab"]));
+        // Synthetic parent (not a tag itself)
+        assert_eq!(tag_filter_targets(&tags(&["x/y"]), "x"), tags(&["x/y"]));
+        assert_eq!(tag_filter_targets(&all, "missing"), tags(&["missing"]));
+    }
+    
+    #[test]
+    fn test_tag_tree_rows() {
+        let rows = tag_tree_rows(&tags(&["a/b/c", "ab", "a/d"]));
+        let listed: Vec<(&str, usize, bool)> = rows.iter()
+            .map(|row| (row.path.as_str(), row.depth, row.has_children))
+            .collect();
+        assert_eq!(listed, vec![
+            ("a", 0, true),
+            ("a/b", 1, true),
+            ("a/b/c", 2, false),
+            ("a/d", 1, false),
+            ("ab", 0, false),
+        ]);
+        assert_eq!(rows[2].name(), "c");
+        assert_eq!(rows[2].ancestors().collect::<Vec<_>>(), vec!["a", "a/b"]);
+    }
+}
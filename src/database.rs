@@ -1,4 +1,4 @@
-use crate::models::{VideoDatabase, VideoFile, SceneInfo, AppSettings};
+use crate::models::{VideoDatabase, VideoFile, SceneInfo, ChapterInfo, AppSettings};
 use std::path::PathBuf;
 use std::fs;
 use std::cell::RefCell;
@@ -24,8 +24,7 @@ pub fn get_current_profile() -> String {
 
 /// Get database directory path
 fn get_database_dir() -> PathBuf {
-    let mut path = dirs::data_local_dir().unwrap_or_else(|| PathBuf::from("."));
-    path.push("CicadaGallery");
+    let path = get_database_dir_path();
     
     // Create directory if it doesn't exist
     if !path.exists() {
@@ -35,6 +34,13 @@ fn get_database_dir() -> PathBuf {
     path
 }
 
+/// Get database directory path (does NOT auto-create)
+fn get_database_dir_path() -> PathBuf {
+    let mut path = dirs::data_local_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push("CicadaGallery");
+    path
+}
+
 /// Get SQLite database file path
 pub fn get_database_path() -> PathBuf {
     let mut path = get_database_dir();
@@ -94,7 +100,23 @@ pub fn init_database(conn: &Connection) -> SqlResult<()> {
             folder TEXT,
             rating INTEGER NOT NULL DEFAULT 0,
             added_date TEXT NOT NULL,
-            last_played TEXT
+            last_played TEXT,
+            frame_rate REAL,
+            playback_position REAL,
+            protected INTEGER NOT NULL DEFAULT 0,
+            play_count INTEGER NOT NULL DEFAULT 0,
+            notes TEXT NOT NULL DEFAULT '',
+            preview_thumbnail_path TEXT,
+            content_hash TEXT,
+            sprite_sheet_path TEXT,
+            watched INTEGER NOT NULL DEFAULT 0,
+            sort_index INTEGER NOT NULL DEFAULT 0,
+            codec TEXT,
+            container TEXT,
+            is_hdr INTEGER NOT NULL DEFAULT 0,
+            modified_time INTEGER,
+            preferred_volume INTEGER,
+            preferred_speed REAL
         );
         
         CREATE TABLE IF NOT EXISTS video_tags (
@@ -104,11 +126,27 @@ pub fn init_database(conn: &Connection) -> SqlResult<()> {
             FOREIGN KEY (video_id) REFERENCES videos(id) ON DELETE CASCADE
         );
         
+        CREATE TABLE IF NOT EXISTS video_folders (
+            video_id TEXT NOT NULL,
+            folder TEXT NOT NULL,
+            PRIMARY KEY (video_id, folder),
+            FOREIGN KEY (video_id) REFERENCES videos(id) ON DELETE CASCADE
+        );
+        
         CREATE TABLE IF NOT EXISTS scenes (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             video_id TEXT NOT NULL,
             timestamp REAL NOT NULL,
             thumbnail_path TEXT NOT NULL,
+            score REAL,
+            FOREIGN KEY (video_id) REFERENCES videos(id) ON DELETE CASCADE
+        );
+        
+        CREATE TABLE IF NOT EXISTS chapters (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            video_id TEXT NOT NULL,
+            start REAL NOT NULL,
+            title TEXT NOT NULL,
             FOREIGN KEY (video_id) REFERENCES videos(id) ON DELETE CASCADE
         );
         
@@ -124,10 +162,68 @@ pub fn init_database(conn: &Connection) -> SqlResult<()> {
         CREATE INDEX IF NOT EXISTS idx_video_folder ON videos(folder);
         CREATE INDEX IF NOT EXISTS idx_video_rating ON videos(rating);
         CREATE INDEX IF NOT EXISTS idx_video_tags_tag ON video_tags(tag);
+        CREATE INDEX IF NOT EXISTS idx_video_folders_folder ON video_folders(folder);
         CREATE INDEX IF NOT EXISTS idx_scenes_video ON scenes(video_id);
+        CREATE INDEX IF NOT EXISTS idx_chapters_video ON chapters(video_id);
         "
     )?;
     
+    migrate_schema(conn)?;
+    
+    Ok(())
+}
+
+/// Columns selected when loading a video (order must match video_from_row)
+const VIDEO_COLUMNS: &str = "id, path, title, duration, file_size, resolution_width, resolution_height, \
+    thumbnail_path, folder, rating, added_date, last_played, frame_rate, playback_position, protected, play_count, notes, preview_thumbnail_path, content_hash, sprite_sheet_path, watched, sort_index, codec, container, is_hdr, modified_time, preferred_volume, preferred_speed";
+
+/// Add a column to an existing table if it does not exist yet
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, definition: &str) -> SqlResult<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt.query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .any(|name| name == column);
+    
+    if !exists {
+        conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+        eprintln!("[Migration] Added column {}.{}", table, column);
+    }
+    
+    Ok(())
+}
+
+/// Upgrade databases created by older versions to the current schema
+fn migrate_schema(conn: &Connection) -> SqlResult<()> {
+    add_column_if_missing(conn, "videos", "frame_rate", "REAL")?;
+    add_column_if_missing(conn, "videos", "playback_position", "REAL")?;
+    add_column_if_missing(conn, "videos", "protected", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "videos", "play_count", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "videos", "notes", "TEXT NOT NULL DEFAULT ''")?;
+    add_column_if_missing(conn, "videos", "preview_thumbnail_path", "TEXT")?;
+    add_column_if_missing(conn, "videos", "content_hash", "TEXT")?;
+    add_column_if_missing(conn, "videos", "sprite_sheet_path", "TEXT")?;
+    add_column_if_missing(conn, "videos", "watched", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "videos", "sort_index", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "videos", "codec", "TEXT")?;
+    add_column_if_missing(conn, "videos", "container", "TEXT")?;
+    add_column_if_missing(conn, "videos", "is_hdr", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "videos", "modified_time", "INTEGER")?;
+    add_column_if_missing(conn, "videos", "preferred_volume", "INTEGER")?;
+    add_column_if_missing(conn, "videos", "preferred_speed", "REAL")?;
+    add_column_if_missing(conn, "scenes", "score", "REAL")?;
+    
+    // videos.folder (one folder per video) -> video_folders.
+    // The column is kept in sync with the first folder, so this only copies rows once.
+    let has_memberships: bool = conn.query_row("SELECT EXISTS(SELECT 1 FROM video_folders)", [], |row| row.get(0))?;
+    if !has_memberships {
+        let migrated = conn.execute(
+            "INSERT OR IGNORE INTO video_folders (video_id, folder) SELECT id, folder FROM videos WHERE folder IS NOT NULL",
+            [],
+        )?;
+        if migrated > 0 {
+            eprintln!("[Migration] Moved {} video folders to video_folders", migrated);
+        }
+    }
     Ok(())
 }
 
@@ -142,7 +238,14 @@ pub fn migrate_from_json(conn: &Connection) -> Result<bool, Box<dyn std::error::
     eprintln!("[Migration] Found legacy JSON database, migrating to SQLite...");
     
     let json = fs::read_to_string(&legacy_path)?;
-    let database: VideoDatabase = serde_json::from_str(&json)?;
+    let mut database: VideoDatabase = serde_json::from_str(&json)?;
+    for video in &mut database.videos {
+        if let Some(folder) = video.folder_legacy.take() {
+            if !video.folders.contains(&folder) {
+                video.folders.push(folder);
+            }
+        }
+    }
     
     // Migrate folders
     for folder in &database.folders {
@@ -171,8 +274,8 @@ pub fn migrate_from_json(conn: &Connection) -> Result<bool, Box<dyn std::error::
 /// Insert a video into the database
 pub fn insert_video(conn: &Connection, video: &VideoFile) -> SqlResult<()> {
     conn.execute(
-        "INSERT OR REPLACE INTO videos (id, path, title, duration, file_size, resolution_width, resolution_height, thumbnail_path, folder, rating, added_date, last_played)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+        "INSERT OR REPLACE INTO videos (id, path, title, duration, file_size, resolution_width, resolution_height, thumbnail_path, folder, rating, added_date, last_played, frame_rate, playback_position, protected, play_count, notes, preview_thumbnail_path, content_hash, sprite_sheet_path, watched, sort_index, codec, container, is_hdr, modified_time, preferred_volume, preferred_speed)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28)",
         params![
             video.id,
             video.path.to_string_lossy(),
@@ -182,16 +285,33 @@ pub fn insert_video(conn: &Connection, video: &VideoFile) -> SqlResult<()> {
             video.resolution.map(|r| r.0 as i64),
             video.resolution.map(|r| r.1 as i64),
             video.thumbnail_path.as_ref().map(|p| p.to_string_lossy().to_string()),
-            video.folder,
+            video.folders.first(), // Legacy single-folder column (first folder)
             video.rating as i64,
             video.added_date.to_rfc3339(),
             video.last_played.map(|d| d.to_rfc3339()),
+            video.frame_rate,
+            video.playback_position,
+            video.protected,
+            video.play_count as i64,
+            video.notes,
+            video.preview_thumbnail_path.as_ref().map(|p| p.to_string_lossy().to_string()),
+            video.content_hash,
+            video.sprite_sheet_path.as_ref().map(|p| p.to_string_lossy().to_string()),
+            video.watched,
+            video.sort_index,
+            video.codec,
+            video.container,
+            video.is_hdr,
+            video.modified_time,
+            video.preferred_volume.map(|v| v as i64),
+            video.preferred_speed,
         ],
     )?;
     
-    // Insert tags
+    // Insert tags (and ensure the tags are in the tags table)
     conn.execute("DELETE FROM video_tags WHERE video_id = ?1", params![video.id])?;
     for tag in &video.tags {
+        add_tag(conn, tag)?;
         conn.execute(
             "INSERT OR IGNORE INTO video_tags (video_id, tag) VALUES (?1, ?2)",
             params![video.id, tag],
@@ -202,20 +322,40 @@ pub fn insert_video(conn: &Connection, video: &VideoFile) -> SqlResult<()> {
     conn.execute("DELETE FROM scenes WHERE video_id = ?1", params![video.id])?;
     for scene in &video.scenes {
         conn.execute(
-            "INSERT INTO scenes (video_id, timestamp, thumbnail_path) VALUES (?1, ?2, ?3)",
+            "INSERT INTO scenes (video_id, timestamp, thumbnail_path, score) VALUES (?1, ?2, ?3, ?4)",
             params![
                 video.id,
                 scene.timestamp,
                 scene.thumbnail_path.to_string_lossy(),
+                scene.score,
             ],
         )?;
     }
     
-    // Ensure folder is in folders table
-    if let Some(ref folder) = video.folder {
+    // Insert chapters
+    conn.execute("DELETE FROM chapters WHERE video_id = ?1", params![video.id])?;
+    for chapter in &video.chapters {
+        conn.execute(
+            "INSERT INTO chapters (video_id, start, title) VALUES (?1, ?2, ?3)",
+            params![video.id, chapter.start, chapter.title],
+        )?;
+    }
+    
+    // Insert folder memberships (and ensure the folders are in the folders table)
+    conn.execute("DELETE FROM video_folders WHERE video_id = ?1", params![video.id])?;
+    for folder in &video.folders {
+        conn.execute(
+            "INSERT OR IGNORE INTO video_folders (video_id, folder) VALUES (?1, ?2)",
+            params![video.id, folder],
+        )?;
         conn.execute("INSERT OR IGNORE INTO folders (name) VALUES (?1)", params![folder])?;
     }
     
+    // Ensure tags are in tags table
+    for tag in &video.tags {
+        add_tag(conn, tag)?;
+    }
+    
     Ok(())
 }
 
@@ -227,7 +367,9 @@ pub fn update_video(conn: &Connection, video: &VideoFile) -> SqlResult<()> {
 /// Delete a video from the database
 pub fn delete_video(conn: &Connection, video_id: &str) -> SqlResult<()> {
     conn.execute("DELETE FROM video_tags WHERE video_id = ?1", params![video_id])?;
+    conn.execute("DELETE FROM video_folders WHERE video_id = ?1", params![video_id])?;
     conn.execute("DELETE FROM scenes WHERE video_id = ?1", params![video_id])?;
+    conn.execute("DELETE FROM chapters WHERE video_id = ?1", params![video_id])?;
     conn.execute("DELETE FROM videos WHERE id = ?1", params![video_id])?;
     Ok(())
 }
@@ -242,10 +384,26 @@ fn video_from_row(row: &rusqlite::Row) -> SqlResult<VideoFile> {
     let resolution_width: Option<i64> = row.get(5)?;
     let resolution_height: Option<i64> = row.get(6)?;
     let thumbnail_path_str: Option<String> = row.get(7)?;
-    let folder: Option<String> = row.get(8)?;
+    // Column 8 (legacy single folder) is not read: folders come from video_folders
     let rating: i64 = row.get(9)?;
     let added_date_str: String = row.get(10)?;
     let last_played_str: Option<String> = row.get(11)?;
+    let frame_rate: Option<f64> = row.get(12)?;
+    let playback_position: Option<f64> = row.get(13)?;
+    let protected: bool = row.get(14)?;
+    let play_count: i64 = row.get(15)?;
+    let notes: String = row.get(16)?;
+    let preview_thumbnail_path: Option<String> = row.get(17)?;
+    let content_hash: Option<String> = row.get(18)?;
+    let sprite_sheet_path: Option<String> = row.get(19)?;
+    let watched: bool = row.get(20)?;
+    let sort_index: i64 = row.get(21)?;
+    let codec: Option<String> = row.get(22)?;
+    let container: Option<String> = row.get(23)?;
+    let is_hdr: bool = row.get(24)?;
+    let modified_time: Option<i64> = row.get(25)?;
+    let preferred_volume: Option<i64> = row.get(26)?;
+    let preferred_speed: Option<f64> = row.get(27)?;
     
     let resolution = match (resolution_width, resolution_height) {
         (Some(w), Some(h)) => Some((w as u32, h as u32)),
@@ -268,16 +426,33 @@ fn video_from_row(row: &rusqlite::Row) -> SqlResult<VideoFile> {
         title,
         duration,
         file_size: file_size as u64,
+        modified_time,
         resolution,
-        frame_rate: None, // Frame rate is loaded separately if needed
+        frame_rate,
+        codec,
+        container,
+        is_hdr,
         thumbnail_path,
+        preview_thumbnail_path: preview_thumbnail_path.map(PathBuf::from),
+        sprite_sheet_path: sprite_sheet_path.map(PathBuf::from),
+        content_hash,
         tags: Vec::new(), // Will be filled separately
-        folder,
+        folders: Vec::new(), // Will be filled separately
+        folder_legacy: None,
         rating: rating as u8,
         is_favorite_legacy: None,
         added_date,
         last_played,
+        play_count: play_count as u32,
+        notes,
+        playback_position,
+        protected,
+        watched,
+        sort_index,
+        preferred_volume: preferred_volume.map(|v| v.clamp(0, u8::MAX as i64) as u8),
+        preferred_speed,
         scenes: Vec::new(), // Will be filled separately
+        chapters: Vec::new(), // Will be filled separately
     })
 }
 
@@ -289,15 +464,38 @@ fn load_video_tags(conn: &Connection, video_id: &str) -> SqlResult<Vec<String>>
     Ok(tags)
 }
 
+/// Load folder memberships for a video
+fn load_video_folders(conn: &Connection, video_id: &str) -> SqlResult<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT folder FROM video_folders WHERE video_id = ?1 ORDER BY rowid")?;
+    let folders = stmt.query_map(params![video_id], |row| row.get(0))?
+        .collect::<SqlResult<Vec<String>>>()?;
+    Ok(folders)
+}
+
+/// Load chapters for a video
+fn load_video_chapters(conn: &Connection, video_id: &str) -> SqlResult<Vec<ChapterInfo>> {
+    let mut stmt = conn.prepare("SELECT start, title FROM chapters WHERE video_id = ?1 ORDER BY start")?;
+    let chapters = stmt.query_map(params![video_id], |row| {
+        Ok(ChapterInfo {
+            start: row.get(0)?,
+            title: row.get(1)?,
+        })
+    })?
+    .collect::<SqlResult<Vec<ChapterInfo>>>()?;
+    Ok(chapters)
+}
+
 /// Load scenes for a video
 fn load_video_scenes(conn: &Connection, video_id: &str) -> SqlResult<Vec<SceneInfo>> {
-    let mut stmt = conn.prepare("SELECT timestamp, thumbnail_path FROM scenes WHERE video_id = ?1 ORDER BY timestamp")?;
+    let mut stmt = conn.prepare("SELECT timestamp, thumbnail_path, score FROM scenes WHERE video_id = ?1 ORDER BY timestamp")?;
     let scenes = stmt.query_map(params![video_id], |row| {
         let timestamp: f64 = row.get(0)?;
         let path_str: String = row.get(1)?;
+        let score: Option<f64> = row.get(2)?;
         Ok(SceneInfo {
             timestamp,
             thumbnail_path: PathBuf::from(path_str),
+            score,
         })
     })?.collect::<SqlResult<Vec<SceneInfo>>>()?;
     Ok(scenes)
@@ -306,9 +504,7 @@ fn load_video_scenes(conn: &Connection, video_id: &str) -> SqlResult<Vec<SceneIn
 /// Load a video by ID with all related data
 pub fn load_video_by_id(conn: &Connection, video_id: &str) -> SqlResult<Option<VideoFile>> {
     let mut stmt = conn.prepare(
-        "SELECT id, path, title, duration, file_size, resolution_width, resolution_height, 
-                thumbnail_path, folder, rating, added_date, last_played 
-         FROM videos WHERE id = ?1"
+        &format!("SELECT {} FROM videos WHERE id = ?1", VIDEO_COLUMNS)
     )?;
     
     let mut rows = stmt.query(params![video_id])?;
@@ -316,7 +512,9 @@ pub fn load_video_by_id(conn: &Connection, video_id: &str) -> SqlResult<Option<V
     if let Some(row) = rows.next()? {
         let mut video = video_from_row(row)?;
         video.tags = load_video_tags(conn, &video.id)?;
+        video.folders = load_video_folders(conn, &video.id)?;
         video.scenes = load_video_scenes(conn, &video.id)?;
+        video.chapters = load_video_chapters(conn, &video.id)?;
         Ok(Some(video))
     } else {
         Ok(None)
@@ -333,9 +531,7 @@ pub fn has_video_path(conn: &Connection, path: &PathBuf) -> SqlResult<bool> {
 /// Get video by path
 pub fn get_video_by_path(conn: &Connection, path: &PathBuf) -> SqlResult<Option<VideoFile>> {
     let mut stmt = conn.prepare(
-        "SELECT id, path, title, duration, file_size, resolution_width, resolution_height, 
-                thumbnail_path, folder, rating, added_date, last_played 
-         FROM videos WHERE path = ?1"
+        &format!("SELECT {} FROM videos WHERE path = ?1", VIDEO_COLUMNS)
     )?;
     
     let path_str = path.to_string_lossy();
@@ -344,7 +540,9 @@ pub fn get_video_by_path(conn: &Connection, path: &PathBuf) -> SqlResult<Option<
     if let Some(row) = rows.next()? {
         let mut video = video_from_row(row)?;
         video.tags = load_video_tags(conn, &video.id)?;
+        video.folders = load_video_folders(conn, &video.id)?;
         video.scenes = load_video_scenes(conn, &video.id)?;
+        video.chapters = load_video_chapters(conn, &video.id)?;
         Ok(Some(video))
     } else {
         Ok(None)
@@ -355,9 +553,7 @@ pub fn get_video_by_path(conn: &Connection, path: &PathBuf) -> SqlResult<Option<
 pub fn load_all_videos(conn: &Connection) -> SqlResult<Vec<VideoFile>> {
     // Load all videos
     let mut stmt = conn.prepare(
-        "SELECT id, path, title, duration, file_size, resolution_width, resolution_height, 
-                thumbnail_path, folder, rating, added_date, last_played 
-         FROM videos"
+        &format!("SELECT {} FROM videos", VIDEO_COLUMNS)
     )?;
     
     let video_rows = stmt.query_map([], video_from_row)?
@@ -367,6 +563,18 @@ pub fn load_all_videos(conn: &Connection) -> SqlResult<Vec<VideoFile>> {
     let mut video_map: std::collections::HashMap<String, VideoFile> = 
         video_rows.into_iter().map(|v| (v.id.clone(), v)).collect();
     
+    // Batch load all folder memberships
+    let mut folder_stmt = conn.prepare("SELECT video_id, folder FROM video_folders ORDER BY rowid")?;
+    let folder_rows = folder_stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+    
+    for (video_id, folder) in folder_rows.flatten() {
+        if let Some(video) = video_map.get_mut(&video_id) {
+            video.folders.push(folder);
+        }
+    }
+    
     // Batch load all tags
     let mut tag_stmt = conn.prepare("SELECT video_id, tag FROM video_tags ORDER BY video_id")?;
     let tag_rows = tag_stmt.query_map([], |row| {
@@ -383,27 +591,47 @@ pub fn load_all_videos(conn: &Connection) -> SqlResult<Vec<VideoFile>> {
     
     // Batch load all scenes
     let mut scene_stmt = conn.prepare(
-        "SELECT video_id, timestamp, thumbnail_path FROM scenes ORDER BY video_id, timestamp"
+        "SELECT video_id, timestamp, thumbnail_path, score FROM scenes ORDER BY video_id, timestamp"
     )?;
     let scene_rows = scene_stmt.query_map([], |row| {
         Ok((
             row.get::<_, String>(0)?,
             row.get::<_, f64>(1)?,
-            row.get::<_, String>(2)?
+            row.get::<_, String>(2)?,
+            row.get::<_, Option<f64>>(3)?
         ))
     })?;
     
     for scene_result in scene_rows {
-        if let Ok((video_id, timestamp, path_str)) = scene_result {
+        if let Ok((video_id, timestamp, path_str, score)) = scene_result {
             if let Some(video) = video_map.get_mut(&video_id) {
                 video.scenes.push(SceneInfo {
                     timestamp,
                     thumbnail_path: PathBuf::from(path_str),
+                    score,
                 });
             }
         }
     }
     
+    // Batch load all chapters
+    let mut chapter_stmt = conn.prepare(
+        "SELECT video_id, start, title FROM chapters ORDER BY video_id, start"
+    )?;
+    let chapter_rows = chapter_stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, f64>(1)?,
+            row.get::<_, String>(2)?
+        ))
+    })?;
+    
+    for (video_id, start, title) in chapter_rows.flatten() {
+        if let Some(video) = video_map.get_mut(&video_id) {
+            video.chapters.push(ChapterInfo { start, title });
+        }
+    }
+    
     // Convert map back to vector
     Ok(video_map.into_values().collect())
 }
@@ -451,7 +679,7 @@ pub fn remove_tag(conn: &Connection, tag: &str) -> SqlResult<()> {
 /// Remove unused folders (not associated with any video)
 pub fn cleanup_unused_folders(conn: &Connection) -> SqlResult<usize> {
     let result = conn.execute(
-        "DELETE FROM folders WHERE name NOT IN (SELECT DISTINCT folder FROM videos WHERE folder IS NOT NULL)",
+        "DELETE FROM folders WHERE name NOT IN (SELECT DISTINCT folder FROM video_folders)",
         [],
     )?;
     Ok(result)
@@ -550,7 +778,9 @@ pub fn save_database(database: &VideoDatabase) -> Result<(), Box<dyn std::error:
     
     // Clear and rebuild
     conn.execute("DELETE FROM video_tags", [])?;
+    conn.execute("DELETE FROM video_folders", [])?;
     conn.execute("DELETE FROM scenes", [])?;
+    conn.execute("DELETE FROM chapters", [])?;
     conn.execute("DELETE FROM videos", [])?;
     conn.execute("DELETE FROM folders", [])?;
     conn.execute("DELETE FROM tags", [])?;
@@ -572,6 +802,86 @@ pub fn save_database(database: &VideoDatabase) -> Result<(), Box<dyn std::error:
     Ok(())
 }
 
+/// Save changed videos (row, tags, scenes, chapters) without rewriting the whole library.
+/// Like the other persist_* helpers this expects the schema to be set up already (done on load).
+pub fn persist_videos(videos: &[&VideoFile]) -> Result<(), Box<dyn std::error::Error>> {
+    if videos.is_empty() {
+        return Ok(());
+    }
+    
+    let conn = open_connection()?;
+    
+    conn.execute("BEGIN TRANSACTION", [])?;
+    for video in videos {
+        insert_video(&conn, video)?;
+    }
+    conn.execute("COMMIT", [])?;
+    
+    Ok(())
+}
+
+/// Save a single changed video
+pub fn persist_video(video: &VideoFile) -> Result<(), Box<dyn std::error::Error>> {
+    persist_videos(&[video])
+}
+
+/// Sync the given tags (tag list and video assignments) after a tag was
+/// added, renamed, merged or deleted
+pub fn persist_tag_change(database: &VideoDatabase, tags: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let conn = open_connection()?;
+    
+    conn.execute("BEGIN TRANSACTION", [])?;
+    for tag in tags {
+        conn.execute("DELETE FROM video_tags WHERE tag = ?1", params![tag])?;
+        if database.tags.iter().any(|t| t == tag) {
+            add_tag(&conn, tag)?;
+        } else {
+            remove_tag(&conn, tag)?;
+        }
+        for video in database.videos.iter().filter(|v| v.tags.iter().any(|t| t == tag)) {
+            conn.execute(
+                "INSERT OR IGNORE INTO video_tags (video_id, tag) VALUES (?1, ?2)",
+                params![video.id, tag],
+            )?;
+        }
+    }
+    conn.execute("COMMIT", [])?;
+    
+    Ok(())
+}
+
+/// Save a content folder rename: the member videos are rewritten and the old folder row dropped
+pub fn persist_folder_rename(database: &VideoDatabase, old_name: &str, new_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let conn = open_connection()?;
+    
+    conn.execute("BEGIN TRANSACTION", [])?;
+    for video in database.videos.iter().filter(|v| v.folders.iter().any(|f| f == new_name)) {
+        insert_video(&conn, video)?;
+    }
+    add_folder(&conn, new_name)?;
+    remove_folder(&conn, old_name)?;
+    conn.execute("COMMIT", [])?;
+    
+    Ok(())
+}
+
+/// Remove deleted videos (with their tags, scenes and chapters)
+pub fn persist_deletion(video_ids: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    if video_ids.is_empty() {
+        return Ok(());
+    }
+    
+    let conn = open_connection()?;
+    
+    conn.execute("BEGIN TRANSACTION", [])?;
+    for video_id in video_ids {
+        delete_video(&conn, video_id)?;
+    }
+    conn.execute("COMMIT", [])?;
+    
+    Ok(())
+}
+
 /// Load database (compatibility wrapper - loads VideoDatabase from SQLite)
 pub fn load_database() -> Result<VideoDatabase, Box<dyn std::error::Error>> {
     let conn = open_connection()?;
@@ -658,12 +968,12 @@ pub fn list_backups() -> Result<Vec<(PathBuf, String)>, Box<dyn std::error::Erro
     Ok(backups)
 }
 
-/// Remove old backups, keeping only the latest N
+/// Remove old backups, keeping only the latest N (0 = keep all)
 fn cleanup_old_backups(keep_count: usize) -> Result<usize, Box<dyn std::error::Error>> {
     let backups = list_backups()?;
     let mut removed = 0;
     
-    if backups.len() > keep_count {
+    if keep_count > 0 && backups.len() > keep_count {
         for (path, _) in backups.into_iter().skip(keep_count) {
             if fs::remove_file(&path).is_ok() {
                 eprintln!("[Backup] Removed old backup: {:?}", path);
@@ -675,14 +985,14 @@ fn cleanup_old_backups(keep_count: usize) -> Result<usize, Box<dyn std::error::E
     Ok(removed)
 }
 
-/// Check if backup is needed (every 3 days)
+/// Check if backup is needed (every `backup_interval_days` days)
 pub fn should_backup(settings: &AppSettings) -> bool {
     if let Some(ref last_backup_str) = settings.last_backup_date {
         if let Ok(last_backup) = DateTime::parse_from_rfc3339(last_backup_str) {
             let now = Utc::now();
             let last_backup_utc = last_backup.with_timezone(&Utc);
             let days_since_backup = (now - last_backup_utc).num_days();
-            return days_since_backup >= 3;
+            return days_since_backup >= settings.backup_interval_days.max(1);
         }
     }
     // No backup date recorded, should backup
@@ -690,7 +1000,8 @@ pub fn should_backup(settings: &AppSettings) -> bool {
 }
 
 /// Create a backup of the database with timestamp filename
-pub fn create_backup() -> Result<PathBuf, Box<dyn std::error::Error>> {
+/// Older backups beyond `keep_count` are removed afterwards (0 = keep all)
+pub fn create_backup(keep_count: usize) -> Result<PathBuf, Box<dyn std::error::Error>> {
     let source_path = get_database_path();
     
     if source_path.exists() {
@@ -705,8 +1016,8 @@ pub fn create_backup() -> Result<PathBuf, Box<dyn std::error::Error>> {
         conn.execute(&format!("VACUUM INTO '{}'", backup_path_str), [])?;
         eprintln!("[Backup] Database backed up to {:?}", backup_path);
         
-        // Cleanup old backups (keep only 3)
-        let _ = cleanup_old_backups(3);
+        // Cleanup old backups
+        let _ = cleanup_old_backups(keep_count);
         
         return Ok(backup_path);
     }
@@ -760,7 +1071,7 @@ pub fn format_backup_timestamp(timestamp: &str) -> String {
 /// Perform backup if needed and update settings
 pub fn perform_backup_if_needed(settings: &mut AppSettings) -> Result<bool, Box<dyn std::error::Error>> {
     if should_backup(settings) {
-        create_backup()?;
+        create_backup(settings.backups_to_keep)?;
         settings.last_backup_date = Some(Utc::now().to_rfc3339());
         save_settings(settings)?;
         Ok(true)
@@ -833,6 +1144,31 @@ pub fn get_profile_cache_dir(profile_name: &str) -> PathBuf {
     path
 }
 
+/// Get the mpv config directory path for the current profile (does NOT auto-create)
+pub fn get_mpv_config_dir_path() -> PathBuf {
+    let profile = get_current_profile();
+    let mut path = if profile == "default" {
+        get_database_dir_path()
+    } else {
+        get_profile_dir_path(&profile)
+    };
+    path.push("mpv");
+    path
+}
+
+/// Get the mpv config directory for the current profile (auto-creates if needed).
+/// Users can place an mpv.conf here to customize playback.
+pub fn get_mpv_config_dir() -> PathBuf {
+    let path = get_mpv_config_dir_path();
+    
+    // Create directory if it doesn't exist
+    if !path.exists() {
+        let _ = fs::create_dir_all(&path);
+    }
+    
+    path
+}
+
 /// List all available profiles
 pub fn list_profiles() -> Result<Vec<(String, u64)>, Box<dyn std::error::Error>> {
     let profiles_dir = get_profiles_dir();
@@ -1041,6 +1377,31 @@ pub fn save_profile_database(profile_name: &str, database: &VideoDatabase) -> Re
     Ok(())
 }
 
+/// Insert videos into another profile's database (at most `limit`).
+/// Videos whose path already exists in that profile are skipped.
+/// Returns the IDs of the inserted videos.
+pub fn copy_videos_to_profile(profile_name: &str, videos: &[&VideoFile], limit: usize) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let conn = open_profile_connection(profile_name)?;
+    init_database(&conn)?;
+    
+    conn.execute("BEGIN TRANSACTION", [])?;
+    let mut inserted = Vec::new();
+    for video in videos {
+        if inserted.len() >= limit {
+            break;
+        }
+        if has_video_path(&conn, &video.path)? {
+            eprintln!("[Profile] Skipped {:?}: already in profile '{}'", video.path, profile_name);
+            continue;
+        }
+        insert_video(&conn, video)?;
+        inserted.push(video.id.clone());
+    }
+    conn.execute("COMMIT", [])?;
+    
+    Ok(inserted)
+}
+
 /// Get thumbnail directory for a specific profile
 pub fn get_profile_thumbnail_dir(profile_name: &str) -> PathBuf {
     let cache_dir = if profile_name == "default" {
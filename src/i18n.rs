@@ -1,25 +1,57 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Language {
     English,
     Japanese,
     Chinese,
+    Custom(String), // Community language loaded from lang/<code>.json
 }
 
 impl Language {
+    /// Built-in languages (community languages are listed by I18n::available_languages)
     pub fn all() -> Vec<Language> {
-        vec![Language::English, Language::Japanese]
+        vec![Language::English, Language::Japanese, Language::Chinese]
     }
     
-    pub fn name(&self) -> &'static str {
+    pub fn name(&self) -> &str {
         match self {
             Language::English => "English",
             Language::Japanese => "日本語",
             Language::Chinese => "中文",
+            Language::Custom(code) => code,
         }
     }
+    
+    /// Map a language code to a language ("en", "ja" and "zh" are built in)
+    fn from_code(code: &str) -> Language {
+        match code {
+            "en" => Language::English,
+            "ja" => Language::Japanese,
+            "zh" => Language::Chinese,
+            _ => Language::Custom(code.to_string()),
+        }
+    }
+}
+
+/// Translation file in the lang directory next to the executable
+#[derive(Debug, Deserialize)]
+struct LanguageFile {
+    #[serde(default)]
+    code: Option<String>, // Language code (defaults to the file name)
+    name: String, // Display name in the language list
+    translations: HashMap<String, String>, // Translation key -> text
+}
+
+/// Directory containing community translation files
+fn get_lang_dir() -> PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(|p| p.to_path_buf()))
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("lang")
 }
 
 impl Default for Language {
@@ -32,6 +64,7 @@ impl Default for Language {
 pub struct I18n {
     current_language: Language,
     translations: HashMap<String, HashMap<Language, String>>,
+    custom_languages: Vec<(Language, String)>, // Community languages and their display names
 }
 
 impl I18n {
@@ -39,8 +72,17 @@ impl I18n {
         let mut i18n = Self {
             current_language: language,
             translations: HashMap::new(),
+            custom_languages: Vec::new(),
         };
         i18n.load_translations();
+        i18n.load_language_files(&get_lang_dir());
+        
+        // The translation file of a saved community language may have been removed
+        if let Language::Custom(_) = i18n.current_language {
+            if !i18n.custom_languages.iter().any(|(lang, _)| *lang == i18n.current_language) {
+                i18n.current_language = Language::English;
+            }
+        }
         i18n
     }
     
@@ -49,7 +91,66 @@ impl I18n {
     }
     
     pub fn get_language(&self) -> Language {
-        self.current_language
+        self.current_language.clone()
+    }
+    
+    /// Built-in languages followed by community languages from the lang directory
+    pub fn available_languages(&self) -> Vec<Language> {
+        let mut languages = Language::all();
+        languages.extend(self.custom_languages.iter().map(|(lang, _)| lang.clone()));
+        languages
+    }
+    
+    /// Display name of a language (community languages use the name from their file)
+    pub fn language_name(&self, language: &Language) -> String {
+        self.custom_languages.iter()
+            .find(|(lang, _)| lang == language)
+            .map(|(_, name)| name.clone())
+            .unwrap_or_else(|| language.name().to_string())
+    }
+    
+    /// Load lang/*.json files as additions or overrides of the built-in translations
+    fn load_language_files(&mut self, lang_dir: &Path) {
+        let Ok(entries) = std::fs::read_dir(lang_dir) else {
+            return;
+        };
+        
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+            .collect();
+        paths.sort();
+        
+        for path in paths {
+            let file: LanguageFile = match std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+            {
+                Ok(file) => file,
+                Err(e) => {
+                    eprintln!("[i18n] Failed to load {:?}: {}", path, e);
+                    continue;
+                }
+            };
+            
+            let code = file.code
+                .or_else(|| path.file_stem().and_then(|s| s.to_str()).map(str::to_string))
+                .unwrap_or_default();
+            if code.is_empty() {
+                continue;
+            }
+            
+            let language = Language::from_code(&code);
+            if let Language::Custom(_) = language {
+                self.custom_languages.retain(|(lang, _)| *lang != language);
+                self.custom_languages.push((language.clone(), file.name));
+            }
+            
+            eprintln!("[i18n] Loaded {} translations for '{}' from {:?}", file.translations.len(), code, path);
+            for (key, text) in file.translations {
+                self.translations.entry(key).or_default().insert(language.clone(), text);
+            }
+        }
     }
     
     pub fn t(&self, key: &str) -> String {
@@ -114,6 +215,22 @@ impl I18n {
         self.add("clear_selection_count", "Clear Selection ({})", "選択解除 ({})", "清除选择 ({})");
         self.add("add_tag_to_selected", "Add Tag to Selected", "選択した動画にタグ追加", "为所选项添加标签");
         self.add("play_video", "Play Video", "動画を再生", "播放视频");
+        self.add("play_from_beginning", "Play from Beginning", "最初から再生", "从头播放");
+        
+        // Protected videos
+        self.add("protected_videos", "Protected Videos", "保護された動画", "受保护的视频");
+        self.add("protect_video", "🔒 Protect", "🔒 保護する", "🔒 保护");
+        self.add("unprotect_video", "🔓 Unprotect", "🔓 保護を解除", "🔓 取消保护");
+        self.add("protection_pin_required", "Set a protection PIN in Options > Data first", "先にオプション > データで保護PINを設定してください", "请先在选项 > 数据中设置保护PIN");
+        self.add("unlock_protected", "🔒 Unlock", "🔒 ロック解除", "🔒 解锁");
+        self.add("lock_protected", "🔓 Lock", "🔓 ロック", "🔓 锁定");
+        self.add("enter_protection_pin", "Enter PIN to show protected videos:", "保護された動画を表示するにはPINを入力してください:", "输入PIN以显示受保护的视频:");
+        self.add("unlock", "Unlock", "解除", "解锁");
+        self.add("wrong_pin", "Incorrect PIN", "PINが正しくありません", "PIN不正确");
+        self.add("protection_pin", "PIN:", "PIN:", "PIN:");
+        self.add("set_pin", "Set PIN", "PINを設定", "设置PIN");
+        self.add("pin_saved", "PIN saved", "PINを保存しました", "PIN已保存");
+        self.add("unlock_to_change_pin", "Unlock protected videos to change the PIN.", "PINを変更するには保護された動画のロックを解除してください。", "请先解锁受保护的视频以更改PIN。");
         self.add("delete", "Delete", "削除", "删除");
         self.add("delete_selected", "Delete Selected", "選択項目を削除", "删除所选项");
         self.add("refresh_selected", "Refresh Selected", "選択項目を再更新", "刷新所选项");
@@ -137,7 +254,6 @@ impl I18n {
         self.add("size_gb", "Size: {:.2} GB", "サイズ: {:.2} GB", "大小: {:.2} GB");
         self.add("size_mb", "Size: {:.1} MB", "サイズ: {:.1} MB", "大小: {:.1} MB");
         self.add("folder", "Folder", "フォルダ", "文件夹");
-        self.add("folder_label", "Folder: {}", "フォルダ: {}", "文件夹: {}");
         self.add("tags_label", "Tags: {}", "タグ: {}", "标签: {}");
         self.add("added_date", "Created Date", "作成日時", "创建日期");
         self.add("added_label", "Created: {}", "作成: {}", "创建: {}");
@@ -145,6 +261,11 @@ impl I18n {
         self.add("less_details", "▲ Less", "▲ 閣じる", "▲ 收起");
         self.add("last_played", "Last Played", "最終再生", "上次播放");
         self.add("last_played_label", "Last Played: {}", "最終再生: {}", "上次播放: {}");
+        self.add("play_count_label", "Play Count: {}", "再生回数: {}", "播放次数: {}");
+        self.add("recent_filter", "🕘 Recent ({}d)", "🕘 最近 ({}日)", "🕘 最近 ({}天)");
+        self.add("show_recent_row", "Show \"Continue watching\" row", "「続きを見る」行を表示", "显示\"继续观看\"行");
+        self.add("continue_watching", "Continue watching", "続きを見る", "继续观看");
+        self.add("recent_days", "Days shown in \"Recent\":", "「最近」に表示する日数:", "\"最近\"显示的天数:");
         self.add("favorite", "Favorite", "お気に入り", "收藏");
         self.add("add_to_favorites", "☆ Add to Favorites", "☆ お気に入りに追加", "☆ 添加到收藏");
         self.add("remove_from_favorites", "★ Remove from Favorites", "★ お気に入りから削除", "★ 从收藏中移除");
@@ -163,6 +284,54 @@ impl I18n {
         self.add("scene_thumbnails", "Scenes", "シーン", "场景");
         self.add("generate_scenes", "Generate Scene Thumbnails", "シーンサムネイルを生成", "生成场景缩略图");
         self.add("refresh_scenes", "↻ Refresh", "↻ 再取得", "↻ 刷新");
+        self.add("view_scenes", "🔍 View", "🔍 一覧", "🔍 查看");
+        self.add("scene_order", "Order:", "並び順:", "排序:");
+        self.add("scene_order_time", "Time", "時間", "时间");
+        self.add("scene_order_score", "Importance", "重要度", "重要度");
+        self.add("add_scene_at", "Add scene at:", "シーンを追加:", "在此添加场景:");
+        self.add("add", "Add", "追加", "添加");
+        self.add("invalid_timestamp", "Invalid timestamp (use HH:MM:SS or seconds)", "無効な時刻です（HH:MM:SS または秒で入力）", "无效的时间（请使用 HH:MM:SS 或秒）");
+        self.add("timestamp_exceeds_duration", "Timestamp is beyond the end of the video", "時刻が動画の長さを超えています", "时间超出视频长度");
+        self.add("capture_current_frame", "📸 Capture current frame as scene", "📸 現在のフレームをシーンとして追加", "📸 将当前帧添加为场景");
+        self.add("mpv_not_running", "mpv is not running. Start playback first.", "mpvが起動していません。先に再生を開始してください。", "mpv 未运行。请先开始播放。");
+        self.add("mpv_playing_other_video", "mpv is playing a different video", "mpvは別の動画を再生中です", "mpv 正在播放其他视频");
+        self.add("chapters", "Chapters", "チャプター", "章节");
+        self.add("play_from_chapter", "Play from this chapter", "このチャプターから再生", "从此章节播放");
+        self.add("scene_detection", "Scene Detection", "シーン検出", "场景检测");
+        self.add("scene_threshold", "Sensitivity threshold", "検出しきい値", "检测阈值");
+        self.add("scene_threshold_hint", "Lower values detect more scenes", "値を下げるとより多くのシーンを検出します", "数值越低检测到的场景越多");
+        self.add("max_scenes", "Max scenes per video", "動画あたりの最大シーン数", "每个视频的最大场景数");
+        self.add("statistics", "📊 Statistics", "📊 統計", "📊 统计");
+        self.add("additions_per_month", "Videos added per month", "月ごとの追加数", "每月添加的视频");
+        self.add("histogram_hint", "Click a bar to show only videos added that month", "バーをクリックするとその月に追加された動画のみ表示します", "点击柱形仅显示该月添加的视频");
+        self.add("clear_month_filter", "Clear month filter", "月フィルタを解除", "清除月份筛选");
+        self.add("generate_all_scenes", "Generate scenes for all videos", "すべての動画のシーンを生成", "为所有视频生成场景");
+        self.add("scene_batch_progress", "Scenes: {} / {} done", "シーン: {} / {} 完了", "场景: {} / {} 完成");
+        self.add("thumbnail_batch_progress", "Thumbnails: {} / {} done", "サムネイル: {} / {} 完了", "缩略图: {} / {} 完成");
+        self.add("batch_already_running", "Another batch is still running", "別の一括処理を実行中です", "另一个批处理仍在运行");
+        self.add("export_contact_sheet", "🖼 Export contact sheet", "🖼 コンタクトシートを書き出し", "🖼 导出缩略图总览");
+        self.add("contact_sheet_no_scenes", "This video has no scenes to export", "この動画には書き出すシーンがありません", "此视频没有可导出的场景");
+        self.add("contact_sheet_saved", "Contact sheet saved", "コンタクトシートを保存しました", "缩略图总览已保存");
+        self.add("contact_sheet_failed", "Failed to export contact sheet", "コンタクトシートの書き出しに失敗しました", "导出缩略图总览失败");
+        self.add("selection_hidden_count", "⚠ {} hidden by filter", "⚠ {}件がフィルタで非表示", "⚠ {} 个被筛选隐藏");
+        self.add("selection_hidden_hint", "Some selected videos are not shown with the current filters. Bulk actions still apply to them.", "選択中の一部の動画は現在のフィルタで表示されていません。一括操作はそれらにも適用されます。", "部分已选视频在当前筛选下未显示。批量操作仍会作用于它们。");
+        self.add("prune_hidden_selection", "Deselect videos hidden by filters", "フィルタで非表示になった動画の選択を解除", "取消选择被筛选隐藏的视频");
+        self.add("max_texture_cache", "Thumbnails kept in memory:", "メモリに保持するサムネイル数:", "内存中保留的缩略图数:");
+        self.add("show_quality_badge", "Show resolution badge on thumbnails", "サムネイルに解像度バッジを表示", "在缩略图上显示分辨率标记");
+        self.add("reprobe_duration", "⏱ Re-probe duration (accurate)", "⏱ 再生時間を再計測（高精度）", "⏱ 重新测量时长（精确）");
+        self.add("recompute_durations", "Recompute all durations", "すべての再生時間を再計算", "重新计算所有时长");
+        self.add("recompute_durations_hint", "Reads every video fully to fix wrong durations (slow)", "すべての動画を読み込んで誤った再生時間を修正します（時間がかかります）", "完整读取所有视频以修正错误的时长（较慢）");
+        self.add("duration_probe_progress", "Durations: {} / {} done", "再生時間: {} / {} 完了", "时长: {} / {} 完成");
+        self.add("durations_recomputed", "Duration re-probe finished", "再生時間の再計測が完了しました", "时长重新测量完成");
+        self.add("remove_similar_scenes", "Remove similar scenes", "類似シーンを削除", "删除相似场景");
+        self.add("similar_scenes_removed", "Removed {} similar scenes", "類似シーンを{}件削除しました", "已删除 {} 个相似场景");
+        self.add("similar_scene_threshold", "Similar scene threshold", "類似シーンのしきい値", "相似场景阈值");
+        self.add("thumbnail_cache", "Thumbnail Cache", "サムネイルキャッシュ", "缩略图缓存");
+        self.add("thumbnail_eviction_enabled", "Delete thumbnails not viewed recently", "最近表示していないサムネイルを削除", "删除最近未查看的缩略图");
+        self.add("thumbnail_eviction_days", "Not viewed for (days):", "未表示の日数:", "未查看天数:");
+        self.add("thumbnail_cache_budget", "Cache size limit:", "キャッシュ容量の上限:", "缓存大小上限:");
+        self.add("thumbnail_eviction_hint", "Runs at startup and after imports when the cache exceeds the limit. Main thumbnails of library videos are kept; other deleted thumbnails are regenerated when shown again.", "起動時とインポート後、キャッシュが上限を超えている場合に実行されます。ライブラリ内の動画のメインサムネイルは残り、削除されたその他のサムネイルは再表示時に再生成されます。", "启动时和导入后若缓存超出上限则执行。库中视频的主缩略图会保留，其他已删除的缩略图会在再次显示时重新生成。");
+        self.add("lightbox_hint", "←→↑↓: Select   Enter: Play   Esc: Close", "←→↑↓: 選択   Enter: 再生   Esc: 閉じる", "←→↑↓: 选择   Enter: 播放   Esc: 关闭");
         self.add("no_scenes_yet", "No scenes detected yet.", "まだシーンが検出されていません。", "尚未检测到场景。");
         self.add("play_from_scene", "Play from Scene", "シーンから再生", "从场景播放");
         self.add("delete_scene", "Delete Scene", "シーンを削除", "删除场景");
@@ -181,6 +350,8 @@ impl I18n {
         self.add("backup", "Backup", "バックアップ", "备份");
         self.add("last_backup", "Last backup", "前回のバックアップ", "上次备份");
         self.add("create_backup", "Create Backup", "バックアップを作成", "创建备份");
+        self.add("backups_to_keep", "Backups to keep", "保持するバックアップ数", "保留的备份数");
+        self.add("unlimited", "(unlimited)", "(無制限)", "(无限制)");
         self.add("restore_from_backup", "Restore from Backup", "バックアップから復元", "从备份恢复");
         self.add("backup_created", "Backup created successfully", "バックアップを作成しました", "备份创建成功");
         self.add("backup_failed", "Failed to create backup", "バックアップの作成に失敗しました", "备份创建失败");
@@ -205,7 +376,7 @@ impl I18n {
         self.add("profile_deleted", "Profile deleted successfully", "プロファイルを削除しました", "配置文件删除成功");
         self.add("profile_create_failed", "Failed to create profile", "プロファイルの作成に失敗しました", "创建配置文件失败");
         self.add("profile_delete_failed", "Failed to delete profile", "プロファイルの削除に失敗しました", "删除配置文件失败");
-        self.add("profile_switch_notice", "The application will restart to switch profiles.", "OKを押すとアプリが再起動してプロファイルが切り替わります。", "按确定后应用程序将重新启动以切换配置文件。");
+        self.add("profile_switch_notice", "Press OK to switch profiles.", "OKを押すとプロファイルが切り替わります。", "按确定切换配置文件。");
         self.add("confirm_delete_profile", "Are you sure you want to delete this profile?", "このプロファイルを削除してもよろしいですか？", "确定要删除此配置文件吗？");
         self.add("profile_has_videos", "This profile contains {} videos.", "このプロファイルには{}本の動画があります。", "此配置文件包含{}个视频。");
         self.add("cannot_delete_default", "Cannot delete the default profile.", "デフォルトプロファイルは削除できません。", "无法删除默认配置文件。");
@@ -317,6 +488,222 @@ impl I18n {
         self.add("free_tier_scene_limit", "(Free: up to 5 scenes)", "(無料版: 最大5シーンまで)", "(免费版：最多5个场景)");
         self.add("free_tier_scene_limit_reached", "Free tier: 5 scenes limit", "無料版: 5シーンまで", "免费版：5个场景限制");
         self.add("premium_unlimited_scenes", "Upgrade to Premium for unlimited scene thumbnails!", "プレミアム版で無制限のシーンサムネイル!", "升级到高级版获取无限场景缩略图！");
+        self.add("edit_title", "Edit title", "タイトルを編集", "编辑标题");
+        self.add("auto_collapse_filters", "Collapse filters into a button on narrow windows", "ウィンドウが狭いときはフィルタをボタンにまとめる", "窗口较窄时将筛选器收起为按钮");
+        self.add("notes", "Notes:", "メモ:", "备注:");
+        self.add("notes_hint", "Add notes about this video…", "この動画についてのメモ…", "添加关于此视频的备注…");
+        self.add("review_mode", "⭐ Review", "⭐ レビュー", "⭐ 审阅");
+        self.add("review_mode_tooltip", "Rate the filtered videos one at a time with the keyboard", "絞り込んだ動画をキーボードで1本ずつ評価", "使用键盘逐个为筛选后的视频评分");
+        self.add("review_mode_hint", "0-5: rate  ←/→: previous/next  Enter: play  Esc: exit", "0-5: 評価  ←/→: 前/次  Enter: 再生  Esc: 終了", "0-5: 评分  ←/→: 上一个/下一个  Enter: 播放  Esc: 退出");
+        self.add("review_unrated", "({} unrated)", "(未評価 {} 件)", "(未评分 {} 个)");
+        self.add("rename_tag", "Rename tag", "タグ名を変更", "重命名标签");
+        self.add("merge_tag_into", "Merge into…", "統合先…", "合并到…");
+        self.add("merge_instead", "Merge", "統合", "合并");
+        self.add("tag_name_exists", "A tag with this name already exists. Merge into it instead?", "同じ名前のタグが既にあります。統合しますか？", "已存在同名标签。是否改为合并？");
+        self.add("rename_folder", "Rename folder", "フォルダ名を変更", "重命名文件夹");
+        self.add("folder_name_empty", "Folder name cannot be empty", "フォルダ名を入力してください", "文件夹名称不能为空");
+        self.add("folder_name_exists", "A folder named \"{}\" already exists", "「{}」という名前のフォルダは既に存在します", "名为“{}”的文件夹已存在");
+        self.add("review_play_hint", "Click to play", "クリックで再生", "点击播放");
+        self.add("undo_videos_restored", "Undo: restored {} video(s)", "元に戻す: {} 件の動画を復元しました", "撤销：已恢复 {} 个视频");
+        self.add("undo_scenes_restored", "Undo: restored {} scene(s)", "元に戻す: {} 件のシーンを復元しました", "撤销：已恢复 {} 个场景");
+        self.add("rate_selected", "⭐ Set rating", "⭐ 評価を設定", "⭐ 设置评分");
+        self.add("clear_rating", "Clear rating", "評価をクリア", "清除评分");
+        self.add("profile_switched", "Switched to profile: {}", "プロファイルを切り替えました: {}", "已切换到配置文件: {}");
+        self.add("profile_switch_failed", "Failed to switch profile", "プロファイルの切り替えに失敗しました", "切换配置文件失败");
+        self.add("manage_profiles", "Manage profiles…", "プロファイルを管理…", "管理配置文件…");
+        self.add("backup_interval_days", "Automatic backup every (days):", "自動バックアップの間隔 (日):", "自动备份间隔 (天):");
+        self.add("move_to_profile", "Move to profile", "プロファイルへ移動", "移动到配置文件");
+        self.add("moved_to_profile", "Moved {} video(s) to profile \"{}\"", "{} 件の動画をプロファイル「{}」へ移動しました", "已将 {} 个视频移动到配置文件“{}”");
+        self.add("move_to_profile_skipped", " ({} skipped: already in the target profile or over the limit)", " ({} 件スキップ: 移動先に既に存在するか上限を超えています)", " (跳过 {} 个: 目标配置文件中已存在或超出上限)");
+        self.add("move_to_profile_failed", "Failed to move videos", "動画の移動に失敗しました", "移动视频失败");
+        self.add("clean_cache", "Clean cache", "キャッシュを整理", "清理缓存");
+        self.add("clean_cache_hint", "Delete cached thumbnails of videos that are no longer in any profile", "どのプロファイルにも存在しない動画のサムネイルを削除します", "删除已不在任何配置文件中的视频的缩略图");
+        self.add("clear_all_cache", "Clear all cache", "キャッシュをすべて削除", "清除全部缓存");
+        self.add("confirm_clear_all_cache", "Delete all cached thumbnails and scene images? They are regenerated when displayed.", "キャッシュされたサムネイルとシーン画像をすべて削除しますか？表示時に再生成されます。", "删除所有缓存的缩略图和场景图片？显示时会重新生成。");
+        self.add("cache_cleaned", "Removed {} files ({} MB freed)", "{} 個のファイルを削除しました ({} MB 解放)", "已删除 {} 个文件 (释放 {} MB)");
+        self.add("clean_cache_failed", "Cache cleanup aborted", "キャッシュの整理を中止しました", "缓存清理已中止");
+        self.add("find_duplicates", "🔍 Duplicates", "🔍 重複検出", "🔍 重复检测");
+        self.add("duplicate_sensitivity", "Max difference:", "許容する差:", "最大差异:");
+        self.add("scan_duplicates", "Scan", "スキャン", "扫描");
+        self.add("duplicate_hint", "Compares thumbnails, so the same video under a different name or folder is found. Higher values find more, but may include different videos.", "サムネイルを比較するため、名前やフォルダが異なる同じ動画も検出できます。値を大きくすると検出数が増えますが、別の動画が含まれることがあります。", "通过比较缩略图，可以找到名称或文件夹不同的相同视频。数值越大找到的越多，但可能包含不同的视频。");
+        self.add("no_duplicates_found", "No duplicates found", "重複は見つかりませんでした", "未找到重复项");
+        self.add("duplicate_groups_found", "{} groups of possible duplicates", "重複の可能性があるグループ: {} 件", "{} 组可能的重复项");
+        self.add("keep_this", "Keep this", "これを残す", "保留此项");
+        self.add("keep_this_tooltip", "Delete the other videos in this group from the library", "このグループの他の動画をライブラリから削除します", "从库中删除此组中的其他视频");
+        self.add("video_extensions", "Video File Types", "動画ファイルの種類", "视频文件类型");
+        self.add("add_extension", "Add", "追加", "添加");
+        self.add("reset_extensions", "Reset to defaults", "既定に戻す", "恢复默认");
+        self.add("video_extensions_hint", "Extensions picked up when adding files and scanning folders. Rescan to pick up newly added types.", "ファイル追加やフォルダのスキャン時に取り込む拡張子です。追加した種類を取り込むには再スキャンしてください。", "添加文件和扫描文件夹时识别的扩展名。添加新类型后请重新扫描。");
+        self.add("exclude_patterns", "Exclude patterns", "除外パターン", "排除模式");
+        self.add("exclude_patterns_hint", "Files whose full path matches are skipped when scanning folders (case-insensitive). Use * and ? for globs over the whole path (e.g. *sample*); other text matches anywhere in the path.", "フルパスが一致するファイルはフォルダのスキャン時にスキップされます (大文字小文字を区別しません)。* と ? でパス全体に対するグロブ (例: *sample*)、それ以外の文字列はパスのどこかに含まれていれば一致します。", "完整路径匹配的文件在扫描文件夹时会被跳过 (不区分大小写)。使用 * 和 ? 对整个路径进行通配 (例如 *sample*)，其他文本只要出现在路径中即匹配。");
+        self.add("watched_folders", "Watched Folders", "監視フォルダ", "监视文件夹");
+        self.add("no_watched_folders", "No folders are being watched", "監視中のフォルダはありません", "没有正在监视的文件夹");
+        self.add("add_watched_folder", "📂 Add folder to watch...", "📂 監視するフォルダを追加...", "📂 添加要监视的文件夹...");
+        self.add("unwatch_folder_tooltip", "Stop watching this folder (imported videos are kept)", "このフォルダの監視を停止します (取り込み済みの動画は残ります)", "停止监视此文件夹 (已导入的视频会保留)");
+        self.add("folder_not_found", "Folder not found", "フォルダが見つかりません", "找不到文件夹");
+        self.add("watched_folders_hint", "New and changed videos in watched folders are imported automatically.", "監視フォルダ内の新しい動画や変更された動画は自動的に取り込まれます。", "监视文件夹中的新视频和已更改的视频会自动导入。");
+        self.add("import_progress", "Importing: {} found, {} / {} processed", "取り込み中: {} 件検出, {} / {} 件処理済み", "导入中: 找到 {} 个, 已处理 {} / {}");
+        self.add("import_finished", "Import finished: {} videos added", "取り込み完了: {} 件の動画を追加しました", "导入完成: 已添加 {} 个视频");
+        self.add("import_cancelled", "Import cancelled: {} videos added", "取り込みを中止しました: {} 件の動画を追加しました", "导入已取消: 已添加 {} 个视频");
+        self.add("import_already_running", "An import is already running", "取り込みはすでに実行中です", "已有导入正在进行");
+        self.add("export_csv", "📄 Export CSV...", "📄 CSVをエクスポート...", "📄 导出 CSV...");
+        self.add("export_all_videos", "Export all videos", "すべての動画をエクスポート", "导出所有视频");
+        self.add("export_all_videos_hint", "When unchecked, only the selected videos (or the currently filtered list if fewer than two are selected) are exported", "オフの場合、選択中の動画 (選択が1件以下の場合は現在の絞り込み結果) のみをエクスポートします", "未勾选时，仅导出选中的视频 (选中少于两个时导出当前筛选结果)");
+        self.add("export_csv_saved", "Exported {} videos to {}", "{} 件の動画を {} にエクスポートしました", "已将 {} 个视频导出到 {}");
+        self.add("import_metadata", "📥 Import metadata...", "📥 メタデータをインポート...", "📥 导入元数据...");
+        self.add("import_metadata_hint", "Reads a JSON array of {path, title, tags, rating, folder, notes} objects. Tags are merged; title, rating, folder and notes are overwritten when present.", "{path, title, tags, rating, folder, notes} オブジェクトの JSON 配列を読み込みます。タグは統合され、タイトル・評価・フォルダ・メモは指定がある場合に上書きされます。", "读取 {path, title, tags, rating, folder, notes} 对象的 JSON 数组。标签会合并；标题、评分、文件夹和备注在提供时会被覆盖。");
+        self.add("import_metadata_done", "Metadata imported: {} matched, {} skipped (not in library)", "メタデータをインポートしました: 一致 {} 件, スキップ {} 件 (ライブラリにありません)", "元数据已导入: 匹配 {} 个, 跳过 {} 个 (不在库中)");
+        self.add("import_metadata_failed", "Failed to import metadata", "メタデータのインポートに失敗しました", "导入元数据失败");
+        self.add("export_playlist", "🎵 Export as playlist (.m3u)...", "🎵 プレイリストとしてエクスポート (.m3u)...", "🎵 导出为播放列表 (.m3u)...");
+        self.add("export_playlist_saved", "Exported a playlist of {} videos to {}", "{} 件の動画のプレイリストを {} にエクスポートしました", "已将包含 {} 个视频的播放列表导出到 {}");
+        self.add("play_all", "▶ Play all", "▶ すべて再生", "▶ 全部播放");
+        self.add("play_all_tooltip", "Play the selected videos, or all videos in the current list, as a playlist in the displayed order", "選択中の動画、または現在のリストのすべての動画を表示順にプレイリストとして再生します", "按显示顺序将选中的视频或当前列表中的所有视频作为播放列表播放");
+        self.add("shuffle_play", "🔀 Shuffle play", "🔀 シャッフル再生", "🔀 随机播放");
+        self.add("shuffle_play_tooltip", "Play the selected videos, or all videos in the current list, in random order", "選択中の動画、または現在のリストのすべての動画をランダムな順序で再生します", "以随机顺序播放选中的视频或当前列表中的所有视频");
+        self.add("thumbnail_max_width", "Thumbnail width:", "サムネイルの幅:", "缩略图宽度:");
+        self.add("thumbnail_quality", "Thumbnail quality:", "サムネイルの画質:", "缩略图质量:");
+        self.add("thumbnail_quality_hint", "Applies to newly generated thumbnails. Quality 2 is best, 31 gives the smallest files.", "新しく生成されるサムネイルに適用されます。画質は2が最高、31が最小サイズです。", "适用于新生成的缩略图。质量 2 为最佳，31 文件最小。");
+        self.add("regenerate_thumbnails", "Regenerate thumbnails", "サムネイルを再生成", "重新生成缩略图");
+        self.add("regenerate_thumbnails_hint", "Delete this library's video and scene thumbnails; they are recreated with the current settings as they are displayed", "このライブラリの動画・シーンのサムネイルを削除し、表示時に現在の設定で再作成します", "删除此库的视频和场景缩略图，显示时将按当前设置重新生成");
+        self.add("thumbnails_regenerating", "Removed {} thumbnails. They will be regenerated as they are displayed.", "{}個のサムネイルを削除しました。表示時に再生成されます。", "已删除 {} 个缩略图，显示时将重新生成。");
+        self.add("set_thumbnail_from_timestamp", "Set thumbnail from timestamp…", "時刻を指定してサムネイルを設定…", "从时间点设置缩略图…");
+        self.add("set_thumbnail", "Set thumbnail", "サムネイルに設定", "设为缩略图");
+        self.add("use_scene_as_thumbnail", "Use this scene as thumbnail", "このシーンをサムネイルにする", "将此场景设为缩略图");
+        self.add("export_failed", "Export failed", "エクスポートに失敗しました", "导出失败");
+        self.add("show_duration_badge", "Show duration on thumbnails", "サムネイルに再生時間を表示", "在缩略图上显示时长");
+        self.add("regenerate_thumbnail", "Regenerate thumbnail", "サムネイルを再生成", "重新生成缩略图");
+        self.add("group_by_folder", "🗂 Group by folder", "🗂 フォルダ別に表示", "🗂 按文件夹分组");
+        self.add("ungrouped", "Ungrouped", "フォルダなし", "未分组");
+        self.add("total_duration", "Total duration: {}", "合計再生時間: {}", "总时长: {}");
+        self.add("total_storage", "Total storage: {} GB", "合計サイズ: {} GB", "总大小: {} GB");
+        self.add("videos_by_rating", "By rating", "評価別", "按评分");
+        self.add("unrated", "Unrated", "未評価", "未评分");
+        self.add("top_tags", "Top tags", "よく使うタグ", "常用标签");
+        self.add("no_tags", "No tags", "タグなし", "无标签");
+        self.add("videos_by_resolution", "By resolution", "解像度別", "按分辨率");
+        self.add("unknown", "Unknown", "不明", "未知");
+        self.add("watched_filter", "Watched:", "視聴状況:", "观看状态:");
+        self.add("watched", "Watched", "視聴済み", "已观看");
+        self.add("unwatched", "Unwatched", "未視聴", "未观看");
+        self.add("watched_badge", "✔ Watched", "✔ 視聴済み", "✔ 已观看");
+        self.add("mark_watched", "✔ Mark as watched", "✔ 視聴済みにする", "✔ 标记为已观看");
+        self.add("mark_unwatched", "Mark as unwatched", "未視聴に戻す", "标记为未观看");
+        self.add("mark_watched_on_play", "Mark videos as watched when playback starts", "再生開始時に視聴済みにする", "开始播放时标记为已观看");
+        self.add("mark_watched_on_play_hint", "When off, a video is marked as watched once it is played past 90%", "オフの場合、90%以上再生した時点で視聴済みになります", "关闭时，播放超过 90% 后标记为已观看");
+        self.add("sort_manual", "Manual", "手動", "手动");
+        self.add("sort_manual_hint", "Custom order: drag a thumbnail onto another one in the grid to move it", "任意の順序: グリッドでサムネイルを別のサムネイルにドラッグして移動します", "自定义顺序：在网格中将缩略图拖到另一个缩略图上即可移动");
+        self.add("default_subtitle", "Subtitle language:", "字幕の言語:", "字幕语言:");
+        self.add("default_audio_lang", "Audio language:", "音声の言語:", "音频语言:");
+        self.add("track_preferences_hint", "Comma-separated language codes in order of preference; \"off\" disables subtitles. Videos without a matching track use mpv's default.", "優先順にカンマ区切りの言語コードを入力します。\"off\" で字幕を無効にします。該当するトラックがない動画はmpvの既定の動作になります。", "按优先顺序输入以逗号分隔的语言代码；\"off\" 关闭字幕。没有匹配音轨的视频使用 mpv 的默认设置。");
+        self.add("pin_favorites_to_top", "Pin favorites to the top", "お気に入りを先頭に固定", "将收藏置顶");
+        self.add("mpv_conf_precedence", "Place an mpv.conf in the folder below for full control over mpv. Options set in mpv.conf take precedence over the GPU high-quality and shader settings above.", "下のフォルダにmpv.confを置くとmpvを自由に設定できます。mpv.confで指定したオプションは、上記のGPU高画質設定やシェーダー設定より優先されます。", "在下面的文件夹中放置 mpv.conf 即可完全控制 mpv。mpv.conf 中设置的选项优先于上方的 GPU 高画质和着色器设置。");
+        self.add("open_mpv_config_dir", "Open", "開く", "打开");
+        self.add("player_error_title", "Video Player Not Available", "動画プレイヤーを起動できません", "无法启动视频播放器");
+        self.add("player_not_found", "No video player could be launched.", "動画プレイヤーを起動できませんでした。", "无法启动任何视频播放器。");
+        self.add("player_launch_failed", "Failed to launch the video player", "動画プレイヤーの起動に失敗しました", "启动视频播放器失败");
+        self.add("player_install_hint", "Install mpv, or place mpv.exe in the \"mpv\" folder next to CicadaGallery.", "mpvをインストールするか、CicadaGalleryと同じ場所にある「mpv」フォルダにmpv.exeを配置してください。", "请安装 mpv，或将 mpv.exe 放入 CicadaGallery 旁边的“mpv”文件夹中。");
+        self.add("ffmpeg_missing_banner", "FFmpeg not found — durations, resolutions, and scenes unavailable", "FFmpegが見つかりません — 再生時間、解像度、シーンは利用できません", "未找到 FFmpeg — 无法获取时长、分辨率和场景");
+        self.add("ffmpeg_setup_link", "How to install FFmpeg", "FFmpegのインストール方法", "如何安装 FFmpeg");
+        self.add("dismiss", "Dismiss", "閉じる", "关闭");
+        self.add("player_install_instructions", "mpv installation instructions", "mpvのインストール方法", "mpv 安装说明");
+        self.add("external_tools", "External Tools", "外部ツール", "外部工具");
+        self.add("tool_path_auto", "(automatic)", "(自動)", "(自动)");
+        self.add("browse", "Browse…", "参照…", "浏览…");
+        self.add("invalid_tool_binary", "Not a working binary (\"-version\" failed): {}", "実行できないファイルです（\"-version\" が失敗しました）: {}", "不是可用的程序（\"-version\" 执行失败）：{}");
+        self.add("take_screenshot", "Save a screenshot of the frame shown in mpv", "mpvで表示中のフレームのスクリーンショットを保存", "保存 mpv 当前画面的截图");
+        self.add("screenshot_saved", "Screenshot saved to {}", "スクリーンショットを保存しました: {}", "截图已保存到 {}");
+        self.add("screenshot_no_player", "Start playback first to take a screenshot", "スクリーンショットを撮るには先に再生を開始してください", "请先开始播放再截图");
+        self.add("screenshot_dir", "Screenshot folder:", "スクリーンショットの保存先:", "截图文件夹:");
+        self.add("thumbnail_cache_pruned", "Thumbnail cache pruned: {} MB freed", "サムネイルキャッシュを整理しました: {} MB 解放", "已清理缩略图缓存：释放 {} MB");
+        self.add("external_tools_hint", "Leave on automatic to use the bundled ffmpeg folder or the one on PATH.", "自動の場合は同梱のffmpegフォルダ、またはPATH上のものを使用します。", "设为自动时使用附带的 ffmpeg 文件夹或 PATH 中的程序。");
+        self.add("copy_path", "Copy Path", "パスをコピー", "复制路径");
+        self.add("copy_file_to", "Copy File to…", "ファイルをコピー…", "复制文件到…");
+        self.add("paths_copied", "Copied {} path(s) to the clipboard", "{}件のパスをクリップボードにコピーしました", "已将 {} 个路径复制到剪贴板");
+        self.add("file_copy_progress", "Copying files {}/{}", "ファイルをコピー中 {}/{}", "正在复制文件 {}/{}");
+        self.add("files_copied", "Copied {} file(s)", "{}件のファイルをコピーしました", "已复制 {} 个文件");
+        self.add("files_copy_failed", " (failed: {})", "（失敗: {}）", "（失败：{}）");
+        self.add("delete_file_to_trash", "🗑 Delete File to Recycle Bin…", "🗑 ファイルをごみ箱に移動…", "🗑 将文件移到回收站…");
+        self.add("delete_file_hint", "The options above only remove the library entry. This one also deletes the video file itself.", "上のボタンはライブラリから削除するだけです。こちらは動画ファイル自体も削除します。", "上方选项只会从库中移除。此选项还会删除视频文件本身。");
+        self.add("confirm_move_to_trash", "The video file(s) will be moved to the Recycle Bin and removed from the library. Continue?", "動画ファイルをごみ箱に移動し、ライブラリから削除します。よろしいですか？", "视频文件将被移到回收站并从库中移除。是否继续？");
+        self.add("move_to_trash", "Move to Recycle Bin", "ごみ箱に移動", "移到回收站");
+        self.add("move_to_trash_failed", " (could not move: {})", "（移動できませんでした: {}）", "（无法移动：{}）");
+        self.add("move_to_trash_missing", " (file not found, kept in library: {})", "（ファイルが見つからないためライブラリに残しました: {}）", "（未找到文件，已保留在库中：{}）");
+        self.add("moved_to_trash", "Moved {} file(s) to the Recycle Bin", "{}件のファイルをごみ箱に移動しました", "已将 {} 个文件移到回收站");
+        self.add("theme_system", "Follow System", "システムに合わせる", "跟随系统");
+        self.add("theme_light", "Light", "ライト", "浅色");
+        self.add("accent_color", "Accent color:", "アクセントカラー:", "强调色:");
+        self.add("theme_dark", "Dark", "ダーク", "深色");
+        self.add("clear_search", "Clear search (Esc)", "検索をクリア (Esc)", "清除搜索 (Esc)");
+        self.add("fixed_grid_columns", "Fixed columns:", "列数を固定:", "固定列数:");
+        self.add("select_all_in_folder", "Select all in this folder", "このフォルダの動画をすべて選択", "选择此文件夹中的全部视频");
+        self.add("select_all_with_tag", "Select all with this tag", "このタグの動画をすべて選択", "选择带有此标签的全部视频");
+        self.add("filter_presets", "Filter presets:", "フィルタプリセット:", "筛选预设:");
+        self.add("apply_preset", "Apply preset...", "プリセットを適用...", "应用预设...");
+        self.add("no_presets", "No saved presets", "保存済みプリセットなし", "没有已保存的预设");
+        self.add("preset_name", "Preset name", "プリセット名", "预设名称");
+        self.add("save_current_filter", "Save current filter", "現在のフィルタを保存", "保存当前筛选");
+        self.add("manage_presets", "Manage presets", "プリセットの管理", "管理预设");
+        self.add("rename", "Rename", "名前を変更", "重命名");
+        self.add("invert_selection", "Invert Selection", "選択を反転", "反向选择");
+        self.add("no_matching_videos", "No videos match the current filters", "現在のフィルタに一致する動画はありません", "没有符合当前筛选条件的视频");
+        self.add("no_matching_videos_hint", "Change the search or filters to see more videos.", "検索やフィルタを変更してください。", "请更改搜索或筛选条件。");
+        self.add("clear_filters", "Clear filters", "フィルタをクリア", "清除筛选");
+        self.add("rating", "Rating", "評価", "评分");
+        self.add("hdr_video", "HDR", "HDR", "HDR");
+        self.add("hdr_colorspace_hint", "Colorspace hint for HDR videos", "HDR動画で色空間ヒントを使用", "HDR视频使用色彩空间提示");
+        self.add("hdr_colorspace_hint_tooltip", "Launch mpv with --target-colorspace-hint=yes for HDR videos so the display switches to HDR and tone mapping behaves", "HDR動画はmpvを--target-colorspace-hint=yesで起動し、ディスプレイのHDR切り替えとトーンマッピングを適切に行います", "对HDR视频使用--target-colorspace-hint=yes启动mpv，使显示器切换到HDR并正确进行色调映射");
+        self.add("codec_label", "Codec: {codec} ({container})", "コーデック: {codec} ({container})", "编解码器: {codec} ({container})");
+        self.add("use_embedded_metadata", "Use embedded title and recording date on import", "取り込み時に埋め込みのタイトルと撮影日時を使用", "导入时使用内嵌的标题和录制日期");
+        self.add("use_embedded_metadata_hint", "Newly added videos take their title and date from the file's metadata tags when present, instead of the filename and file timestamps", "新しく追加した動画は、ファイル名やファイルの日時の代わりに、メタデータタグのタイトルと日時を使用します（存在する場合）", "新添加的视频在存在元数据标签时，使用其中的标题和日期代替文件名和文件时间");
+        self.add("rescan_debounce", "Rescan delay after file changes:", "ファイル変更後の再スキャン待機時間:", "文件变更后的重新扫描延迟:");
+        self.add("rescan_debounce_hint", "Watched folders are rescanned once no files have changed for this long, so copying many files triggers a single rescan", "この時間ファイル変更がなくなってから監視フォルダを再スキャンします（大量コピー時も再スキャンは1回）", "在此时间内没有文件变化后才重新扫描监视文件夹（大量复制时只扫描一次）");
+        self.add("watcher_degraded", "⚠ Folder watching limited", "⚠ フォルダ監視が制限中", "⚠ 文件夹监视受限");
+        self.add("watcher_failed", "The folder watcher stopped with an error and will be restarted automatically.", "フォルダ監視がエラーで停止しました。自動的に再開します。", "文件夹监视因错误停止，将自动重新启动。");
+        self.add("scene_thread_count", "Parallel scene thumbnail jobs", "シーンサムネイルの並列処理数", "场景缩略图并行任务数");
+        self.add("max_concurrent_texture_loads", "Thumbnails loaded in parallel:", "サムネイルの同時読み込み数:", "并行加载的缩略图数:");
+        self.add("import_issues", "Import issues ({})", "インポートの問題 ({})", "导入问题 ({})");
+        self.add("import_issues_hint", "These files were skipped or could not be fully read:", "以下のファイルはスキップされたか、完全に読み込めませんでした:", "以下文件被跳过或无法完整读取:");
+        self.add("issue_path_unreadable", "Cannot access file", "ファイルにアクセスできません", "无法访问文件");
+        self.add("issue_unreadable", "Could not read video (no thumbnail or duration)", "動画を読み込めません (サムネイル・再生時間なし)", "无法读取视频(无缩略图和时长)");
+        self.add("issue_no_thumbnail", "Thumbnail could not be generated", "サムネイルを生成できませんでした", "无法生成缩略图");
+        self.add("issue_no_metadata", "Duration/resolution could not be read", "再生時間・解像度を読み込めませんでした", "无法读取时长/分辨率");
+        self.add("copy_paths", "Copy list", "一覧をコピー", "复制列表");
+        self.add("watcher_folders_unavailable", "These folders are not available and are not being watched. Watching resumes when they come back:", "以下のフォルダは利用できないため監視されていません。再接続されると監視を再開します:", "以下文件夹不可用，未被监视。重新连接后将恢复监视:");
+        self.add("license_expires_banner", "Your license expires on {}", "ライセンスの有効期限は {} です", "您的许可证将于 {} 到期");
+        self.add("license_expired_title", "License expired", "ライセンスの期限切れ", "许可证已过期");
+        self.add("license_expired_notice", "Your premium license expired on {}. CicadaGallery is now running as the free version.", "プレミアムライセンスは {} に期限切れになりました。CicadaGallery は無料版として動作しています。", "您的高级许可证已于 {} 过期。CicadaGallery 现以免费版运行。");
+        self.add("license_expired_features", "Premium features (ratings, multi-select filters, GPU rendering, shaders, unlimited videos) are disabled until you enter a valid license key.", "有効なライセンスキーを入力するまで、プレミアム機能 (レーティング、複数選択フィルター、GPUレンダリング、シェーダー、動画数無制限) は無効になります。", "在输入有效的许可证密钥之前,高级功能(评分、多选筛选、GPU 渲染、着色器、无限视频)将被禁用。");
+        self.add("deactivate_license", "Deactivate License", "ライセンスを無効化", "停用许可证");
+        self.add("confirm_deactivate_license", "Remove the license from this computer? Premium features will be disabled until you enter the key again.", "このコンピューターからライセンスを削除しますか？キーを再入力するまでプレミアム機能は無効になります。", "要从此计算机中移除许可证吗？在重新输入密钥之前,高级功能将被禁用。");
+        self.add("license_deactivated", "License deactivated - running in free mode", "ライセンスを無効化しました - 無料版で動作中", "许可证已停用 - 正在以免费模式运行");
+        self.add("license_expiry_warning_days", "Warn days before expiry (0 = off):", "期限切れ前に警告する日数 (0 = オフ):", "到期前提醒天数 (0 = 关闭):");
+        self.add("player_levels_label", "Volume {volume} · Speed {speed}x", "音量 {volume} · 速度 {speed}x", "音量 {volume} · 速度 {speed}x");
+        self.add("reset_player_levels", "Use the global volume and normal speed again", "全体の音量と通常速度に戻す", "恢复为全局音量和正常速度");
+        self.add("loop_playback", "Loop playback", "ループ再生", "循环播放");
+        self.add("fullscreen_on_launch", "Start in fullscreen", "全画面で起動", "以全屏启动");
+        self.add("auto_tag_from_folder", "Tag imported videos with their folder name", "インポートした動画にフォルダ名のタグを付ける", "为导入的视频添加文件夹名称标签");
+        self.add("auto_tag_from_folder_hint", "Videos found when adding or rescanning folders get the name of the directory they are in as a tag", "フォルダの追加・再スキャンで見つかった動画に、置かれているディレクトリ名をタグとして付けます", "添加或重新扫描文件夹时找到的视频会以所在目录名称作为标签");
+        self.add("apply", "Apply", "適用", "应用");
+        self.add("tag_rules", "Auto-tagging rules", "自動タグ付けルール", "自动标签规则");
+        self.add("tag_rule_pattern", "Pattern (regex)", "パターン (正規表現)", "模式 (正则表达式)");
+        self.add("tag_rule_tag", "Tag", "タグ", "标签");
+        self.add("tag_rule_invalid", "Invalid pattern", "無効なパターン", "无效的模式");
+        self.add("add_tag_rule", "Add rule", "ルールを追加", "添加规则");
+        self.add("apply_tag_rules_to_all", "Apply rules to all", "すべてに適用", "应用到全部");
+        self.add("tag_rules_preview", "{videos} videos would get {tags} new tags", "{videos} 件の動画に {tags} 個のタグが追加されます", "{videos} 个视频将添加 {tags} 个新标签");
+        self.add("tag_rules_applied", "Tagged {} videos", "{} 件の動画にタグを付けました", "已为 {} 个视频添加标签");
+        self.add("tag_rules_hint", "Rules are matched against the title, then the file path, of imported videos. Use $1 or ${name} in the tag for capture groups.", "インポートした動画のタイトル、次にファイルパスに対して照合します。タグ内の $1 や ${name} はキャプチャグループに置き換えられます。", "依次匹配导入视频的标题和文件路径。标签中的 $1 或 ${name} 会替换为捕获组。");
+        self.add("move_to_folder", "Move to folder", "フォルダへ移動", "移动到文件夹");
+        self.add("moved_to_folder", "Moved {} videos to \"{}\"", "{} 件の動画を「{}」に移動しました", "已将 {} 个视频移动到“{}”");
+        self.add("folders_label", "Folders:", "フォルダ:", "文件夹:");
+        self.add("remove_from_folder", "Remove from folder", "フォルダから外す", "从文件夹中移除");
+        self.add("add_to_folder", "Add to folder", "フォルダに追加", "添加到文件夹");
+        self.add("no_other_folders", "No other folders", "他のフォルダはありません", "没有其他文件夹");
+        self.add("folder_selected", "📁 Set folder", "📁 フォルダを設定", "📁 设置文件夹");
+        self.add("existing_folders", "Existing Folders", "既存のフォルダ", "现有文件夹");
+        self.add("create_new_folder", "Create new folder:", "新しいフォルダを作成:", "创建新文件夹:");
+        self.add("added_to_folder", "Added {} videos to \"{}\"", "{} 件の動画を「{}」に追加しました", "已将 {} 个视频添加到“{}”");
+        self.add("removed_from_folder", "Removed {} videos from \"{}\"", "{} 件の動画を「{}」から外しました", "已将 {} 个视频从“{}”中移除");
     }
     
     fn add(&mut self, key: &str, en: &str, ja: &str, zh: &str) {
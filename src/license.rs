@@ -38,6 +38,12 @@ impl License {
             false
         }
     }
+    
+    /// Whole days left until `expires_at` (negative once expired, None for perpetual licenses)
+    pub fn days_until_expiry(&self) -> Option<i64> {
+        self.info.expires_at
+            .map(|ts| (ts - chrono::Utc::now().timestamp()).div_euclid(24 * 60 * 60))
+    }
 }
 
 /// Verify a license key
@@ -8,6 +8,7 @@ mod database;
 mod scene_detection;
 mod i18n;
 mod license;
+mod pin;
 #[cfg(feature = "premium")]
 mod license_premium;
 mod app;
@@ -8,6 +8,15 @@ use crate::i18n::Language;
 pub struct SceneInfo {
     pub timestamp: f64, // timestamp in seconds
     pub thumbnail_path: PathBuf,
+    #[serde(default)]
+    pub score: Option<f64>, // FFmpeg scene-change score (None for interval/manual scenes)
+}
+
+/// Chapter embedded in a video file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChapterInfo {
+    pub start: f64, // start time in seconds
+    pub title: String,
 }
 
 /// Video file information structure
@@ -18,19 +27,54 @@ pub struct VideoFile {
     pub title: String,
     pub duration: Option<f64>, // in seconds
     pub file_size: u64,
+    #[serde(default)]
+    pub modified_time: Option<i64>, // File modification time (seconds since epoch) when the metadata was read
     pub resolution: Option<(u32, u32)>, // (width, height)
     #[serde(default)]
     pub frame_rate: Option<f64>, // frames per second
+    #[serde(default)]
+    pub codec: Option<String>, // Video codec name reported by FFprobe (e.g. "h264")
+    #[serde(default)]
+    pub container: Option<String>, // Container format (e.g. "mp4", "matroska")
+    #[serde(default)]
+    pub is_hdr: bool, // HDR transfer function (PQ / HLG) detected by FFprobe
     pub thumbnail_path: Option<PathBuf>,
+    #[serde(default)]
+    pub preview_thumbnail_path: Option<PathBuf>, // Larger preview for the info panel, generated on first selection
+    #[serde(default)]
+    pub sprite_sheet_path: Option<PathBuf>, // Strip of frames for hover-scrub in the grid, generated on first hover
     pub tags: Vec<String>,
-    pub folder: Option<String>,
+    #[serde(default)]
+    pub folders: Vec<String>, // Collections the video belongs to (any number)
+    #[serde(default, skip_serializing, rename = "folder")]
+    pub folder_legacy: Option<String>, // Single folder of older JSON databases, moved into `folders` on migration
     #[serde(default)]
     pub rating: u8, // 0-5 star rating (0 = no rating, 1-5 = stars)
     #[serde(default, skip_serializing, rename = "is_favorite")]
     pub is_favorite_legacy: Option<bool>, // For backward compatibility during deserialization
     pub added_date: DateTime<Utc>,
     pub last_played: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub play_count: u32, // Number of times the video was launched
+    #[serde(default)]
+    pub playback_position: Option<f64>, // Saved resume position in seconds
+    #[serde(default)]
+    pub protected: bool, // Hidden until unlocked with the protection PIN
+    #[serde(default)]
+    pub watched: bool, // Already seen (set by hand, on play or near the end of playback)
+    #[serde(default)]
+    pub sort_index: i64, // Position in the manual sort order (0 = not placed yet, listed last)
+    #[serde(default)]
+    pub preferred_volume: Option<u8>, // Volume used for this video instead of the global mpv volume
+    #[serde(default)]
+    pub preferred_speed: Option<f64>, // Playback speed used for this video (None = 1.0)
+    #[serde(default)]
+    pub notes: String, // Free-text notes about the video
+    #[serde(default)]
+    pub content_hash: Option<String>, // Hash of size + first megabyte, used to follow moved/renamed files
     pub scenes: Vec<SceneInfo>, // Scene thumbnails and timestamps
+    #[serde(default)]
+    pub chapters: Vec<ChapterInfo>, // Chapters embedded in the file
 }
 
 impl VideoFile {
@@ -42,6 +86,7 @@ impl VideoFile {
             .to_string();
         
         let id = uuid::Uuid::new_v4().to_string();
+        let modified_time = Self::read_modified_time(&path);
         
         // Try to get file creation time, fall back to modified time, then to current time
         let added_date = std::fs::metadata(&path)
@@ -68,32 +113,70 @@ impl VideoFile {
             title,
             duration: None,
             file_size: 0,
+            modified_time,
             resolution: None,
             frame_rate: None,
+            codec: None,
+            container: None,
+            is_hdr: false,
             thumbnail_path: None,
+            preview_thumbnail_path: None,
+            sprite_sheet_path: None,
+            content_hash: None,
             tags: Vec::new(),
-            folder: None,
+            folders: Vec::new(),
+            folder_legacy: None,
             rating: 0,
             is_favorite_legacy: None,
             added_date,
             last_played: None,
+            play_count: 0,
+            playback_position: None,
+            protected: false,
+            watched: false,
+            sort_index: 0,
+            preferred_volume: None,
+            preferred_speed: None,
+            notes: String::new(),
             scenes: Vec::new(),
+            chapters: Vec::new(),
         }
     }
     
-    pub fn is_video_file(path: &PathBuf) -> bool {
+    /// Modification time of a file in seconds since epoch (None if unavailable)
+    pub fn read_modified_time(path: &std::path::Path) -> Option<i64> {
+        let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+        let duration = modified.duration_since(std::time::UNIX_EPOCH).ok()?;
+        Some(duration.as_secs() as i64)
+    }
+    
+    /// Check the file extension against the configured list (case-insensitive)
+    pub fn is_video_file(path: &std::path::Path, extensions: &[String]) -> bool {
         if let Some(ext) = path.extension() {
-            let ext = ext.to_string_lossy().to_lowercase();
-            matches!(
-                ext.as_str(),
-                "mp4" | "avi" | "mkv" | "mov" | "wmv" | "flv" | "webm" | "m4v" | "mpg" | "mpeg"
-            )
+            let ext = ext.to_string_lossy();
+            extensions.iter().any(|allowed| allowed.eq_ignore_ascii_case(&ext))
         } else {
             false
         }
     }
 }
 
+/// One entry of a metadata import file (JSON array), matched to a library video by path
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetadataImportRecord {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>, // Merged into the video's tags
+    #[serde(default)]
+    pub rating: Option<u8>,
+    #[serde(default)]
+    pub folder: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
 /// Application database
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct VideoDatabase {
@@ -108,8 +191,8 @@ impl VideoDatabase {
     }
     
     pub fn add_video(&mut self, video: VideoFile) {
-        // Add folder to folders list if it doesn't exist
-        if let Some(ref folder) = video.folder {
+        // Add folders to folders list if they don't exist
+        for folder in &video.folders {
             self.add_folder(folder.clone());
         }
         self.videos.push(video);
@@ -146,7 +229,7 @@ impl VideoDatabase {
     pub fn get_by_folder(&self, folder: &str) -> Vec<&VideoFile> {
         self.videos
             .iter()
-            .filter(|v| v.folder.as_deref() == Some(folder))
+            .filter(|v| v.folders.iter().any(|f| f == folder))
             .collect()
     }
     
@@ -231,6 +314,45 @@ impl VideoDatabase {
         count
     }
     
+    /// Rename a tag in the tag list and on every video
+    pub fn rename_tag(&mut self, old_name: &str, new_name: &str) {
+        for tag in self.tags.iter_mut().filter(|t| *t == old_name) {
+            *tag = new_name.to_string();
+        }
+        for video in &mut self.videos {
+            for tag in video.tags.iter_mut().filter(|t| *t == old_name) {
+                *tag = new_name.to_string();
+            }
+        }
+    }
+    
+    /// Rename a content folder and move its videos to the new name
+    pub fn rename_folder(&mut self, old_name: &str, new_name: &str) {
+        for folder in self.folders.iter_mut().filter(|f| *f == old_name) {
+            *folder = new_name.to_string();
+        }
+        for video in &mut self.videos {
+            if video.folders.iter().any(|f| f == old_name) {
+                video.folders.retain(|f| f != old_name && f != new_name);
+                video.folders.push(new_name.to_string());
+            }
+        }
+    }
+    
+    /// Move all videos from one tag to another and remove the source tag
+    pub fn merge_tags(&mut self, source: &str, target: &str) {
+        for video in &mut self.videos {
+            if video.tags.iter().any(|t| t == source) {
+                video.tags.retain(|t| t != source);
+                if !video.tags.iter().any(|t| t == target) {
+                    video.tags.push(target.to_string());
+                }
+            }
+        }
+        self.tags.retain(|t| t != source);
+        self.add_tag(target.to_string());
+    }
+    
     /// Remove unused tags from the database
     /// Tags that are not used by any video will be removed
     pub fn cleanup_unused_tags(&mut self) {
@@ -247,7 +369,7 @@ impl VideoDatabase {
     pub fn cleanup_unused_folders(&mut self) {
         let used_folders: std::collections::HashSet<String> = self.videos
             .iter()
-            .filter_map(|v| v.folder.clone())
+            .flat_map(|v| v.folders.iter().cloned())
             .collect();
         
         self.folders.retain(|folder| used_folders.contains(folder));
@@ -279,14 +401,60 @@ impl VideoDatabase {
     }
 }
 
+/// UI theme selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemeMode {
+    System, // Follow the OS light/dark setting
+    Light,
+    Dark,
+}
+
+/// Auto-tagging rule: videos whose title or path matches `pattern` get the tag built from `tag`
+/// (`$1`, `${name}` are replaced by the capture groups)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TagRule {
+    pub pattern: String, // Regular expression
+    pub tag: String, // Tag template
+}
+
+/// A named snapshot of the sidebar filters and search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterPreset {
+    pub name: String,
+    #[serde(default)]
+    pub folders: Vec<String>, // Selected folder filter
+    #[serde(default)]
+    pub tags: Vec<String>, // Selected tag filter
+    #[serde(default)]
+    pub tag_filter_and: bool, // true = AND, false = OR
+    #[serde(default)]
+    pub min_rating: u8, // 0 = all
+    #[serde(default)]
+    pub search_query: String,
+}
+
 /// Application settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppSettings {
     pub thumbnail_scale: f32,
     pub mpv_always_on_top: bool,
+    #[serde(default)]
+    pub loop_playback: bool, // Repeat the video endlessly in mpv (--loop-file=inf)
+    #[serde(default)]
+    pub fullscreen_on_launch: bool, // Start mpv in fullscreen (--fullscreen)
     pub show_full_filename: bool,
     pub show_tags_in_grid: bool,
-    pub dark_mode: bool,
+    pub dark_mode: bool, // Legacy theme flag (used when theme_mode is missing)
+    #[serde(default)]
+    pub theme_mode: Option<ThemeMode>, // UI theme (None = migrate from dark_mode)
+    #[serde(default = "default_accent_color")]
+    pub accent_color: [u8; 3], // Selection highlight color (RGB)
+    #[serde(default = "default_scene_panel_width")]
+    pub scene_panel_width: f32, // Width of the right scene panel (px)
+    #[serde(default)]
+    pub fixed_grid_columns: bool, // Use grid_columns instead of fitting columns to the width
+    #[serde(default = "default_grid_columns")]
+    pub grid_columns: usize, // Column count of the fixed-column grid (2-10)
     pub use_gpu_hq: bool,
     pub use_custom_shaders: bool,
     pub selected_shader: Option<String>, // Selected shader filename
@@ -294,7 +462,9 @@ pub struct AppSettings {
     #[serde(default)]
     pub added_dates_updated: bool, // Flag to track if added_date has been updated from file metadata
     #[serde(default)]
-    pub watched_folders: Vec<std::path::PathBuf>, // Folders being watched for changes
+    pub watched_folders: Vec<std::path::PathBuf>, // Folders being watched for changes (current profile)
+    #[serde(default)]
+    pub profile_watched_folders: std::collections::HashMap<String, Vec<std::path::PathBuf>>, // Watched folders of each profile
     #[serde(default = "default_mpv_shortcuts_open")]
     pub mpv_shortcuts_open: bool, // MPV shortcuts panel open/collapsed state
     #[serde(default)]
@@ -313,12 +483,128 @@ pub struct AppSettings {
     pub last_backup_date: Option<String>, // Last database backup date (RFC3339)
     #[serde(default = "default_profile")]
     pub current_profile: String, // Current active profile name
+    #[serde(default = "default_backups_to_keep")]
+    pub backups_to_keep: usize, // Number of backups to keep (0 = unlimited)
+    #[serde(default = "default_backup_interval_days")]
+    pub backup_interval_days: i64, // Days between automatic backups
+    #[serde(default)]
+    pub protection_pin_hash: Option<String>, // PBKDF2-HMAC-SHA256 hash of the PIN for protected videos (plain SHA-256 before a salt existed)
+    #[serde(default)]
+    pub protection_pin_salt: Option<String>, // Per-install salt for the PIN hash
+    #[serde(default = "default_recent_days")]
+    pub recent_days: u32, // Days covered by the "Recent" filter
+    #[serde(default)]
+    pub show_recent_row: bool, // Show "Continue watching" row above the grid
+    #[serde(default)]
+    pub prune_hidden_selection: bool, // Deselect videos hidden by the current filters
+    #[serde(default = "default_video_extensions")]
+    pub video_extensions: Vec<String>, // File extensions picked up when scanning/adding (without dot)
+    #[serde(default)]
+    pub exclude_patterns: Vec<String>, // Glob/substring patterns skipped when scanning folders
+    #[serde(default)]
+    pub use_embedded_metadata: bool, // Prefer the title/creation date embedded in files on import
+    #[serde(default)]
+    pub auto_tag_from_folder: bool, // Tag videos imported from folders with their parent directory name
+    #[serde(default = "default_rescan_debounce_secs")]
+    pub rescan_debounce_secs: u32, // Quiet time after file events before the watcher rescans
+    #[serde(default = "default_license_expiry_warning_days")]
+    pub license_expiry_warning_days: u32, // Warn this many days before the license expires (0 = never)
+    #[serde(default)]
+    pub license_expired_notice_shown: Option<i64>, // expires_at of the license whose expiry notice was already shown
+    #[serde(default)]
+    pub show_quality_badge: bool, // Show a resolution badge (e.g. "1080p") on grid thumbnails
+    #[serde(default = "default_show_duration_badge")]
+    pub show_duration_badge: bool, // Show the video length on grid thumbnails
+    #[serde(default)]
+    pub group_by_folder: bool, // Split the grid into collapsible sections per folder
+    #[serde(default)]
+    pub mark_watched_on_play: bool, // Mark videos watched when playback starts instead of after 90%
+    #[serde(default = "default_hdr_colorspace_hint")]
+    pub hdr_colorspace_hint: bool, // Pass --target-colorspace-hint to mpv for HDR videos
+    #[serde(default)]
+    pub pin_favorites_to_top: bool, // List top-rated videos first, whatever the sort
+    #[serde(default = "default_pin_min_rating")]
+    pub pin_min_rating: u8, // Minimum rating of pinned videos (premium; free tier pins favorites)
+    #[serde(default)]
+    pub default_subtitle: Option<String>, // Preferred subtitle languages for mpv (e.g. "ja,en"), or "off"
+    #[serde(default)]
+    pub default_audio_lang: Option<String>, // Preferred audio languages for mpv (e.g. "jpn")
+    #[serde(default)]
+    pub ffmpeg_path: Option<PathBuf>, // User-selected ffmpeg binary (None = bundled/system)
+    #[serde(default)]
+    pub ffprobe_path: Option<PathBuf>, // User-selected ffprobe binary (None = bundled/system)
+    #[serde(default)]
+    pub screenshot_dir: Option<PathBuf>, // Where mpv saves screenshots (None = Pictures/CicadaGallery)
+    #[serde(default = "default_auto_collapse_filters")]
+    pub auto_collapse_filters: bool, // Move filters into a window on narrow windows
+    #[serde(default = "default_max_texture_cache")]
+    pub max_texture_cache: usize, // Maximum number of thumbnail textures kept in memory
+    #[serde(default)]
+    pub thumbnail_eviction_enabled: bool, // Delete thumbnails not viewed recently when the cache is too large
+    #[serde(default = "default_thumbnail_eviction_days")]
+    pub thumbnail_eviction_days: u32, // Thumbnails not viewed within this many days can be deleted
+    #[serde(default = "default_thumbnail_cache_budget_mb")]
+    pub thumbnail_cache_budget_mb: u64, // Disk budget for the thumbnail cache (MB)
+    #[serde(default = "default_thumbnail_max_width")]
+    pub thumbnail_max_width: u32, // Maximum width of generated thumbnails (px)
+    #[serde(default = "default_thumbnail_quality")]
+    pub thumbnail_quality: u8, // FFmpeg JPEG quality of generated thumbnails (2 = best, 31 = smallest)
+    #[serde(default = "default_scene_threshold")]
+    pub scene_threshold: f32, // FFmpeg scene-change threshold (0.0-1.0)
+    #[serde(default = "default_max_scenes")]
+    pub max_scenes: usize, // Maximum number of detected scenes per video
+    #[serde(default = "default_scene_thread_count")]
+    pub scene_thread_count: usize, // FFmpeg processes extracting scene thumbnails at once
+    #[serde(default = "default_max_concurrent_texture_loads")]
+    pub max_concurrent_texture_loads: usize, // Thumbnail images decoded in parallel
+    #[serde(default = "default_similar_scene_threshold")]
+    pub similar_scene_threshold: f32, // Adjacent scenes at least this similar are removed (0.0-1.0)
+    #[serde(default)]
+    pub filter_presets: Vec<FilterPreset>, // Saved filter combinations
+    #[serde(default)]
+    pub tag_rules: Vec<TagRule>, // Regex auto-tagging rules applied on import
 }
 
 fn default_mpv_shortcuts_open() -> bool {
     true
 }
 
+fn default_show_duration_badge() -> bool {
+    true
+}
+
+/// Default parallelism of scene thumbnail extraction and thumbnail image loading
+pub const DEFAULT_SCENE_THREAD_COUNT: usize = 4;
+pub const DEFAULT_MAX_CONCURRENT_TEXTURE_LOADS: usize = 8;
+
+fn default_scene_thread_count() -> usize {
+    DEFAULT_SCENE_THREAD_COUNT
+}
+
+fn default_max_concurrent_texture_loads() -> usize {
+    DEFAULT_MAX_CONCURRENT_TEXTURE_LOADS
+}
+
+fn default_rescan_debounce_secs() -> u32 {
+    3
+}
+
+fn default_license_expiry_warning_days() -> u32 {
+    7
+}
+
+fn default_hdr_colorspace_hint() -> bool {
+    true
+}
+
+fn default_pin_min_rating() -> u8 {
+    5
+}
+
+fn default_auto_collapse_filters() -> bool {
+    true
+}
+
 fn default_mpv_volume() -> u8 {
     100
 }
@@ -327,20 +613,91 @@ fn default_profile() -> String {
     "default".to_string()
 }
 
+/// Extensions scanned by default
+pub const DEFAULT_VIDEO_EXTENSIONS: &[&str] = &["mp4", "avi", "mkv", "mov", "wmv", "flv", "webm", "m4v", "mpg", "mpeg"];
+
+pub fn default_video_extensions() -> Vec<String> {
+    DEFAULT_VIDEO_EXTENSIONS.iter().map(|ext| ext.to_string()).collect()
+}
+
+fn default_backups_to_keep() -> usize {
+    3
+}
+
+fn default_backup_interval_days() -> i64 {
+    3
+}
+
+fn default_recent_days() -> u32 {
+    14
+}
+
+pub fn default_accent_color() -> [u8; 3] {
+    [100, 200, 255]
+}
+
+fn default_scene_panel_width() -> f32 {
+    300.0
+}
+
+fn default_grid_columns() -> usize {
+    4
+}
+
+fn default_thumbnail_eviction_days() -> u32 {
+    30
+}
+
+fn default_thumbnail_cache_budget_mb() -> u64 {
+    1024
+}
+
+fn default_thumbnail_max_width() -> u32 {
+    320
+}
+
+fn default_thumbnail_quality() -> u8 {
+    2
+}
+
+fn default_max_texture_cache() -> usize {
+    500
+}
+
+fn default_scene_threshold() -> f32 {
+    0.3
+}
+
+fn default_max_scenes() -> usize {
+    50
+}
+
+fn default_similar_scene_threshold() -> f32 {
+    0.9
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
             thumbnail_scale: 1.25,
             mpv_always_on_top: true,
+            loop_playback: false,
+            fullscreen_on_launch: false,
             show_full_filename: false,
             show_tags_in_grid: true,
             dark_mode: false,
+            theme_mode: Some(ThemeMode::System),
+            accent_color: default_accent_color(),
+            scene_panel_width: default_scene_panel_width(),
+            fixed_grid_columns: false,
+            grid_columns: default_grid_columns(),
             use_gpu_hq: false,
             use_custom_shaders: false,
             selected_shader: None,
             language: Language::default(),
             added_dates_updated: false,
             watched_folders: Vec::new(),
+            profile_watched_folders: std::collections::HashMap::new(),
             mpv_shortcuts_open: true,
             mpv_shortcuts_position: None,
             mpv_volume: 100,
@@ -350,6 +707,46 @@ impl Default for AppSettings {
             window_maximized: false,
             last_backup_date: None,
             current_profile: "default".to_string(),
+            backups_to_keep: 3,
+            backup_interval_days: 3,
+            protection_pin_hash: None,
+            protection_pin_salt: None,
+            recent_days: 14,
+            show_recent_row: false,
+            prune_hidden_selection: false,
+            video_extensions: default_video_extensions(),
+            exclude_patterns: Vec::new(),
+            use_embedded_metadata: false,
+            auto_tag_from_folder: false,
+            rescan_debounce_secs: 3,
+            license_expiry_warning_days: 7,
+            license_expired_notice_shown: None,
+            show_quality_badge: false,
+            show_duration_badge: true,
+            group_by_folder: false,
+            mark_watched_on_play: false,
+            hdr_colorspace_hint: true,
+            pin_favorites_to_top: false,
+            pin_min_rating: 5,
+            default_subtitle: None,
+            default_audio_lang: None,
+            ffmpeg_path: None,
+            ffprobe_path: None,
+            screenshot_dir: None,
+            auto_collapse_filters: true,
+            max_texture_cache: 500,
+            thumbnail_eviction_enabled: false,
+            thumbnail_eviction_days: 30,
+            thumbnail_cache_budget_mb: 1024,
+            thumbnail_max_width: 320,
+            thumbnail_quality: 2,
+            scene_threshold: 0.3,
+            max_scenes: 50,
+            scene_thread_count: DEFAULT_SCENE_THREAD_COUNT,
+            max_concurrent_texture_loads: DEFAULT_MAX_CONCURRENT_TEXTURE_LOADS,
+            similar_scene_threshold: 0.9,
+            filter_presets: Vec::new(),
+            tag_rules: Vec::new(),
         }
     }
 }
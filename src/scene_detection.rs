@@ -1,12 +1,15 @@
-use crate::models::{SceneInfo, VideoFile};
-use crate::thumbnail::{get_ffmpeg_path, get_ffprobe_path};
+use crate::models::{ChapterInfo, SceneInfo, VideoFile};
+use chrono::{DateTime, Datelike, Utc};
+use crate::thumbnail::{get_ffmpeg_path, get_ffprobe_path, is_valid_tool_binary, thumbnail_options};
 use std::path::Path;
 use std::process::Command;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
 
 /// Detect scenes in a video using FFmpeg and generate thumbnails
-pub fn detect_scenes(video: &mut VideoFile, cache_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+/// `threshold` is the scene-change score a frame must exceed, `max_scenes` caps the result,
+/// `thread_count` is the number of FFmpeg processes extracting thumbnails at once
+pub fn detect_scenes(video: &mut VideoFile, cache_dir: &Path, threshold: f32, max_scenes: usize, thread_count: usize) -> Result<(), Box<dyn std::error::Error>> {
     // Create scene thumbnails directory
     let video_id = &video.id;
     let scene_dir = cache_dir.join("scenes").join(video_id);
@@ -30,10 +33,11 @@ pub fn detect_scenes(video: &mut VideoFile, cache_dir: &Path) -> Result<(), Box<
     
     // Use FFmpeg to detect scene changes and extract frames
     // This command detects scenes and outputs timestamps
+    let filter = format!("select='gt(scene,{})',metadata=print:key=lavfi.scene_score", threshold);
     let mut cmd = Command::new(get_ffmpeg_path());
     cmd.args(&[
             "-i", video.path.to_str().unwrap(),
-            "-filter:v", "select='gt(scene,0.3)',showinfo",
+            "-filter:v", &filter,
             "-vsync", "vfr",
             "-f", "null",
             "-"
@@ -51,14 +55,16 @@ pub fn detect_scenes(video: &mut VideoFile, cache_dir: &Path) -> Result<(), Box<
     
     if output.is_err() {
         // FFmpeg not available, create a few sample scenes at regular intervals
-        return generate_interval_scenes(video, cache_dir);
+        return generate_interval_scenes(video, cache_dir, max_scenes, thread_count);
     }
     
     let output = output?;
     let stderr = String::from_utf8_lossy(&output.stderr);
     
-    // Parse scene timestamps from FFmpeg output
+    // Parse scene timestamps and scores from FFmpeg output
+    // Each selected frame prints a "pts_time:" line followed by "lavfi.scene_score=<score>"
     let mut timestamps: Vec<f64> = Vec::new();
+    let mut scores: Vec<(f64, f64)> = Vec::new();
     for line in stderr.lines() {
         if line.contains("pts_time:") {
             if let Some(time_str) = line.split("pts_time:").nth(1) {
@@ -68,25 +74,36 @@ pub fn detect_scenes(video: &mut VideoFile, cache_dir: &Path) -> Result<(), Box<
                     }
                 }
             }
+        } else if let Some(score_str) = line.split("lavfi.scene_score=").nth(1) {
+            if let (Some(&timestamp), Ok(score)) = (timestamps.last(), score_str.trim().parse::<f64>()) {
+                scores.push((timestamp, score));
+            }
         }
     }
     
     // If no scenes detected or FFmpeg failed, use interval-based approach
     if timestamps.is_empty() {
-        return generate_interval_scenes(video, cache_dir);
+        return generate_interval_scenes(video, cache_dir, max_scenes, thread_count);
     }
     
-    // Limit to reasonable number of scenes (e.g., max 50)
-    timestamps.truncate(50);
+    // Limit to the configured number of scenes
+    timestamps.truncate(max_scenes);
     
     // Generate thumbnails for detected scenes in parallel
-    generate_thumbnails_parallel(video, &timestamps, &scene_dir)?;
+    generate_thumbnails_parallel(video, &timestamps, &scene_dir, thread_count)?;
+    
+    // Attach scene-change scores to the generated scenes
+    for scene in &mut video.scenes {
+        if let Some(&(_, score)) = scores.iter().find(|(timestamp, _)| *timestamp == scene.timestamp) {
+            scene.score = Some(score);
+        }
+    }
     
     Ok(())
 }
 
-/// Generate scene thumbnails at regular intervals (fallback when FFmpeg scene detection fails)
-fn generate_interval_scenes(video: &mut VideoFile, cache_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+/// Generate scene thumbnails at regular intervals (fallback when FFmpeg scene detection fails), at most `max_scenes`
+fn generate_interval_scenes(video: &mut VideoFile, cache_dir: &Path, max_scenes: usize, thread_count: usize) -> Result<(), Box<dyn std::error::Error>> {
     let video_id = &video.id;
     let scene_dir = cache_dir.join("scenes").join(video_id);
     
@@ -103,7 +120,7 @@ fn generate_interval_scenes(video: &mut VideoFile, cache_dir: &Path) -> Result<(
     
     // Collect timestamps to generate
     let mut timestamps = Vec::new();
-    for i in 0..num_scenes.min(50) {
+    for i in 0..num_scenes.min(max_scenes) {
         let timestamp = i as f64 * interval;
         if timestamp >= duration {
             break;
@@ -116,6 +133,7 @@ fn generate_interval_scenes(video: &mut VideoFile, cache_dir: &Path) -> Result<(
             video.scenes.push(SceneInfo {
                 timestamp,
                 thumbnail_path: thumbnail_path.clone(),
+                score: None,
             });
         } else {
             timestamps.push(timestamp);
@@ -124,7 +142,7 @@ fn generate_interval_scenes(video: &mut VideoFile, cache_dir: &Path) -> Result<(
     
     // Generate missing thumbnails in parallel
     if !timestamps.is_empty() {
-        generate_thumbnails_parallel(video, &timestamps, &scene_dir)?;
+        generate_thumbnails_parallel(video, &timestamps, &scene_dir, thread_count)?;
     }
     
     Ok(())
@@ -135,9 +153,10 @@ fn generate_thumbnails_parallel(
     video: &mut VideoFile,
     timestamps: &[f64],
     scene_dir: &Path,
+    thread_count: usize,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    // Determine number of threads (use 4 for optimal balance between speed and resource usage)
-    let num_threads = 4.min(timestamps.len());
+    // Number of threads (configurable, 4 by default for a balance between speed and resource usage)
+    let num_threads = thread_count.max(1).min(timestamps.len());
     
     if num_threads == 0 {
         return Ok(());
@@ -147,6 +166,9 @@ fn generate_thumbnails_parallel(
     let scene_dir = scene_dir.to_path_buf();
     let scenes = Arc::new(Mutex::new(Vec::new()));
     let ffmpeg_path = get_ffmpeg_path(); // Get path once before spawning threads
+    let (max_width, quality) = thumbnail_options();
+    let scale = format!("scale='min({},iw)':-1", max_width);
+    let quality = quality.to_string();
     
     // Split timestamps into chunks for each thread
     let chunk_size = (timestamps.len() + num_threads - 1) / num_threads;
@@ -158,6 +180,8 @@ fn generate_thumbnails_parallel(
         let scene_dir = scene_dir.clone();
         let scenes = Arc::clone(&scenes);
         let ffmpeg_path = ffmpeg_path.clone();
+        let scale = scale.clone();
+        let quality = quality.clone();
         
         let handle = thread::spawn(move || {
             let mut local_scenes = Vec::new();
@@ -172,8 +196,8 @@ fn generate_thumbnails_parallel(
                         "-ss", &timestamp.to_string(),  // Fast seek before input
                         "-i", video_path.to_str().unwrap(),
                         "-vframes", "1",
-                        "-q:v", "3",  // Slightly lower quality for speed (was 2)
-                        "-vf", "scale='min(320,iw)':-1",  // Limit size for faster processing
+                        "-q:v", &quality,
+                        "-vf", &scale,  // Limit size from the thumbnail options
                         "-y",
                         thumbnail_path.to_str().unwrap()
                     ]);
@@ -192,6 +216,7 @@ fn generate_thumbnails_parallel(
                     local_scenes.push(SceneInfo {
                         timestamp: *timestamp,
                         thumbnail_path,
+                        score: None,
                     });
                 }
             }
@@ -220,6 +245,19 @@ fn generate_thumbnails_parallel(
     Ok(())
 }
 
+/// Check whether FFprobe can be run (`ffprobe -version`).
+/// The result is cached for the rest of the session.
+pub fn is_ffprobe_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        let available = is_valid_tool_binary(&get_ffprobe_path());
+        if !available {
+            eprintln!("[ffprobe] ffprobe could not be run; metadata and scenes are unavailable");
+        }
+        available
+    })
+}
+
 /// Get video duration using FFprobe
 pub fn get_video_duration(video_path: &Path) -> Option<f64> {
     let mut cmd = Command::new(get_ffprobe_path());
@@ -244,6 +282,40 @@ pub fn get_video_duration(video_path: &Path) -> Option<f64> {
     duration_str.trim().parse::<f64>().ok()
 }
 
+/// Get an accurate video duration by reading every packet of the video stream.
+/// Slower than get_video_duration, but correct for VBR files with a wrong header duration.
+pub fn get_video_duration_accurate(video_path: &Path) -> Option<f64> {
+    let mut cmd = Command::new(get_ffmpeg_path());
+    cmd.args([
+            "-i", video_path.to_str()?,
+            "-map", "0:v:0",
+            "-c", "copy",
+            "-f", "null",
+            "-"
+        ]);
+    
+    // Hide console window on Windows
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    
+    let output = cmd.output().ok()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    
+    // The last progress line ("... time=HH:MM:SS.xx ...") holds the total stream time
+    let time_str = stderr.rsplit("time=").next()?.split_whitespace().next()?;
+    let duration = parse_timestamp(time_str)?;
+    
+    if duration > 0.0 {
+        Some(duration)
+    } else {
+        get_video_duration(video_path)
+    }
+}
+
 /// Get video resolution (width, height) using FFprobe
 pub fn get_video_resolution(video_path: &Path) -> Option<(u32, u32)> {
     let mut cmd = Command::new(get_ffprobe_path());
@@ -314,6 +386,187 @@ pub fn get_video_frame_rate(video_path: &Path) -> Option<f64> {
     fps_str.parse::<f64>().ok()
 }
 
+/// Get the video codec (e.g. "h264", "hevc", "av1") and container format using FFprobe
+/// Returns (None, None) when the file cannot be probed
+pub fn get_video_codec(video_path: &Path) -> (Option<String>, Option<String>) {
+    let Some(path_str) = video_path.to_str() else {
+        return (None, None);
+    };
+    let mut cmd = Command::new(get_ffprobe_path());
+    cmd.args([
+            "-v", "error",
+            "-select_streams", "v:0",
+            "-show_entries", "stream=codec_name:format=format_name",
+            "-of", "default=noprint_wrappers=1",
+            path_str
+        ]);
+    
+    // Hide console window on Windows
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    
+    let Ok(output) = cmd.output() else {
+        return (None, None);
+    };
+    
+    let mut codec = None;
+    let mut container = None;
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if let Some(name) = line.strip_prefix("codec_name=") {
+            codec = Some(name.trim().to_string()).filter(|n| !n.is_empty());
+        } else if let Some(names) = line.strip_prefix("format_name=") {
+            // FFprobe lists every demuxer alias (e.g. "mov,mp4,m4a,3gp,3g2,mj2");
+            // prefer the one matching the file extension
+            let extension = video_path.extension()
+                .and_then(|e| e.to_str())
+                .map(|e| e.to_lowercase());
+            let aliases: Vec<&str> = names.trim().split(',').filter(|n| !n.is_empty()).collect();
+            container = aliases.iter()
+                .find(|alias| Some(alias.to_string()) == extension)
+                .or(aliases.first())
+                .map(|alias| alias.to_string());
+        }
+    }
+    
+    (codec, container)
+}
+
+/// Whether the video stream uses an HDR transfer function (PQ / HLG) according to FFprobe
+/// Returns false when the file cannot be probed
+pub fn is_video_hdr(video_path: &Path) -> bool {
+    let Some(path_str) = video_path.to_str() else {
+        return false;
+    };
+    let mut cmd = Command::new(get_ffprobe_path());
+    cmd.args([
+            "-v", "error",
+            "-select_streams", "v:0",
+            "-show_entries", "stream=color_transfer",
+            "-of", "default=noprint_wrappers=1:nokey=1",
+            path_str
+        ]);
+    
+    // Hide console window on Windows
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    
+    let Ok(output) = cmd.output() else {
+        return false;
+    };
+    
+    // smpte2084 = HDR10 / Dolby Vision (PQ), arib-std-b67 = HLG
+    let transfer = String::from_utf8_lossy(&output.stdout);
+    matches!(transfer.trim(), "smpte2084" | "arib-std-b67")
+}
+
+/// Get the title and recording date embedded in the container (format tags) using FFprobe
+/// Missing, empty or placeholder values (e.g. the 1904/1970 epoch of zeroed MP4 headers) are None
+pub fn get_embedded_metadata(video_path: &Path) -> (Option<String>, Option<DateTime<Utc>>) {
+    let Some(path_str) = video_path.to_str() else {
+        return (None, None);
+    };
+    let mut cmd = Command::new(get_ffprobe_path());
+    cmd.args([
+            "-v", "error",
+            "-show_entries", "format_tags=title,creation_time",
+            "-of", "default=noprint_wrappers=1",
+            path_str
+        ]);
+    
+    // Hide console window on Windows
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    
+    let Ok(output) = cmd.output() else {
+        return (None, None);
+    };
+    
+    let mut title = None;
+    let mut creation_time = None;
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        // Tag keys are case-insensitive ("TAG:title", "TAG:TITLE")
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim_start_matches("TAG:").to_lowercase().as_str() {
+            "title" if !value.is_empty() => title = Some(value.to_string()),
+            "creation_time" => {
+                creation_time = DateTime::parse_from_rfc3339(value)
+                    .ok()
+                    .map(|d| d.with_timezone(&Utc))
+                    .filter(|d| d.year() > 1970);
+            }
+            _ => {}
+        }
+    }
+    
+    (title, creation_time)
+}
+
+/// Get embedded chapters using FFprobe (-show_chapters)
+pub fn get_video_chapters(video_path: &Path) -> Vec<ChapterInfo> {
+    let Some(path_str) = video_path.to_str() else {
+        return Vec::new();
+    };
+    
+    let mut cmd = Command::new(get_ffprobe_path());
+    cmd.args([
+            "-v", "error",
+            "-show_chapters",
+            "-of", "json",
+            path_str
+        ]);
+    
+    // Hide console window on Windows
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    
+    let output = match cmd.output() {
+        Ok(output) => output,
+        Err(_) => return Vec::new(),
+    };
+    
+    let json: serde_json::Value = match serde_json::from_slice(&output.stdout) {
+        Ok(json) => json,
+        Err(_) => return Vec::new(),
+    };
+    
+    let Some(entries) = json.get("chapters").and_then(|c| c.as_array()) else {
+        return Vec::new();
+    };
+    
+    entries.iter()
+        .enumerate()
+        .filter_map(|(i, chapter)| {
+            // start_time is reported as a string like "12.345000"
+            let start = chapter.get("start_time")?.as_str()?.parse::<f64>().ok()?;
+            let title = chapter.get("tags")
+                .and_then(|tags| tags.get("title"))
+                .and_then(|title| title.as_str())
+                .map(|title| title.to_string())
+                .unwrap_or_else(|| format!("Chapter {}", i + 1));
+            Some(ChapterInfo { start, title })
+        })
+        .collect()
+}
+
 /// Format timestamp as HH:MM:SS
 pub fn format_timestamp(seconds: f64) -> String {
     let hours = (seconds / 3600.0).floor() as u32;
@@ -327,6 +580,35 @@ pub fn format_timestamp(seconds: f64) -> String {
     }
 }
 
+/// Parse a timestamp entered as HH:MM:SS, MM:SS or plain seconds
+pub fn parse_timestamp(input: &str) -> Option<f64> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    
+    let parts: Vec<&str> = input.split(':').collect();
+    if parts.len() > 3 {
+        return None;
+    }
+    
+    let mut seconds = 0.0;
+    for (i, part) in parts.iter().enumerate() {
+        let value = part.trim().parse::<f64>().ok()?;
+        // "nan" / "inf" parse as f64 but are not positions
+        if !value.is_finite() || value < 0.0 {
+            return None;
+        }
+        // Only the leading field may exceed 59 (e.g. "90" or "90:00")
+        if i > 0 && value >= 60.0 {
+            return None;
+        }
+        seconds = seconds * 60.0 + value;
+    }
+    
+    Some(seconds)
+}
+
 /// Add a single scene at a specific timestamp
 /// Returns the created SceneInfo if successful
 pub fn add_scene_at_timestamp(video: &mut VideoFile, timestamp: f64, cache_dir: &Path) -> Option<SceneInfo> {
@@ -343,13 +625,14 @@ pub fn add_scene_at_timestamp(video: &mut VideoFile, timestamp: f64, cache_dir:
     
     // Use FFmpeg to extract the frame at the specified timestamp
     let ffmpeg_path = get_ffmpeg_path();
+    let (max_width, quality) = thumbnail_options();
     let mut cmd = Command::new(&ffmpeg_path);
     cmd.args(&[
         "-ss", &timestamp.to_string(),
         "-i", video.path.to_str()?,
         "-vframes", "1",
-        "-q:v", "2",
-        "-vf", "scale='min(320,iw)':-1",
+        "-q:v", &quality.to_string(),
+        "-vf", &format!("scale='min({},iw)':-1", max_width),
         "-y",
         thumbnail_path.to_str()?,
     ]);
@@ -368,6 +651,7 @@ pub fn add_scene_at_timestamp(video: &mut VideoFile, timestamp: f64, cache_dir:
         let scene = SceneInfo {
             timestamp,
             thumbnail_path: thumbnail_path.clone(),
+            score: None,
         };
         
         // Insert scene in sorted order by timestamp
@@ -381,3 +665,20 @@ pub fn add_scene_at_timestamp(video: &mut VideoFile, timestamp: f64, cache_dir:
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("90"), Some(90.0));
+        assert_eq!(parse_timestamp("1:30"), Some(90.0));
+        assert_eq!(parse_timestamp("1:02:03"), Some(3723.0));
+        assert_eq!(parse_timestamp(""), None);
+        assert_eq!(parse_timestamp("nan"), None);
+        assert_eq!(parse_timestamp("inf"), None);
+        assert_eq!(parse_timestamp("1:99"), None);
+        assert_eq!(parse_timestamp("1:2:3:4"), None);
+    }
+}
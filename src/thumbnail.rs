@@ -1,10 +1,63 @@
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::collections::{HashMap, HashSet};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+
+/// Maximum width of generated thumbnails (px), shared by all worker threads
+static THUMBNAIL_MAX_WIDTH: AtomicU32 = AtomicU32::new(320);
+/// FFmpeg JPEG quality of generated thumbnails (2 = best, 31 = smallest)
+static THUMBNAIL_QUALITY: AtomicU32 = AtomicU32::new(2);
+
+/// User-selected FFmpeg/FFprobe binaries (None = bundled or system binary)
+static FFMPEG_PATH_OVERRIDE: Mutex<Option<PathBuf>> = Mutex::new(None);
+static FFPROBE_PATH_OVERRIDE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Use the given FFmpeg/FFprobe binaries instead of searching for them
+pub fn set_tool_paths(ffmpeg: Option<PathBuf>, ffprobe: Option<PathBuf>) {
+    if let Ok(mut path) = FFMPEG_PATH_OVERRIDE.lock() {
+        *path = ffmpeg;
+    }
+    if let Ok(mut path) = FFPROBE_PATH_OVERRIDE.lock() {
+        *path = ffprobe;
+    }
+}
+
+/// Check that a binary runs and reports its version (`<binary> -version`)
+pub fn is_valid_tool_binary(path: &Path) -> bool {
+    let mut cmd = Command::new(path);
+    cmd.arg("-version");
+    
+    // Hide console window on Windows
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    
+    cmd.output().map(|o| o.status.success()).unwrap_or(false)
+}
+
+/// Apply the thumbnail size/quality settings to all thumbnails generated from now on
+pub fn set_thumbnail_options(max_width: u32, quality: u8) {
+    THUMBNAIL_MAX_WIDTH.store(max_width.max(1), Ordering::Relaxed);
+    THUMBNAIL_QUALITY.store(u32::from(quality).clamp(2, 31), Ordering::Relaxed);
+}
+
+/// Current (max_width, quality) for generated thumbnails
+pub fn thumbnail_options() -> (u32, u32) {
+    (THUMBNAIL_MAX_WIDTH.load(Ordering::Relaxed), THUMBNAIL_QUALITY.load(Ordering::Relaxed))
+}
 
 /// Get the path to the bundled ffmpeg executable
 pub fn get_ffmpeg_path() -> PathBuf {
+    if let Some(path) = FFMPEG_PATH_OVERRIDE.lock().ok().and_then(|p| p.clone()) {
+        return path;
+    }
+    
     let exe_dir = std::env::current_exe()
         .ok()
         .and_then(|p| p.parent().map(|p| p.to_path_buf()))
@@ -27,6 +80,10 @@ pub fn get_ffmpeg_path() -> PathBuf {
 
 /// Get the path to the bundled ffprobe executable
 pub fn get_ffprobe_path() -> PathBuf {
+    if let Some(path) = FFPROBE_PATH_OVERRIDE.lock().ok().and_then(|p| p.clone()) {
+        return path;
+    }
+    
     let exe_dir = std::env::current_exe()
         .ok()
         .and_then(|p| p.parent().map(|p| p.to_path_buf()))
@@ -60,11 +117,15 @@ fn hash_path(path: &Path) -> String {
     format!("{:016x}", hasher.finish())
 }
 
+/// Path of the automatically extracted main thumbnail
+pub fn video_thumbnail_path(video_path: &Path, cache_dir: &Path) -> PathBuf {
+    // Use hash of full path to avoid collisions between same-named files in different folders
+    cache_dir.join(format!("{}_thumb.jpg", hash_path(video_path)))
+}
+
 /// 動画ファイルからサムネイルパスを生成（実際の生成は後で実装）
 pub fn create_video_thumbnail(video_path: &Path, cache_dir: &Path) -> Option<PathBuf> {
-    // Use hash of full path to avoid collisions between same-named files in different folders
-    let path_hash = hash_path(video_path);
-    let thumbnail_path = cache_dir.join(format!("{}_thumb.jpg", path_hash));
+    let thumbnail_path = video_thumbnail_path(video_path, cache_dir);
     
     // サムネイルが既に存在する場合はそれを返す
     if thumbnail_path.exists() {
@@ -79,6 +140,9 @@ pub fn create_video_thumbnail(video_path: &Path, cache_dir: &Path) -> Option<Pat
     // Try multiple seek positions for short videos
     // Try 5 seconds first, then 1 second, then 0.1 seconds (near start)
     let seek_positions = ["5.0", "1.0", "0.1"];
+    let (max_width, quality) = thumbnail_options();
+    let scale = format!("scale='min({},iw)':-1", max_width);
+    let quality = quality.to_string();
     
     for seek_pos in seek_positions {
         let mut cmd = Command::new(get_ffmpeg_path());
@@ -86,8 +150,8 @@ pub fn create_video_thumbnail(video_path: &Path, cache_dir: &Path) -> Option<Pat
                 "-ss", seek_pos,  // Seek position
                 "-i", video_path.to_str()?,
                 "-vframes", "1",  // Extract one frame
-                "-q:v", "2",  // High quality
-                "-vf", "scale=320:-1",  // Scale to width 320, maintain aspect ratio
+                "-q:v", &quality,  // Quality from the options (2 = best)
+                "-vf", &scale,  // Limit to the configured width, maintain aspect ratio
                 "-y",  // Overwrite output file
                 thumbnail_path.to_str()?
             ]);
@@ -118,3 +182,440 @@ pub fn create_video_thumbnail(video_path: &Path, cache_dir: &Path) -> Option<Pat
     None
 }
 
+/// Where an evicted thumbnail can be regenerated from
+#[derive(Debug, Clone)]
+pub enum ThumbnailSource {
+    Video(PathBuf),      // Main thumbnail of a video
+    Scene(PathBuf, f64), // Scene thumbnail (video path, timestamp)
+    Preview(PathBuf, Option<f64>), // Info panel preview (video path, duration)
+    Sprite(PathBuf, Option<f64>),  // Hover-scrub sprite sheet (video path, duration)
+}
+
+/// Number of frames in a hover-scrub sprite sheet
+pub const SPRITE_FRAMES: u32 = 10;
+
+/// Path of the larger preview thumbnail shown in the info panel
+pub fn preview_thumbnail_path(video_path: &Path, cache_dir: &Path) -> PathBuf {
+    cache_dir.join(format!("{}_preview.jpg", hash_path(video_path)))
+}
+
+/// Path of a main thumbnail picked by the user at a timestamp
+pub fn custom_thumbnail_path(video_path: &Path, timestamp: f64, cache_dir: &Path) -> PathBuf {
+    cache_dir.join(format!("{}_thumb_{}.jpg", hash_path(video_path), (timestamp * 1000.0) as u64))
+}
+
+/// Timestamp of a user-picked main thumbnail, read back from its file name
+pub fn custom_thumbnail_timestamp(thumbnail_path: &Path) -> Option<f64> {
+    let stem = thumbnail_path.file_stem()?.to_str()?;
+    let (_, millis) = stem.rsplit_once("_thumb_")?;
+    millis.parse::<u64>().ok().map(|ms| ms as f64 / 1000.0)
+}
+
+/// Extract the frame at `timestamp` as a video's main thumbnail
+pub fn create_thumbnail_at(video_path: &Path, timestamp: f64, cache_dir: &Path) -> Option<PathBuf> {
+    let thumbnail_path = custom_thumbnail_path(video_path, timestamp, cache_dir);
+    let (max_width, quality) = thumbnail_options();
+    extract_frame(video_path, timestamp, max_width, quality, &thumbnail_path).then_some(thumbnail_path)
+}
+
+/// Path of the hover-scrub sprite sheet shown in the grid
+pub fn sprite_sheet_path(video_path: &Path, cache_dir: &Path) -> PathBuf {
+    cache_dir.join(format!("{}_sprite.jpg", hash_path(video_path)))
+}
+
+/// Extract a single frame at the given timestamp, scaled down to at most max_width
+fn extract_frame(video_path: &Path, timestamp: f64, max_width: u32, quality: u32, output_path: &Path) -> bool {
+    if let Some(parent) = output_path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    
+    let (Some(input), Some(output)) = (video_path.to_str(), output_path.to_str()) else {
+        return false;
+    };
+    
+    let mut cmd = Command::new(get_ffmpeg_path());
+    cmd.args([
+        "-ss", &timestamp.to_string(),
+        "-i", input,
+        "-vframes", "1",
+        "-q:v", &quality.to_string(),
+        "-vf", &format!("scale='min({},iw)':-1", max_width),
+        "-y",
+        output,
+    ]);
+    
+    // Hide console window on Windows
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    
+    let _ = cmd.output();
+    output_path.exists()
+}
+
+/// Regenerate a thumbnail that was evicted from the disk cache
+pub fn regenerate_thumbnail(source: &ThumbnailSource, thumbnail_path: &Path) -> bool {
+    match source {
+        ThumbnailSource::Video(video_path) => {
+            let cache_dir = thumbnail_path.parent().map(Path::to_path_buf).unwrap_or_else(get_cache_dir);
+            create_video_thumbnail(video_path, &cache_dir).is_some()
+        }
+        ThumbnailSource::Scene(video_path, timestamp) => {
+            let (max_width, quality) = thumbnail_options();
+            extract_frame(video_path, *timestamp, max_width, quality, thumbnail_path)
+        }
+        ThumbnailSource::Preview(video_path, duration) => {
+            // Mid-point of the video, at a resolution suited to the 280px info panel on HiDPI screens
+            let timestamp = duration.map(|d| d / 2.0).unwrap_or(5.0);
+            extract_frame(video_path, timestamp, 960, 2, thumbnail_path)
+                || extract_frame(video_path, 0.1, 960, 2, thumbnail_path)
+        }
+        ThumbnailSource::Sprite(video_path, duration) => {
+            create_sprite_sheet(video_path, *duration, thumbnail_path)
+        }
+    }
+}
+
+/// Extract SPRITE_FRAMES evenly spaced frames and join them into one horizontal strip
+fn create_sprite_sheet(video_path: &Path, duration: Option<f64>, output_path: &Path) -> bool {
+    const FRAME_WIDTH: u32 = 240;
+    
+    let Some(duration) = duration.filter(|d| *d > 0.0) else {
+        return false;
+    };
+    
+    let mut frames = Vec::new();
+    for i in 0..SPRITE_FRAMES {
+        let timestamp = (i as f64 + 0.5) / SPRITE_FRAMES as f64 * duration;
+        let frame_path = output_path.with_file_name(format!(
+            "{}_{}.jpg",
+            output_path.file_stem().and_then(|s| s.to_str()).unwrap_or("sprite"),
+            i
+        ));
+        let frame = if extract_frame(video_path, timestamp, FRAME_WIDTH, 4, &frame_path) {
+            image::open(&frame_path).ok()
+        } else {
+            None
+        };
+        let _ = std::fs::remove_file(&frame_path);
+        match frame {
+            Some(frame) => frames.push(frame.to_rgb8()),
+            None => {
+                eprintln!("[Thumbnail] Sprite frame {} of {:?} failed", i, video_path);
+                return false;
+            }
+        }
+    }
+    
+    // Frames of one video share a size; scale any stragglers to the first frame's
+    let (width, height) = frames[0].dimensions();
+    let mut canvas = image::RgbImage::new(width * SPRITE_FRAMES, height);
+    for (i, frame) in frames.into_iter().enumerate() {
+        let frame = if frame.dimensions() == (width, height) {
+            frame
+        } else {
+            image::imageops::resize(&frame, width, height, image::imageops::FilterType::Triangle)
+        };
+        image::imageops::replace(&mut canvas, &frame, (i as u32 * width) as i64, 0);
+    }
+    
+    match canvas.save_with_format(output_path, image::ImageFormat::Jpeg) {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("[Thumbnail] Failed to save sprite sheet {:?}: {}", output_path, e);
+            false
+        }
+    }
+}
+
+/// Last-accessed timestamps of thumbnails on disk (LRU disk cache policy)
+#[derive(Debug, Default)]
+pub struct ThumbnailAccessLog {
+    entries: HashMap<PathBuf, i64>, // Thumbnail path -> last access (unix seconds)
+    dirty: bool,
+}
+
+impl ThumbnailAccessLog {
+    fn log_path() -> PathBuf {
+        get_cache_dir().join("access_log.json")
+    }
+    
+    /// Load the access log from the cache directory
+    pub fn load() -> Self {
+        let entries = std::fs::read_to_string(Self::log_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { entries, dirty: false }
+    }
+    
+    /// Save the access log if anything changed
+    pub fn save(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        let path = Self::log_path();
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        match serde_json::to_string(&self.entries) {
+            Ok(content) => {
+                if let Err(e) = std::fs::write(&path, content) {
+                    eprintln!("[Thumbnail] Failed to save access log: {}", e);
+                } else {
+                    self.dirty = false;
+                }
+            }
+            Err(e) => eprintln!("[Thumbnail] Failed to serialize access log: {}", e),
+        }
+    }
+    
+    /// Record that a thumbnail was accessed now
+    pub fn touch(&mut self, thumbnail_path: &Path) {
+        self.entries.insert(thumbnail_path.to_path_buf(), chrono::Utc::now().timestamp());
+        self.dirty = true;
+    }
+    
+    /// Last access of a thumbnail, falling back to the file's modification time
+    fn last_access(&self, thumbnail_path: &Path, metadata: &std::fs::Metadata) -> i64 {
+        self.entries.get(thumbnail_path).copied().unwrap_or_else(|| {
+            metadata.modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0)
+        })
+    }
+}
+
+/// Collect all image files in the thumbnail cache with their size
+fn collect_cache_files(dir: &Path, files: &mut Vec<(PathBuf, std::fs::Metadata)>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            collect_cache_files(&path, files);
+        } else if path.extension().and_then(|e| e.to_str()).is_some_and(|e| e.eq_ignore_ascii_case("jpg")) {
+            files.push((path, metadata));
+        }
+    }
+}
+
+/// Delete cached thumbnails that are not in `referenced`. Returns (files removed, bytes freed).
+pub fn remove_orphaned_thumbnails(referenced: &std::collections::HashSet<PathBuf>) -> (usize, u64) {
+    let cache_dir = get_cache_dir();
+    let mut files = Vec::new();
+    collect_cache_files(&cache_dir, &mut files);
+    
+    let mut removed = 0;
+    let mut freed = 0;
+    for (path, metadata) in files {
+        if !referenced.contains(&path) && std::fs::remove_file(&path).is_ok() {
+            removed += 1;
+            freed += metadata.len();
+        }
+    }
+    
+    // Drop scene directories left empty (remove_dir fails on non-empty ones)
+    if let Ok(entries) = std::fs::read_dir(cache_dir.join("scenes")) {
+        for entry in entries.flatten() {
+            let _ = std::fs::remove_dir(entry.path());
+        }
+    }
+    
+    eprintln!("[Thumbnail] Removed {} orphaned cache files ({} bytes)", removed, freed);
+    (removed, freed)
+}
+
+/// Delete every cached thumbnail. Returns (files removed, bytes freed).
+/// Thumbnails are regenerated on demand when displayed again.
+pub fn clear_thumbnail_cache() -> (usize, u64) {
+    let cache_dir = get_cache_dir();
+    let mut files = Vec::new();
+    collect_cache_files(&cache_dir, &mut files);
+    
+    let mut removed = 0;
+    let mut freed = 0;
+    for (path, metadata) in files {
+        if std::fs::remove_file(&path).is_ok() {
+            removed += 1;
+            freed += metadata.len();
+        }
+    }
+    let _ = std::fs::remove_dir_all(cache_dir.join("scenes"));
+    
+    eprintln!("[Thumbnail] Cleared cache: {} files ({} bytes)", removed, freed);
+    (removed, freed)
+}
+
+/// Delete thumbnails not accessed within `max_age_days` when the cache exceeds `budget_bytes`.
+/// Least recently used thumbnails are removed first, until the cache fits the budget.
+/// `protected` files (main thumbnails of videos in the library) are never removed.
+/// Evicted thumbnails are regenerated on demand when displayed again.
+/// Returns the number of deleted files and the bytes freed.
+pub fn evict_stale_thumbnails(log: &mut ThumbnailAccessLog, budget_bytes: u64, max_age_days: u32, protected: &HashSet<PathBuf>) -> (usize, u64) {
+    let mut files = Vec::new();
+    collect_cache_files(&get_cache_dir(), &mut files);
+    
+    let mut total_size: u64 = files.iter().map(|(_, m)| m.len()).sum();
+    if total_size <= budget_bytes {
+        return (0, 0);
+    }
+    
+    let cutoff = chrono::Utc::now().timestamp() - i64::from(max_age_days) * 24 * 60 * 60;
+    let mut candidates: Vec<(i64, PathBuf, u64)> = files.iter()
+        .filter(|(path, _)| !protected.contains(path))
+        .map(|(path, metadata)| (log.last_access(path, metadata), path.clone(), metadata.len()))
+        .filter(|(accessed, _, _)| *accessed < cutoff)
+        .collect();
+    candidates.sort_by_key(|(accessed, _, _)| *accessed);
+    
+    let mut evicted = 0;
+    let mut freed: u64 = 0;
+    for (_, path, size) in candidates {
+        if total_size <= budget_bytes {
+            break;
+        }
+        if std::fs::remove_file(&path).is_ok() {
+            total_size = total_size.saturating_sub(size);
+            freed += size;
+            log.entries.remove(&path);
+            log.dirty = true;
+            evicted += 1;
+        }
+    }
+    
+    eprintln!("[Thumbnail] Evicted {} stale thumbnails, freed {} MB (cache now {} MB)", evicted, freed / (1024 * 1024), total_size / (1024 * 1024));
+    (evicted, freed)
+}
+
+/// 5x7 bitmap glyphs for timestamp labels (digits and ':'), one byte per row
+fn glyph_rows(c: char) -> Option<[u8; 7]> {
+    Some(match c {
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        ':' => [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00],
+        _ => return None,
+    })
+}
+
+/// Draw a timestamp label with a dark background at (x, y)
+fn draw_label(canvas: &mut image::RgbaImage, text: &str, x: u32, y: u32, scale: u32) {
+    let glyph_width = 6 * scale;
+    let width = text.chars().count() as u32 * glyph_width + 2 * scale;
+    let height = 9 * scale;
+    
+    for py in y..(y + height).min(canvas.height()) {
+        for px in x..(x + width).min(canvas.width()) {
+            canvas.put_pixel(px, py, image::Rgba([0, 0, 0, 255]));
+        }
+    }
+    
+    for (i, c) in text.chars().enumerate() {
+        let Some(rows) = glyph_rows(c) else {
+            continue;
+        };
+        let origin_x = x + scale + i as u32 * glyph_width;
+        let origin_y = y + scale;
+        for (row, bits) in rows.iter().enumerate() {
+            for col in 0..5 {
+                if bits & (0x10 >> col) == 0 {
+                    continue;
+                }
+                for dy in 0..scale {
+                    for dx in 0..scale {
+                        let px = origin_x + col * scale + dx;
+                        let py = origin_y + row as u32 * scale + dy;
+                        if px < canvas.width() && py < canvas.height() {
+                            canvas.put_pixel(px, py, image::Rgba([255, 255, 255, 255]));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Tile scene thumbnails into a single PNG grid, each labeled with its timestamp
+pub fn create_contact_sheet(scenes: &[crate::models::SceneInfo], output_path: &Path, columns: u32) -> Result<(), String> {
+    const TILE_WIDTH: u32 = 320;
+    const TILE_HEIGHT: u32 = 180;
+    const GAP: u32 = 4;
+    
+    if scenes.is_empty() {
+        return Err("No scenes to export".to_string());
+    }
+    
+    let columns = columns.max(1).min(scenes.len() as u32);
+    let rows = (scenes.len() as u32).div_ceil(columns);
+    let mut canvas = image::RgbaImage::from_pixel(
+        columns * (TILE_WIDTH + GAP) + GAP,
+        rows * (TILE_HEIGHT + GAP) + GAP,
+        image::Rgba([24, 24, 24, 255]),
+    );
+    
+    for (i, scene) in scenes.iter().enumerate() {
+        let tile_x = GAP + (i as u32 % columns) * (TILE_WIDTH + GAP);
+        let tile_y = GAP + (i as u32 / columns) * (TILE_HEIGHT + GAP);
+        
+        // Letterbox the thumbnail inside its tile
+        match image::open(&scene.thumbnail_path) {
+            Ok(img) => {
+                let img = img.thumbnail(TILE_WIDTH, TILE_HEIGHT).to_rgba8();
+                let offset_x = tile_x + (TILE_WIDTH - img.width()) / 2;
+                let offset_y = tile_y + (TILE_HEIGHT - img.height()) / 2;
+                image::imageops::overlay(&mut canvas, &img, offset_x as i64, offset_y as i64);
+            }
+            Err(e) => eprintln!("[ContactSheet] Skipping unreadable thumbnail {:?}: {}", scene.thumbnail_path, e),
+        }
+        
+        let label = crate::scene_detection::format_timestamp(scene.timestamp);
+        draw_label(&mut canvas, &label, tile_x + 4, tile_y + TILE_HEIGHT - 9 * 2 - 4, 2);
+    }
+    
+    canvas.save_with_format(output_path, image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to save contact sheet: {}", e))
+}
+
+/// 64-bit difference hash (dHash) of an image, for finding near-identical frames
+pub fn perceptual_hash(image_path: &Path) -> Option<u64> {
+    let img = image::open(image_path).ok()?;
+    let small = img.resize_exact(9, 8, image::imageops::FilterType::Triangle).to_luma8();
+    
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if small.get_pixel(x, y)[0] > small.get_pixel(x + 1, y)[0] {
+                hash |= 1;
+            }
+        }
+    }
+    Some(hash)
+}
+
+/// Number of differing bits between two perceptual hashes
+pub fn hash_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// Similarity of two perceptual hashes (1.0 = identical)
+pub fn hash_similarity(a: u64, b: u64) -> f32 {
+    1.0 - (a ^ b).count_ones() as f32 / 64.0
+}
@@ -1,7 +1,9 @@
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::io::Write;
-use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::fs::{File, OpenOptions};
+use std::sync::mpsc::Sender;
+use std::time::Duration;
 
 const IPC_PIPE_NAME: &str = "\\\\.\\pipe\\mpv-cicada-ipc";
 
@@ -129,7 +131,8 @@ fn get_mpv_path() -> Option<PathBuf> {
 }
 
 /// Send IPC command to existing mpv instance
-fn send_ipc_command(video_path: &str, timestamp_seconds: f64) -> bool {
+/// `append_paths` are queued after the loaded video (playlist)
+fn send_ipc_command(video_path: &str, timestamp_seconds: f64, append_paths: &[&str], options: &MpvOptions) -> bool {
     eprintln!("IPC通信を試行: パイプ={}", IPC_PIPE_NAME);
     
     // 名前付きパイプに接続を試みる
@@ -166,6 +169,32 @@ fn send_ipc_command(video_path: &str, timestamp_seconds: f64) -> bool {
             // seekが失敗しても続行
         }
         
+        // 動画ごとの音量・再生速度、ループ・フルスクリーン設定を反映
+        let properties = [
+            ("volume", serde_json::json!(f64::from(options.volume))),
+            ("speed", serde_json::json!(options.speed.unwrap_or(1.0))),
+            ("loop-file", serde_json::json!(if options.loop_playback { "inf" } else { "no" })),
+            ("fullscreen", serde_json::json!(options.fullscreen)),
+        ];
+        for (property, value) in properties {
+            let set_cmd = serde_json::json!({ "command": ["set_property", property, value] });
+            if pipe.write_all(format!("{}\n", set_cmd).as_bytes()).is_err() {
+                eprintln!("{}の設定に失敗", property);
+            }
+        }
+        
+        // 残りの動画をプレイリストに追加
+        for path in append_paths {
+            let append_cmd = format!(
+                "{{\"command\":[\"loadfile\",\"{}\",\"append\"]}}\n",
+                path.replace("\\", "\\\\")
+            );
+            if pipe.write_all(append_cmd.as_bytes()).is_err() {
+                eprintln!("プレイリスト追加コマンド送信失敗");
+                break;
+            }
+        }
+        
         eprintln!("✓ 既存のmpvインスタンスにコマンドを送信しました: {}秒", timestamp_seconds);
         return true;
     }
@@ -174,6 +203,122 @@ fn send_ipc_command(video_path: &str, timestamp_seconds: f64) -> bool {
     false
 }
 
+/// Default folder for mpv screenshots (Pictures/CicadaGallery)
+pub fn default_screenshot_dir() -> PathBuf {
+    let mut path = dirs::picture_dir()
+        .or_else(dirs::data_local_dir)
+        .unwrap_or_else(|| PathBuf::from("."));
+    path.push("CicadaGallery");
+    path
+}
+
+/// Ask the running mpv instance to save a screenshot into `screenshot_dir`
+/// Returns false if no instance is reachable over IPC
+pub fn take_screenshot(screenshot_dir: &Path) -> bool {
+    let Ok(mut pipe) = OpenOptions::new().write(true).open(IPC_PIPE_NAME) else {
+        eprintln!("[mpv] Screenshot failed: no running mpv instance");
+        return false;
+    };
+    let _ = std::fs::create_dir_all(screenshot_dir);
+    
+    // The directory may have changed since mpv was launched
+    let dir_cmd = serde_json::json!({
+        "command": ["set_property", "screenshot-directory", screenshot_dir.to_string_lossy()]
+    });
+    let screenshot_cmd = serde_json::json!({ "command": ["screenshot"] });
+    for cmd in [dir_cmd, screenshot_cmd] {
+        if pipe.write_all(format!("{}\n", cmd).as_bytes()).is_err() {
+            eprintln!("[mpv] Screenshot command failed");
+            return false;
+        }
+    }
+    true
+}
+
+/// Playback state reported by a running mpv instance
+#[derive(Debug, Clone)]
+pub struct PlaybackStatus {
+    pub path: PathBuf,
+    pub position: f64,
+    pub duration: Option<f64>,
+    pub volume: Option<f64>,
+    pub speed: Option<f64>,
+}
+
+/// Read a property from mpv over an open IPC connection
+fn get_ipc_property(reader: &mut BufReader<&File>, property: &str, request_id: u64) -> Option<serde_json::Value> {
+    let request = format!(
+        "{{\"command\":[\"get_property\",\"{}\"],\"request_id\":{}}}\n",
+        property, request_id
+    );
+    let mut writer = *reader.get_ref();
+    writer.write_all(request.as_bytes()).ok()?;
+    
+    // mpv may interleave event messages, so skip lines until our reply arrives
+    for _ in 0..50 {
+        let mut line = String::new();
+        if reader.read_line(&mut line).ok()? == 0 {
+            return None;
+        }
+        let Ok(reply) = serde_json::from_str::<serde_json::Value>(&line) else {
+            continue;
+        };
+        if reply.get("request_id").and_then(|v| v.as_u64()) == Some(request_id) {
+            if reply.get("error").and_then(|e| e.as_str()) == Some("success") {
+                return reply.get("data").cloned();
+            }
+            return None;
+        }
+    }
+    
+    None
+}
+
+/// Query the running mpv instance for the current file and playback position
+/// Returns None if no instance is reachable over IPC
+pub fn query_playback_status() -> Option<PlaybackStatus> {
+    let pipe = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(IPC_PIPE_NAME)
+        .ok()?;
+    let mut reader = BufReader::new(&pipe);
+    
+    let path = get_ipc_property(&mut reader, "path", 1)?.as_str()?.to_string();
+    let position = get_ipc_property(&mut reader, "time-pos", 2)?.as_f64()?;
+    let duration = get_ipc_property(&mut reader, "duration", 3).and_then(|v| v.as_f64());
+    let volume = get_ipc_property(&mut reader, "volume", 4).and_then(|v| v.as_f64());
+    let speed = get_ipc_property(&mut reader, "speed", 5).and_then(|v| v.as_f64());
+    
+    Some(PlaybackStatus {
+        path: PathBuf::from(path),
+        position,
+        duration,
+        volume,
+        speed,
+    })
+}
+
+/// Poll mpv in a background thread and report its playback status
+/// The thread stops once the player has been unreachable for several polls
+pub fn spawn_playback_monitor(sender: Sender<PlaybackStatus>) {
+    std::thread::spawn(move || {
+        let mut misses = 0;
+        while misses < 5 {
+            std::thread::sleep(Duration::from_secs(2));
+            match query_playback_status() {
+                Some(status) => {
+                    misses = 0;
+                    if sender.send(status).is_err() {
+                        break;
+                    }
+                }
+                None => misses += 1,
+            }
+        }
+    });
+}
+
 /// Helper function to try running a command and check if it succeeds
 fn try_play_video(command: &str, args: &[&str]) -> bool {
     let mut cmd = Command::new(command);
@@ -197,26 +342,120 @@ fn try_play_video(command: &str, args: &[&str]) -> bool {
     }
 }
 
-/// Play video with timestamp using available video players
-/// Tries mpv.net, mpv, VLC, then falls back to system default player
-pub fn play_video_at_timestamp(video_path: &Path, timestamp_seconds: f64, always_on_top: bool, use_gpu_hq: bool, use_custom_shaders: bool, selected_shader: Option<&str>, volume: u8) -> Result<(), Box<dyn std::error::Error>> {
+/// Player options passed to mpv when it is launched
+#[derive(Debug, Clone, Copy)]
+pub struct MpvOptions<'a> {
+    pub always_on_top: bool,
+    pub loop_playback: bool, // Repeat the file endlessly (--loop-file=inf)
+    pub fullscreen: bool, // Start in fullscreen (--fullscreen)
+    pub use_gpu_hq: bool,
+    pub use_custom_shaders: bool,
+    pub selected_shader: Option<&'a str>,
+    pub volume: u8,
+    pub speed: Option<f64>, // Playback speed (None = mpv default)
+    pub subtitle: Option<&'a str>, // Preferred subtitle language(s), or "off" to disable subtitles
+    pub audio_lang: Option<&'a str>, // Preferred audio language(s)
+    pub screenshot_dir: &'a Path, // Where mpv saves screenshots
+    pub colorspace_hint: bool, // HDR video: let mpv pass its colorspace to the display (--target-colorspace-hint)
+}
+
+/// mpv arguments for the preferred subtitle and audio tracks.
+/// Files without a matching track keep mpv's default choice.
+fn track_args(subtitle: Option<&str>, audio_lang: Option<&str>) -> Vec<String> {
+    let mut args = Vec::new();
+    match subtitle.map(str::trim).filter(|s| !s.is_empty()) {
+        Some(off) if off.eq_ignore_ascii_case("off") => args.push("--sid=no".to_string()),
+        Some(langs) => args.push(format!("--slang={}", langs)),
+        None => {}
+    }
+    if let Some(langs) = audio_lang.map(str::trim).filter(|s| !s.is_empty()) {
+        args.push(format!("--alang={}", langs));
+    }
+    args
+}
+
+/// Option names set in the user's mpv.conf (e.g. "scale", "glsl-shaders")
+#[cfg(target_os = "windows")]
+fn user_conf_options(config_dir: &Path) -> Vec<String> {
+    let Ok(content) = std::fs::read_to_string(config_dir.join("mpv.conf")) else {
+        return Vec::new();
+    };
+    content.lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('['))
+        .map(|line| line.split('=').next().unwrap_or(line).trim().trim_start_matches("--").to_string())
+        .collect()
+}
+
+/// Drop app-provided tuning flags whose option is set in the user's mpv.conf,
+/// since command-line flags would otherwise take precedence over the config file.
+#[cfg(target_os = "windows")]
+fn without_user_overrides(args: Vec<String>, user_options: &[String]) -> Vec<String> {
+    args.into_iter()
+        .filter(|arg| {
+            let name = arg.trim_start_matches("--").split('=').next().unwrap_or("");
+            !user_options.iter().any(|opt| opt == name)
+        })
+        .collect()
+}
+
+/// Returned when neither mpv, VLC nor the system default player could be launched
+#[derive(Debug)]
+pub struct PlayerNotFound;
+
+impl std::fmt::Display for PlayerNotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "no video player could be launched")
+    }
+}
+
+impl std::error::Error for PlayerNotFound {}
+
+/// Play videos with timestamp using available video players
+/// Tries mpv.net, mpv, VLC, then falls back to system default player.
+/// Several paths are played as a playlist in the given order; the start time is meant for
+/// single videos (mpv applies it to every file), so playlists should pass 0.
+pub fn play_video_at_timestamp(video_paths: &[PathBuf], timestamp_seconds: f64, options: &MpvOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(video_path) = video_paths.first() else {
+        return Ok(());
+    };
     let video_path_str = video_path.to_str().unwrap();
+    let path_strs: Vec<&str> = video_paths.iter().filter_map(|p| p.to_str()).collect();
     
     // 既存のmpvインスタンスにコマンドを送信
-    if send_ipc_command(video_path_str, timestamp_seconds) {
+    if send_ipc_command(video_path_str, timestamp_seconds, &path_strs[1..], options) {
         return Ok(());
     }
     
     // 既存のインスタンスがない場合は新規起動
     let start_arg = format!("--start={}", timestamp_seconds);
-    let volume_arg = format!("--volume={}", volume);
+    let volume_arg = format!("--volume={}", options.volume);
+    let mut track_args = track_args(options.subtitle, options.audio_lang);
+    if options.colorspace_hint {
+        track_args.push("--target-colorspace-hint=yes".to_string());
+    }
+    if let Some(speed) = options.speed {
+        track_args.push(format!("--speed={}", speed));
+    }
+    if options.loop_playback {
+        track_args.push("--loop-file=inf".to_string());
+    }
+    if options.fullscreen {
+        track_args.push("--fullscreen".to_string());
+    }
+    
+    // Per-profile mpv config directory (mpv.conf, input.conf, scripts)
+    let config_dir = crate::database::get_mpv_config_dir();
+    let config_dir_arg = format!("--config-dir={}", config_dir.display());
+    let _ = std::fs::create_dir_all(options.screenshot_dir);
+    let screenshot_dir_arg = format!("--screenshot-directory={}", options.screenshot_dir.display());
     let ipc_arg = format!("--input-ipc-server={}", IPC_PIPE_NAME);
     let ontop_arg = "--ontop";
     let log_arg = "--log-file=mpv.log";
     
     // Collect shader files if custom shaders are enabled
-    let shader_files = if use_custom_shaders {
-        get_shader_files(selected_shader)
+    let shader_files = if options.use_custom_shaders {
+        get_shader_files(options.selected_shader)
     } else {
         Vec::new()
     };
@@ -240,29 +479,35 @@ pub fn play_video_at_timestamp(video_path: &Path, timestamp_seconds: f64, always
     
     #[cfg(target_os = "windows")]
     {
+        // Rendering options; anything the user sets in mpv.conf wins
+        let mut tuning_args: Vec<String> = Vec::new();
+        
+        // Enable GPU rendering if using gpu-hq or custom shaders
+        if options.use_gpu_hq || (options.use_custom_shaders && !shader_args.is_empty()) {
+            tuning_args.push("--vo=gpu-next".to_string());
+            tuning_args.push("--gpu-api=d3d11".to_string());
+        }
+        
+        if options.use_gpu_hq {
+            tuning_args.extend(gpu_hq_args.iter().map(|s| s.to_string()));
+        }
+        
+        // Add custom shader arguments
+        tuning_args.extend(shader_args.clone());
+        let tuning_args = without_user_overrides(tuning_args, &user_conf_options(&config_dir));
+        
         // Try to find mpv.exe using get_mpv_path (EXE dir first, then relative)
         if let Some(mpv_path) = get_mpv_path() {
             // Build arguments based on options
-            let mut args: Vec<String> = vec![ipc_arg.clone(), start_arg.clone(), volume_arg.clone(), log_arg.to_string()];
-            
-            // Enable GPU rendering if using gpu-hq or custom shaders
-            if use_gpu_hq || (use_custom_shaders && !shader_args.is_empty()) {
-                args.push("--vo=gpu-next".to_string());
-                args.push("--gpu-api=d3d11".to_string());
-            }
-            
-            if use_gpu_hq {
-                args.extend(gpu_hq_args.iter().map(|s| s.to_string()));
-            }
-            
-            // Add custom shader arguments
-            args.extend(shader_args.clone());
+            let mut args: Vec<String> = vec![config_dir_arg.clone(), screenshot_dir_arg.clone(), ipc_arg.clone(), start_arg.clone(), volume_arg.clone(), log_arg.to_string()];
+            args.extend(track_args.clone());
+            args.extend(tuning_args.clone());
                 
-                if always_on_top {
+                if options.always_on_top {
                     args.push(ontop_arg.to_string());
                 }
                 
-                args.push(video_path_str.to_string());
+                args.extend(path_strs.iter().map(|s| s.to_string()));
                 
                 let mpv_path_str = mpv_path.to_str().unwrap_or("mpv.exe");
                 eprintln!("🚀 Launching mpv: {} {:?}", mpv_path_str, args);
@@ -276,26 +521,15 @@ pub fn play_video_at_timestamp(video_path: &Path, timestamp_seconds: f64, always
         }
         
         // Try system mpv.exe
-        let mut args: Vec<String> = vec![ipc_arg.clone(), start_arg.clone()];
-        
-        // Enable GPU rendering if using gpu-hq or custom shaders
-        if use_gpu_hq || (use_custom_shaders && !shader_args.is_empty()) {
-            args.push("--vo=gpu-next".to_string());
-            args.push("--gpu-api=d3d11".to_string());
-        }
-        
-        if use_gpu_hq {
-            args.extend(gpu_hq_args.iter().map(|s| s.to_string()));
-        }
+        let mut args: Vec<String> = vec![config_dir_arg.clone(), screenshot_dir_arg.clone(), ipc_arg.clone(), start_arg.clone()];
+        args.extend(track_args.clone());
+        args.extend(tuning_args);
         
-        // Add custom shader arguments
-        args.extend(shader_args.clone());
-        
-        if always_on_top {
+        if options.always_on_top {
             args.push(ontop_arg.to_string());
         }
         
-        args.push(video_path_str.to_string());
+        args.extend(path_strs.iter().map(|s| s.to_string()));
         
         // Convert to &str refs
         let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
@@ -315,7 +549,7 @@ pub fn play_video_at_timestamp(video_path: &Path, timestamp_seconds: f64, always
             if std::path::Path::new(vlc_path).exists() {
                 if let Ok(_) = Command::new(vlc_path)
                     .arg(format!("--start-time={}", timestamp_seconds as i64))
-                    .arg(video_path)
+                    .args(video_paths)
                     .spawn()
                 {
                     return Ok(());
@@ -324,15 +558,18 @@ pub fn play_video_at_timestamp(video_path: &Path, timestamp_seconds: f64, always
         }
         
         // Fallback to default player (no timestamp support)
-        play_video(video_path)?;
+        play_video(video_path).map_err(|_| PlayerNotFound)?;
     }
     
     #[cfg(target_os = "linux")]
     {
         // Try mpv first
         if let Ok(_) = Command::new("mpv")
+            .arg(&config_dir_arg)
+            .arg(&screenshot_dir_arg)
             .arg(format!("--start={}", timestamp_seconds))
-            .arg(video_path)
+            .args(&track_args)
+            .args(video_paths)
             .spawn()
         {
             return Ok(());
@@ -341,22 +578,25 @@ pub fn play_video_at_timestamp(video_path: &Path, timestamp_seconds: f64, always
         // Try VLC
         if let Ok(_) = Command::new("vlc")
             .arg(format!("--start-time={}", timestamp_seconds as i64))
-            .arg(video_path)
+            .args(video_paths)
             .spawn()
         {
             return Ok(());
         }
         
         // Fallback to default player
-        play_video(video_path)?;
+        play_video(video_path).map_err(|_| PlayerNotFound)?;
     }
     
     #[cfg(target_os = "macos")]
     {
         // Try mpv first
         if let Ok(_) = Command::new("mpv")
+            .arg(&config_dir_arg)
+            .arg(&screenshot_dir_arg)
             .arg(format!("--start={}", timestamp_seconds))
-            .arg(video_path)
+            .args(&track_args)
+            .args(video_paths)
             .spawn()
         {
             return Ok(());
@@ -365,14 +605,14 @@ pub fn play_video_at_timestamp(video_path: &Path, timestamp_seconds: f64, always
         // Try VLC
         if let Ok(_) = Command::new("/Applications/VLC.app/Contents/MacOS/VLC")
             .arg(format!("--start-time={}", timestamp_seconds as i64))
-            .arg(video_path)
+            .args(video_paths)
             .spawn()
         {
             return Ok(());
         }
         
         // Fallback to default player
-        play_video(video_path)?;
+        play_video(video_path).map_err(|_| PlayerNotFound)?;
     }
     
     Ok(())
@@ -474,3 +714,20 @@ pub fn show_in_folder(file_path: &Path) -> Result<(), Box<dyn std::error::Error>
     }
     Ok(())
 }
+
+/// Open a directory in the file explorer
+pub fn open_directory(dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(target_os = "windows")]
+    {
+        Command::new("explorer").arg(dir).spawn()?;
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Command::new("xdg-open").arg(dir).spawn()?;
+    }
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("open").arg(dir).spawn()?;
+    }
+    Ok(())
+}
@@ -1,9 +1,12 @@
-use crate::models::VideoFile;
+use crate::models::{TagRule, VideoFile};
 use crate::thumbnail;
-use crate::scene_detection::{get_video_duration, get_video_resolution, get_video_frame_rate};
-use std::path::PathBuf;
+use crate::scene_detection::{get_video_duration, get_video_resolution, get_video_frame_rate, get_video_codec, is_video_hdr, get_video_chapters, get_embedded_metadata};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use walkdir::WalkDir;
 use rayon::prelude::*;
+use regex::Regex;
 
 /// Generate folder name from file path
 /// Uses the immediate parent directory name as the folder
@@ -18,8 +21,85 @@ pub fn generate_folder_from_path(path: &PathBuf) -> Option<String> {
     None
 }
 
+/// Number of leading bytes included in the content hash
+const CONTENT_HASH_BYTES: u64 = 1024 * 1024;
+
+/// Hash the file size and first megabyte of a video
+/// Cheap enough to run on import, and stable across renames/moves of the same file
+pub fn compute_content_hash(path: &Path) -> Option<String> {
+    use sha2::{Digest, Sha256};
+    
+    let file = std::fs::File::open(path).ok()?;
+    let size = file.metadata().ok()?.len();
+    
+    let mut head = Vec::new();
+    file.take(CONTENT_HASH_BYTES).read_to_end(&mut head).ok()?;
+    
+    let mut hasher = Sha256::new();
+    hasher.update(size.to_le_bytes());
+    hasher.update(&head);
+    Some(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Check whether a path matches any exclude pattern.
+/// Patterns are matched case-insensitively against the full path: patterns containing
+/// `*` or `?` are globs over the whole path, anything else is a substring match.
+/// Path separators are normalized to `/` on both sides.
+pub fn is_excluded(path: &Path, patterns: &[String]) -> bool {
+    let path = path.to_string_lossy().replace('\\', "/").to_lowercase();
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.trim().replace('\\', "/").to_lowercase();
+        if pattern.is_empty() {
+            false
+        } else if pattern.contains('*') || pattern.contains('?') {
+            glob_match(&pattern, &path)
+        } else {
+            path.contains(&pattern)
+        }
+    })
+}
+
+/// Minimal glob matcher: `*` matches any run of characters (including `/`), `?` matches one
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None; // (pattern index after '*', text index)
+    
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p + 1, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            // Let the last '*' swallow one more character
+            p = star_p;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 /// Scan video files from directory (file path collection only - fast)
-pub fn scan_directory(dir: PathBuf) -> Vec<VideoFile> {
+/// Files matching `exclude_patterns` are skipped
+pub fn scan_directory(dir: PathBuf, extensions: &[String], exclude_patterns: &[String]) -> Vec<VideoFile> {
+    scan_directory_with_progress(dir, extensions, exclude_patterns, &AtomicBool::new(false), |_| {})
+}
+
+/// Scan like `scan_directory`, reporting the number of video files found so far.
+/// Stops early (returning what was found) when `cancel` is set.
+pub fn scan_directory_with_progress(
+    dir: PathBuf,
+    extensions: &[String],
+    exclude_patterns: &[String],
+    cancel: &AtomicBool,
+    mut on_progress: impl FnMut(usize),
+) -> Vec<VideoFile> {
     let mut videos = Vec::new();
     
     for entry in WalkDir::new(dir)
@@ -27,13 +107,17 @@ pub fn scan_directory(dir: PathBuf) -> Vec<VideoFile> {
         .into_iter()
         .filter_map(|e| e.ok())
     {
+        if cancel.load(Ordering::Relaxed) {
+            break;
+        }
+        
         let path = entry.path().to_path_buf();
         
-        if path.is_file() && VideoFile::is_video_file(&path) {
+        if path.is_file() && VideoFile::is_video_file(&path, extensions) && !is_excluded(&path, exclude_patterns) {
             let mut video = VideoFile::new(path.clone());
             
             // Auto-generate folder from path
-            video.folder = generate_folder_from_path(&path);
+            video.folders = generate_folder_from_path(&path).into_iter().collect();
             
             // Get file size
             if let Ok(metadata) = std::fs::metadata(&path) {
@@ -41,63 +125,144 @@ pub fn scan_directory(dir: PathBuf) -> Vec<VideoFile> {
             }
             
             videos.push(video);
+            on_progress(videos.len());
         }
     }
     
     videos
 }
 
+/// Use the title and recording date embedded in files when importing (opt-in setting)
+static USE_EMBEDDED_METADATA: AtomicBool = AtomicBool::new(false);
+
+/// Set whether imports prefer embedded metadata (called on startup and when the setting changes)
+pub fn set_use_embedded_metadata(enabled: bool) {
+    USE_EMBEDDED_METADATA.store(enabled, Ordering::Relaxed);
+}
+
+/// Replace the filename title and filesystem date with the embedded ones, when enabled and present
+pub fn apply_embedded_metadata(video: &mut VideoFile) {
+    if !USE_EMBEDDED_METADATA.load(Ordering::Relaxed) {
+        return;
+    }
+    let (title, creation_time) = get_embedded_metadata(&video.path);
+    if let Some(title) = title {
+        video.title = title;
+    }
+    if let Some(creation_time) = creation_time {
+        video.added_date = creation_time;
+    }
+}
+
+/// Tag name for the directory a video sits in (whitespace collapsed, control characters dropped)
+pub fn folder_tag(path: &Path) -> Option<String> {
+    let name = path.parent()?.file_name()?.to_string_lossy();
+    let tag = name
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect::<String>();
+    (!tag.is_empty()).then_some(tag)
+}
+
+/// Compile auto-tagging rules; invalid patterns are left out and their error is returned per rule
+pub fn compile_tag_rules(rules: &[TagRule]) -> (Vec<(Regex, String)>, Vec<Option<String>>) {
+    let mut compiled = Vec::new();
+    let mut errors = Vec::new();
+    for rule in rules {
+        if rule.pattern.is_empty() {
+            errors.push(None);
+            continue;
+        }
+        match Regex::new(&rule.pattern) {
+            Ok(regex) => {
+                compiled.push((regex, rule.tag.clone()));
+                errors.push(None);
+            }
+            Err(e) => errors.push(Some(e.to_string())),
+        }
+    }
+    (compiled, errors)
+}
+
+/// Tags produced by the rules for a video (first match in the title, otherwise in the path)
+pub fn rule_tags(rules: &[(Regex, String)], video: &VideoFile) -> Vec<String> {
+    let path = video.path.to_string_lossy();
+    let mut tags = Vec::new();
+    for (regex, template) in rules {
+        let Some(caps) = regex.captures(&video.title).or_else(|| regex.captures(&path)) else {
+            continue;
+        };
+        let mut tag = String::new();
+        caps.expand(template, &mut tag);
+        let tag = tag.trim().to_string();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// Generate the thumbnail and read metadata for one scanned video
+fn process_video(mut video: VideoFile, cache_dir: &Path) -> VideoFile {
+    // Generate thumbnail
+    video.thumbnail_path = thumbnail::create_video_thumbnail(&video.path, cache_dir);
+    
+    // Get video metadata using FFmpeg
+    video.duration = get_video_duration(&video.path);
+    video.resolution = get_video_resolution(&video.path);
+    video.frame_rate = get_video_frame_rate(&video.path);
+    (video.codec, video.container) = get_video_codec(&video.path);
+    video.is_hdr = is_video_hdr(&video.path);
+    video.chapters = get_video_chapters(&video.path);
+    video.content_hash = compute_content_hash(&video.path);
+    apply_embedded_metadata(&mut video);
+    
+    video
+}
+
+/// i18n key describing what FFmpeg/FFprobe could not read from a processed video, if anything
+pub fn processing_issue(video: &VideoFile) -> Option<&'static str> {
+    match (video.thumbnail_path.is_none(), video.duration.is_none()) {
+        (true, true) => Some("issue_unreadable"),
+        (true, false) => Some("issue_no_thumbnail"),
+        (false, true) => Some("issue_no_metadata"),
+        (false, false) => None,
+    }
+}
+
 /// Process videos in parallel to generate thumbnails and metadata
 /// This is the slow part that benefits from parallelization
-pub fn process_videos_parallel(videos: Vec<VideoFile>, cache_dir: &PathBuf) -> Vec<VideoFile> {
-    let cache_dir = cache_dir.clone();
-    
+pub fn process_videos_parallel(videos: Vec<VideoFile>, cache_dir: &Path) -> Vec<VideoFile> {
     videos.into_par_iter()
-        .map(|mut video| {
-            // Generate thumbnail
-            video.thumbnail_path = thumbnail::create_video_thumbnail(&video.path, &cache_dir);
-            
-            // Get video metadata using FFmpeg
-            video.duration = get_video_duration(&video.path);
-            video.resolution = get_video_resolution(&video.path);
-            video.frame_rate = get_video_frame_rate(&video.path);
-            
-            video
-        })
+        .map(|video| process_video(video, cache_dir))
         .collect()
 }
 
-/// Process videos in parallel with a limit (for free tier)
-pub fn process_videos_parallel_with_limit(
-    videos: Vec<VideoFile>, 
-    cache_dir: &PathBuf, 
-    max_count: usize
-) -> Vec<VideoFile> {
-    let cache_dir = cache_dir.clone();
-    let videos_to_process: Vec<_> = videos.into_iter().take(max_count).collect();
-    
-    videos_to_process.into_par_iter()
-        .map(|mut video| {
-            // Generate thumbnail
-            video.thumbnail_path = thumbnail::create_video_thumbnail(&video.path, &cache_dir);
-            
-            // Get video metadata using FFmpeg
-            video.duration = get_video_duration(&video.path);
-            video.resolution = get_video_resolution(&video.path);
-            video.frame_rate = get_video_frame_rate(&video.path);
-            
-            video
-        })
-        .collect()
+/// Process videos in parallel, handing each one to `on_processed` as soon as it is ready.
+/// Videos not yet started when `cancel` is set are skipped.
+pub fn process_videos_streaming(
+    videos: Vec<VideoFile>,
+    cache_dir: &Path,
+    cancel: &AtomicBool,
+    on_processed: impl Fn(VideoFile) + Sync,
+) {
+    videos.into_par_iter().for_each(|video| {
+        if !cancel.load(Ordering::Relaxed) {
+            on_processed(process_video(video, cache_dir));
+        }
+    });
 }
 
 /// Add a single file
-pub fn add_single_file(path: PathBuf) -> Option<VideoFile> {
-    if path.is_file() && VideoFile::is_video_file(&path) {
+pub fn add_single_file(path: PathBuf, extensions: &[String]) -> Option<VideoFile> {
+    if path.is_file() && VideoFile::is_video_file(&path, extensions) {
         let mut video = VideoFile::new(path.clone());
         
         // Auto-generate folder from path
-        video.folder = generate_folder_from_path(&path);
+        video.folders = generate_folder_from_path(&path).into_iter().collect();
         
         if let Ok(metadata) = std::fs::metadata(&path) {
             video.file_size = metadata.len();
@@ -108,3 +273,38 @@ pub fn add_single_file(path: PathBuf) -> Option<VideoFile> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn excluded(path: &str, pattern: &str) -> bool {
+        is_excluded(Path::new(path), &[pattern.to_string()])
+    }
+    
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*.mp4", "/videos/a.mp4"));
+        assert!(!glob_match("*.mp4", "/videos/a.mkv"));
+        assert!(glob_match("*/sample/*", "/videos/sample/a.mp4"));
+        assert!(glob_match("*clip_??.mp4", "/v/clip_01.mp4"));
+        assert!(!glob_match("*clip_??.mp4", "/v/clip_1.mp4"));
+        assert!(glob_match("*a*b*", "xxaxxbxx"));
+        assert!(glob_match("**", ""));
+        assert!(!glob_match("?", ""));
+    }
+    
+    #[test]
+    fn test_is_excluded() {
+        // Substring fallback
+        assert!(excluded("/videos/Sample/a.mp4", "sample"));
+        assert!(!excluded("/videos/a.mp4", "sample"));
+        // Case-insensitive globs
+        assert!(excluded("/videos/A.MP4", "*.mp4"));
+        // Windows separators on either side are normalized to '/'
+        assert!(excluded(r"C:\videos\trailers\a.mp4", "*/trailers/*"));
+        assert!(excluded("/videos/trailers/a.mp4", r"\trailers\"));
+        // Blank patterns never match
+        assert!(!excluded("/videos/a.mp4", "  "));
+    }
+}